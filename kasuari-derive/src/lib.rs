@@ -0,0 +1,132 @@
+//! Derive macros for [kasuari](https://docs.rs/kasuari), enabled by its `derive` feature and
+//! re-exported from it. See [`LayoutVars`].
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, Type};
+
+/// Derive constructors and helpers for a struct whose fields are all `Variable`s.
+///
+/// This generates the following inherent methods:
+///
+/// - `new()`, which creates a new variable for every field.
+/// - `variables(&self)`, an array of the variables in the order of the fields.
+/// - `register_names(&self, names, prefix)`, which inserts a name for every variable into a
+///   `NameRegistry`, made of the prefix, a dot and the name of the field.
+/// - `width(&self)`, i.e. `right - left`, if the struct has `left` and `right` fields.
+/// - `height(&self)`, i.e. `bottom - top`, if the struct has `top` and `bottom` fields.
+///
+/// ```ignore
+/// use kasuari::{LayoutVars, NameRegistry, Variable};
+///
+/// #[derive(LayoutVars)]
+/// struct Panel {
+///     left: Variable,
+///     right: Variable,
+/// }
+///
+/// let panel = Panel::new();
+/// let mut names = NameRegistry::new();
+/// panel.register_names(&mut names, "panel");
+/// assert_eq!(names.name(panel.left), Some("panel.left"));
+/// assert_eq!(panel.width(), panel.right - panel.left);
+/// ```
+#[proc_macro_derive(LayoutVars)]
+pub fn derive_layout_vars(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    layout_vars(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn layout_vars(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "LayoutVars can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "LayoutVars can only be derived for structs",
+            ))
+        }
+    };
+    let mut names: Vec<&Ident> = Vec::with_capacity(fields.len());
+    for field in fields {
+        if !is_variable(&field.ty) {
+            return Err(Error::new(
+                field.ty.span(),
+                "the fields of a LayoutVars struct must be Variables",
+            ));
+        }
+        names.extend(&field.ident);
+    }
+    let labels: Vec<String> = names.iter().map(|name| format!(".{name}")).collect();
+    let count = names.len();
+    let has = |field: &str| names.iter().any(|name| *name == field);
+    let extent = |method: &str, start: &str, end: &str| {
+        if !(has(start) && has(end)) {
+            return TokenStream2::new();
+        }
+        let doc = format!(" The {method}, i.e. `{end} - {start}`.");
+        let (method, start, end) = (
+            format_ident!("{method}"),
+            format_ident!("{start}"),
+            format_ident!("{end}"),
+        );
+        quote! {
+            #[doc = #doc]
+            pub fn #method(&self) -> ::kasuari::Expression {
+                self.#end - self.#start
+            }
+        }
+    };
+    let width = extent("width", "left", "right");
+    let height = extent("height", "top", "bottom");
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Construct with a new variable for every field.
+            pub fn new() -> Self {
+                Self {
+                    #(#names: ::kasuari::Variable::new(),)*
+                }
+            }
+
+            /// The variables, in the order of the fields.
+            pub fn variables(&self) -> [::kasuari::Variable; #count] {
+                [#(self.#names),*]
+            }
+
+            /// Name every variable after its field, prefixed with `prefix` and a dot.
+            pub fn register_names(&self, names: &mut ::kasuari::NameRegistry, prefix: &str) {
+                #(names.insert(self.#names, [prefix, #labels].concat());)*
+            }
+
+            #width
+            #height
+        }
+    })
+}
+
+/// Whether a type names `Variable`, e.g. `Variable` or `kasuari::Variable`.
+fn is_variable(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Variable" && segment.arguments.is_none()),
+        _ => false,
+    }
+}
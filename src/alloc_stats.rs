@@ -0,0 +1,52 @@
+//! Allocation statistics used to profile the solver. These are only collected when the
+//! `alloc-stats` feature is enabled.
+
+use core::ops;
+
+/// Counts of the allocations a [`Solver`](crate::Solver) has performed.
+///
+/// The counts are cumulative since the solver was created or
+/// [`Solver::reset_alloc_stats`](crate::Solver::reset_alloc_stats) was last called. To measure a
+/// single operation, subtract the statistics taken before it from those taken after it.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct AllocStats {
+    /// The number of tableau rows that were allocated.
+    pub rows_created: usize,
+
+    /// The number of tableau rows that were freed.
+    pub rows_dropped: usize,
+
+    /// The number of maps and sets that allocated their initial storage.
+    pub map_allocations: usize,
+
+    /// The number of maps and sets that outgrew their storage and had to rehash their contents.
+    pub map_rehashes: usize,
+}
+
+impl AllocStats {
+    /// Record a change in the capacity of a map or set.
+    pub(crate) fn record_growth(&mut self, before: usize, after: usize) {
+        if after > before {
+            if before == 0 {
+                self.map_allocations += 1;
+            } else {
+                self.map_rehashes += 1;
+            }
+        }
+    }
+}
+
+impl ops::Sub for AllocStats {
+    type Output = AllocStats;
+
+    /// The allocations performed between two snapshots of the statistics.
+    #[inline]
+    fn sub(self, rhs: AllocStats) -> AllocStats {
+        AllocStats {
+            rows_created: self.rows_created.saturating_sub(rhs.rows_created),
+            rows_dropped: self.rows_dropped.saturating_sub(rhs.rows_dropped),
+            map_allocations: self.map_allocations.saturating_sub(rhs.map_allocations),
+            map_rehashes: self.map_rehashes.saturating_sub(rhs.map_rehashes),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use core::time::Duration;
+
+/// How an animation started with [`Solver::animate`](crate::Solver::animate) progresses from its
+/// start to its target over its duration.
+#[derive(Debug, Copy, Clone)]
+pub enum Easing {
+    /// Move at a constant speed.
+    Linear,
+    /// Start slowly and speed up (cubic).
+    EaseIn,
+    /// Start quickly and slow down towards the target (cubic).
+    EaseOut,
+    /// Start slowly, speed up, and slow down towards the target (cubic).
+    EaseInOut,
+    /// A custom curve, mapping the elapsed fraction of the duration (from 0 to 1) to the fraction
+    /// of the distance to the target covered. It should map 0 to 0 and 1 to 1.
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    /// The fraction of the distance to the target covered after the fraction `t` of the duration.
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => {
+                let u = 1.0 - t;
+                1.0 - u * u * u
+            }
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 2.0 - 2.0 * t;
+                    1.0 - u * u * u / 2.0
+                }
+            }
+            Easing::Custom(curve) => curve(t),
+        }
+    }
+}
+
+/// The parameters of a damped spring pulling an edit variable towards its target, for
+/// [`Solver::spring_to`](crate::Solver::spring_to).
+///
+/// The variable accelerates by `stiffness` times its distance from the target, and decelerates by
+/// `damping` times its velocity (per second). A spring is critically damped, settling as quickly
+/// as possible without overshooting, when `damping` is `2 * stiffness.sqrt()`; less damping makes
+/// it bounce.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spring {
+    /// How strongly the variable is pulled towards the target.
+    pub stiffness: f64,
+    /// How strongly the velocity of the variable is damped.
+    pub damping: f64,
+}
+
+impl Spring {
+    /// A critically damped spring with the given stiffness.
+    pub fn critically_damped(stiffness: f64) -> Spring {
+        Spring {
+            stiffness,
+            damping: 2.0 * sqrt(stiffness),
+        }
+    }
+}
+
+impl Default for Spring {
+    /// A slightly bouncy spring that settles in about half a second.
+    fn default() -> Spring {
+        Spring {
+            stiffness: 170.0,
+            damping: 20.0,
+        }
+    }
+}
+
+/// The square root by Newton's method, as `f64::sqrt` needs `std`.
+fn sqrt(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut root = if x > 1.0 { x / 2.0 } else { 1.0 };
+    for _ in 0..64 {
+        let next = (root + x / root) / 2.0;
+        if next == root {
+            break;
+        }
+        root = next;
+    }
+    root
+}
+
+/// The progress of an edit variable towards a target.
+#[derive(Debug, Clone)]
+pub(crate) enum Animation {
+    /// Following an easing curve over a fixed duration.
+    Tween {
+        from: f64,
+        target: f64,
+        elapsed: Duration,
+        duration: Duration,
+        easing: Easing,
+    },
+    /// Pulled by a spring until it comes to rest.
+    Spring {
+        value: f64,
+        velocity: f64,
+        target: f64,
+        spring: Spring,
+    },
+}
+
+/// A spring whose distance from its target and velocity are both below this is at rest.
+const REST: f64 = 1e-3;
+
+/// The longest step with which springs are integrated, for stability with large `dt`.
+const MAX_SPRING_STEP: f64 = 1.0 / 240.0;
+
+impl Animation {
+    pub(crate) fn tween(from: f64, target: f64, duration: Duration, easing: Easing) -> Animation {
+        Animation::Tween {
+            from,
+            target,
+            elapsed: Duration::ZERO,
+            duration,
+            easing,
+        }
+    }
+
+    /// Advance the animation by `dt`, returning the value to suggest and whether the animation is
+    /// finished.
+    pub(crate) fn advance(&mut self, dt: Duration) -> (f64, bool) {
+        match self {
+            Animation::Tween {
+                from,
+                target,
+                elapsed,
+                duration,
+                easing,
+            } => {
+                *elapsed = elapsed.saturating_add(dt);
+                if *elapsed >= *duration {
+                    return (*target, true);
+                }
+                let t = elapsed.as_secs_f64() / duration.as_secs_f64();
+                (*from + (*target - *from) * easing.apply(t), false)
+            }
+            Animation::Spring {
+                value,
+                velocity,
+                target,
+                spring,
+            } => {
+                // Semi-implicit Euler, which is stable for stiff springs with small enough steps.
+                let mut remaining = dt.as_secs_f64();
+                while remaining > 0.0 {
+                    let step = remaining.min(MAX_SPRING_STEP);
+                    let acceleration =
+                        spring.stiffness * (*target - *value) - spring.damping * *velocity;
+                    *velocity += acceleration * step;
+                    *value += *velocity * step;
+                    remaining -= step;
+                }
+                if (*target - *value).abs() < REST && velocity.abs() < REST {
+                    return (*target, true);
+                }
+                (*value, false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easing_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn advance() {
+        let mut animation = Animation::tween(10.0, 20.0, Duration::from_secs(1), Easing::Linear);
+        assert_eq!(animation.advance(Duration::from_millis(250)), (12.5, false));
+        assert_eq!(animation.advance(Duration::from_millis(250)), (15.0, false));
+        assert_eq!(animation.advance(Duration::from_secs(1)), (20.0, true));
+    }
+
+    #[test]
+    fn spring_settles() {
+        assert_eq!(sqrt(400.0), 20.0);
+        for spring in [Spring::default(), Spring::critically_damped(100.0)] {
+            let mut animation = Animation::Spring {
+                value: 0.0,
+                velocity: 0.0,
+                target: 10.0,
+                spring,
+            };
+            let mut frames = 0;
+            while !animation.advance(Duration::from_millis(16)).1 {
+                frames += 1;
+                assert!(frames < 120, "{spring:?} did not settle in two seconds");
+            }
+        }
+
+        // a critically damped spring does not overshoot
+        let mut animation = Animation::Spring {
+            value: 0.0,
+            velocity: 0.0,
+            target: 10.0,
+            spring: Spring::critically_damped(100.0),
+        };
+        for _ in 0..100 {
+            assert!(animation.advance(Duration::from_millis(16)).0 <= 10.0);
+        }
+    }
+}
@@ -0,0 +1,455 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::constraint::ConstraintData;
+use crate::row::{Symbol, SymbolKind};
+use crate::state::{EditState, RowState, TableauState};
+use crate::{
+    Constraint, DecodeStateError, RelationalOperator, SolverState, Strength, Term, Variable,
+};
+
+/// The bytes every encoded state starts with.
+const MAGIC: &[u8; 4] = b"KSRS";
+
+/// The version of the format written by [`SolverState::to_bytes`]. Version 1 didn't have the
+/// namespace of the variable counter, which is 0 in the states it encodes.
+const VERSION: u8 = 2;
+
+impl SolverState {
+    /// Encode the state in a compact binary format.
+    ///
+    /// The format starts with a version, so that states written by this version of the crate can
+    /// be read by later versions. Integers are written as variable length integers and floats as
+    /// their 8 bytes, so that the encoding is far smaller than a text format and reproduces the
+    /// values exactly. This suits restoring sessions and attaching failing states to bug reports.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, SolverState, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_constraint(width | EQ(Strength::WEAK) | 100.0).unwrap();
+    ///
+    /// let bytes = solver.state(true).to_bytes();
+    /// let state = SolverState::from_bytes(&bytes).unwrap();
+    /// let restored = Solver::from_state(state).unwrap();
+    /// assert_eq!(restored.get_value(width), 100.0);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer { bytes: Vec::new() };
+        w.bytes.extend_from_slice(MAGIC);
+        w.u8(VERSION);
+        w.u8(u8::from(self.merge_parallel) | (u8::from(self.tableau.is_some()) << 1));
+        w.f64(self.strict_epsilon);
+        w.uint(self.namespace.into());
+        w.uint(self.created_variables);
+        w.list(&self.conditions, |w, &condition| w.uint(condition));
+        w.list(&self.params, |w, &(v, value)| {
+            w.variable(v);
+            w.f64(value);
+        });
+        w.list(&self.defaults, |w, &(v, value)| {
+            w.variable(v);
+            w.f64(value);
+        });
+        w.list(&self.constraints, Writer::constraint);
+        w.list(&self.named, |w, (name, i)| {
+            w.str(name);
+            w.usize(*i);
+        });
+        w.list(&self.pins, |w, &(v, i)| {
+            w.variable(v);
+            w.usize(i);
+        });
+        w.list(&self.bounds, |w, &(v, op, i)| {
+            w.variable(v);
+            w.u8(operator_code(op));
+            w.usize(i);
+        });
+        w.list(&self.stays, |w, &(v, i, value)| {
+            w.variable(v);
+            w.usize(i);
+            w.f64(value);
+        });
+        w.list(&self.disabled, |w, &(i, weight)| {
+            w.usize(i);
+            match weight {
+                Some(weight) => {
+                    w.u8(1);
+                    w.f64(weight);
+                }
+                None => w.u8(0),
+            }
+        });
+        w.list(&self.edits, |w, edit| {
+            w.variable(edit.variable);
+            w.usize(edit.constraint);
+            w.f64(edit.constant);
+        });
+        if let Some(tableau) = &self.tableau {
+            w.tableau(tableau);
+        }
+        w.bytes
+    }
+
+    /// Decode a state encoded with [`SolverState::to_bytes`].
+    ///
+    /// The bytes are checked to be a complete encoding of a state, but not to be the state of a
+    /// solver: [`Solver::from_state`](crate::Solver::from_state) checks that its constraints are
+    /// consistent, but takes a tableau as is.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SolverState, DecodeStateError> {
+        let mut r = Reader { bytes, position: 0 };
+        if r.take(MAGIC.len())? != MAGIC {
+            return Err(DecodeStateError::NotAState);
+        }
+        let version = r.u8()?;
+        if version == 0 || version > VERSION {
+            return Err(DecodeStateError::UnsupportedVersion(version));
+        }
+        let flags = r.u8()?;
+        if flags > 0b11 {
+            return Err(r.invalid());
+        }
+        let state = SolverState {
+            merge_parallel: flags & 1 != 0,
+            strict_epsilon: r.f64()?,
+            namespace: if version > 1 { r.namespace()? } else { 0 },
+            created_variables: r.uint()?,
+            conditions: r.list(Reader::uint)?,
+            params: r.list(|r| Ok((r.variable()?, r.f64()?)))?,
+            defaults: r.list(|r| Ok((r.variable()?, r.f64()?)))?,
+            constraints: r.list(Reader::constraint)?,
+            named: r.list(|r| Ok((r.str()?, r.usize()?)))?,
+            pins: r.list(|r| Ok((r.variable()?, r.usize()?)))?,
+            bounds: r.list(|r| Ok((r.variable()?, r.operator()?, r.usize()?)))?,
+            stays: r.list(|r| Ok((r.variable()?, r.usize()?, r.f64()?)))?,
+            disabled: r.list(|r| {
+                let i = r.usize()?;
+                let weight = match r.u8()? {
+                    0 => None,
+                    1 => Some(r.f64()?),
+                    _ => return Err(r.invalid()),
+                };
+                Ok((i, weight))
+            })?,
+            edits: r.list(|r| {
+                Ok(EditState {
+                    variable: r.variable()?,
+                    constraint: r.usize()?,
+                    constant: r.f64()?,
+                })
+            })?,
+            tableau: if flags & 0b10 != 0 {
+                Some(r.tableau()?)
+            } else {
+                None
+            },
+        };
+        if r.position != bytes.len() {
+            return Err(r.invalid());
+        }
+        Ok(state)
+    }
+}
+
+fn operator_code(op: RelationalOperator) -> u8 {
+    match op {
+        RelationalOperator::LessOrEqual => 0,
+        RelationalOperator::Equal => 1,
+        RelationalOperator::GreaterOrEqual => 2,
+    }
+}
+
+/// The kinds of symbols, in the order of their discriminants.
+const SYMBOL_KINDS: [SymbolKind; 5] = [
+    SymbolKind::Invalid,
+    SymbolKind::External,
+    SymbolKind::Slack,
+    SymbolKind::Error,
+    SymbolKind::Dummy,
+];
+
+/// The flags of the optional parts of a constraint.
+const STRICT: u8 = 1;
+const WEIGHT: u8 = 1 << 1;
+const NAME: u8 = 1 << 2;
+const TAG: u8 = 1 << 3;
+const CONDITION: u8 = 1 << 4;
+
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    /// Write an unsigned LEB128 integer.
+    fn uint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.uint(value as u64);
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) {
+        self.usize(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    /// Variable ids keep their flags in the highest bits, which are rotated to the lowest bits so
+    /// that small ids stay short.
+    fn variable(&mut self, v: Variable) {
+        self.uint(v.id().rotate_left(2));
+    }
+
+    fn symbol(&mut self, symbol: Symbol) {
+        self.uint(((symbol.id() as u64) << 3) | symbol.kind() as u64);
+    }
+
+    fn list<T>(&mut self, items: &[T], mut write: impl FnMut(&mut Writer, &T)) {
+        self.usize(items.len());
+        for item in items {
+            write(self, item);
+        }
+    }
+
+    fn constraint(&mut self, constraint: &Constraint) {
+        let data = ConstraintData::from(constraint);
+        self.list(&data.terms, |w, term| {
+            w.variable(term.variable);
+            w.f64(term.coefficient);
+        });
+        self.f64(data.constant);
+        self.u8(operator_code(data.operator));
+        self.f64(data.strength.value());
+        let flags = [
+            (data.strict, STRICT),
+            (data.weight != 1.0, WEIGHT),
+            (data.name.is_some(), NAME),
+            (data.tag.is_some(), TAG),
+            (data.condition.is_some(), CONDITION),
+        ];
+        self.u8(flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, flag)| flag)
+            .sum());
+        if data.weight != 1.0 {
+            self.f64(data.weight);
+        }
+        if let Some(name) = &data.name {
+            self.str(name);
+        }
+        if let Some(tag) = data.tag {
+            self.uint(tag);
+        }
+        if let Some(condition) = data.condition {
+            self.uint(condition);
+        }
+    }
+
+    fn row(&mut self, row: &RowState) {
+        self.f64(row.constant);
+        self.list(&row.cells, |w, &(symbol, coefficient)| {
+            w.symbol(symbol);
+            w.f64(coefficient);
+        });
+    }
+
+    fn tableau(&mut self, tableau: &TableauState) {
+        self.list(&tableau.tags, |w, &(i, marker, other)| {
+            w.usize(i);
+            w.symbol(marker);
+            w.symbol(other);
+        });
+        self.list(&tableau.parallel, |w, &i| w.usize(i));
+        self.list(&tableau.groups, |w, (i, members, weight)| {
+            w.usize(*i);
+            w.list(members, |w, &member| w.usize(member));
+            w.f64(*weight);
+        });
+        self.list(&tableau.merged, |w, &(member, primary, weight)| {
+            w.usize(member);
+            w.usize(primary);
+            w.f64(weight);
+        });
+        self.list(&tableau.variables, |w, &(v, symbol, count)| {
+            w.variable(v);
+            w.symbol(symbol);
+            w.usize(count);
+        });
+        self.list(&tableau.rows, |w, (symbol, row)| {
+            w.symbol(*symbol);
+            w.row(row);
+        });
+        self.row(&tableau.objective);
+        self.list(&tableau.infeasible_rows, |w, &symbol| w.symbol(symbol));
+        self.usize(tableau.id_tick);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// The error for an invalid value ending at the current position.
+    fn invalid(&self) -> DecodeStateError {
+        DecodeStateError::InvalidValue(self.position)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeStateError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(DecodeStateError::Truncated)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeStateError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn uint(&mut self) -> Result<u64, DecodeStateError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = u64::from(byte & 0x7f);
+            if bits << shift >> shift != bits {
+                return Err(self.invalid());
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(self.invalid())
+    }
+
+    fn usize(&mut self) -> Result<usize, DecodeStateError> {
+        let value = self.uint()?;
+        usize::try_from(value).map_err(|_| self.invalid())
+    }
+
+    fn namespace(&mut self) -> Result<u16, DecodeStateError> {
+        let value = self.uint()?;
+        u16::try_from(value).map_err(|_| self.invalid())
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeStateError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(f64::from_le_bytes(bytes))
+    }
+
+    fn str(&mut self) -> Result<String, DecodeStateError> {
+        let len = self.usize()?;
+        let bytes = self.take(len)?;
+        let value = core::str::from_utf8(bytes).map_err(|_| self.invalid())?;
+        Ok(String::from(value))
+    }
+
+    fn variable(&mut self) -> Result<Variable, DecodeStateError> {
+        Ok(Variable::from_id(self.uint()?.rotate_right(2)))
+    }
+
+    fn operator(&mut self) -> Result<RelationalOperator, DecodeStateError> {
+        match self.u8()? {
+            0 => Ok(RelationalOperator::LessOrEqual),
+            1 => Ok(RelationalOperator::Equal),
+            2 => Ok(RelationalOperator::GreaterOrEqual),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn symbol(&mut self) -> Result<Symbol, DecodeStateError> {
+        let value = self.uint()?;
+        let kind = SYMBOL_KINDS.get((value & 0b111) as usize).copied();
+        let id = usize::try_from(value >> 3).ok();
+        match (id, kind) {
+            (Some(id), Some(kind)) => Ok(Symbol::new(id, kind)),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    /// Read a list. Every item takes at least one byte, so a length longer than the rest of the
+    /// bytes is rejected before anything is allocated for it.
+    fn list<T>(
+        &mut self,
+        mut read: impl FnMut(&mut Self) -> Result<T, DecodeStateError>,
+    ) -> Result<Vec<T>, DecodeStateError> {
+        let len = self.usize()?;
+        if len > self.bytes.len() - self.position {
+            return Err(DecodeStateError::Truncated);
+        }
+        let mut items = Vec::with_capacity(len);
+        for _ in 0..len {
+            items.push(read(self)?);
+        }
+        Ok(items)
+    }
+
+    fn constraint(&mut self) -> Result<Constraint, DecodeStateError> {
+        let terms = self.list(|r| Ok(Term::new(r.variable()?, r.f64()?)))?;
+        let constant = self.f64()?;
+        let operator = self.operator()?;
+        let strength = Strength::new(self.f64()?);
+        let flags = self.u8()?;
+        if flags >= CONDITION << 1 {
+            return Err(self.invalid());
+        }
+        let data = ConstraintData {
+            terms,
+            constant,
+            operator,
+            strength,
+            strict: flags & STRICT != 0,
+            weight: if flags & WEIGHT != 0 {
+                self.f64()?
+            } else {
+                1.0
+            },
+            name: (flags & NAME != 0).then(|| self.str()).transpose()?,
+            tag: (flags & TAG != 0).then(|| self.uint()).transpose()?,
+            condition: (flags & CONDITION != 0).then(|| self.uint()).transpose()?,
+        };
+        Ok(Constraint::from(data))
+    }
+
+    fn row(&mut self) -> Result<RowState, DecodeStateError> {
+        Ok(RowState {
+            constant: self.f64()?,
+            cells: self.list(|r| Ok((r.symbol()?, r.f64()?)))?,
+        })
+    }
+
+    fn tableau(&mut self) -> Result<TableauState, DecodeStateError> {
+        Ok(TableauState {
+            tags: self.list(|r| Ok((r.usize()?, r.symbol()?, r.symbol()?)))?,
+            parallel: self.list(Reader::usize)?,
+            groups: self.list(|r| Ok((r.usize()?, r.list(Reader::usize)?, r.f64()?)))?,
+            merged: self.list(|r| Ok((r.usize()?, r.usize()?, r.f64()?)))?,
+            variables: self.list(|r| Ok((r.variable()?, r.symbol()?, r.usize()?)))?,
+            rows: self.list(|r| Ok((r.symbol()?, r.row()?)))?,
+            objective: self.row()?,
+            infeasible_rows: self.list(Reader::symbol)?,
+            id_tick: self.usize()?,
+        })
+    }
+}
@@ -0,0 +1,185 @@
+use alloc::vec::Vec;
+
+use crate::WeightedRelation::*;
+use crate::{Axis, Constraint, Element, Strength, Variable};
+
+/// Values this close below the midpoint between two cells are rounded up like the midpoint, so
+/// that the rounding errors of the solver don't split edges that are meant to be equal.
+const SNAP_EPSILON: f64 = 1e-6;
+
+/// A rectangle of terminal cells, with the fields of ratatui's `Rect`.
+///
+/// The solver works with floats, while terminal user interfaces lay out whole cells. A rectangle
+/// of cells is created from the solved edges of an [`Element`] with [`Element::cells`], which
+/// rounds each edge rather than each size, so that elements sharing an edge share it in cells as
+/// well, and [`Element::cells_along`] additionally keeps a row of elements from overlapping.
+/// Converting to and from a tuple of `(x, y, width, height)` lets libraries such as ratatui
+/// convert it into their own rectangle type.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{CellRect, Element, Solver, Strength};
+///
+/// let (area, sidebar) = (Element::new(), Element::new());
+/// let mut solver = Solver::new();
+/// solver.add_constraints(area.at_cells(CellRect::new(0, 0, 81, 24), Strength::REQUIRED)).unwrap();
+/// solver.add_constraints(sidebar.inside(&area, 0.0, Strength::REQUIRED)).unwrap();
+/// solver.add_constraint(sidebar.width() | EQ(Strength::REQUIRED) | (area.width() / 3.0)).unwrap();
+/// solver.add_constraint(sidebar.left | EQ(Strength::REQUIRED) | area.left).unwrap();
+/// solver.add_constraint(sidebar.height() | EQ(Strength::REQUIRED) | area.height()).unwrap();
+///
+/// // the width of 27 cells is rounded from 27.0 rather than truncated
+/// let rect = sidebar.cells(|v| solver.get_value(v));
+/// assert_eq!(rect, CellRect::new(0, 0, 27, 24));
+/// assert_eq!(<(u16, u16, u16, u16)>::from(rect), (0, 0, 27, 24));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CellRect {
+    /// The column of the left edge.
+    pub x: u16,
+    /// The row of the top edge.
+    pub y: u16,
+    /// The number of columns.
+    pub width: u16,
+    /// The number of rows.
+    pub height: u16,
+}
+
+impl CellRect {
+    /// Construct a rectangle from the position of its top left corner and its size.
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> CellRect {
+        CellRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The column just after the right edge, i.e. `x + width`, saturating at `u16::MAX`.
+    pub const fn right(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// The row just after the bottom edge, i.e. `y + height`, saturating at `u16::MAX`.
+    pub const fn bottom(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Round a solved position to the nearest cell boundary.
+    ///
+    /// Halves round up, and so do values within a small epsilon below a half, which absorbs the
+    /// rounding errors of the solver. Values below zero, including negative infinity, snap to 0,
+    /// values beyond `u16::MAX` snap to `u16::MAX`, and NaN snaps to 0.
+    ///
+    /// ```
+    /// use kasuari::CellRect;
+    ///
+    /// assert_eq!(CellRect::snap(2.5), 3);
+    /// assert_eq!(CellRect::snap(2.4999999999), 3);
+    /// assert_eq!(CellRect::snap(2.49), 2);
+    /// assert_eq!(CellRect::snap(-3.0), 0);
+    /// assert_eq!(CellRect::snap(1e9), u16::MAX);
+    /// ```
+    pub fn snap(value: f64) -> u16 {
+        // Casts truncate towards zero and saturate, which floors the values that don't saturate to
+        // 0, and turn NaN into 0.
+        (value + 0.5 + SNAP_EPSILON) as u16
+    }
+
+    /// Snap a sequence of solved `(start, end)` segments to cells, returning the position and
+    /// length of each.
+    ///
+    /// Each segment starts no earlier than the previous one ends and has a length of at least
+    /// zero, even if the solved segments overlap slightly or are out of order. Segments that
+    /// share an edge in the solution share it in cells, so the segments of a row of elements tile
+    /// the cells between them without gaps or overlaps.
+    ///
+    /// ```
+    /// use kasuari::CellRect;
+    ///
+    /// let cells = CellRect::snap_segments([(0.0, 10.5), (10.4999, 20.0), (20.0, 19.0)]);
+    /// assert_eq!(cells, [(0, 11), (11, 9), (20, 0)]);
+    /// ```
+    pub fn snap_segments<I: IntoIterator<Item = (f64, f64)>>(segments: I) -> Vec<(u16, u16)> {
+        let mut previous_end = 0;
+        segments
+            .into_iter()
+            .map(|(start, end)| {
+                let start = CellRect::snap(start).max(previous_end);
+                let end = CellRect::snap(end).max(start);
+                previous_end = end;
+                (start, end - start)
+            })
+            .collect()
+    }
+}
+
+impl From<(u16, u16, u16, u16)> for CellRect {
+    fn from((x, y, width, height): (u16, u16, u16, u16)) -> CellRect {
+        CellRect::new(x, y, width, height)
+    }
+}
+
+impl From<CellRect> for (u16, u16, u16, u16) {
+    fn from(rect: CellRect) -> (u16, u16, u16, u16) {
+        (rect.x, rect.y, rect.width, rect.height)
+    }
+}
+
+impl Element {
+    /// The rectangle of cells covered by the element, with the values of its edges given by
+    /// `value_of`, e.g. `|v| solver.get_value(v)` or `|v| solution.value_of(v)`.
+    ///
+    /// Each edge is [snapped](CellRect::snap) on its own, and the size is the distance between
+    /// the snapped edges, or zero if the element has a negative size.
+    pub fn cells(&self, value_of: impl Fn(Variable) -> f64) -> CellRect {
+        let [(x, width), (y, height)] = [Axis::Horizontal, Axis::Vertical].map(|axis| {
+            let start = CellRect::snap(value_of(self.start(axis)));
+            let end = CellRect::snap(value_of(self.end(axis)));
+            (start, end.saturating_sub(start))
+        });
+        CellRect::new(x, y, width, height)
+    }
+
+    /// The rectangles of cells covered by a row or column of elements, which are kept from
+    /// overlapping along the axis, see [`CellRect::snap_segments`].
+    ///
+    /// This is the snapping of e.g. ratatui's `Layout::split`: the elements are expected to be in
+    /// order along the axis, as with [`Element::no_overlap`]. The other axis is snapped like
+    /// [`Element::cells`].
+    pub fn cells_along(
+        elements: &[Element],
+        axis: Axis,
+        value_of: impl Fn(Variable) -> f64,
+    ) -> Vec<CellRect> {
+        let segments = CellRect::snap_segments(
+            elements
+                .iter()
+                .map(|element| (value_of(element.start(axis)), value_of(element.end(axis)))),
+        );
+        elements
+            .iter()
+            .zip(segments)
+            .map(|(element, (position, length))| {
+                let mut rect = element.cells(&value_of);
+                match axis {
+                    Axis::Horizontal => (rect.x, rect.width) = (position, length),
+                    Axis::Vertical => (rect.y, rect.height) = (position, length),
+                }
+                rect
+            })
+            .collect()
+    }
+
+    /// Constrain the edges of the element to those of a rectangle of cells, e.g. the area a
+    /// terminal user interface lays out its widgets in.
+    pub fn at_cells(&self, rect: CellRect, strength: Strength) -> Vec<Constraint> {
+        alloc::vec![
+            self.left | EQ(strength) | f64::from(rect.x),
+            self.top | EQ(strength) | f64::from(rect.y),
+            self.right | EQ(strength) | f64::from(rect.right()),
+            self.bottom | EQ(strength) | f64::from(rect.bottom()),
+        ]
+    }
+}
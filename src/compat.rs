@@ -0,0 +1,6 @@
+//! Compatibility layers for code written against the API of other crates and solvers.
+
+#[cfg(feature = "cassowary-compat")]
+pub mod cassowary;
+#[cfg(feature = "kiwi-compat")]
+pub mod kiwi;
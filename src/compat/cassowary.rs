@@ -0,0 +1,416 @@
+//! The API of the unmaintained [`cassowary`](https://docs.rs/cassowary/0.3.0) crate that kasuari
+//! was forked from, implemented on top of kasuari.
+//!
+//! Strengths are plain `f64`s in the [`strength`] module, constraints have the original
+//! constructor and accessors, and the solver has the original method signatures and error types.
+//! Code written for `cassowary` 0.3 compiles against this module once its `cassowary::` paths
+//! point here, e.g. by renaming the dependency and importing `cassowary::compat::cassowary`
+//! instead of the crate root:
+//!
+//! ```toml
+//! cassowary = { package = "kasuari", version = "0.4", features = ["cassowary-compat"] }
+//! ```
+//!
+//! ```
+//! use kasuari::compat::cassowary::strength::{REQUIRED, WEAK};
+//! use kasuari::compat::cassowary::WeightedRelation::*;
+//! use kasuari::compat::cassowary::{Solver, Variable};
+//!
+//! let (left, width) = (Variable::new(), Variable::new());
+//! let mut solver = Solver::new();
+//! solver
+//!     .add_constraints(&[
+//!         left | EQ(REQUIRED) | 10.0,
+//!         width | GE(REQUIRED) | 0.0,
+//!         width | EQ(WEAK) | 80.0,
+//!     ])
+//!     .unwrap();
+//! assert_eq!(solver.get_value(width), 80.0);
+//! ```
+//!
+//! [`Variable`], [`Term`] and [`Expression`] are the types of kasuari, which have the same fields
+//! and operators, and [`Constraint::as_kasuari`] and [`Solver::as_kasuari`] give access to the rest
+//! of kasuari's API for migrating piecemeal.
+
+use core::hash::{Hash, Hasher};
+use core::ops;
+
+use portable_atomic_util::Arc;
+
+pub use crate::{Expression, RelationalOperator, Term, Variable};
+
+/// The strengths of constraints as plain `f64`s, and the functions to create them.
+pub mod strength {
+    use crate::Strength;
+
+    /// The strength of a constraint that must be satisfied.
+    pub const REQUIRED: f64 = Strength::REQUIRED.value();
+
+    /// A strong strength.
+    pub const STRONG: f64 = Strength::STRONG.value();
+
+    /// A medium strength.
+    pub const MEDIUM: f64 = Strength::MEDIUM.value();
+
+    /// A weak strength.
+    pub const WEAK: f64 = Strength::WEAK.value();
+
+    /// Create a strength as a linear combination of the strong, medium and weak strengths, see
+    /// [`Strength::create`].
+    pub fn create(a: f64, b: f64, c: f64, w: f64) -> f64 {
+        Strength::create(a, b, c, w).value()
+    }
+
+    /// Clip a strength to the legal range from 0 to [`REQUIRED`].
+    pub fn clip(s: f64) -> f64 {
+        Strength::new(s).value()
+    }
+}
+
+/// A constraint of the `cassowary` API, which keeps the expression it was created from.
+///
+/// Constraints are compared by identity like the constraints of kasuari: clones are equal, but
+/// two constraints created from the same expression are not.
+#[derive(Debug, Clone)]
+pub struct Constraint(Arc<ConstraintData>);
+
+#[derive(Debug)]
+struct ConstraintData {
+    expression: Expression,
+    constraint: crate::Constraint,
+}
+
+impl Constraint {
+    /// Construct a new constraint from an expression, a relational operator and a strength, which
+    /// corresponds to the equation `e op 0.0`.
+    pub fn new(e: Expression, op: RelationalOperator, strength: f64) -> Constraint {
+        let constraint = crate::Constraint::new(e.clone(), op, crate::Strength::new(strength));
+        Constraint(Arc::new(ConstraintData {
+            expression: e,
+            constraint,
+        }))
+    }
+
+    /// The expression of the left hand side of the constraint equation.
+    pub fn expr(&self) -> &Expression {
+        &self.0.expression
+    }
+
+    /// The relational operator governing the constraint.
+    pub fn op(&self) -> RelationalOperator {
+        self.0.constraint.op()
+    }
+
+    /// The strength of the constraint that the solver will use.
+    pub fn strength(&self) -> f64 {
+        self.0.constraint.strength().value()
+    }
+
+    /// The kasuari constraint that the solver adds for this constraint.
+    pub fn as_kasuari(&self) -> &crate::Constraint {
+        &self.0.constraint
+    }
+}
+
+impl Hash for Constraint {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.0.constraint.hash(hasher);
+    }
+}
+
+impl PartialEq for Constraint {
+    fn eq(&self, other: &Constraint) -> bool {
+        self.0.constraint == other.0.constraint
+    }
+}
+
+impl Eq for Constraint {}
+
+/// The syntactic sugar for specifying constraints with `f64` strengths, e.g.
+/// `x | EQ(REQUIRED) | y`.
+#[derive(Debug, Copy, Clone)]
+pub enum WeightedRelation {
+    /// `==`
+    EQ(f64),
+    /// `<=`
+    LE(f64),
+    /// `>=`
+    GE(f64),
+}
+
+impl From<WeightedRelation> for (RelationalOperator, f64) {
+    fn from(relation: WeightedRelation) -> (RelationalOperator, f64) {
+        match relation {
+            WeightedRelation::EQ(s) => (RelationalOperator::Equal, s),
+            WeightedRelation::LE(s) => (RelationalOperator::LessOrEqual, s),
+            WeightedRelation::GE(s) => (RelationalOperator::GreaterOrEqual, s),
+        }
+    }
+}
+
+/// This is an intermediate type used in the syntactic sugar for specifying constraints. You should
+/// not use it directly.
+#[derive(Debug)]
+pub struct PartialConstraint(pub Expression, pub WeightedRelation);
+
+impl PartialConstraint {
+    /// Complete the constraint with its right hand side.
+    fn constrain(self, rhs: Expression) -> Constraint {
+        let (op, strength) = self.1.into();
+        Constraint::new(self.0 - rhs, op, strength)
+    }
+}
+
+macro_rules! impl_relation_syntax {
+    ($($side:ty),*) => {$(
+        impl ops::BitOr<WeightedRelation> for $side {
+            type Output = PartialConstraint;
+
+            fn bitor(self, rhs: WeightedRelation) -> PartialConstraint {
+                PartialConstraint(Expression::from(self), rhs)
+            }
+        }
+
+        impl ops::BitOr<$side> for PartialConstraint {
+            type Output = Constraint;
+
+            fn bitor(self, rhs: $side) -> Constraint {
+                self.constrain(Expression::from(rhs))
+            }
+        }
+    )*};
+}
+
+impl_relation_syntax!(f64, Variable, Term, Expression);
+
+impl ops::BitOr<WeightedRelation> for f32 {
+    type Output = PartialConstraint;
+
+    fn bitor(self, rhs: WeightedRelation) -> PartialConstraint {
+        (self as f64).bitor(rhs)
+    }
+}
+
+impl ops::BitOr<f32> for PartialConstraint {
+    type Output = Constraint;
+
+    fn bitor(self, rhs: f32) -> Constraint {
+        self.bitor(rhs as f64)
+    }
+}
+
+/// The possible error conditions that [`Solver::add_constraint`] can fail with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddConstraintError {
+    /// The constraint specified has already been added to the solver.
+    DuplicateConstraint,
+    /// The constraint is required, but it is unsatisfiable in conjunction with the existing
+    /// constraints.
+    UnsatisfiableConstraint,
+    /// The solver entered an invalid state.
+    InternalSolverError(&'static str),
+}
+
+/// The possible error conditions that [`Solver::remove_constraint`] can fail with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RemoveConstraintError {
+    /// The constraint specified was not already in the solver, so cannot be removed.
+    UnknownConstraint,
+    /// The solver entered an invalid state.
+    InternalSolverError(&'static str),
+}
+
+/// The possible error conditions that [`Solver::add_edit_variable`] can fail with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddEditVariableError {
+    /// The specified variable is already marked as an edit variable in the solver.
+    DuplicateEditVariable,
+    /// The specified strength was `REQUIRED`. This is illegal for edit variable strengths.
+    BadRequiredStrength,
+}
+
+/// The possible error conditions that [`Solver::remove_edit_variable`] can fail with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RemoveEditVariableError {
+    /// The specified variable was not an edit variable in the solver, so cannot be removed.
+    UnknownEditVariable,
+    /// The solver entered an invalid state.
+    InternalSolverError(&'static str),
+}
+
+/// The possible error conditions that [`Solver::suggest_value`] can fail with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SuggestValueError {
+    /// The specified variable was not an edit variable in the solver, so cannot have its value
+    /// suggested.
+    UnknownEditVariable,
+    /// The solver entered an invalid state.
+    InternalSolverError(&'static str),
+}
+
+/// The messages `cassowary` reported its internal errors with.
+fn internal(error: crate::InternalSolverError) -> &'static str {
+    match error {
+        crate::InternalSolverError::ObjectiveUnbounded => "The objective is unbounded.",
+        crate::InternalSolverError::DualOptimizeFailed => "Dual optimise failed.",
+        crate::InternalSolverError::FailedToFindLeavingRow => "Failed to find leaving row.",
+        crate::InternalSolverError::EditConstraintNotInSystem => "Edit constraint not in system",
+    }
+}
+
+/// A constraint solver with the API of `cassowary`, backed by a kasuari [`Solver`](crate::Solver).
+#[derive(Default)]
+pub struct Solver {
+    inner: crate::Solver,
+}
+
+impl Solver {
+    /// Construct a new solver.
+    pub fn new() -> Solver {
+        Solver {
+            inner: crate::Solver::new(),
+        }
+    }
+
+    /// Add a number of constraints to the solver, stopping at the first that fails.
+    pub fn add_constraints<'a, I: IntoIterator<Item = &'a Constraint>>(
+        &mut self,
+        constraints: I,
+    ) -> Result<(), AddConstraintError> {
+        for constraint in constraints {
+            self.add_constraint(constraint.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Add a constraint to the solver.
+    pub fn add_constraint(&mut self, constraint: Constraint) -> Result<(), AddConstraintError> {
+        self.inner
+            .add_constraint(constraint.0.constraint.clone())
+            .map_err(|error| match error {
+                crate::AddConstraintError::DuplicateConstraint => {
+                    AddConstraintError::DuplicateConstraint
+                }
+                crate::AddConstraintError::UnsatisfiableConstraint => {
+                    AddConstraintError::UnsatisfiableConstraint
+                }
+                // Only variables created by the solver's counter collide, which this API has no
+                // way to create.
+                crate::AddConstraintError::VariableCollision(_) => {
+                    AddConstraintError::InternalSolverError("Variable collision")
+                }
+                crate::AddConstraintError::InternalSolverError(e) => {
+                    AddConstraintError::InternalSolverError(internal(e))
+                }
+            })
+    }
+
+    /// Remove a constraint from the solver.
+    pub fn remove_constraint(
+        &mut self,
+        constraint: &Constraint,
+    ) -> Result<(), RemoveConstraintError> {
+        self.inner
+            .remove_constraint(&constraint.0.constraint)
+            .map_err(|error| match error {
+                crate::RemoveConstraintError::UnknownConstraint => {
+                    RemoveConstraintError::UnknownConstraint
+                }
+                crate::RemoveConstraintError::InternalSolverError(e) => {
+                    RemoveConstraintError::InternalSolverError(internal(e))
+                }
+            })
+    }
+
+    /// Test whether a constraint has been added to the solver.
+    pub fn has_constraint(&self, constraint: &Constraint) -> bool {
+        self.inner.has_constraint(&constraint.0.constraint)
+    }
+
+    /// Add an edit variable to the solver, whose value can then be suggested with
+    /// [`Solver::suggest_value`].
+    ///
+    /// The strength can't be [`REQUIRED`](strength::REQUIRED).
+    pub fn add_edit_variable(
+        &mut self,
+        v: Variable,
+        strength: f64,
+    ) -> Result<(), AddEditVariableError> {
+        self.inner
+            .add_edit_variable(v, crate::Strength::new(strength))
+            .map(|_| ())
+            .map_err(|error| match error {
+                crate::AddEditVariableError::DuplicateEditVariable => {
+                    AddEditVariableError::DuplicateEditVariable
+                }
+                crate::AddEditVariableError::BadRequiredStrength => {
+                    AddEditVariableError::BadRequiredStrength
+                }
+                // Like the `cassowary` crate, panic if the edit constraint can't be added.
+                crate::AddEditVariableError::VariableCollision(_)
+                | crate::AddEditVariableError::InternalSolverError(_) => panic!("{error}"),
+            })
+    }
+
+    /// Remove an edit variable from the solver.
+    pub fn remove_edit_variable(&mut self, v: Variable) -> Result<(), RemoveEditVariableError> {
+        self.inner
+            .remove_edit_variable(v)
+            .map_err(|error| match error {
+                crate::RemoveEditVariableError::UnknownEditVariable => {
+                    RemoveEditVariableError::UnknownEditVariable
+                }
+                crate::RemoveEditVariableError::InternalSolverError(e) => {
+                    RemoveEditVariableError::InternalSolverError(internal(e))
+                }
+            })
+    }
+
+    /// Test whether an edit variable has been added to the solver.
+    pub fn has_edit_variable(&self, v: &Variable) -> bool {
+        self.inner.has_edit_variable(v)
+    }
+
+    /// Suggest a value for an edit variable.
+    pub fn suggest_value(
+        &mut self,
+        variable: Variable,
+        value: f64,
+    ) -> Result<(), SuggestValueError> {
+        self.inner
+            .suggest_value(variable, value)
+            .map_err(|error| match error {
+                crate::SuggestValueError::UnknownEditVariable => {
+                    SuggestValueError::UnknownEditVariable
+                }
+                crate::SuggestValueError::InternalSolverError(e) => {
+                    SuggestValueError::InternalSolverError(internal(e))
+                }
+            })
+    }
+
+    /// Fetch the variables whose values changed since the last call, with their new values.
+    pub fn fetch_changes(&mut self) -> &[(Variable, f64)] {
+        self.inner.fetch_changes()
+    }
+
+    /// Get the stored value of a variable.
+    pub fn get_value(&self, v: Variable) -> f64 {
+        self.inner.get_value(v)
+    }
+
+    /// Reset the solver to the empty starting condition.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// The kasuari solver behind this one.
+    pub fn as_kasuari(&self) -> &crate::Solver {
+        &self.inner
+    }
+
+    /// Convert into the kasuari solver behind this one, e.g. to continue with kasuari's API.
+    pub fn into_kasuari(self) -> crate::Solver {
+        self.inner
+    }
+}
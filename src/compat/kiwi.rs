@@ -0,0 +1,126 @@
+//! The method names of [kiwi](https://github.com/nucleic/kiwi), the C++ solver behind kiwisolver
+//! and kiwi.js, as aliases of the methods of [`Solver`].
+//!
+//! Importing the [`Kiwi`] trait lets code ported from kiwi keep its method names while it is
+//! being translated. The table lists every method of kiwi's solver, the kasuari method it maps
+//! to, and where the behavior differs, so it doubles as a checklist for porting.
+//!
+//! | kiwi                 | kasuari                          | differences                     |
+//! |----------------------|----------------------------------|---------------------------------|
+//! | `addConstraint`      | [`Solver::add_constraint`]       | errors are returned, not thrown |
+//! | `removeConstraint`   | [`Solver::remove_constraint`]    | errors are returned, not thrown |
+//! | `hasConstraint`      | [`Solver::has_constraint`]       |                                 |
+//! | `addEditVariable`    | [`Solver::add_edit_variable`]    | errors are returned, not thrown |
+//! | `removeEditVariable` | [`Solver::remove_edit_variable`] | errors are returned, not thrown |
+//! | `hasEditVariable`    | [`Solver::has_edit_variable`]    |                                 |
+//! | `suggestValue`       | [`Solver::suggest_value`]        | errors are returned, not thrown |
+//! | `updateVariables`    | [`Solver::fetch_changes`]        | returns the changes, see below  |
+//! | `reset`              | [`Solver::reset`]                | same name                       |
+//! | `dumps`              | [`Solver::dumps`]                | same name, similar format       |
+//! | `dump`               | none                             | print [`Solver::dumps`] instead |
+//!
+//! Besides the names, ported code trips over these differences:
+//!
+//! - Variables of kiwi hold their values, which `updateVariables` writes into them. The
+//!   [`Variable`]s of kasuari are plain ids: [`Solver::get_value`] always returns the current
+//!   value, and `updateVariables` returns the variables that changed since the last call along
+//!   with their values.
+//! - Variables of kiwi have names. Name them with a [`NameRegistry`](crate::NameRegistry), or
+//!   with [`Variable::named`] and the `variable-names` feature.
+//! - Strengths are [`Strength`]s rather than numbers, and `strength::create` is
+//!   [`Strength::create`].
+//! - Constraints are created with the `|` operators, e.g. `x | GE(Strength::REQUIRED) | 10.0`,
+//!   rather than by comparing expressions, which Rust can't overload to return constraints.
+//!
+//! ```
+//! use kasuari::compat::kiwi::Kiwi;
+//! use kasuari::WeightedRelation::*;
+//! use kasuari::{Constraint, Solver, Strength, Variable};
+//!
+//! let mut solver = Solver::new();
+//! let (left, width) = (Variable::new(), Variable::new());
+//! let min: Constraint = width | GE(Strength::REQUIRED) | 10.0;
+//! solver.addConstraint(min.clone()).unwrap();
+//! assert!(solver.hasConstraint(&min));
+//! solver.addEditVariable(left, Strength::STRONG).unwrap();
+//! solver.suggestValue(left, 20.0).unwrap();
+//! let changes = solver.updateVariables();
+//! assert!(changes.contains(&(left, 20.0)) && changes.contains(&(width, 10.0)));
+//! ```
+
+use crate::{
+    AddConstraintError, AddEditVariableError, Constraint, RemoveConstraintError,
+    RemoveEditVariableError, Solver, Strength, SuggestValueError, Variable,
+};
+
+/// The methods of kiwi's solver, under their kiwi names. See the [module](self) for the kasuari
+/// methods they map to.
+#[allow(non_snake_case)]
+pub trait Kiwi {
+    /// Add a constraint, see [`Solver::add_constraint`].
+    fn addConstraint(&mut self, constraint: Constraint) -> Result<(), AddConstraintError>;
+
+    /// Remove a constraint, see [`Solver::remove_constraint`].
+    fn removeConstraint(&mut self, constraint: &Constraint) -> Result<(), RemoveConstraintError>;
+
+    /// Whether a constraint is in the solver, see [`Solver::has_constraint`].
+    fn hasConstraint(&self, constraint: &Constraint) -> bool;
+
+    /// Add an edit variable, see [`Solver::add_edit_variable`].
+    fn addEditVariable(
+        &mut self,
+        v: Variable,
+        strength: Strength,
+    ) -> Result<(), AddEditVariableError>;
+
+    /// Remove an edit variable, see [`Solver::remove_edit_variable`].
+    fn removeEditVariable(&mut self, v: Variable) -> Result<(), RemoveEditVariableError>;
+
+    /// Whether a variable is an edit variable of the solver, see [`Solver::has_edit_variable`].
+    fn hasEditVariable(&self, v: &Variable) -> bool;
+
+    /// Suggest a value for an edit variable, see [`Solver::suggest_value`].
+    fn suggestValue(&mut self, v: Variable, value: f64) -> Result<(), SuggestValueError>;
+
+    /// The variables whose values changed since the last call, with their new values, see
+    /// [`Solver::fetch_changes`].
+    fn updateVariables(&mut self) -> &[(Variable, f64)];
+}
+
+impl Kiwi for Solver {
+    fn addConstraint(&mut self, constraint: Constraint) -> Result<(), AddConstraintError> {
+        self.add_constraint(constraint)
+    }
+
+    fn removeConstraint(&mut self, constraint: &Constraint) -> Result<(), RemoveConstraintError> {
+        self.remove_constraint(constraint)
+    }
+
+    fn hasConstraint(&self, constraint: &Constraint) -> bool {
+        self.has_constraint(constraint)
+    }
+
+    fn addEditVariable(
+        &mut self,
+        v: Variable,
+        strength: Strength,
+    ) -> Result<(), AddEditVariableError> {
+        self.add_edit_variable(v, strength).map(|_| ())
+    }
+
+    fn removeEditVariable(&mut self, v: Variable) -> Result<(), RemoveEditVariableError> {
+        self.remove_edit_variable(v)
+    }
+
+    fn hasEditVariable(&self, v: &Variable) -> bool {
+        self.has_edit_variable(v)
+    }
+
+    fn suggestValue(&mut self, v: Variable, value: f64) -> Result<(), SuggestValueError> {
+        self.suggest_value(v, value)
+    }
+
+    fn updateVariables(&mut self) -> &[(Variable, f64)] {
+        self.fetch_changes()
+    }
+}
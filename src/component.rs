@@ -0,0 +1,92 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{Constraint, Variable};
+
+/// A set of constraints that share no variables with the constraints outside of it, created by
+/// [`Solver::components`].
+///
+/// The variables of one component can't affect the values of the variables of another, so each
+/// component is effectively an independent layout.
+///
+/// [`Solver::components`]: crate::Solver::components
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    variables: Vec<Variable>,
+    constraints: Vec<Constraint>,
+}
+
+impl Component {
+    /// The variables in the component, ordered by variable.
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    /// The constraints in the component, in no particular order.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+}
+
+/// Partition constraints into components connected by shared variables, ordered by their first
+/// variable. Constraints without variables are left out, and parameters don't connect constraints.
+pub(crate) fn components<'a>(constraints: impl Iterator<Item = &'a Constraint>) -> Vec<Component> {
+    let constraints: Vec<&Constraint> = constraints.collect();
+    fn variables(constraint: &Constraint) -> impl Iterator<Item = Variable> + '_ {
+        let variables = constraint.terms().iter().map(|term| term.variable);
+        variables.filter(|v| !v.is_param())
+    }
+    let mut indices = BTreeMap::new();
+    for constraint in &constraints {
+        for v in variables(constraint) {
+            let next = indices.len();
+            indices.entry(v).or_insert(next);
+        }
+    }
+
+    // Union-find over the variables, joining the variables of each constraint.
+    let mut parents: Vec<usize> = (0..indices.len()).collect();
+    fn root(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    }
+    for constraint in &constraints {
+        let mut variables = variables(constraint);
+        let Some(first) = variables.next() else {
+            continue;
+        };
+        let first = root(&mut parents, indices[&first]);
+        for v in variables {
+            let other = root(&mut parents, indices[&v]);
+            parents[other] = first;
+        }
+    }
+
+    // Components are numbered in the order of their first variable.
+    let mut numbers = BTreeMap::new();
+    let mut components: Vec<Component> = Vec::new();
+    for (&v, &i) in &indices {
+        let root = root(&mut parents, i);
+        let next = numbers.len();
+        let number = *numbers.entry(root).or_insert(next);
+        if number == components.len() {
+            components.push(Component {
+                variables: Vec::new(),
+                constraints: Vec::new(),
+            });
+        }
+        components[number].variables.push(v);
+    }
+    for constraint in constraints {
+        if let Some(v) = variables(constraint).next() {
+            let root = root(&mut parents, indices[&v]);
+            components[numbers[&root]]
+                .constraints
+                .push(constraint.clone());
+        }
+    }
+    components
+}
@@ -1,15 +1,31 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
 use core::hash::{Hash, Hasher};
-use core::ops;
+use core::{fmt, ops};
 
+use hashbrown::HashMap;
 use portable_atomic_util::Arc;
 
-use crate::{Expression, RelationalOperator, Strength, Term, Variable, WeightedRelation};
+use crate::names::WithNames;
+use crate::{
+    ConstraintGroup, Expression, NameRegistry, RelationalOperator, Strength, Term, Variable,
+    WeightedRelation,
+};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Inner {
-    expression: Expression,
+    /// The terms of the expression, shared by the constraints derived from this one with another
+    /// constant or strength.
+    terms: Arc<[Term]>,
+    constant: f64,
     strength: Strength,
     operator: RelationalOperator,
+    name: Option<String>,
+    tag: Option<u64>,
+    condition: Option<u64>,
+    weight: f64,
+    /// Whether the inequality is strict, i.e. `<` or `>` rather than `<=` or `>=`.
+    strict: bool,
 }
 
 /// A constraint, consisting of an equation governed by an expression and a relational operator,
@@ -23,22 +39,202 @@ impl Constraint {
     /// Construct a new constraint from an expression, a relational operator and a strength.
     /// This corresponds to the equation `e op 0.0`, e.g. `x + y >= 0.0`. For equations with a
     /// non-zero right hand side, subtract it from the equation to give a zero right hand side.
+    ///
+    /// The expression is [simplified](Expression::simplify).
     pub fn new(
-        expression: Expression,
+        mut expression: Expression,
         operator: RelationalOperator,
         strength: Strength,
     ) -> Constraint {
+        expression.simplify();
         Constraint {
             inner: Arc::new(Inner {
-                expression,
+                terms: Arc::from(expression.terms),
+                constant: expression.constant,
                 operator,
                 strength,
+                name: None,
+                tag: None,
+                condition: None,
+                weight: 1.0,
+                strict: false,
             }),
         }
     }
+
+    /// A new constraint with the same equation and strength as this one, carrying a name.
+    ///
+    /// Adding a named constraint to a solver is the same as adding it with
+    /// [`Solver::add_named`](crate::Solver::add_named): it replaces the constraint previously added
+    /// under the name, and can be looked up and removed by name later.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    /// let max = max.with_name("sidebar.max");
+    /// solver.add_constraint(max.clone()).unwrap();
+    /// assert_eq!(solver.named("sidebar.max"), Some(&max));
+    /// solver.remove_named("sidebar.max").unwrap();
+    /// ```
+    pub fn with_name(&self, name: impl Into<String>) -> Constraint {
+        self.with(|inner| inner.name = Some(name.into()))
+    }
+
+    /// A new constraint with the same equation and strength as this one, carrying a tag.
+    ///
+    /// Tags map constraints back to whatever created them, e.g. the id of a widget, for
+    /// diagnostics and for removing them all at once with
+    /// [`Solver::remove_by_tag`](crate::Solver::remove_by_tag). Many constraints can share a tag.
+    pub fn with_tag(&self, tag: u64) -> Constraint {
+        self.with(|inner| inner.tag = Some(tag))
+    }
+
+    /// A new constraint with the same equation and strength as this one, that only has an effect
+    /// while a condition is active.
+    ///
+    /// Conditions are switched with [`Solver::set_condition`](crate::Solver::set_condition) and
+    /// start out inactive, so a constraint added under a condition that was never activated is
+    /// added [disabled](crate::Solver::set_enabled). This switches whole sets of constraints at
+    /// once, e.g. the stacked and the side by side arrangement of a responsive layout.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// const STACKED: u64 = 1;
+    /// let mut solver = Solver::new();
+    /// let height = Variable::new();
+    /// let stacked: Constraint = height | EQ(Strength::STRONG) | 20.0;
+    /// solver.add_constraint(stacked.when(STACKED)).unwrap();
+    /// solver.add_constraint(height | EQ(Strength::WEAK) | 10.0).unwrap();
+    /// assert_eq!(solver.get_value(height), 10.0);
+    ///
+    /// solver.set_condition(STACKED, true).unwrap();
+    /// assert_eq!(solver.get_value(height), 20.0);
+    /// ```
+    pub fn when(&self, condition: u64) -> Constraint {
+        self.with(|inner| inner.condition = Some(condition))
+    }
+
+    /// A new constraint with the same equation and strength as this one, whose errors count
+    /// `weight` times as much.
+    ///
+    /// The weight biases the trade-off between constraints of the same strength without moving
+    /// the constraint to another strength: [`Constraint::strength`] and everything based on it,
+    /// such as [`Solver::remove_below_strength`](crate::Solver::remove_below_strength), are
+    /// unaffected. Negative weights count as zero, and required constraints ignore their weight.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let (left, right) = (Variable::new(), Variable::new());
+    /// solver.add_constraint((left + right) | EQ(Strength::REQUIRED) | 100.0).unwrap();
+    /// let left_preferred: Constraint = left | EQ(Strength::WEAK) | 80.0;
+    /// let right_preferred: Constraint = right | EQ(Strength::WEAK) | 80.0;
+    /// solver.add_constraint(left_preferred).unwrap();
+    /// solver.add_constraint(right_preferred.with_weight(2.0)).unwrap();
+    /// assert_eq!(solver.get_value(right), 80.0);
+    /// assert_eq!(solver.get_value(left), 20.0);
+    /// ```
+    pub fn with_weight(&self, weight: f64) -> Constraint {
+        self.with(|inner| inner.weight = weight.max(0.0))
+    }
+
+    /// A new constraint like this one, with another constant in its expression.
+    ///
+    /// The new constraint shares the terms of this one rather than copying them, so stamping out
+    /// many constraints that only differ in their constant, e.g. one limit per row of a long list,
+    /// stores the terms once. It keeps the name, tag, condition and weight of this one.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Strength, Variable};
+    ///
+    /// let (x, y) = (Variable::new(), Variable::new());
+    /// let template: Constraint = (x + y) | LE(Strength::REQUIRED) | 0.0;
+    /// let rows: Vec<Constraint> = (1..=3)
+    ///     .map(|row| template.with_constant(-10.0 * row as f64))
+    ///     .collect();
+    /// assert_eq!(rows[2].constant(), -30.0);
+    /// assert!(rows.iter().all(|row| row.shares_terms_with(&template)));
+    /// ```
+    pub fn with_constant(&self, constant: f64) -> Constraint {
+        self.with(|inner| inner.constant = constant)
+    }
+
+    /// A new constraint like this one, with another strength.
+    ///
+    /// Like [`Constraint::with_constant`], the new constraint shares the terms of this one.
+    pub fn with_strength(&self, strength: Strength) -> Constraint {
+        self.with(|inner| inner.strength = strength)
+    }
+
+    fn with(&self, change: impl FnOnce(&mut Inner)) -> Constraint {
+        let mut inner = Inner::clone(&self.inner);
+        change(&mut inner);
+        Constraint {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// The name the constraint was created with, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name.as_deref()
+    }
+
+    /// The tag the constraint was created with, if any.
+    pub fn tag(&self) -> Option<u64> {
+        self.inner.tag
+    }
+
+    /// The condition the constraint is guarded by, if any.
+    pub fn condition(&self) -> Option<u64> {
+        self.inner.condition
+    }
+
+    /// Whether the constraint is a strict inequality, created with
+    /// [`WeightedRelation::LT`] or [`WeightedRelation::GT`].
+    ///
+    /// The solver keeps a strict inequality's expression at least its
+    /// [strict epsilon](crate::Solver::set_strict_epsilon) away from zero.
+    pub fn is_strict(&self) -> bool {
+        self.inner.strict
+    }
+
+    /// The factor the constraint's errors are weighted by, see [`Constraint::with_weight`].
+    pub fn weight(&self) -> f64 {
+        self.inner.weight
+    }
+
+    /// The coefficient of the constraint's errors in the objective.
+    pub(crate) fn error_weight(&self) -> f64 {
+        self.inner.strength.value() * self.inner.weight
+    }
     /// The expression of the left hand side of the constraint equation.
-    pub fn expr(&self) -> &Expression {
-        &self.inner.expression
+    ///
+    /// This builds a new expression from the constraint's [terms](Constraint::terms) and
+    /// [constant](Constraint::constant), which can be read without building one.
+    pub fn expr(&self) -> Expression {
+        Expression::new(self.inner.terms.to_vec(), self.inner.constant)
+    }
+    /// The terms of the left hand side of the constraint equation.
+    pub fn terms(&self) -> &[Term] {
+        &self.inner.terms
+    }
+    /// The constant of the left hand side of the constraint equation.
+    pub fn constant(&self) -> f64 {
+        self.inner.constant
+    }
+    /// Whether the two constraints share their terms, i.e. one was derived from the other with
+    /// [`Constraint::with_constant`] or [`Constraint::with_strength`].
+    pub fn shares_terms_with(&self, other: &Constraint) -> bool {
+        Arc::ptr_eq(&self.inner.terms, &other.inner.terms)
     }
     /// The relational operator governing the constraint.
     pub fn op(&self) -> RelationalOperator {
@@ -48,6 +244,369 @@ impl Constraint {
     pub fn strength(&self) -> Strength {
         self.inner.strength
     }
+
+    /// The variables the constraint's expression mentions, each only once, in the order they first
+    /// appear.
+    pub fn variables(&self) -> impl Iterator<Item = Variable> + '_ {
+        let terms = &self.inner.terms;
+        terms
+            .iter()
+            .enumerate()
+            .filter(move |&(i, term)| terms[..i].iter().all(|t| t.variable != term.variable))
+            .map(|(_, term)| term.variable)
+    }
+
+    /// A new constraint with each variable replaced by the variable it maps to, if any.
+    ///
+    /// This stamps out a prototype set of constraints for many instances of a widget. Variables
+    /// that are not in the map are kept, e.g. those of a container shared by all instances. The
+    /// result is a new constraint, so it is not equal to this one even if no variable is replaced.
+    ///
+    /// The strength, weight, [tag](Constraint::with_tag) and [condition](Constraint::when) are
+    /// kept. The [name](Constraint::with_name) is not: a name identifies a single constraint in a
+    /// solver, so the copies stamped out for several instances would replace each other when added.
+    ///
+    /// ```
+    /// use hashbrown::HashMap;
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Strength, Variable};
+    ///
+    /// let (left, right, container) = (Variable::new(), Variable::new(), Variable::new());
+    /// let prototype: Constraint = right | LE(Strength::REQUIRED) | (left + container);
+    ///
+    /// let (instance_left, instance_right) = (Variable::new(), Variable::new());
+    /// let map = HashMap::from([(left, instance_left), (right, instance_right)]);
+    /// let instance = prototype.remap(&map);
+    /// let variables: Vec<Variable> = instance.variables().collect();
+    /// assert_eq!(variables, [instance_right, instance_left, container]);
+    /// ```
+    pub fn remap(&self, map: &HashMap<Variable, Variable>) -> Constraint {
+        Constraint::new(self.expr().remap(map), self.op(), self.strength()).with(|inner| {
+            inner.weight = self.inner.weight;
+            inner.strict = self.inner.strict;
+            inner.tag = self.inner.tag;
+            inner.condition = self.inner.condition;
+        })
+    }
+
+    /// Whether two constraints describe the same equation with the same strength.
+    ///
+    /// Constraints compare equal with `==` only if they are the same constraint, i.e. clones of
+    /// each other. This instead compares what the constraints say: the operator and whether it is
+    /// strict, the strength, the weight and the expression, after combining the terms of each
+    /// variable. The order of the terms doesn't matter, but no other normalization is done, so
+    /// `2x >= 2` is not equivalent to `x >= 1`.
+    pub fn structurally_equals(&self, other: &Constraint) -> bool {
+        self.op() == other.op()
+            && self.strength() == other.strength()
+            && self.weight() == other.weight()
+            && self.is_strict() == other.is_strict()
+            && self.constant() == other.constant()
+            && self.combined_terms() == other.combined_terms()
+    }
+
+    /// A stable identifier of the constraint, derived from what it says.
+    ///
+    /// Constraints compare equal with `==` only if they are clones of each other, an identity that
+    /// doesn't survive serialization or the end of the process. The id is instead a hash of the
+    /// operator, strength, weight, expression, name, tag and condition of the constraint, so it is
+    /// the same in every process as long as the [ids](Variable::id) of its variables are, and can
+    /// be logged, stored, and used to look up and remove the constraint with
+    /// [`Solver::constraint_by_id`](crate::Solver::constraint_by_id) and
+    /// [`Solver::remove_by_id`](crate::Solver::remove_by_id).
+    ///
+    /// Constraints that are [structurally equal](Constraint::structurally_equals) and have the same
+    /// name, tag and condition have the same id. Constraints that differ have different ids, except
+    /// for the tiny chance of a collision of the 64 bit hashes.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Strength, Variable};
+    ///
+    /// let (x, y) = (Variable::from_id(1), Variable::from_id(2));
+    /// let a: Constraint = (x + y) | GE(Strength::REQUIRED) | 10.0;
+    /// let b: Constraint = (y + x) | GE(Strength::REQUIRED) | 10.0;
+    /// assert_ne!(a, b);
+    /// assert_eq!(a.id(), b.id());
+    /// assert_ne!(a.id(), a.with_name("gap").id());
+    /// ```
+    pub fn id(&self) -> ConstraintId {
+        let mut hasher = StableHasher::new();
+        hasher.write(&[self.op() as u8, u8::from(self.is_strict())]);
+        hasher.f64(self.strength().value());
+        hasher.f64(self.weight());
+        hasher.f64(self.constant());
+        let terms = self.combined_terms();
+        hasher.u64(terms.len() as u64);
+        for (variable, coefficient) in terms {
+            hasher.u64(variable.id());
+            hasher.f64(coefficient);
+        }
+        match self.name() {
+            Some(name) => {
+                hasher.u64(name.len() as u64 + 1);
+                hasher.write(name.as_bytes());
+            }
+            None => hasher.u64(0),
+        }
+        for value in [self.tag(), self.condition()] {
+            match value {
+                Some(value) => {
+                    hasher.write(&[1]);
+                    hasher.u64(value);
+                }
+                None => hasher.write(&[0]),
+            }
+        }
+        ConstraintId(hasher.0)
+    }
+
+    /// The coefficient of each variable in the expression, leaving out variables that cancel out.
+    fn combined_terms(&self) -> BTreeMap<Variable, f64> {
+        let mut combined = BTreeMap::new();
+        for term in self.terms() {
+            *combined.entry(term.variable).or_insert(0.0) += term.coefficient;
+        }
+        combined.retain(|_, coefficient| *coefficient != 0.0);
+        combined
+    }
+
+    /// A pair of constraints keeping an expression between `lo` and `hi`, to be added and removed
+    /// together with [`Solver::add_range`](crate::Solver::add_range) and
+    /// [`Solver::remove_range`](crate::Solver::remove_range).
+    ///
+    /// ```
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let range = Constraint::range(width, 100.0, 200.0, Strength::REQUIRED);
+    /// solver.add_range(&range).unwrap();
+    /// assert_eq!(solver.get_value(width), 100.0);
+    /// solver.remove_range(&range).unwrap();
+    /// ```
+    pub fn range(
+        expression: impl Into<Expression>,
+        lo: f64,
+        hi: f64,
+        strength: Strength,
+    ) -> RangeConstraint {
+        let expression = expression.into();
+        RangeConstraint {
+            lower: Constraint::new(
+                expression.clone() - lo,
+                RelationalOperator::GreaterOrEqual,
+                strength,
+            ),
+            upper: Constraint::new(expression - hi, RelationalOperator::LessOrEqual, strength),
+        }
+    }
+
+    /// This constraint and a fallback that is preferred less, as a group to be added with
+    /// [`Solver::add_group`](crate::Solver::add_group), so that the two are added and removed
+    /// together.
+    ///
+    /// Both constraints keep their strengths and weights, and those decide the preference: give the
+    /// fallback a lower strength or weight than this constraint, so that this one wins where they
+    /// disagree. This is a soft preference, not a disjunction. Both constraints pull on the
+    /// solution at the same time, and when this constraint can't hold, the solver violates it as
+    /// little as possible instead of jumping to satisfy the fallback, so the result is a weighted
+    /// compromise. For a genuine choice, e.g. snapping to the left or the right edge, switch
+    /// between the two with [conditions](Constraint::when).
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let (x, width) = (Variable::new(), Variable::new());
+    /// let left: Constraint = x | EQ(Strength::MEDIUM) | 0.0;
+    /// let right: Constraint = (x + width) | EQ(Strength::WEAK) | 100.0;
+    /// solver.add_constraint(width | EQ(Strength::REQUIRED) | 20.0).unwrap();
+    /// solver.add_group(&left.or(&right)).unwrap();
+    /// assert_eq!(solver.get_value(x), 0.0);
+    /// ```
+    pub fn or(&self, fallback: &Constraint) -> ConstraintGroup {
+        let mut group = ConstraintGroup::new();
+        group.push(self.clone());
+        group.push(fallback.clone());
+        group
+    }
+
+    /// Constraints making `variable` the minimum of some expressions, as a group to be added with
+    /// [`Solver::add_group`](crate::Solver::add_group).
+    ///
+    /// The variable is kept at or below each expression by required constraints, and pulled up
+    /// towards each of them with `strength`, so it settles on the smallest one. This suits
+    /// toolbars and overflow layouts, e.g. a width that is the lesser of the available space and
+    /// the preferred width. Weaker constraints pulling the variable the other way don't move it
+    /// off the minimum.
+    ///
+    /// ```
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let (available, preferred, width) = (Variable::new(), Variable::new(), Variable::new());
+    /// solver.add_edit_variable(available, Strength::STRONG).unwrap();
+    /// solver.add_edit_variable(preferred, Strength::STRONG).unwrap();
+    /// solver
+    ///     .add_group(&Constraint::min_of(width, [available, preferred], Strength::MEDIUM))
+    ///     .unwrap();
+    /// solver.suggest_value(available, 80.0).unwrap();
+    /// solver.suggest_value(preferred, 120.0).unwrap();
+    /// assert_eq!(solver.get_value(width), 80.0);
+    /// ```
+    pub fn min_of<E: Into<Expression>>(
+        variable: Variable,
+        expressions: impl IntoIterator<Item = E>,
+        strength: Strength,
+    ) -> ConstraintGroup {
+        Constraint::extremum_of(
+            variable,
+            expressions,
+            RelationalOperator::LessOrEqual,
+            strength,
+        )
+    }
+
+    /// Constraints making `variable` the maximum of some expressions, as a group to be added with
+    /// [`Solver::add_group`](crate::Solver::add_group).
+    ///
+    /// The variable is kept at or above each expression by required constraints, and pulled down
+    /// towards each of them with `strength`, so it settles on the largest one. See
+    /// [`Constraint::min_of`].
+    pub fn max_of<E: Into<Expression>>(
+        variable: Variable,
+        expressions: impl IntoIterator<Item = E>,
+        strength: Strength,
+    ) -> ConstraintGroup {
+        Constraint::extremum_of(
+            variable,
+            expressions,
+            RelationalOperator::GreaterOrEqual,
+            strength,
+        )
+    }
+
+    /// Constraints keeping `variable op expression` for each expression, with a pull of `strength`
+    /// towards equality.
+    fn extremum_of<E: Into<Expression>>(
+        variable: Variable,
+        expressions: impl IntoIterator<Item = E>,
+        bound: RelationalOperator,
+        strength: Strength,
+    ) -> ConstraintGroup {
+        let pull = match bound {
+            RelationalOperator::LessOrEqual => RelationalOperator::GreaterOrEqual,
+            _ => RelationalOperator::LessOrEqual,
+        };
+        let mut group = ConstraintGroup::new();
+        for expression in expressions {
+            let difference = variable - expression.into();
+            group.push(Constraint::new(
+                difference.clone(),
+                bound,
+                Strength::REQUIRED,
+            ));
+            group.push(Constraint::new(difference, pull, strength));
+        }
+        group
+    }
+
+    /// The constraint as a group that spreads its violation fairly among constraints of the same
+    /// strength, to be added with [`Solver::add_group`](crate::Solver::add_group).
+    ///
+    /// The solver minimizes the total weighted violation of the non-required constraints, which
+    /// usually puts all of the violation on one of two equally strong conflicting constraints:
+    /// one box keeps its preferred size and the other collapses. Here the violation is instead
+    /// split into `segments` pieces of size `step`, each costing more than the one before, so that
+    /// the cost grows about quadratically with the violation and the solver prefers to violate
+    /// each constraint a little. Violations beyond `segments * step` cost as much per unit as the
+    /// last piece. The group holds the constraint's equation as a required constraint with new
+    /// deviation variables, plus a few constraints per piece, keeping the tag and condition of
+    /// the constraint. Required constraints are returned as they are.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let (left, right) = (Variable::new(), Variable::new());
+    /// solver.add_constraint((left + right) | EQ(Strength::REQUIRED) | 100.0).unwrap();
+    /// let left_preferred: Constraint = left | EQ(Strength::WEAK) | 80.0;
+    /// let right_preferred: Constraint = right | EQ(Strength::WEAK) | 80.0;
+    /// solver.add_group(&left_preferred.fair(5.0, 8)).unwrap();
+    /// solver.add_group(&right_preferred.fair(5.0, 8)).unwrap();
+    /// assert_eq!(solver.get_value(left), 50.0);
+    /// assert_eq!(solver.get_value(right), 50.0);
+    /// ```
+    pub fn fair(&self, step: f64, segments: usize) -> ConstraintGroup {
+        let mut group = ConstraintGroup::new();
+        if self.strength() == Strength::REQUIRED || segments == 0 {
+            group.push(self.clone());
+            return group;
+        }
+        let mut expression = self.expr();
+        let directions: &[f64] = match self.op() {
+            // `expression <= 0` is violated by a positive excess, which is taken off.
+            RelationalOperator::LessOrEqual => &[-1.0],
+            RelationalOperator::GreaterOrEqual => &[1.0],
+            RelationalOperator::Equal => &[-1.0, 1.0],
+        };
+        for &direction in directions {
+            for segment in 0..segments {
+                let deviation = Variable::new();
+                expression += Term::new(deviation, direction);
+                let bound = |op, constant| {
+                    self.derived(
+                        Expression::from(deviation) - constant,
+                        op,
+                        Strength::REQUIRED,
+                    )
+                };
+                group.push(bound(RelationalOperator::GreaterOrEqual, 0.0));
+                if segment + 1 < segments {
+                    group.push(bound(RelationalOperator::LessOrEqual, step));
+                }
+                let cost = (2 * segment + 1) as f64;
+                group.push(
+                    self.derived(
+                        Expression::from(deviation),
+                        RelationalOperator::LessOrEqual,
+                        self.strength(),
+                    )
+                    .with(|inner| inner.weight = self.weight() * cost),
+                );
+            }
+        }
+        group.push(
+            self.derived(expression, self.op(), Strength::REQUIRED)
+                .with(|inner| inner.strict = self.inner.strict),
+        );
+        group
+    }
+
+    /// A new constraint with the tag and condition of this one.
+    fn derived(
+        &self,
+        expression: Expression,
+        operator: RelationalOperator,
+        strength: Strength,
+    ) -> Constraint {
+        Constraint::new(expression, operator, strength).with(|inner| {
+            inner.tag = self.inner.tag;
+            inner.condition = self.inner.condition;
+        })
+    }
+
+    /// Format the constraint using the names in the registry, with its constant moved to the
+    /// right hand side, e.g. `sidebar.width >= 200`.
+    ///
+    /// The [`Display`](fmt::Display) implementation formats it without names and followed by its
+    /// strength, e.g. `v0 >= 200 [REQUIRED]`.
+    pub fn display_with<'a>(&'a self, names: &'a NameRegistry) -> impl fmt::Display + 'a {
+        WithNames { value: self, names }
+    }
 }
 
 impl Hash for Constraint {
@@ -66,6 +625,165 @@ impl PartialEq for Constraint {
 
 impl Eq for Constraint {}
 
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        NameRegistry::new().write_constraint(f, self)?;
+        write!(f, " [{}]", self.strength())
+    }
+}
+
+/// The parts of a constraint, which it is serialized as.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct ConstraintData {
+    pub(crate) terms: alloc::vec::Vec<Term>,
+    pub(crate) constant: f64,
+    pub(crate) operator: RelationalOperator,
+    pub(crate) strength: Strength,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) strict: bool,
+    #[cfg_attr(feature = "serde", serde(default = "ConstraintData::default_weight"))]
+    pub(crate) weight: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tag: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) condition: Option<u64>,
+}
+
+impl ConstraintData {
+    #[cfg(feature = "serde")]
+    fn default_weight() -> f64 {
+        1.0
+    }
+}
+
+impl From<&Constraint> for ConstraintData {
+    fn from(constraint: &Constraint) -> ConstraintData {
+        let inner = &constraint.inner;
+        ConstraintData {
+            terms: inner.terms.to_vec(),
+            constant: inner.constant,
+            operator: inner.operator,
+            strength: inner.strength,
+            strict: inner.strict,
+            weight: inner.weight,
+            name: inner.name.clone(),
+            tag: inner.tag,
+            condition: inner.condition,
+        }
+    }
+}
+
+/// Constraints are compared by identity, so the constraint is a new constraint, distinct from the
+/// one the data was taken from.
+impl From<ConstraintData> for Constraint {
+    fn from(data: ConstraintData) -> Constraint {
+        let constraint = Constraint::new(
+            Expression::new(data.terms, data.constant),
+            data.operator,
+            data.strength,
+        );
+        constraint.with(|inner| {
+            inner.strict = data.strict;
+            inner.weight = data.weight.max(0.0);
+            inner.name = data.name;
+            inner.tag = data.tag;
+            inner.condition = data.condition;
+        })
+    }
+}
+
+/// Serializes the equation, strength, name, tag, condition and weight of the constraint.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Constraint {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&ConstraintData::from(self), serializer)
+    }
+}
+
+/// Constraints are compared by identity, so every deserialized constraint is a new constraint,
+/// distinct from the one that was serialized.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Constraint {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data: ConstraintData = serde::Deserialize::deserialize(deserializer)?;
+        Ok(Constraint::from(data))
+    }
+}
+
+/// A stable identifier of a constraint, derived from its content, see [`Constraint::id`].
+///
+/// Ids are formatted as `c` followed by 16 hexadecimal digits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ConstraintId(u64);
+
+impl ConstraintId {
+    /// The id with the given value, as returned by [`ConstraintId::value`].
+    pub const fn from_value(value: u64) -> ConstraintId {
+        ConstraintId(value)
+    }
+
+    /// The value of the id.
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for ConstraintId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "c{:016x}", self.0)
+    }
+}
+
+/// The 64 bit FNV-1a hash, which unlike the hashers of the standard library and hashbrown is the
+/// same on every target and in every version.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> StableHasher {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Hash a float, with zero and negative zero hashing the same since they compare equal.
+    fn f64(&mut self, value: f64) {
+        self.u64(if value == 0.0 { 0 } else { value.to_bits() });
+    }
+}
+
+/// Two constraints keeping an expression within a range, created by [`Constraint::range`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeConstraint {
+    lower: Constraint,
+    upper: Constraint,
+}
+
+impl RangeConstraint {
+    /// The constraint keeping the expression above the lower bound.
+    pub fn lower(&self) -> &Constraint {
+        &self.lower
+    }
+
+    /// The constraint keeping the expression below the upper bound.
+    pub fn upper(&self) -> &Constraint {
+        &self.upper
+    }
+}
+
 /// This is an intermediate type used in the syntactic sugar for specifying constraints. You should
 /// not use it directly.
 pub struct PartialConstraint {
@@ -83,12 +801,105 @@ impl PartialConstraint {
     }
 }
 
+impl PartialConstraint {
+    /// Complete the constraint with its right hand side.
+    fn constrain(self, rhs: impl Into<Expression>) -> Constraint {
+        Self::complete(self.expression - rhs.into(), self.relation)
+    }
+
+    /// Complete the constraint with a borrowed right hand side, without cloning it.
+    fn constrain_ref(self, rhs: &Expression) -> Constraint {
+        Self::complete(self.expression - rhs, self.relation)
+    }
+
+    fn complete(expression: Expression, relation: WeightedRelation) -> Constraint {
+        let strict = relation.is_strict();
+        let (operator, strength) = relation.into();
+        Constraint::new(expression, operator, strength).with(|inner| inner.strict = strict)
+    }
+}
+
+/// A constraint waiting for its strength, created by the [`equals`](Expression::equals),
+/// [`leq`](Expression::leq) and [`geq`](Expression::geq) methods of expressions, variables and
+/// terms.
+///
+/// This is an alternative to the `|EQ(strength)|` syntax for code that avoids overloading the `|`
+/// operator. Converting the builder into a constraint without giving a strength makes it required.
+///
+/// ```
+/// use kasuari::{Solver, Strength, Variable};
+///
+/// let mut solver = Solver::new();
+/// let (left, width) = (Variable::new(), Variable::new());
+/// solver.add_constraint(width.geq(0.0).strength(Strength::REQUIRED)).unwrap();
+/// solver.add_constraint((left + width).leq(100.0).into()).unwrap();
+/// solver.add_constraint(width.equals(80.0).medium()).unwrap();
+/// solver.add_constraint(left.equals(40.0).weak()).unwrap();
+/// assert_eq!(solver.get_value(width), 80.0);
+/// assert_eq!(solver.get_value(left), 20.0);
+/// ```
+#[must_use]
+pub struct ConstraintBuilder {
+    expression: Expression,
+    relation: fn(Strength) -> WeightedRelation,
+}
+
+impl ConstraintBuilder {
+    /// A builder for the constraint `lhs relation rhs`.
+    pub(crate) fn new(
+        lhs: Expression,
+        relation: fn(Strength) -> WeightedRelation,
+        rhs: impl Into<Expression>,
+    ) -> ConstraintBuilder {
+        ConstraintBuilder {
+            expression: lhs - rhs.into(),
+            relation,
+        }
+    }
+
+    /// Complete the constraint with a strength.
+    pub fn strength(self, strength: Strength) -> Constraint {
+        PartialConstraint::complete(self.expression, (self.relation)(strength))
+    }
+
+    /// Complete the constraint with [`Strength::REQUIRED`].
+    pub fn required(self) -> Constraint {
+        self.strength(Strength::REQUIRED)
+    }
+
+    /// Complete the constraint with [`Strength::STRONG`].
+    pub fn strong(self) -> Constraint {
+        self.strength(Strength::STRONG)
+    }
+
+    /// Complete the constraint with [`Strength::MEDIUM`].
+    pub fn medium(self) -> Constraint {
+        self.strength(Strength::MEDIUM)
+    }
+
+    /// Complete the constraint with [`Strength::WEAK`].
+    pub fn weak(self) -> Constraint {
+        self.strength(Strength::WEAK)
+    }
+}
+
+impl From<ConstraintBuilder> for Constraint {
+    fn from(builder: ConstraintBuilder) -> Constraint {
+        builder.required()
+    }
+}
+
 impl ops::BitOr<f64> for PartialConstraint {
     type Output = Constraint;
     fn bitor(self, rhs: f64) -> Constraint {
-        let (operator, strength) = self.relation.into();
-        #[allow(clippy::suspicious_arithmetic_impl)]
-        Constraint::new(self.expression - rhs, operator, strength)
+        self.constrain(rhs)
+    }
+}
+
+impl ops::BitOr<&Expression> for PartialConstraint {
+    type Output = Constraint;
+    fn bitor(self, rhs: &Expression) -> Constraint {
+        self.constrain_ref(rhs)
     }
 }
 
@@ -102,26 +913,111 @@ impl ops::BitOr<f32> for PartialConstraint {
 impl ops::BitOr<Variable> for PartialConstraint {
     type Output = Constraint;
     fn bitor(self, rhs: Variable) -> Constraint {
-        let (operator, strength) = self.relation.into();
-        #[allow(clippy::suspicious_arithmetic_impl)]
-        Constraint::new(self.expression - rhs, operator, strength)
+        self.constrain(rhs)
     }
 }
 
 impl ops::BitOr<Term> for PartialConstraint {
     type Output = Constraint;
     fn bitor(self, rhs: Term) -> Constraint {
-        let (operator, strength) = self.relation.into();
-        #[allow(clippy::suspicious_arithmetic_impl)]
-        Constraint::new(self.expression - rhs, operator, strength)
+        self.constrain(rhs)
     }
 }
 
 impl ops::BitOr<Expression> for PartialConstraint {
     type Output = Constraint;
     fn bitor(self, rhs: Expression) -> Constraint {
-        let (operator, strength) = self.relation.into();
-        #[allow(clippy::suspicious_arithmetic_impl)]
-        Constraint::new(self.expression - rhs, operator, strength)
+        self.constrain(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn variables() {
+        let x = Variable::from_id(0);
+        let y = Variable::from_id(1);
+        let constraint = Constraint::new(
+            x + 2.0 * y - x + 3.0,
+            RelationalOperator::Equal,
+            Strength::REQUIRED,
+        );
+        assert_eq!(constraint.expr().terms.len(), 1);
+        assert_eq!(constraint.variables().collect::<Vec<_>>(), [y]);
+        let constraint = Constraint::new(
+            x + 2.0 * y + x + 3.0,
+            RelationalOperator::Equal,
+            Strength::REQUIRED,
+        );
+        assert_eq!(
+            constraint.expr().terms,
+            [Term::new(x, 2.0), Term::new(y, 2.0)]
+        );
+    }
+
+    #[test]
+    fn simplify_long_expression() {
+        let variables: Vec<Variable> = (0..40).map(Variable::from_id).collect();
+        let mut expression: Expression = variables.iter().map(|&v| Term::new(v, 1.0)).collect();
+        expression += variables
+            .iter()
+            .map(|&v| Term::new(v, 1.0))
+            .collect::<Expression>();
+        expression -= Expression::from(variables[7]) * 2.0;
+        expression.simplify();
+        assert_eq!(expression.terms.len(), 39);
+        assert!(expression.terms.iter().all(|term| term.coefficient == 2.0));
+        assert_eq!(expression.terms[7].variable, variables[8]);
+    }
+
+    #[test]
+    fn structurally_equals() {
+        let x = Variable::from_id(0);
+        let y = Variable::from_id(1);
+        let constraint = |expression: Expression, strength| {
+            Constraint::new(expression, RelationalOperator::GreaterOrEqual, strength)
+        };
+        let a = constraint(x + 2.0 * y - x + 3.0, Strength::REQUIRED);
+        assert!(a.structurally_equals(&a.clone()));
+        assert!(a.structurally_equals(&constraint(2.0 * y + 3.0, Strength::REQUIRED)));
+        assert!(!a.structurally_equals(&constraint(2.0 * y + 3.0, Strength::STRONG)));
+        assert!(!a.structurally_equals(&constraint(2.0 * y + 4.0, Strength::REQUIRED)));
+        assert!(!a.structurally_equals(&constraint(y + 1.5, Strength::REQUIRED)));
+        assert!(!a.structurally_equals(&Constraint::new(
+            2.0 * y + 3.0,
+            RelationalOperator::Equal,
+            Strength::REQUIRED
+        )));
+    }
+
+    #[test]
+    fn remap() {
+        let x = Variable::from_id(0);
+        let y = Variable::from_id(1);
+        let z = Variable::from_id(2);
+        let map = HashMap::from([(x, z)]);
+        let constraint = Constraint::new(
+            2.0 * x - y + 3.0,
+            RelationalOperator::LessOrEqual,
+            Strength::STRONG,
+        );
+        let remapped = constraint.remap(&map);
+        assert_ne!(remapped, constraint);
+        assert_eq!(remapped.expr(), 2.0 * z - y + 3.0);
+        assert_eq!(remapped.op(), RelationalOperator::LessOrEqual);
+        assert_eq!(remapped.strength(), Strength::STRONG);
+        assert!(constraint
+            .remap(&HashMap::new())
+            .structurally_equals(&constraint));
+
+        let labelled = constraint.with_name("gap").with_tag(3).when(4);
+        let remapped = labelled.remap(&map);
+        assert_eq!(remapped.tag(), Some(3));
+        assert_eq!(remapped.condition(), Some(4));
+        assert_eq!(remapped.name(), None);
     }
 }
@@ -1,6 +1,8 @@
+use alloc::string::String;
+
 use thiserror::Error;
 
-use crate::InternalSolverError;
+use crate::{InternalSolverError, Variable};
 
 /// The possible error conditions that `Solver::add_constraint` can fail with.
 #[derive(Debug, Copy, Clone, Error)]
@@ -14,6 +16,13 @@ pub enum AddConstraintError {
     #[error("The constraint is required, but it is unsatisfiable in conjunction with the existing constraints.")]
     UnsatisfiableConstraint,
 
+    /// The constraint refers to a variable in the namespace of the solver's
+    /// [`VariableCounter`](crate::VariableCounter) that the counter hasn't created yet, which would
+    /// collide with a variable created later by
+    /// [`Solver::new_variable`](crate::Solver::new_variable).
+    #[error("The variable {0} would collide with a variable created later by the solver.")]
+    VariableCollision(Variable),
+
     /// The solver entered an invalid state.
     #[error("The solver entered an invalid state. If this occurs please report the issue.")]
     InternalSolverError(#[from] InternalSolverError),
@@ -32,6 +41,42 @@ pub enum RemoveConstraintError {
     InternalSolverError(#[from] InternalSolverError),
 }
 
+/// The possible error conditions that `Solver::update_constant` can fail with.
+#[derive(Debug, Copy, Clone, Error)]
+pub enum UpdateConstantError {
+    /// The constraint specified was not already in the solver, so cannot be updated.
+    #[error("The constraint specified was not already in the solver, so cannot be updated.")]
+    UnknownConstraint,
+
+    /// The constraint is required, but the updated constraint is unsatisfiable in conjunction with
+    /// the other constraints. The constraint is left as it was.
+    #[error("The constraint is required, but the updated constraint is unsatisfiable in conjunction with the other constraints.")]
+    UnsatisfiableConstraint,
+
+    /// The solver entered an invalid state.
+    #[error("The solver entered an invalid state. If this occurs please report the issue.")]
+    InternalSolverError(#[from] InternalSolverError),
+}
+
+/// The possible error conditions that `Solver::set_enabled` can fail with.
+#[derive(Debug, Copy, Clone, Error)]
+pub enum SetEnabledError {
+    /// The constraint specified was not already in the solver, so cannot be enabled or disabled.
+    #[error(
+        "The constraint specified was not already in the solver, so cannot be enabled or disabled."
+    )]
+    UnknownConstraint,
+
+    /// The constraint is required, but it is unsatisfiable in conjunction with the enabled
+    /// constraints, so it stays disabled.
+    #[error("The constraint is required, but it is unsatisfiable in conjunction with the enabled constraints.")]
+    UnsatisfiableConstraint,
+
+    /// The solver entered an invalid state.
+    #[error("The solver entered an invalid state. If this occurs please report the issue.")]
+    InternalSolverError(#[from] InternalSolverError),
+}
+
 /// The possible error conditions that `Solver::add_edit_variable` can fail with.
 #[derive(Debug, Copy, Clone, Error)]
 pub enum AddEditVariableError {
@@ -42,6 +87,15 @@ pub enum AddEditVariableError {
     /// The specified strength was `REQUIRED`. This is illegal for edit variable strengths.
     #[error("The specified strength was `REQUIRED`. This is illegal for edit variable strengths.")]
     BadRequiredStrength,
+
+    /// The variable is in the namespace of the solver's [`VariableCounter`](crate::VariableCounter)
+    /// but the counter hasn't created it yet, see [`AddConstraintError::VariableCollision`].
+    #[error("The variable {0} would collide with a variable created later by the solver.")]
+    VariableCollision(Variable),
+
+    /// The solver entered an invalid state.
+    #[error("The solver entered an invalid state. If this occurs please report the issue.")]
+    InternalSolverError(#[from] InternalSolverError),
 }
 
 /// The possible error conditions that `Solver::remove_edit_variable` can fail with.
@@ -74,3 +128,162 @@ pub enum SuggestValueError {
     #[error("The solver entered an invalid state. If this occurs please report the issue.")]
     InternalSolverError(#[from] InternalSolverError),
 }
+
+/// The possible error conditions that `Solver::suggest_or_add` can fail with.
+#[derive(Debug, Copy, Clone, Error)]
+pub enum SuggestOrAddError {
+    /// The specified strength was `REQUIRED`. This is illegal for edit variable strengths.
+    #[error("The specified strength was `REQUIRED`. This is illegal for edit variable strengths.")]
+    BadRequiredStrength,
+
+    /// The variable would collide with a variable created later by the solver, see
+    /// [`AddEditVariableError::VariableCollision`].
+    #[error("The variable {0} would collide with a variable created later by the solver.")]
+    VariableCollision(Variable),
+
+    /// The solver entered an invalid state. If this occurs please report the issue.
+    #[error("The solver entered an invalid state. If this occurs please report the issue.")]
+    InternalSolverError(#[from] InternalSolverError),
+}
+
+/// The possible error conditions that replaying a [`SolverOp`](crate::SolverOp) can fail with.
+#[derive(Debug, Copy, Clone, Error)]
+pub enum SolverOpError {
+    /// Adding a constraint failed.
+    #[error(transparent)]
+    AddConstraint(#[from] AddConstraintError),
+
+    /// Removing a constraint failed.
+    #[error(transparent)]
+    RemoveConstraint(#[from] RemoveConstraintError),
+
+    /// Adding an edit variable failed.
+    #[error(transparent)]
+    AddEditVariable(#[from] AddEditVariableError),
+
+    /// Removing an edit variable failed.
+    #[error(transparent)]
+    RemoveEditVariable(#[from] RemoveEditVariableError),
+
+    /// Suggesting a value failed.
+    #[error(transparent)]
+    SuggestValue(#[from] SuggestValueError),
+
+    /// Enabling or disabling a constraint failed.
+    #[error(transparent)]
+    SetEnabled(#[from] SetEnabledError),
+
+    /// The solver entered an invalid state.
+    #[error("The solver entered an invalid state. If this occurs please report the issue.")]
+    InternalSolverError(#[from] InternalSolverError),
+}
+
+/// The possible error conditions that `Solver::from_state` can fail with.
+#[derive(Debug, Copy, Clone, Error)]
+pub enum RestoreStateError {
+    /// The state refers to a constraint or symbol that it doesn't hold.
+    #[error("The state refers to a constraint or symbol that it doesn't hold.")]
+    InvalidState,
+
+    /// The state refers to a variable in the namespace of its counter that the counter hadn't
+    /// created, which would collide with a variable created later by the restored solver.
+    #[error("The variable {0} would collide with a variable created later by the solver.")]
+    VariableCollision(Variable),
+
+    /// Adding the constraints or edit variables of a state without a tableau failed.
+    #[error(transparent)]
+    Solver(#[from] SolverOpError),
+}
+
+/// The possible error conditions that `SolverState::from_bytes` can fail with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+pub enum DecodeStateError {
+    /// The bytes don't start like an encoded state.
+    #[error("The bytes are not an encoded solver state.")]
+    NotAState,
+
+    /// The state was encoded with a version of the format that this version can't read.
+    #[error("The state was encoded with the unsupported format version {0}.")]
+    UnsupportedVersion(u8),
+
+    /// The bytes end in the middle of the state.
+    #[error("The encoded state is truncated.")]
+    Truncated,
+
+    /// The bytes hold an invalid value, which ends at the given offset.
+    #[error("The encoded state holds an invalid value before byte {0}.")]
+    InvalidValue(usize),
+}
+
+/// The possible error conditions that `FixedSolver` operations can fail with.
+#[cfg(feature = "heapless")]
+#[derive(Debug, Copy, Clone, Error)]
+pub enum FixedSolverError {
+    /// The operation needs more distinct variables than the solver has room for.
+    #[error("The operation needs more distinct variables than the solver has room for.")]
+    VariableCapacityExceeded,
+
+    /// The operation needs more constraints than the solver has room for.
+    #[error("The operation needs more constraints than the solver has room for.")]
+    ConstraintCapacityExceeded,
+
+    /// The constraint specified was not already in the solver, so cannot be removed.
+    #[error("The constraint specified was not already in the solver, so cannot be removed.")]
+    UnknownConstraint,
+
+    /// The constraint is required, but it is unsatisfiable in conjunction with the existing
+    /// constraints.
+    #[error("The constraint is required, but it is unsatisfiable in conjunction with the existing constraints.")]
+    UnsatisfiableConstraint,
+
+    /// The specified variable is already marked as an edit variable in the solver.
+    #[error("The specified variable is already marked as an edit variable in the solver.")]
+    DuplicateEditVariable,
+
+    /// The specified strength was `REQUIRED`. This is illegal for edit variable strengths.
+    #[error("The specified strength was `REQUIRED`. This is illegal for edit variable strengths.")]
+    BadRequiredStrength,
+
+    /// The specified variable was not an edit variable in the solver.
+    #[error("The specified variable was not an edit variable in the solver.")]
+    UnknownEditVariable,
+
+    /// The solver entered an invalid state.
+    #[error("The solver entered an invalid state. If this occurs please report the issue.")]
+    InternalSolverError(#[from] InternalSolverError),
+}
+
+/// The possible error conditions that `LayoutSpec::compile` can fail with.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LayoutSpecError {
+    /// More than one element of the spec has the given name.
+    #[error("More than one element of the spec is named `{0}`.")]
+    DuplicateElement(String),
+
+    /// A relation refers to an element that the spec does not define.
+    #[error("A relation refers to the element `{0}`, which is not defined.")]
+    UnknownElement(String),
+}
+
+/// The possible error conditions that `ImportedProgram::from_lp` and `ImportedProgram::from_mps`
+/// can fail with.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ImportError {
+    /// A line of the text is not valid in its section.
+    #[error("Line {0} is not valid in its section.")]
+    InvalidLine(usize),
+
+    /// A line of MPS text refers to a row that the ROWS section does not define.
+    #[error("Line {line} refers to the row `{row}`, which is not defined.")]
+    UnknownRow {
+        /// The number of the line, starting at one.
+        line: usize,
+        /// The name of the row.
+        row: String,
+    },
+
+    /// A column must take integer values, which constraints can't express.
+    #[error("The column `{0}` is an integer column, which is not supported.")]
+    IntegerColumn(String),
+}
@@ -1,8 +1,12 @@
 use alloc::vec;
 use alloc::vec::Vec;
-use core::ops;
+use core::{fmt, iter, ops};
 
-use crate::{Term, Variable};
+use hashbrown::HashMap;
+
+use crate::names::WithNames;
+use crate::row::near_zero;
+use crate::{ConstraintBuilder, NameRegistry, Term, Variable, WeightedRelation};
 
 /// An expression that can be the left hand or right hand side of a constraint equation.
 ///
@@ -13,6 +17,7 @@ use crate::{Term, Variable};
 /// expression = term_1 + term_2 + ... + term_n + constant
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Expression {
     /// The terms in the expression.
     pub terms: Vec<Term>,
@@ -45,6 +50,45 @@ impl Expression {
         }
     }
 
+    /// Constructs an expression from pairs of variables and their coefficients, plus a constant.
+    ///
+    /// This suits code that generates constraints from data rather than with operators.
+    /// Expressions can also be collected from an iterator of pairs, with a constant of zero.
+    ///
+    /// ```
+    /// use kasuari::{Expression, Term, Variable};
+    ///
+    /// let (x, y) = (Variable::new(), Variable::new());
+    /// let expression = Expression::from_pairs(&[(x, 2.0), (y, -1.0)], 5.0);
+    /// assert_eq!(expression, 2.0 * x - y + 5.0);
+    /// let collected: Expression = [(x, 2.0), (y, -1.0)].into_iter().collect();
+    /// assert_eq!(collected.terms, [Term::new(x, 2.0), Term::new(y, -1.0)]);
+    /// ```
+    pub fn from_pairs(pairs: &[(Variable, f64)], constant: f64) -> Expression {
+        let terms = pairs
+            .iter()
+            .map(|&(variable, coefficient)| Term::new(variable, coefficient))
+            .collect();
+        Expression::new(terms, constant)
+    }
+
+    /// The sum of some variables, terms or expressions.
+    ///
+    /// Expressions also implement [`Sum`](core::iter::Sum), so an iterator of any of these can
+    /// be summed with `.sum::<Expression>()` instead.
+    ///
+    /// ```
+    /// use kasuari::{Expression, Variable};
+    ///
+    /// let widths = [Variable::new(), Variable::new(), Variable::new()];
+    /// let total: Expression = widths.iter().copied().sum();
+    /// assert_eq!(total, Expression::sum(widths));
+    /// assert_eq!(total.terms.len(), 3);
+    /// ```
+    pub fn sum<E: Into<Expression>>(iter: impl IntoIterator<Item = E>) -> Expression {
+        iter.into_iter().map(Into::into).sum()
+    }
+
     /// Constructs an expression from a single term.
     ///
     /// ```text
@@ -82,6 +126,94 @@ impl Expression {
             constant: 0.0,
         }
     }
+
+    /// A copy of the expression with each variable replaced by the variable it maps to, if any.
+    ///
+    /// Variables that are not in the map are kept.
+    pub fn remap(&self, map: &HashMap<Variable, Variable>) -> Expression {
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| {
+                let variable = map.get(&term.variable).copied().unwrap_or(term.variable);
+                Term::new(variable, term.coefficient)
+            })
+            .collect();
+        Expression::new(terms, self.constant)
+    }
+
+    /// Combine the terms of each variable into one, and drop the terms whose coefficients are
+    /// (nearly) zero.
+    ///
+    /// Expressions built with operators keep every term they were built from, so `a + b - a` has
+    /// three terms. Simplified, it has the single term `b`, in the position of the first term of
+    /// each variable. [`Constraint::new`](crate::Constraint::new) simplifies its expression.
+    ///
+    /// ```
+    /// use kasuari::{Expression, Term, Variable};
+    ///
+    /// let (a, b) = (Variable::new(), Variable::new());
+    /// let mut expression: Expression = a + 2.0 * b - a + 3.0;
+    /// expression.simplify();
+    /// assert_eq!(expression.terms, [Term::new(b, 2.0)]);
+    /// assert_eq!(expression.constant, 3.0);
+    /// ```
+    pub fn simplify(&mut self) {
+        let mut simplified: Vec<Term> = Vec::with_capacity(self.terms.len());
+        // Looking up terms by scanning is faster for the short expressions that are typical.
+        let mut index: HashMap<Variable, usize> = HashMap::new();
+        for term in self.terms.drain(..) {
+            let existing = if simplified.len() < 16 {
+                simplified.iter().position(|t| t.variable == term.variable)
+            } else {
+                if index.is_empty() {
+                    index.extend(simplified.iter().enumerate().map(|(i, t)| (t.variable, i)));
+                }
+                let next = simplified.len();
+                Some(*index.entry(term.variable).or_insert(next)).filter(|&i| i < next)
+            };
+            match existing {
+                Some(i) => simplified[i].coefficient += term.coefficient,
+                None => simplified.push(term),
+            }
+        }
+        simplified.retain(|term| !near_zero(term.coefficient));
+        self.terms = simplified;
+    }
+
+    /// Format the expression using the names in the registry, e.g. `2 * x + y + 4`.
+    ///
+    /// The [`Display`](fmt::Display) implementation formats it without names, e.g.
+    /// `2 * v0 + v1 + 4`.
+    pub fn display_with<'a>(&'a self, names: &'a NameRegistry) -> impl fmt::Display + 'a {
+        WithNames { value: self, names }
+    }
+
+    /// Start a constraint that this expression equals another, without the `|EQ(strength)|`
+    /// syntax. See [`ConstraintBuilder`] for giving it a strength.
+    ///
+    /// It is not called `eq`, which would take precedence over [`PartialEq::eq`] in method calls.
+    pub fn equals(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        ConstraintBuilder::new(self, WeightedRelation::EQ, rhs)
+    }
+
+    /// Start a constraint that this expression is less than or equal to another, see
+    /// [`Expression::equals`].
+    pub fn leq(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        ConstraintBuilder::new(self, WeightedRelation::LE, rhs)
+    }
+
+    /// Start a constraint that this expression is greater than or equal to another, see
+    /// [`Expression::equals`].
+    pub fn geq(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        ConstraintBuilder::new(self, WeightedRelation::GE, rhs)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        NameRegistry::new().write_expression(f, self)
+    }
 }
 
 impl From<f64> for Expression {
@@ -114,6 +246,35 @@ impl FromIterator<Term> for Expression {
     }
 }
 
+impl FromIterator<(Variable, f64)> for Expression {
+    fn from_iter<I: IntoIterator<Item = (Variable, f64)>>(iter: I) -> Self {
+        iter.into_iter()
+            .map(|(variable, coefficient)| Term::new(variable, coefficient))
+            .collect()
+    }
+}
+
+impl iter::Sum<Expression> for Expression {
+    fn sum<I: Iterator<Item = Expression>>(iter: I) -> Expression {
+        iter.fold(Expression::from_constant(0.0), |mut sum, expression| {
+            sum += expression;
+            sum
+        })
+    }
+}
+
+impl iter::Sum<Term> for Expression {
+    fn sum<I: Iterator<Item = Term>>(iter: I) -> Expression {
+        iter.collect()
+    }
+}
+
+impl iter::Sum<Variable> for Expression {
+    fn sum<I: Iterator<Item = Variable>>(iter: I) -> Expression {
+        iter.map(Term::from_variable).collect()
+    }
+}
+
 impl ops::Neg for Expression {
     type Output = Expression;
 
@@ -361,3 +522,139 @@ impl ops::SubAssign<Expression> for Expression {
         self.constant += rhs.constant;
     }
 }
+
+impl ops::Neg for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn neg(self) -> Expression {
+        self * -1.0
+    }
+}
+
+impl ops::Mul<f64> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn mul(self, rhs: f64) -> Expression {
+        let terms = self.terms.iter().map(|&term| term * rhs).collect();
+        Expression::new(terms, self.constant * rhs)
+    }
+}
+
+impl ops::Add<f64> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, rhs: f64) -> Expression {
+        Expression::new(self.terms.clone(), self.constant + rhs)
+    }
+}
+
+impl ops::Sub<f64> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, rhs: f64) -> Expression {
+        Expression::new(self.terms.clone(), self.constant - rhs)
+    }
+}
+
+impl ops::Add<&Expression> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, rhs: &Expression) -> Expression {
+        let mut terms = Vec::with_capacity(self.terms.len() + rhs.terms.len());
+        terms.extend_from_slice(&self.terms);
+        terms.extend_from_slice(&rhs.terms);
+        Expression::new(terms, self.constant + rhs.constant)
+    }
+}
+
+impl ops::Sub<&Expression> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, rhs: &Expression) -> Expression {
+        let mut terms = Vec::with_capacity(self.terms.len() + rhs.terms.len());
+        terms.extend_from_slice(&self.terms);
+        terms.extend(rhs.terms.iter().map(|&term| -term));
+        Expression::new(terms, self.constant - rhs.constant)
+    }
+}
+
+impl ops::Add<&Expression> for Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn add(mut self, rhs: &Expression) -> Expression {
+        self += rhs;
+        self
+    }
+}
+
+impl ops::AddAssign<&Expression> for Expression {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Expression) {
+        self.terms.extend_from_slice(&rhs.terms);
+        self.constant += rhs.constant;
+    }
+}
+
+impl ops::Sub<&Expression> for Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(mut self, rhs: &Expression) -> Expression {
+        self -= rhs;
+        self
+    }
+}
+
+impl ops::SubAssign<&Expression> for Expression {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Expression) {
+        self.terms.extend(rhs.terms.iter().map(|&term| -term));
+        self.constant -= rhs.constant;
+    }
+}
+
+impl ops::Add<Variable> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, rhs: Variable) -> Expression {
+        self + Term::from_variable(rhs)
+    }
+}
+
+impl ops::Sub<Variable> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, rhs: Variable) -> Expression {
+        self + Term::new(rhs, -1.0)
+    }
+}
+
+impl ops::Add<Term> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, rhs: Term) -> Expression {
+        let mut terms = Vec::with_capacity(self.terms.len() + 1);
+        terms.extend_from_slice(&self.terms);
+        terms.push(rhs);
+        Expression::new(terms, self.constant)
+    }
+}
+
+impl ops::Sub<Term> for &Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, rhs: Term) -> Expression {
+        self + -rhs
+    }
+}
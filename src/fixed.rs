@@ -0,0 +1,871 @@
+//! A fixed-capacity variant of the solver that never allocates.
+//!
+//! [`FixedSolver`] implements the same Cassowary algorithm as [`Solver`](crate::Solver), but keeps
+//! its whole tableau in arrays whose sizes are chosen with const generics. Instead of growing, it
+//! returns [`FixedSolverError::VariableCapacityExceeded`] or
+//! [`FixedSolverError::ConstraintCapacityExceeded`] when an operation does not fit. This makes it
+//! suitable for firmware and other environments without a heap.
+//!
+//! Constraints are passed in as slices of [`Term`]s, so no [`Constraint`] (which allocates) needs
+//! to be built. [`FixedSolver::add_constraint`] is provided for convenience when one is available.
+
+use crate::row::{near_zero, SymbolKind};
+use crate::solver::DEFAULT_STRICT_EPSILON;
+use crate::{
+    Constraint, FixedSolverError, InternalSolverError, RelationalOperator, Strength, Term, Variable,
+};
+
+/// A column of the dense tableau.
+///
+/// `Marker` and `Other` are the two symbols a constraint may introduce (slack/error/dummy), and
+/// are indexed by the slot of the constraint that owns them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Sym {
+    Invalid,
+    External(usize),
+    Marker(usize),
+    Other(usize),
+    Artificial,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Row<const VARS: usize, const CONSTRAINTS: usize> {
+    external: [f64; VARS],
+    constraint: [[f64; 2]; CONSTRAINTS],
+    artificial: f64,
+    constant: f64,
+}
+
+impl<const VARS: usize, const CONSTRAINTS: usize> Row<VARS, CONSTRAINTS> {
+    const fn new(constant: f64) -> Self {
+        Row {
+            external: [0.0; VARS],
+            constraint: [[0.0; 2]; CONSTRAINTS],
+            artificial: 0.0,
+            constant,
+        }
+    }
+
+    fn cell(&self, s: Sym) -> f64 {
+        match s {
+            Sym::External(i) => self.external[i],
+            Sym::Marker(i) => self.constraint[i][0],
+            Sym::Other(i) => self.constraint[i][1],
+            Sym::Artificial => self.artificial,
+            Sym::Invalid => 0.0,
+        }
+    }
+
+    fn cell_mut(&mut self, s: Sym) -> &mut f64 {
+        match s {
+            Sym::External(i) => &mut self.external[i],
+            Sym::Marker(i) => &mut self.constraint[i][0],
+            Sym::Other(i) => &mut self.constraint[i][1],
+            Sym::Artificial => &mut self.artificial,
+            Sym::Invalid => unreachable!(),
+        }
+    }
+
+    /// The non-zero cells of the row, in a fixed order.
+    fn cells(&self) -> impl Iterator<Item = (Sym, f64)> + '_ {
+        let external = (0..VARS).map(|i| (Sym::External(i), self.external[i]));
+        let constraint = (0..CONSTRAINTS).flat_map(|i| {
+            [
+                (Sym::Marker(i), self.constraint[i][0]),
+                (Sym::Other(i), self.constraint[i][1]),
+            ]
+        });
+        external
+            .chain(constraint)
+            .chain(core::iter::once((Sym::Artificial, self.artificial)))
+            .filter(|&(_, coefficient)| coefficient != 0.0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cells().next().is_none()
+    }
+
+    fn insert_symbol(&mut self, s: Sym, coefficient: f64) {
+        let cell = self.cell_mut(s);
+        *cell += coefficient;
+        if near_zero(*cell) {
+            *cell = 0.0;
+        }
+    }
+
+    fn insert_row(&mut self, other: &Self, coefficient: f64) -> bool {
+        let constant_diff = other.constant * coefficient;
+        self.constant += constant_diff;
+        for (s, v) in other.cells() {
+            self.insert_symbol(s, v * coefficient);
+        }
+        constant_diff != 0.0
+    }
+
+    fn reverse_sign(&mut self) {
+        self.constant = -self.constant;
+        for v in self
+            .external
+            .iter_mut()
+            .chain(self.constraint.iter_mut().flatten())
+        {
+            *v = -*v;
+        }
+        self.artificial = -self.artificial;
+    }
+
+    fn solve_for_symbol(&mut self, s: Sym) {
+        let cell = self.cell_mut(s);
+        let coeff = -1.0 / *cell;
+        *cell = 0.0;
+        self.constant *= coeff;
+        for v in self
+            .external
+            .iter_mut()
+            .chain(self.constraint.iter_mut().flatten())
+        {
+            *v *= coeff;
+        }
+        self.artificial *= coeff;
+    }
+
+    fn solve_for_symbols(&mut self, lhs: Sym, rhs: Sym) {
+        self.insert_symbol(lhs, -1.0);
+        self.solve_for_symbol(rhs);
+    }
+
+    fn substitute(&mut self, s: Sym, row: &Self) -> bool {
+        let coeff = self.cell(s);
+        if coeff != 0.0 {
+            *self.cell_mut(s) = 0.0;
+            self.insert_row(row, coeff)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct VarSlot {
+    variable: Variable,
+    refs: usize,
+    value: f64,
+    changed: bool,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct ConstraintSlot<const VARS: usize> {
+    id: u32,
+    strength: Strength,
+    kinds: [SymbolKind; 2],
+    uses: [bool; VARS],
+}
+
+#[derive(Debug, Copy, Clone)]
+struct EditSlot {
+    variable: Variable,
+    constraint: usize,
+    constant: f64,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Objective {
+    Main,
+    Artificial,
+}
+
+/// Identifies a constraint that has been added to a [`FixedSolver`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FixedConstraint {
+    slot: usize,
+    id: u32,
+}
+
+/// A constraint solver using the Cassowary algorithm that is backed by fixed-size arrays.
+///
+/// `VARS` is the maximum number of distinct variables the solver can reference at once, and
+/// `CONSTRAINTS` is the maximum number of constraints (including one per edit variable). The
+/// memory used by the solver grows with `VARS * CONSTRAINTS`, so keep both as small as the layout
+/// allows.
+#[derive(Debug, Clone)]
+pub struct FixedSolver<const VARS: usize, const CONSTRAINTS: usize> {
+    vars: [Option<VarSlot>; VARS],
+    constraints: [Option<ConstraintSlot<VARS>>; CONSTRAINTS],
+    edits: [Option<EditSlot>; VARS],
+    rows: [Option<(Sym, Row<VARS, CONSTRAINTS>)>; CONSTRAINTS],
+    infeasible: [[bool; 2]; CONSTRAINTS],
+    objective: Row<VARS, CONSTRAINTS>,
+    artificial: Option<Row<VARS, CONSTRAINTS>>,
+    id_tick: u32,
+}
+
+impl<const VARS: usize, const CONSTRAINTS: usize> Default for FixedSolver<VARS, CONSTRAINTS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const VARS: usize, const CONSTRAINTS: usize> FixedSolver<VARS, CONSTRAINTS> {
+    /// Construct a new, empty solver.
+    pub const fn new() -> Self {
+        FixedSolver {
+            vars: [None; VARS],
+            constraints: [None; CONSTRAINTS],
+            edits: [None; VARS],
+            rows: [None; CONSTRAINTS],
+            infeasible: [[false; 2]; CONSTRAINTS],
+            objective: Row::new(0.0),
+            artificial: None,
+            id_tick: 0,
+        }
+    }
+
+    /// Add a constraint to the solver.
+    ///
+    /// The constraint's expression is copied into the tableau; the constraint itself is not
+    /// retained. Strict inequalities are kept `1e-6` from equality.
+    pub fn add_constraint(
+        &mut self,
+        constraint: &Constraint,
+    ) -> Result<FixedConstraint, FixedSolverError> {
+        let offset = match constraint.op() {
+            _ if !constraint.is_strict() => 0.0,
+            RelationalOperator::LessOrEqual => DEFAULT_STRICT_EPSILON,
+            RelationalOperator::GreaterOrEqual => -DEFAULT_STRICT_EPSILON,
+            RelationalOperator::Equal => 0.0,
+        };
+        self.add_terms(
+            constraint.terms(),
+            constraint.constant() + offset,
+            constraint.op(),
+            match constraint.strength() {
+                Strength::REQUIRED => Strength::REQUIRED,
+                strength => strength * constraint.weight(),
+            },
+        )
+    }
+
+    /// Add the constraint `terms + constant op 0.0` with the given strength to the solver.
+    pub fn add_terms(
+        &mut self,
+        terms: &[Term],
+        constant: f64,
+        operator: RelationalOperator,
+        strength: Strength,
+    ) -> Result<FixedConstraint, FixedSolverError> {
+        let slot = self
+            .constraints
+            .iter()
+            .position(Option::is_none)
+            .ok_or(FixedSolverError::ConstraintCapacityExceeded)?;
+        self.reserve_variables(terms)?;
+
+        let (mut row, kinds) = self.create_row(slot, terms, constant, operator, strength);
+        let id = self.id_tick;
+        self.id_tick = self.id_tick.wrapping_add(1);
+        let mut subject = self.choose_subject(&row, kinds);
+
+        if subject == Sym::Invalid && self.all_dummies(&row) {
+            if !near_zero(row.constant) {
+                self.release_variables(&self.constraints[slot].unwrap().uses);
+                self.constraints[slot] = None;
+                return Err(FixedSolverError::UnsatisfiableConstraint);
+            }
+            subject = Sym::Marker(slot);
+        }
+
+        if subject == Sym::Invalid {
+            if !self.add_with_artificial_variable(&row)? {
+                let _ = self.remove_slot(slot);
+                return Err(FixedSolverError::UnsatisfiableConstraint);
+            }
+        } else {
+            row.solve_for_symbol(subject);
+            self.substitute(subject, &row);
+            self.insert_row(subject, row);
+        }
+
+        self.constraints[slot].as_mut().unwrap().id = id;
+        self.optimize(Objective::Main)?;
+        Ok(FixedConstraint { slot, id })
+    }
+
+    /// Remove a constraint from the solver.
+    pub fn remove_constraint(
+        &mut self,
+        constraint: FixedConstraint,
+    ) -> Result<(), FixedSolverError> {
+        if !self.has_constraint(constraint) {
+            return Err(FixedSolverError::UnknownConstraint);
+        }
+        self.remove_slot(constraint.slot)
+    }
+
+    /// Test whether a constraint is currently in the solver.
+    pub fn has_constraint(&self, constraint: FixedConstraint) -> bool {
+        self.constraints[constraint.slot].is_some_and(|slot| slot.id == constraint.id)
+    }
+
+    /// Add an edit variable to the solver.
+    ///
+    /// This method should be called before the `suggest_value` method is used to supply a
+    /// suggested value for the given edit variable.
+    pub fn add_edit_variable(
+        &mut self,
+        v: Variable,
+        strength: Strength,
+    ) -> Result<(), FixedSolverError> {
+        if self.has_edit_variable(v) {
+            return Err(FixedSolverError::DuplicateEditVariable);
+        }
+        if strength == Strength::REQUIRED {
+            return Err(FixedSolverError::BadRequiredStrength);
+        }
+        let edit = self
+            .edits
+            .iter()
+            .position(Option::is_none)
+            .ok_or(FixedSolverError::VariableCapacityExceeded)?;
+        let constraint = self.add_terms(
+            &[Term::from_variable(v)],
+            0.0,
+            RelationalOperator::Equal,
+            strength,
+        )?;
+        self.edits[edit] = Some(EditSlot {
+            variable: v,
+            constraint: constraint.slot,
+            constant: 0.0,
+        });
+        Ok(())
+    }
+
+    /// Remove an edit variable from the solver.
+    pub fn remove_edit_variable(&mut self, v: Variable) -> Result<(), FixedSolverError> {
+        let edit = self
+            .edit_index(v)
+            .ok_or(FixedSolverError::UnknownEditVariable)?;
+        let constraint = self.edits[edit].take().unwrap().constraint;
+        self.remove_slot(constraint)
+    }
+
+    /// Test whether an edit variable has been added to the solver.
+    pub fn has_edit_variable(&self, v: Variable) -> bool {
+        self.edit_index(v).is_some()
+    }
+
+    /// Suggest a value for the given edit variable.
+    ///
+    /// This method should be used after an edit variable has been added to the solver in order
+    /// to suggest the value for that variable.
+    pub fn suggest_value(
+        &mut self,
+        variable: Variable,
+        value: f64,
+    ) -> Result<(), FixedSolverError> {
+        let edit = self
+            .edit_index(variable)
+            .ok_or(FixedSolverError::UnknownEditVariable)?;
+        let info = self.edits[edit].as_mut().unwrap();
+        let delta = value - info.constant;
+        info.constant = value;
+        let marker = Sym::Marker(info.constraint);
+        let other = Sym::Other(info.constraint);
+
+        if let Some(index) = self.row_index(marker) {
+            let row = &mut self.rows[index].as_mut().unwrap().1;
+            row.constant -= delta;
+            if row.constant < 0.0 {
+                self.mark_infeasible(marker);
+            }
+        } else if let Some(index) = self.row_index(other) {
+            let row = &mut self.rows[index].as_mut().unwrap().1;
+            row.constant += delta;
+            if row.constant < 0.0 {
+                self.mark_infeasible(other);
+            }
+        } else {
+            for index in 0..CONSTRAINTS {
+                let Some((symbol, row)) = self.rows[index].as_mut() else {
+                    continue;
+                };
+                let coeff = row.cell(marker);
+                if coeff != 0.0 {
+                    row.constant += delta * coeff;
+                    let (symbol, constant) = (*symbol, row.constant);
+                    if constant < 0.0 && !matches!(symbol, Sym::External(_)) {
+                        self.mark_infeasible(symbol);
+                    }
+                }
+            }
+        }
+        self.dual_optimize()?;
+        Ok(())
+    }
+
+    /// Fetches all changes to the values of variables since the last call to this function.
+    ///
+    /// Each change comprises the variable changed and the new value of that variable.
+    pub fn fetch_changes(&mut self) -> impl Iterator<Item = (Variable, f64)> + '_ {
+        for index in 0..VARS {
+            let new_value = self.value_at(index);
+            if let Some(slot) = self.vars[index].as_mut() {
+                slot.changed = slot.value != new_value;
+                slot.value = new_value;
+            }
+        }
+        self.vars
+            .iter()
+            .flatten()
+            .filter(|slot| slot.changed)
+            .map(|slot| (slot.variable, slot.value))
+    }
+
+    /// Get the current value of a variable.
+    ///
+    /// Variables that are not referenced by any constraint have a value of zero.
+    pub fn get_value(&self, v: Variable) -> f64 {
+        self.var_index(v).map_or(0.0, |index| self.value_at(index))
+    }
+
+    /// Reset the solver to the empty starting condition.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn value_at(&self, index: usize) -> f64 {
+        self.row_index(Sym::External(index))
+            .map_or(0.0, |row| self.rows[row].as_ref().unwrap().1.constant)
+    }
+
+    fn var_index(&self, v: Variable) -> Option<usize> {
+        self.vars
+            .iter()
+            .position(|slot| slot.is_some_and(|slot| slot.variable == v))
+    }
+
+    fn edit_index(&self, v: Variable) -> Option<usize> {
+        self.edits
+            .iter()
+            .position(|slot| slot.is_some_and(|slot| slot.variable == v))
+    }
+
+    fn row_index(&self, s: Sym) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| row.is_some_and(|(symbol, _)| symbol == s))
+    }
+
+    fn kind(&self, s: Sym) -> SymbolKind {
+        match s {
+            Sym::Invalid => SymbolKind::Invalid,
+            Sym::External(_) => SymbolKind::External,
+            Sym::Marker(i) => self.constraints[i].map_or(SymbolKind::Invalid, |c| c.kinds[0]),
+            Sym::Other(i) => self.constraints[i].map_or(SymbolKind::Invalid, |c| c.kinds[1]),
+            Sym::Artificial => SymbolKind::Slack,
+        }
+    }
+
+    fn mark_infeasible(&mut self, s: Sym) {
+        match s {
+            Sym::Marker(i) => self.infeasible[i][0] = true,
+            Sym::Other(i) => self.infeasible[i][1] = true,
+            _ => {}
+        }
+    }
+
+    fn insert_row(&mut self, s: Sym, row: Row<VARS, CONSTRAINTS>) {
+        // There is always a free slot: the tableau holds at most one row per constraint.
+        let free = self.rows.iter().position(Option::is_none).unwrap();
+        self.rows[free] = Some((s, row));
+    }
+
+    fn take_row(&mut self, index: usize) -> (Sym, Row<VARS, CONSTRAINTS>) {
+        self.rows[index].take().unwrap()
+    }
+
+    /// Make sure every variable in `terms` has a slot, failing before anything is modified.
+    fn reserve_variables(&mut self, terms: &[Term]) -> Result<(), FixedSolverError> {
+        let mut missing = 0;
+        for (i, term) in terms.iter().enumerate() {
+            let seen = terms[..i].iter().any(|t| t.variable == term.variable);
+            if !seen && self.var_index(term.variable).is_none() {
+                missing += 1;
+            }
+        }
+        if missing > self.vars.iter().filter(|slot| slot.is_none()).count() {
+            return Err(FixedSolverError::VariableCapacityExceeded);
+        }
+        Ok(())
+    }
+
+    /// Get the slot for a variable, creating one if needed. Capacity must have been reserved.
+    fn var_slot(&mut self, v: Variable) -> usize {
+        if let Some(index) = self.var_index(v) {
+            return index;
+        }
+        let index = self.vars.iter().position(Option::is_none).unwrap();
+        self.vars[index] = Some(VarSlot {
+            variable: v,
+            refs: 0,
+            value: 0.0,
+            changed: false,
+        });
+        index
+    }
+
+    fn release_variables(&mut self, uses: &[bool; VARS]) {
+        for (index, _) in uses.iter().enumerate().filter(|(_, &used)| used) {
+            let slot = self.vars[index].as_mut().unwrap();
+            slot.refs -= 1;
+            if slot.refs == 0 {
+                self.vars[index] = None;
+                let symbol = Sym::External(index);
+                if let Some(row) = self.row_index(symbol) {
+                    self.rows[row] = None;
+                }
+                for (_, row) in self.rows.iter_mut().flatten() {
+                    *row.cell_mut(symbol) = 0.0;
+                }
+                *self.objective.cell_mut(symbol) = 0.0;
+            }
+        }
+    }
+
+    /// Create a new row for a constraint in the given slot, and record the slot.
+    fn create_row(
+        &mut self,
+        slot: usize,
+        terms: &[Term],
+        constant: f64,
+        operator: RelationalOperator,
+        strength: Strength,
+    ) -> (Row<VARS, CONSTRAINTS>, [SymbolKind; 2]) {
+        let mut row = Row::new(constant);
+        let mut uses = [false; VARS];
+
+        for term in terms {
+            if !near_zero(term.coefficient) {
+                let index = self.var_slot(term.variable);
+                if !uses[index] {
+                    uses[index] = true;
+                    self.vars[index].as_mut().unwrap().refs += 1;
+                }
+                let symbol = Sym::External(index);
+                if let Some(other) = self.row_index(symbol) {
+                    let other = self.rows[other].unwrap().1;
+                    row.insert_row(&other, term.coefficient);
+                } else {
+                    row.insert_symbol(symbol, term.coefficient);
+                }
+            }
+        }
+
+        let marker = Sym::Marker(slot);
+        let other = Sym::Other(slot);
+        let kinds = match operator {
+            RelationalOperator::GreaterOrEqual | RelationalOperator::LessOrEqual => {
+                let coeff = if operator == RelationalOperator::LessOrEqual {
+                    1.0
+                } else {
+                    -1.0
+                };
+                row.insert_symbol(marker, coeff);
+                if strength < Strength::REQUIRED {
+                    row.insert_symbol(other, -coeff);
+                    self.objective.insert_symbol(other, strength.value());
+                    [SymbolKind::Slack, SymbolKind::Error]
+                } else {
+                    [SymbolKind::Slack, SymbolKind::Invalid]
+                }
+            }
+            RelationalOperator::Equal => {
+                if strength < Strength::REQUIRED {
+                    row.insert_symbol(marker, -1.0);
+                    row.insert_symbol(other, 1.0);
+                    self.objective.insert_symbol(marker, strength.value());
+                    self.objective.insert_symbol(other, strength.value());
+                    [SymbolKind::Error, SymbolKind::Error]
+                } else {
+                    row.insert_symbol(marker, 1.0);
+                    [SymbolKind::Dummy, SymbolKind::Invalid]
+                }
+            }
+        };
+        self.constraints[slot] = Some(ConstraintSlot {
+            id: 0,
+            strength,
+            kinds,
+            uses,
+        });
+
+        if row.constant < 0.0 {
+            row.reverse_sign();
+        }
+        (row, kinds)
+    }
+
+    /// Choose the subject for solving for the row, see `Solver::choose_subject`.
+    fn choose_subject(&self, row: &Row<VARS, CONSTRAINTS>, kinds: [SymbolKind; 2]) -> Sym {
+        if let Some((s, _)) = row.cells().find(|(s, _)| matches!(s, Sym::External(_))) {
+            return s;
+        }
+        for (s, kind) in row.cells().filter_map(|(s, _)| match s {
+            Sym::Marker(_) => Some((s, kinds[0])),
+            Sym::Other(_) => Some((s, kinds[1])),
+            _ => None,
+        }) {
+            if matches!(kind, SymbolKind::Slack | SymbolKind::Error) && row.cell(s) < 0.0 {
+                return s;
+            }
+        }
+        Sym::Invalid
+    }
+
+    fn all_dummies(&self, row: &Row<VARS, CONSTRAINTS>) -> bool {
+        row.cells().all(|(s, _)| self.kind(s) == SymbolKind::Dummy)
+    }
+
+    /// Add the row to the tableau using an artificial variable.
+    ///
+    /// This will return false if the constraint cannot be satisfied.
+    fn add_with_artificial_variable(
+        &mut self,
+        row: &Row<VARS, CONSTRAINTS>,
+    ) -> Result<bool, InternalSolverError> {
+        self.insert_row(Sym::Artificial, *row);
+        self.artificial = Some(*row);
+
+        self.optimize(Objective::Artificial)?;
+        let success = near_zero(self.artificial.unwrap().constant);
+        self.artificial = None;
+
+        if let Some(index) = self.row_index(Sym::Artificial) {
+            let (_, mut row) = self.take_row(index);
+            if row.is_empty() {
+                return Ok(success);
+            }
+            let Some((entering, _)) = row
+                .cells()
+                .find(|&(s, _)| matches!(self.kind(s), SymbolKind::Slack | SymbolKind::Error))
+            else {
+                return Ok(false);
+            };
+            row.solve_for_symbols(Sym::Artificial, entering);
+            self.substitute(entering, &row);
+            self.insert_row(entering, row);
+        }
+
+        for (_, row) in self.rows.iter_mut().flatten() {
+            row.artificial = 0.0;
+        }
+        self.objective.artificial = 0.0;
+        Ok(success)
+    }
+
+    /// Substitute the parametric symbol with the given row in the tableau and objectives.
+    fn substitute(&mut self, symbol: Sym, row: &Row<VARS, CONSTRAINTS>) {
+        for index in 0..CONSTRAINTS {
+            let Some((other_symbol, other_row)) = self.rows[index].as_mut() else {
+                continue;
+            };
+            other_row.substitute(symbol, row);
+            let (other_symbol, constant) = (*other_symbol, other_row.constant);
+            if !matches!(other_symbol, Sym::External(_)) && constant < 0.0 {
+                self.mark_infeasible(other_symbol);
+            }
+        }
+        self.objective.substitute(symbol, row);
+        if let Some(artificial) = self.artificial.as_mut() {
+            artificial.substitute(symbol, row);
+        }
+    }
+
+    fn objective(&self, objective: Objective) -> &Row<VARS, CONSTRAINTS> {
+        match objective {
+            Objective::Main => &self.objective,
+            Objective::Artificial => self.artificial.as_ref().unwrap(),
+        }
+    }
+
+    /// Optimize the system for the given objective function.
+    fn optimize(&mut self, objective: Objective) -> Result<(), InternalSolverError> {
+        loop {
+            let entering = self
+                .objective(objective)
+                .cells()
+                .find(|&(s, v)| self.kind(s) != SymbolKind::Dummy && v < 0.0)
+                .map(|(s, _)| s);
+            let Some(entering) = entering else {
+                return Ok(());
+            };
+            let index = self
+                .leaving_row(entering)
+                .ok_or(InternalSolverError::ObjectiveUnbounded)?;
+            let (leaving, mut row) = self.take_row(index);
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows[index] = Some((entering, row));
+        }
+    }
+
+    /// Optimize the system using the dual of the simplex method.
+    fn dual_optimize(&mut self) -> Result<(), InternalSolverError> {
+        while let Some(leaving) = self.pop_infeasible() {
+            let Some(index) = self.row_index(leaving) else {
+                continue;
+            };
+            if self.rows[index].unwrap().1.constant >= 0.0 {
+                continue;
+            }
+            let (_, mut row) = self.take_row(index);
+            let entering = self.dual_entering_symbol(&row);
+            if entering == Sym::Invalid {
+                self.rows[index] = Some((leaving, row));
+                return Err(InternalSolverError::DualOptimizeFailed);
+            }
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows[index] = Some((entering, row));
+        }
+        Ok(())
+    }
+
+    fn pop_infeasible(&mut self) -> Option<Sym> {
+        for (i, flags) in self.infeasible.iter_mut().enumerate() {
+            if flags[0] {
+                flags[0] = false;
+                return Some(Sym::Marker(i));
+            }
+            if flags[1] {
+                flags[1] = false;
+                return Some(Sym::Other(i));
+            }
+        }
+        None
+    }
+
+    fn dual_entering_symbol(&self, row: &Row<VARS, CONSTRAINTS>) -> Sym {
+        let mut entering = Sym::Invalid;
+        let mut ratio = f64::INFINITY;
+        for (symbol, value) in row.cells() {
+            if value > 0.0 && self.kind(symbol) != SymbolKind::Dummy {
+                let r = self.objective.cell(symbol) / value;
+                if r < ratio {
+                    ratio = r;
+                    entering = symbol;
+                }
+            }
+        }
+        entering
+    }
+
+    /// Compute the index of the row which holds the exit symbol for a pivot.
+    fn leaving_row(&self, entering: Sym) -> Option<usize> {
+        let mut ratio = f64::INFINITY;
+        let mut found = None;
+        for (index, (symbol, row)) in self
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| Some((i, r.as_ref()?)))
+        {
+            if !matches!(symbol, Sym::External(_)) {
+                let temp = row.cell(entering);
+                if temp < 0.0 {
+                    let temp_ratio = -row.constant / temp;
+                    if temp_ratio < ratio {
+                        ratio = temp_ratio;
+                        found = Some(index);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Compute the index of the leaving row for a marker variable, see
+    /// `Solver::get_marker_leaving_row`.
+    fn marker_leaving_row(&self, marker: Sym) -> Option<usize> {
+        let mut r1 = f64::INFINITY;
+        let mut r2 = r1;
+        let mut first = None;
+        let mut second = None;
+        let mut third = None;
+        for (index, (symbol, row)) in self
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| Some((i, r.as_ref()?)))
+        {
+            let c = row.cell(marker);
+            if c == 0.0 {
+                continue;
+            }
+            if matches!(symbol, Sym::External(_)) {
+                third = Some(index);
+            } else if c < 0.0 {
+                let r = -row.constant / c;
+                if r < r1 {
+                    r1 = r;
+                    first = Some(index);
+                }
+            } else {
+                let r = row.constant / c;
+                if r < r2 {
+                    r2 = r;
+                    second = Some(index);
+                }
+            }
+        }
+        first.or(second).or(third)
+    }
+
+    /// Remove the constraint in the given slot from the tableau.
+    fn remove_slot(&mut self, slot: usize) -> Result<(), FixedSolverError> {
+        let constraint = self.constraints[slot].unwrap();
+        let marker = Sym::Marker(slot);
+        let other = Sym::Other(slot);
+
+        // Remove the error effects from the objective function before pivoting.
+        let error = if constraint.kinds[0] == SymbolKind::Error {
+            Some(marker)
+        } else if constraint.kinds[1] == SymbolKind::Error {
+            Some(other)
+        } else {
+            None
+        };
+        if let Some(error) = error {
+            let strength = constraint.strength.value();
+            if let Some(index) = self.row_index(error) {
+                let row = self.rows[index].unwrap().1;
+                self.objective.insert_row(&row, -strength);
+            } else {
+                self.objective.insert_symbol(error, -strength);
+            }
+        }
+
+        if let Some(index) = self.row_index(marker) {
+            self.rows[index] = None;
+        } else if let Some(index) = self.marker_leaving_row(marker) {
+            let (leaving, mut row) = self.take_row(index);
+            row.solve_for_symbols(leaving, marker);
+            self.substitute(marker, &row);
+        }
+
+        // Clear any remaining references to the constraint's symbols so the slot can be reused.
+        if let Some(index) = self.row_index(other) {
+            self.rows[index] = None;
+        }
+        for (_, row) in self.rows.iter_mut().flatten() {
+            row.constraint[slot] = [0.0; 2];
+        }
+        self.objective.constraint[slot] = [0.0; 2];
+        self.infeasible[slot] = [false; 2];
+        self.constraints[slot] = None;
+
+        self.optimize(Objective::Main)?;
+        self.release_variables(&constraint.uses);
+        Ok(())
+    }
+}
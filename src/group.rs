@@ -0,0 +1,85 @@
+use alloc::vec::Vec;
+
+use crate::{Constraint, RangeConstraint};
+
+/// A set of constraints that are added to and removed from a solver together, with
+/// [`Solver::add_group`](crate::Solver::add_group) and
+/// [`Solver::remove_group`](crate::Solver::remove_group).
+///
+/// This suits a widget that owns many constraints: they are added when it is mounted and removed
+/// when it is unmounted, without keeping track of them one by one.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{ConstraintGroup, Solver, Strength, Variable};
+///
+/// let mut solver = Solver::new();
+/// let (left, width) = (Variable::new(), Variable::new());
+/// let mut group = ConstraintGroup::new();
+/// group.push(left | EQ(Strength::REQUIRED) | 10.0);
+/// group.push(width | EQ(Strength::STRONG) | 80.0);
+/// solver.add_group(&group).unwrap();
+/// assert_eq!(solver.get_value(width), 80.0);
+///
+/// solver.remove_group(&group).unwrap();
+/// assert_eq!(solver.get_value(width), 0.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConstraintGroup {
+    constraints: Vec<Constraint>,
+}
+
+impl ConstraintGroup {
+    /// Create an empty group.
+    pub fn new() -> ConstraintGroup {
+        ConstraintGroup::default()
+    }
+
+    /// Add a constraint to the group.
+    ///
+    /// This doesn't affect solvers that the group has already been added to.
+    pub fn push(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// Add both constraints of a range to the group.
+    pub fn push_range(&mut self, range: &RangeConstraint) {
+        self.constraints.push(range.lower().clone());
+        self.constraints.push(range.upper().clone());
+    }
+
+    /// The constraints in the group, in the order they were added to it.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// The number of constraints in the group.
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Whether the group has no constraints.
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+}
+
+impl From<Vec<Constraint>> for ConstraintGroup {
+    fn from(constraints: Vec<Constraint>) -> ConstraintGroup {
+        ConstraintGroup { constraints }
+    }
+}
+
+impl FromIterator<Constraint> for ConstraintGroup {
+    fn from_iter<I: IntoIterator<Item = Constraint>>(iter: I) -> ConstraintGroup {
+        ConstraintGroup {
+            constraints: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Constraint> for ConstraintGroup {
+    fn extend<I: IntoIterator<Item = Constraint>>(&mut self, iter: I) {
+        self.constraints.extend(iter);
+    }
+}
@@ -0,0 +1,187 @@
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use crate::{
+    AddConstraintError, AddEditVariableError, Constraint, ConstraintGroup, EditHandle,
+    InternalSolverError, RemoveConstraintError, RemoveEditVariableError, Solver, Strength,
+    Variable,
+};
+
+/// A constraint that is removed from its solver when the guard is dropped, returned by
+/// [`Solver::add_constraint_scoped`].
+///
+/// This suits temporary constraints, e.g. those of a modal overlay, whose removal is easily
+/// forgotten on an early return. The guard borrows the solver mutably, so the solver is used
+/// through the guard while it is alive.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{Solver, Strength, Variable};
+///
+/// let mut solver = Solver::new();
+/// let width = Variable::new();
+/// solver.add_constraint(width | EQ(Strength::WEAK) | 100.0).unwrap();
+/// {
+///     let guard = solver
+///         .add_constraint_scoped(width | EQ(Strength::STRONG) | 40.0)
+///         .unwrap();
+///     assert_eq!(guard.get_value(width), 40.0);
+/// }
+/// assert_eq!(solver.get_value(width), 100.0);
+/// ```
+pub struct ConstraintGuard<'a> {
+    solver: &'a mut Solver,
+    constraint: Option<Constraint>,
+}
+
+impl<'a> ConstraintGuard<'a> {
+    pub(crate) fn new(solver: &'a mut Solver, constraint: Constraint) -> ConstraintGuard<'a> {
+        ConstraintGuard {
+            solver,
+            constraint: Some(constraint),
+        }
+    }
+
+    /// The guarded constraint.
+    pub fn constraint(&self) -> &Constraint {
+        self.constraint.as_ref().unwrap()
+    }
+
+    /// Keep the constraint in the solver after all, returning it.
+    pub fn release(mut self) -> Constraint {
+        self.constraint.take().unwrap()
+    }
+}
+
+impl Deref for ConstraintGuard<'_> {
+    type Target = Solver;
+
+    fn deref(&self) -> &Solver {
+        self.solver
+    }
+}
+
+impl DerefMut for ConstraintGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Solver {
+        self.solver
+    }
+}
+
+impl Drop for ConstraintGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(constraint) = self.constraint.take() {
+            // The constraint may already have been removed through the guard.
+            let _ = self.solver.remove_constraint(&constraint);
+        }
+    }
+}
+
+/// A batch of constraints and edit variables that are all removed from their solver when the
+/// scope is closed or dropped, returned by [`Solver::scope`].
+///
+/// This gives constraints the lifetime of a frame or a screen without keeping track of them one by
+/// one. Like [`ConstraintGuard`], the scope borrows the solver mutably and derefs to it, so
+/// anything added directly to the solver through the scope outlives it.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{Solver, Strength, Variable};
+///
+/// let mut solver = Solver::new();
+/// let (left, width) = (Variable::new(), Variable::new());
+/// solver.add_constraint(width | EQ(Strength::WEAK) | 100.0).unwrap();
+///
+/// let mut screen = solver.scope();
+/// screen.add_constraint(left | EQ(Strength::REQUIRED) | 10.0).unwrap();
+/// screen.add_edit_variable(width, Strength::STRONG).unwrap();
+/// screen.suggest_value(width, 50.0).unwrap();
+/// assert_eq!(screen.get_value(width), 50.0);
+/// screen.close().unwrap();
+///
+/// assert!(!solver.has_edit_variable(&width));
+/// assert_eq!(solver.get_value(width), 100.0);
+/// ```
+pub struct Scope<'a> {
+    solver: &'a mut Solver,
+    constraints: Vec<Constraint>,
+    edits: Vec<Variable>,
+}
+
+impl<'a> Scope<'a> {
+    pub(crate) fn new(solver: &'a mut Solver) -> Scope<'a> {
+        Scope {
+            solver,
+            constraints: Vec::new(),
+            edits: Vec::new(),
+        }
+    }
+
+    /// Add a constraint to the solver, to be removed when the scope ends.
+    pub fn add_constraint(&mut self, constraint: Constraint) -> Result<(), AddConstraintError> {
+        self.solver.add_constraint(constraint.clone())?;
+        self.constraints.push(constraint);
+        Ok(())
+    }
+
+    /// Add an edit variable to the solver, to be removed when the scope ends.
+    pub fn add_edit_variable(
+        &mut self,
+        v: Variable,
+        strength: Strength,
+    ) -> Result<EditHandle, AddEditVariableError> {
+        let handle = self.solver.add_edit_variable(v, strength)?;
+        self.edits.push(v);
+        Ok(handle)
+    }
+
+    /// The constraints added through the scope, in the order they were added.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Remove everything added through the scope from the solver.
+    ///
+    /// Dropping the scope does the same but ignores errors. Constraints and edit variables that
+    /// were already removed through the scope are skipped.
+    pub fn close(mut self) -> Result<(), InternalSolverError> {
+        self.remove_all()
+    }
+
+    fn remove_all(&mut self) -> Result<(), InternalSolverError> {
+        for v in self.edits.drain(..) {
+            match self.solver.remove_edit_variable(v) {
+                Ok(()) | Err(RemoveEditVariableError::UnknownEditVariable) => {}
+                Err(RemoveEditVariableError::InternalSolverError(e)) => return Err(e),
+            }
+        }
+        let group: ConstraintGroup = self
+            .constraints
+            .drain(..)
+            .filter(|constraint| self.solver.has_constraint(constraint))
+            .collect();
+        match self.solver.remove_group(&group) {
+            Ok(()) | Err(RemoveConstraintError::UnknownConstraint) => Ok(()),
+            Err(RemoveConstraintError::InternalSolverError(e)) => Err(e),
+        }
+    }
+}
+
+impl Deref for Scope<'_> {
+    type Target = Solver;
+
+    fn deref(&self) -> &Solver {
+        self.solver
+    }
+}
+
+impl DerefMut for Scope<'_> {
+    fn deref_mut(&mut self) -> &mut Solver {
+        self.solver
+    }
+}
+
+impl Drop for Scope<'_> {
+    fn drop(&mut self) {
+        let _ = self.remove_all();
+    }
+}
@@ -0,0 +1,38 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::Variable;
+
+/// Write a string as a JSON string.
+pub(crate) fn write_string(out: &mut String, value: &str) -> fmt::Result {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    Ok(())
+}
+
+/// Write a number as JSON, or `null` for infinities and NaN, which JSON can't represent.
+pub(crate) fn write_number(out: &mut String, value: f64) -> fmt::Result {
+    if value.is_finite() {
+        write!(out, "{value}")
+    } else {
+        out.push_str("null");
+        Ok(())
+    }
+}
+
+/// Write a variable as a JSON string of `v` followed by its id, since ids may be too large for the
+/// numbers of JavaScript.
+pub(crate) fn write_variable(out: &mut String, v: Variable) -> fmt::Result {
+    write!(out, "\"v{}\"", v.id())
+}
@@ -0,0 +1,147 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+use crate::{AddEditVariableError, EditHandle, Solver, Strength, SuggestValueError, Variable};
+
+/// A solver whose variables are identified by keys of the application, e.g. widget ids.
+///
+/// Integrations otherwise keep a map between their own ids and [`Variable`]s next to the solver.
+/// `KeyedSolver` keeps that map instead: [`KeyedSolver::var`] returns the variable of a key,
+/// creating it the first time, and values and changes are reported by key. Constraints are still
+/// built from the variables and added through [`KeyedSolver::solver_mut`].
+///
+/// The methods taking keys are named apart from the [`Solver`] methods taking variables, so that
+/// both can be used side by side.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{KeyedSolver, Strength};
+///
+/// #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+/// enum Key {
+///     Left(u32),
+///     Width(u32),
+/// }
+///
+/// let mut solver = KeyedSolver::new();
+/// let (left, width) = (solver.var(Key::Left(7)), solver.var(Key::Width(7)));
+/// let inner = solver.solver_mut();
+/// inner.add_constraint(left | EQ(Strength::REQUIRED) | 10.0).unwrap();
+/// inner.add_constraint(width | EQ(Strength::REQUIRED) | (left * 2.0)).unwrap();
+/// assert_eq!(solver.value(Key::Width(7)), Some(20.0));
+///
+/// let mut changes = solver.fetch_key_changes().to_vec();
+/// changes.sort_by_key(|&(key, _)| matches!(key, Key::Width(_)));
+/// assert_eq!(changes, [(Key::Left(7), 10.0), (Key::Width(7), 20.0)]);
+/// ```
+pub struct KeyedSolver<K> {
+    solver: Solver,
+    variables: HashMap<K, Variable>,
+    keys: HashMap<Variable, K>,
+    changes: Vec<(K, f64)>,
+}
+
+impl<K: Hash + Eq + Copy> KeyedSolver<K> {
+    /// Construct a new solver without any keys.
+    pub fn new() -> KeyedSolver<K> {
+        KeyedSolver {
+            solver: Solver::new(),
+            variables: HashMap::new(),
+            keys: HashMap::new(),
+            changes: Vec::new(),
+        }
+    }
+
+    /// The variable of a key, created the first time the key is used.
+    pub fn var(&mut self, key: K) -> Variable {
+        *self.variables.entry(key).or_insert_with(|| {
+            let variable = Variable::new();
+            self.keys.insert(variable, key);
+            variable
+        })
+    }
+
+    /// The variable of a key, if the key has been used.
+    pub fn variable(&self, key: K) -> Option<Variable> {
+        self.variables.get(&key).copied()
+    }
+
+    /// The key of a variable, if it was created for one.
+    pub fn key(&self, variable: Variable) -> Option<K> {
+        self.keys.get(&variable).copied()
+    }
+
+    /// The current value of the variable of a key, see [`Solver::get_value`], or `None` if the key
+    /// has not been used.
+    pub fn value(&self, key: K) -> Option<f64> {
+        self.variable(key)
+            .map(|variable| self.solver.get_value(variable))
+    }
+
+    /// Add an edit variable for a key, see [`Solver::add_edit_variable`].
+    pub fn add_edit_key(
+        &mut self,
+        key: K,
+        strength: Strength,
+    ) -> Result<EditHandle, AddEditVariableError> {
+        let variable = self.var(key);
+        self.solver.add_edit_variable(variable, strength)
+    }
+
+    /// Suggest a value for the edit variable of a key, see [`Solver::suggest_value`].
+    pub fn suggest_key_value(&mut self, key: K, value: f64) -> Result<(), SuggestValueError> {
+        let variable = self
+            .variable(key)
+            .ok_or(SuggestValueError::UnknownEditVariable)?;
+        self.solver.suggest_value(variable, value)
+    }
+
+    /// The keys whose values changed since the last call, with their new values, see
+    /// [`Solver::fetch_changes`].
+    ///
+    /// Changes of variables that were not created for a key are left out.
+    pub fn fetch_key_changes(&mut self) -> &[(K, f64)] {
+        self.changes.clear();
+        let keys = &self.keys;
+        let changes = self.solver.fetch_changes().iter();
+        self.changes
+            .extend(changes.filter_map(|&(variable, value)| Some((*keys.get(&variable)?, value))));
+        &self.changes
+    }
+
+    /// Forget a key, returning its variable if it had one.
+    ///
+    /// The variable stays in the solver until the constraints that use it are removed, and using
+    /// the key again creates a new variable.
+    pub fn forget(&mut self, key: K) -> Option<Variable> {
+        let variable = self.variables.remove(&key)?;
+        self.keys.remove(&variable);
+        Some(variable)
+    }
+
+    /// The underlying solver.
+    pub fn solver(&self) -> &Solver {
+        &self.solver
+    }
+
+    /// The underlying solver, to add and remove constraints.
+    ///
+    /// The keys are kept whatever is done to the solver, e.g. after a [`Solver::reset`] they still
+    /// name the same variables, now unconstrained.
+    pub fn solver_mut(&mut self) -> &mut Solver {
+        &mut self.solver
+    }
+
+    /// The underlying solver, dropping the keys.
+    pub fn into_inner(self) -> Solver {
+        self.solver
+    }
+}
+
+impl<K: Hash + Eq + Copy> Default for KeyedSolver<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
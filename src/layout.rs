@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+
+use crate::WeightedRelation::*;
+use crate::{Constraint, Expression, Strength, Variable};
+
+/// One of the two axes of a two dimensional layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Axis {
+    /// The x axis, along which `left` is the start of an element and `right` is its end.
+    #[default]
+    Horizontal,
+    /// The y axis, along which `top` is the start of an element and `bottom` is its end.
+    Vertical,
+}
+
+/// The edges of a rectangular element in a two dimensional layout.
+///
+/// The solver itself has no notion of elements, so this is only a convenient bundle of variables
+/// for building constraints. The edges are not constrained in any way until constraints are added,
+/// in particular an element may have a negative size.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Element {
+    /// The position of the left edge.
+    pub left: Variable,
+    /// The position of the top edge.
+    pub top: Variable,
+    /// The position of the right edge.
+    pub right: Variable,
+    /// The position of the bottom edge.
+    pub bottom: Variable,
+}
+
+impl Element {
+    /// Construct an element with new variables for each of its edges.
+    pub fn new() -> Element {
+        Element {
+            left: Variable::new(),
+            top: Variable::new(),
+            right: Variable::new(),
+            bottom: Variable::new(),
+        }
+    }
+
+    /// The width of the element, i.e. `right - left`.
+    pub fn width(&self) -> Expression {
+        self.right - self.left
+    }
+
+    /// The height of the element, i.e. `bottom - top`.
+    pub fn height(&self) -> Expression {
+        self.bottom - self.top
+    }
+
+    /// The edge at which the element starts along an axis, i.e. `left` or `top`.
+    pub fn start(&self, axis: Axis) -> Variable {
+        match axis {
+            Axis::Horizontal => self.left,
+            Axis::Vertical => self.top,
+        }
+    }
+
+    /// The edge at which the element ends along an axis, i.e. `right` or `bottom`.
+    pub fn end(&self, axis: Axis) -> Variable {
+        match axis {
+            Axis::Horizontal => self.right,
+            Axis::Vertical => self.bottom,
+        }
+    }
+
+    /// The size of the element along an axis, i.e. its width or height.
+    pub fn size(&self, axis: Axis) -> Expression {
+        self.end(axis) - self.start(axis)
+    }
+
+    /// Constrain the element to start at least `gap` after `anchor` ends along an axis.
+    pub fn after_along(
+        &self,
+        anchor: &Element,
+        axis: Axis,
+        gap: f64,
+        strength: Strength,
+    ) -> Vec<Constraint> {
+        let constraint = self.start(axis) | GE(strength) | (anchor.end(axis) + gap);
+        alloc::vec![constraint]
+    }
+
+    /// Constrain the element to be to the right of `anchor`, at least `gap` after its right edge.
+    pub fn after(&self, anchor: &Element, gap: f64, strength: Strength) -> Vec<Constraint> {
+        self.after_along(anchor, Axis::Horizontal, gap, strength)
+    }
+
+    /// Constrain the element to be above `anchor`, with its bottom edge at least `gap` before the
+    /// top edge of `anchor`.
+    pub fn above(&self, anchor: &Element, gap: f64, strength: Strength) -> Vec<Constraint> {
+        anchor.after_along(self, Axis::Vertical, gap, strength)
+    }
+
+    /// Constrain the element to lie within `container`, at least `padding` from each of its edges.
+    pub fn inside(&self, container: &Element, padding: f64, strength: Strength) -> Vec<Constraint> {
+        [Axis::Horizontal, Axis::Vertical]
+            .into_iter()
+            .flat_map(|axis| {
+                [
+                    self.start(axis) | GE(strength) | (container.start(axis) + padding),
+                    self.end(axis) | LE(strength) | (container.end(axis) - padding),
+                ]
+            })
+            .collect()
+    }
+
+    /// Constrain elements to have the same size along an axis.
+    pub fn same_size(elements: &[Element], axis: Axis, strength: Strength) -> Vec<Constraint> {
+        elements
+            .windows(2)
+            .map(|pair| pair[0].size(axis) | EQ(strength) | pair[1].size(axis))
+            .collect()
+    }
+
+    /// Require elements not to overlap along an axis.
+    ///
+    /// Whether two elements overlap depends on which one comes first, and "either before or after"
+    /// cannot be expressed with linear constraints. Instead the elements are kept in the order
+    /// they are given: each element starts no earlier than the previous one ends. As each element
+    /// is also required to have a non-negative size, this keeps every pair of elements apart with
+    /// only one constraint per element.
+    ///
+    /// ```
+    /// use kasuari::{Axis, Element, Solver};
+    ///
+    /// let elements = [Element::new(), Element::new(), Element::new()];
+    /// let mut solver = Solver::new();
+    /// solver
+    ///     .add_constraints(Element::no_overlap(&elements, Axis::Horizontal))
+    ///     .unwrap();
+    /// ```
+    pub fn no_overlap(elements: &[Element], axis: Axis) -> Vec<Constraint> {
+        let sizes = elements
+            .iter()
+            .map(|element| element.size(axis) | GE(Strength::REQUIRED) | 0.0);
+        let order = elements
+            .windows(2)
+            .flat_map(|pair| pair[1].after_along(&pair[0], axis, 0.0, Strength::REQUIRED));
+        sizes.chain(order).collect()
+    }
+}
+
+impl Default for Element {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -21,6 +21,12 @@
 //! (a + b) * 2.0 + c |GE(s)| d + 1.0
 //! ```
 //!
+//! The same constraint can be built with methods instead, see [`ConstraintBuilder`]:
+//!
+//! ```ignore
+//! ((a + b) * 2.0 + c).geq(d + 1.0).strength(s)
+//! ```
+//!
 //! # A simple example
 //!
 //! Imagine a layout consisting of two elements laid out horizontally. For small window widths the
@@ -235,25 +241,94 @@
 
 #![no_std]
 extern crate alloc;
+#[cfg(any(feature = "profiling", feature = "variable-names"))]
+extern crate std;
 
+#[cfg(feature = "alloc-stats")]
+mod alloc_stats;
+#[cfg(feature = "animation")]
+mod animation;
+mod binary;
+#[cfg(feature = "cells")]
+mod cells;
+#[cfg(any(feature = "cassowary-compat", feature = "kiwi-compat"))]
+pub mod compat;
+mod component;
 mod constraint;
 mod error;
 mod expression;
+#[cfg(feature = "heapless")]
+mod fixed;
+mod group;
+mod guard;
+mod json;
+mod keyed;
+mod layout;
+mod lp;
+mod names;
+mod op;
+mod param;
+mod pivot;
+#[cfg(feature = "profiling")]
+mod profile;
 mod relations;
 mod row;
+mod solution;
 mod solver;
+#[cfg(feature = "serde")]
+mod spec;
+mod state;
 mod strength;
 mod term;
+mod typed;
+mod var_map;
 mod variable;
 
-pub use self::constraint::{Constraint, PartialConstraint};
+#[cfg(feature = "derive")]
+pub use kasuari_derive::LayoutVars;
+
+#[cfg(feature = "alloc-stats")]
+pub use self::alloc_stats::AllocStats;
+#[cfg(feature = "animation")]
+pub use self::animation::{Easing, Spring};
+#[cfg(feature = "cells")]
+pub use self::cells::CellRect;
+pub use self::component::Component;
+pub use self::constraint::{
+    Constraint, ConstraintBuilder, ConstraintId, PartialConstraint, RangeConstraint,
+};
+#[cfg(feature = "heapless")]
+pub use self::error::FixedSolverError;
+#[cfg(feature = "serde")]
+pub use self::error::LayoutSpecError;
 pub use self::error::{
-    AddConstraintError, AddEditVariableError, RemoveConstraintError, RemoveEditVariableError,
-    SuggestValueError,
+    AddConstraintError, AddEditVariableError, DecodeStateError, ImportError, RemoveConstraintError,
+    RemoveEditVariableError, RestoreStateError, SetEnabledError, SolverOpError, SuggestOrAddError,
+    SuggestValueError, UpdateConstantError,
 };
 pub use self::expression::Expression;
+#[cfg(feature = "heapless")]
+pub use self::fixed::{FixedConstraint, FixedSolver};
+pub use self::group::ConstraintGroup;
+pub use self::guard::{ConstraintGuard, Scope};
+pub use self::keyed::KeyedSolver;
+pub use self::layout::{Axis, Element};
+pub use self::lp::ImportedProgram;
+pub use self::names::NameRegistry;
+pub use self::op::SolverOp;
+pub use self::param::Param;
+pub use self::pivot::{PivotEvent, PivotPhase, PivotSymbol};
+#[cfg(feature = "profiling")]
+pub use self::profile::ConstraintProfile;
 pub use self::relations::{RelationalOperator, WeightedRelation};
-pub use self::solver::{InternalSolverError, Solver};
-pub use self::strength::Strength;
+pub use self::solution::Solution;
+pub use self::solver::{
+    Checkpoint, Convergence, EditHandle, InternalSolverError, Snapshot, Solver,
+};
+#[cfg(feature = "serde")]
+pub use self::spec::{CompiledLayout, ElementSpec, LayoutSpec, Priority, RelationSpec, SizeSpec};
+pub use self::state::SolverState;
+pub use self::strength::{Strength, StrengthLevels};
 pub use self::term::Term;
-pub use self::variable::Variable;
+pub use self::typed::{TypedExpression, TypedPartialConstraint, TypedVariable};
+pub use self::variable::{Variable, VariableCounter};
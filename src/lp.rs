@@ -0,0 +1,939 @@
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::WeightedRelation::*;
+use crate::{Constraint, Expression, ImportError, RelationalOperator, Strength, Variable};
+
+/// A linear program equivalent to the constraints of a solver, written by
+/// [`Solver::export_lp`](crate::Solver::export_lp) and
+/// [`Solver::export_mps`](crate::Solver::export_mps).
+///
+/// Variables become free columns named `v` followed by their id. Each constraint becomes a row
+/// named `c` followed by its position, and non-required constraints get non-negative error columns
+/// named after their row, which are weighted by the strength of the constraint in the objective.
+#[derive(Debug, Default)]
+pub(crate) struct LinearProgram {
+    /// The coefficients of the objective to minimize.
+    objective: Vec<(String, f64)>,
+    rows: Vec<Row>,
+    /// The variables, which are the columns without bounds.
+    variables: BTreeSet<Variable>,
+}
+
+#[derive(Debug)]
+struct Row {
+    name: String,
+    terms: Vec<(String, f64)>,
+    op: RelationalOperator,
+    rhs: f64,
+}
+
+impl LinearProgram {
+    /// Add the row `terms op rhs`, with error columns weighted by `weight` unless it is required.
+    pub(crate) fn add_row(
+        &mut self,
+        terms: &[(Variable, f64)],
+        op: RelationalOperator,
+        rhs: f64,
+        weight: Option<f64>,
+    ) {
+        let name = format!("c{}", self.rows.len() + 1);
+        let mut row_terms: Vec<(String, f64)> = terms
+            .iter()
+            .map(|&(variable, coefficient)| (column_name(variable), coefficient))
+            .collect();
+        self.variables
+            .extend(terms.iter().map(|&(variable, _)| variable));
+        if let Some(weight) = weight {
+            // The errors measure how far the row is from being satisfied.
+            let errors: &[(&str, f64)] = match op {
+                RelationalOperator::Equal => &[("p", -1.0), ("m", 1.0)],
+                RelationalOperator::LessOrEqual => &[("e", -1.0)],
+                RelationalOperator::GreaterOrEqual => &[("e", 1.0)],
+            };
+            for &(suffix, coefficient) in errors {
+                let error = format!("{name}{suffix}");
+                self.objective.push((error.clone(), weight));
+                row_terms.push((error, coefficient));
+            }
+        }
+        if row_terms.is_empty() {
+            // A required constraint without variables is satisfied, or it couldn't have been added.
+            return;
+        }
+        self.rows.push(Row {
+            name,
+            terms: row_terms,
+            op,
+            rhs,
+        });
+    }
+
+    /// Write the program in the CPLEX LP format.
+    pub(crate) fn write_lp(&self, out: &mut String) -> fmt::Result {
+        writeln!(out, "\\ kasuari solver")?;
+        writeln!(out, "Minimize")?;
+        write!(out, " obj:")?;
+        write_terms(out, &self.objective)?;
+        writeln!(out, "\nSubject To")?;
+        for row in &self.rows {
+            write!(out, " {}:", row.name)?;
+            write_terms(out, &row.terms)?;
+            let op = match row.op {
+                RelationalOperator::LessOrEqual => "<=",
+                RelationalOperator::Equal => "=",
+                RelationalOperator::GreaterOrEqual => ">=",
+            };
+            writeln!(out, " {op} {}", row.rhs)?;
+        }
+        writeln!(out, "Bounds")?;
+        for &variable in &self.variables {
+            writeln!(out, " {} free", column_name(variable))?;
+        }
+        writeln!(out, "End")
+    }
+
+    /// Write the program in the MPS format, with fields separated by spaces.
+    pub(crate) fn write_mps(&self, out: &mut String) -> fmt::Result {
+        writeln!(out, "NAME          kasuari")?;
+        writeln!(out, "ROWS")?;
+        writeln!(out, " N  obj")?;
+        for row in &self.rows {
+            let kind = match row.op {
+                RelationalOperator::LessOrEqual => "L",
+                RelationalOperator::Equal => "E",
+                RelationalOperator::GreaterOrEqual => "G",
+            };
+            writeln!(out, " {kind}  {}", row.name)?;
+        }
+        // The entries of a column must be contiguous, so they are collected by column first.
+        let mut columns: Vec<(String, Vec<(&str, f64)>)> = self
+            .variables
+            .iter()
+            .map(|&variable| (column_name(variable), Vec::new()))
+            .chain(
+                self.objective
+                    .iter()
+                    .map(|(error, weight)| (error.clone(), alloc::vec![("obj", *weight)])),
+            )
+            .collect();
+        let index: HashMap<String, usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, (column, _))| (column.clone(), i))
+            .collect();
+        for row in &self.rows {
+            for (column, coefficient) in &row.terms {
+                columns[index[column]].1.push((&row.name, *coefficient));
+            }
+        }
+        writeln!(out, "COLUMNS")?;
+        for (column, entries) in &columns {
+            for (row, coefficient) in entries {
+                writeln!(out, "    {column:<10} {row:<10} {coefficient}")?;
+            }
+        }
+        writeln!(out, "RHS")?;
+        for row in self.rows.iter().filter(|row| row.rhs != 0.0) {
+            writeln!(out, "    RHS        {:<10} {}", row.name, row.rhs)?;
+        }
+        writeln!(out, "BOUNDS")?;
+        for &variable in &self.variables {
+            writeln!(out, " FR BND        {}", column_name(variable))?;
+        }
+        writeln!(out, "ENDATA")
+    }
+}
+
+/// The name of the column of a variable.
+fn column_name(variable: Variable) -> String {
+    format!("v{}", variable.id())
+}
+
+/// Write a sum of terms, e.g. ` 2 v0 - v1`, over several lines if it is long, since readers limit
+/// the length of lines.
+fn write_terms(out: &mut String, terms: &[(String, f64)]) -> fmt::Result {
+    for (i, (column, coefficient)) in terms.iter().enumerate() {
+        if i > 0 && i % 8 == 0 {
+            write!(out, "\n   ")?;
+        }
+        let sign = if *coefficient < 0.0 { "-" } else { "+" };
+        if i > 0 || *coefficient < 0.0 {
+            write!(out, " {sign}")?;
+        }
+        let magnitude = coefficient.abs();
+        if magnitude == 1.0 {
+            write!(out, " {column}")?;
+        } else {
+            write!(out, " {magnitude} {column}")?;
+        }
+    }
+    Ok(())
+}
+
+/// A linear program read from text in the CPLEX LP or the MPS format, as constraints on a new
+/// variable for each column.
+///
+/// This reads the programs written by [`Solver::export_lp`](crate::Solver::export_lp) and
+/// [`Solver::export_mps`](crate::Solver::export_mps) back, as well as programs from elsewhere, e.g.
+/// standard LP instances for testing. Rows and bounds become required constraints. An error
+/// column, i.e. a non-negative column that only appears in one row and that the objective
+/// penalizes, turns its row back into a constraint with the strength of the penalty instead of
+/// becoming a variable. The rest of the objective can't be expressed with constraints in general,
+/// so it is returned by [`ImportedProgram::objective`] for the caller to optimize.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{ImportedProgram, Solver, Strength};
+///
+/// let program = ImportedProgram::from_lp(
+///     "Maximize
+///       obj: 3 x + 2 y
+///      Subject To
+///       c1: x + y <= 4
+///       c2: x + 3 y <= 6
+///      End",
+/// )
+/// .unwrap();
+/// let mut solver = Solver::new();
+/// solver.add_constraints(program.constraints().iter().cloned()).unwrap();
+///
+/// // the objective is minimized, so the maximum is its negated minimum
+/// let objective = solver.new_variable();
+/// solver
+///     .add_constraint(objective | EQ(Strength::REQUIRED) | program.objective().clone())
+///     .unwrap();
+/// assert!((-solver.minimize(objective) - 12.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImportedProgram {
+    columns: Vec<(String, Variable)>,
+    index: HashMap<String, usize>,
+    constraints: Vec<Constraint>,
+    objective: Expression,
+}
+
+impl ImportedProgram {
+    /// Read a program in the CPLEX LP format.
+    ///
+    /// Integer, binary and semi-continuous sections, quadratic terms and ranged rows are not
+    /// supported.
+    pub fn from_lp(text: &str) -> Result<ImportedProgram, ImportError> {
+        parse_lp(text)?.import()
+    }
+
+    /// Read a program in the MPS format.
+    ///
+    /// Both the fixed and the free format are read, as long as names don't contain spaces.
+    /// Integer columns are not supported.
+    pub fn from_mps(text: &str) -> Result<ImportedProgram, ImportError> {
+        parse_mps(text)?.import()
+    }
+
+    /// The variable of a column, if the column became a variable.
+    pub fn variable(&self, column: &str) -> Option<Variable> {
+        self.index.get(column).map(|&i| self.columns[i].1)
+    }
+
+    /// The columns that became variables and their variables, in the order the columns first
+    /// appear in the text.
+    pub fn columns(&self) -> impl Iterator<Item = (&str, Variable)> {
+        self.columns
+            .iter()
+            .map(|(column, variable)| (column.as_str(), *variable))
+    }
+
+    /// The constraints of the program, ready to be added to a solver.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Consume the program, returning its constraints.
+    pub fn into_constraints(self) -> Vec<Constraint> {
+        self.constraints
+    }
+
+    /// The part of the objective that is not expressed by constraints, to be minimized.
+    ///
+    /// The objective of a maximization is negated. Its constant is left out.
+    pub fn objective(&self) -> &Expression {
+        &self.objective
+    }
+}
+
+/// The bounds of a column that has none specified.
+const DEFAULT_BOUNDS: (f64, f64) = (0.0, f64::INFINITY);
+
+/// A linear program as read from text, before it is turned into constraints.
+#[derive(Debug, Default)]
+struct Parsed {
+    /// The coefficients of the objective to minimize.
+    objective: Vec<(String, f64)>,
+    rows: Vec<Row>,
+    bounds: HashMap<String, (f64, f64)>,
+    /// The columns in the order they first appear.
+    columns: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl Parsed {
+    fn column(&mut self, column: &str) {
+        if !self.seen.contains(column) {
+            self.seen.insert(column.into());
+            self.columns.push(column.into());
+        }
+    }
+
+    fn bounds_mut(&mut self, column: &str) -> &mut (f64, f64) {
+        self.column(column);
+        self.bounds.entry(column.into()).or_insert(DEFAULT_BOUNDS)
+    }
+
+    fn import(self) -> Result<ImportedProgram, ImportError> {
+        let mut penalties: HashMap<&str, f64> = HashMap::new();
+        for (column, coefficient) in &self.objective {
+            *penalties.entry(column).or_default() += coefficient;
+        }
+        let mut uses: HashMap<&str, usize> = HashMap::new();
+        for (column, _) in self.rows.iter().flat_map(|row| &row.terms) {
+            *uses.entry(column).or_default() += 1;
+        }
+        let penalty = |column: &str| {
+            let bounds = self.bounds.get(column).copied().unwrap_or(DEFAULT_BOUNDS);
+            let penalty = penalties.get(column).copied().unwrap_or(0.0);
+            (penalty > 0.0 && uses.get(column) == Some(&1) && bounds == DEFAULT_BOUNDS)
+                .then_some(penalty)
+        };
+
+        // Find the strength of each row whose error columns can be folded into it.
+        let mut folded: HashSet<&str> = HashSet::new();
+        let weights: Vec<Option<f64>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let errors: Vec<(&str, f64, f64)> = row
+                    .terms
+                    .iter()
+                    .filter_map(|(column, k)| Some((column.as_str(), *k, penalty(column)?)))
+                    .collect();
+                let weight = match (row.op, errors.as_slice()) {
+                    (RelationalOperator::LessOrEqual, &[(_, k, w)]) if k < 0.0 => Some(w / -k),
+                    (RelationalOperator::GreaterOrEqual, &[(_, k, w)]) if k > 0.0 => Some(w / k),
+                    (RelationalOperator::Equal, &[(_, k1, w1), (_, k2, w2)])
+                        if k1 * k2 < 0.0 && w1 / k1.abs() == w2 / k2.abs() =>
+                    {
+                        Some(w1 / k1.abs())
+                    }
+                    _ => None,
+                };
+                if weight.is_some() {
+                    folded.extend(errors.iter().map(|&(column, _, _)| column));
+                }
+                weight
+            })
+            .collect();
+
+        let columns: Vec<(String, Variable)> = self
+            .columns
+            .iter()
+            .filter(|column| !folded.contains(column.as_str()))
+            .map(|column| (column.clone(), Variable::new()))
+            .collect();
+        let index: HashMap<String, usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, (column, _))| (column.clone(), i))
+            .collect();
+        let variable = |column: &str| columns[index[column]].1;
+
+        let mut constraints = Vec::with_capacity(self.rows.len() + columns.len());
+        for (row, weight) in self.rows.iter().zip(weights) {
+            if row.rhs.is_infinite() {
+                continue;
+            }
+            let pairs: Vec<(Variable, f64)> = row
+                .terms
+                .iter()
+                .filter(|(column, _)| !folded.contains(column.as_str()))
+                .map(|(column, coefficient)| (variable(column), *coefficient))
+                .collect();
+            let expression = Expression::from_pairs(&pairs, -row.rhs);
+            constraints.push(match weight {
+                None => Constraint::new(expression, row.op, Strength::REQUIRED),
+                Some(weight) if weight < Strength::REQUIRED.value() => {
+                    Constraint::new(expression, row.op, Strength::new(weight))
+                }
+                Some(weight) => Constraint::new(expression, row.op, Strength::STRONG)
+                    .with_weight(weight / Strength::STRONG.value()),
+            });
+        }
+        for (column, variable) in &columns {
+            let (lower, upper) = self.bounds.get(column).copied().unwrap_or(DEFAULT_BOUNDS);
+            if lower == upper {
+                constraints.push(*variable | EQ(Strength::REQUIRED) | lower);
+                continue;
+            }
+            if lower.is_finite() {
+                constraints.push(*variable | GE(Strength::REQUIRED) | lower);
+            }
+            if upper.is_finite() {
+                constraints.push(*variable | LE(Strength::REQUIRED) | upper);
+            }
+        }
+        let mut objective: Expression = self
+            .objective
+            .iter()
+            .filter(|(column, _)| !folded.contains(column.as_str()))
+            .map(|(column, coefficient)| (variable(column), *coefficient))
+            .collect();
+        objective.simplify();
+        Ok(ImportedProgram {
+            columns,
+            index,
+            constraints,
+            objective,
+        })
+    }
+}
+
+/// The sections of a program in the LP format.
+#[derive(Clone, Copy, PartialEq)]
+enum LpSection {
+    Objective,
+    Constraints,
+    Bounds,
+    Integers,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Sign(f64),
+    Colon,
+    Op(RelationalOperator),
+}
+
+fn parse_lp(text: &str) -> Result<Parsed, ImportError> {
+    let mut parsed = Parsed::default();
+    let mut section = None;
+    let mut maximize = false;
+    // The objective and the rows can span lines, so their tokens are collected per section.
+    let mut objective: Vec<(Token, usize)> = Vec::new();
+    let mut rows: Vec<(Token, usize)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        let line = line.split('\\').next().unwrap_or_default().trim();
+        let (keyword, rest) = lp_section(line);
+        if let Some((keyword, maximizes)) = keyword {
+            section = Some(keyword);
+            maximize |= maximizes;
+        }
+        match section {
+            _ if rest.is_empty() => {}
+            Some(LpSection::Objective) => objective.extend(tokenize(rest, number)?),
+            Some(LpSection::Constraints) => rows.extend(tokenize(rest, number)?),
+            Some(LpSection::Bounds) => parse_bound(&mut parsed, &tokenize(rest, number)?, number)?,
+            Some(LpSection::Integers) => {
+                let column = rest.split_whitespace().next().unwrap_or_default();
+                return Err(ImportError::IntegerColumn(column.into()));
+            }
+            Some(LpSection::End) => break,
+            None => return Err(ImportError::InvalidLine(number)),
+        }
+    }
+
+    let mut tokens = Tokens::new(&objective);
+    tokens.skip_label();
+    let (terms, _) = tokens.expression()?;
+    if let Some(line) = tokens.line() {
+        return Err(ImportError::InvalidLine(line));
+    }
+    let sign = if maximize { -1.0 } else { 1.0 };
+    for (column, coefficient) in terms {
+        parsed.column(&column);
+        parsed.objective.push((column, sign * coefficient));
+    }
+
+    let mut tokens = Tokens::new(&rows);
+    while let Some(line) = tokens.line() {
+        let name = tokens
+            .skip_label()
+            .unwrap_or_else(|| format!("r{}", parsed.rows.len() + 1));
+        let (terms, constant) = tokens.expression()?;
+        let Some(Token::Op(op)) = tokens.next() else {
+            return Err(ImportError::InvalidLine(line));
+        };
+        let rhs = tokens.number().ok_or(ImportError::InvalidLine(line))?;
+        for (column, _) in &terms {
+            parsed.column(column);
+        }
+        parsed.rows.push(Row {
+            name,
+            terms,
+            op,
+            rhs: rhs - constant,
+        });
+    }
+    Ok(parsed)
+}
+
+/// The section that a line of LP text starts, whether it is a maximization, and the rest of the
+/// line.
+fn lp_section(line: &str) -> (Option<(LpSection, bool)>, &str) {
+    let mut words = line.splitn(2, char::is_whitespace);
+    let first = words.next().unwrap_or_default().to_ascii_lowercase();
+    let rest = words.next().unwrap_or_default().trim_start();
+    let section = match first.as_str() {
+        "minimize" | "minimise" | "minimum" | "min" => (LpSection::Objective, false),
+        "maximize" | "maximise" | "maximum" | "max" => (LpSection::Objective, true),
+        "st" | "s.t." | "st." => (LpSection::Constraints, false),
+        "subject" | "such" => {
+            let mut words = rest.splitn(2, char::is_whitespace);
+            let second = words.next().unwrap_or_default().to_ascii_lowercase();
+            if !matches!(second.as_str(), "to" | "that") {
+                return (None, line);
+            }
+            let rest = words.next().unwrap_or_default().trim_start();
+            return (Some((LpSection::Constraints, false)), rest);
+        }
+        "bounds" | "bound" => (LpSection::Bounds, false),
+        "general" | "generals" | "gen" | "integer" | "integers" | "binary" | "binaries" | "bin"
+        | "semi-continuous" | "semis" | "semi" => (LpSection::Integers, false),
+        "end" => (LpSection::End, false),
+        _ => return (None, line),
+    };
+    (Some(section), rest)
+}
+
+/// Read a line of the bounds section, e.g. `x free`, `x >= -5` or `0 <= x <= 10`.
+fn parse_bound(
+    parsed: &mut Parsed,
+    line: &[(Token, usize)],
+    number: usize,
+) -> Result<(), ImportError> {
+    let mut tokens = Tokens::new(line);
+    let invalid = ImportError::InvalidLine(number);
+    let lower = tokens.number();
+    let lower = match lower {
+        Some(value) => {
+            let Some(Token::Op(op)) = tokens.next() else {
+                return Err(invalid);
+            };
+            Some((op, value))
+        }
+        None => None,
+    };
+    let Some(Token::Name(column)) = tokens.next() else {
+        return Err(invalid);
+    };
+    let bounds = parsed.bounds_mut(&column);
+    if let Some((op, value)) = lower {
+        // `value op column`, so the operator is reversed.
+        match op {
+            RelationalOperator::LessOrEqual => bounds.0 = value,
+            RelationalOperator::GreaterOrEqual => bounds.1 = value,
+            RelationalOperator::Equal => *bounds = (value, value),
+        }
+    }
+    match tokens.next() {
+        None => {}
+        Some(Token::Name(free)) if lower.is_none() && free.eq_ignore_ascii_case("free") => {
+            *bounds = (f64::NEG_INFINITY, f64::INFINITY);
+        }
+        Some(Token::Op(op)) => {
+            let value = tokens.number().ok_or(invalid.clone())?;
+            match op {
+                RelationalOperator::LessOrEqual => bounds.1 = value,
+                RelationalOperator::GreaterOrEqual => bounds.0 = value,
+                RelationalOperator::Equal => *bounds = (value, value),
+            }
+        }
+        Some(_) => return Err(invalid),
+    }
+    match tokens.line() {
+        Some(_) => Err(invalid),
+        None => Ok(()),
+    }
+}
+
+/// Split LP text into tokens, each with the number of its line.
+fn tokenize(text: &str, line: usize) -> Result<Vec<(Token, usize)>, ImportError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '+' => Token::Sign(1.0),
+            '-' => Token::Sign(-1.0),
+            ':' => Token::Colon,
+            '<' | '>' | '=' => {
+                let next = chars.next_if(|&(_, next)| matches!(next, '<' | '>' | '='));
+                let op = match (c, next.map(|(_, next)| next)) {
+                    ('<', None | Some('=')) | ('=', Some('<')) => RelationalOperator::LessOrEqual,
+                    ('>', None | Some('=')) | ('=', Some('>')) => {
+                        RelationalOperator::GreaterOrEqual
+                    }
+                    ('=', None | Some('=')) => RelationalOperator::Equal,
+                    _ => return Err(ImportError::InvalidLine(line)),
+                };
+                Token::Op(op)
+            }
+            _ if c.is_ascii_digit() || c == '.' => {
+                let mut end = text.len();
+                let mut exponent = false;
+                while let Some(&(i, next)) = chars.peek() {
+                    let continues = match next {
+                        '0'..='9' | '.' => true,
+                        'e' | 'E' if !exponent => {
+                            // Only an exponent if digits follow, otherwise a name starts.
+                            let after = &text[i + 1..];
+                            let after = after.strip_prefix(['+', '-']).unwrap_or(after);
+                            exponent = after.starts_with(|c: char| c.is_ascii_digit());
+                            exponent
+                        }
+                        '+' | '-' => text[..i].ends_with(['e', 'E']) && exponent,
+                        _ => false,
+                    };
+                    if !continues {
+                        end = i;
+                        break;
+                    }
+                    chars.next();
+                }
+                let number = text[start..end]
+                    .parse()
+                    .map_err(|_| ImportError::InvalidLine(line))?;
+                Token::Number(number)
+            }
+            _ => {
+                let mut end = text.len();
+                while let Some(&(i, next)) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '+' | '-' | ':' | '<' | '>' | '=') {
+                        end = i;
+                        break;
+                    }
+                    chars.next();
+                }
+                Token::Name(text[start..end].into())
+            }
+        };
+        tokens.push((token, line));
+    }
+    Ok(tokens)
+}
+
+/// A cursor over the tokens of a section of LP text.
+struct Tokens<'a> {
+    tokens: &'a [(Token, usize)],
+    position: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(tokens: &'a [(Token, usize)]) -> Tokens<'a> {
+        Tokens {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens
+            .get(self.position + offset)
+            .map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek(0)?.clone();
+        self.position += 1;
+        Some(token)
+    }
+
+    /// The line of the next token, if there is one.
+    fn line(&self) -> Option<usize> {
+        self.tokens.get(self.position).map(|&(_, line)| line)
+    }
+
+    /// Skip a label such as `c1:`, returning the name.
+    fn skip_label(&mut self) -> Option<String> {
+        match (self.peek(0)?, self.peek(1)?) {
+            (Token::Name(name), Token::Colon) => {
+                let name = name.clone();
+                self.position += 2;
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    /// Read a number with an optional sign, including infinities.
+    fn number(&mut self) -> Option<f64> {
+        let start = self.position;
+        let mut sign = 1.0;
+        while let Some(Token::Sign(s)) = self.peek(0) {
+            sign *= s;
+            self.position += 1;
+        }
+        match self.next() {
+            Some(Token::Number(value)) => Some(sign * value),
+            Some(Token::Name(name))
+                if name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity") =>
+            {
+                Some(sign * f64::INFINITY)
+            }
+            _ => {
+                self.position = start;
+                None
+            }
+        }
+    }
+
+    /// Read a sum of terms and constants up to the next operator or label, returning the terms
+    /// and the sum of the constants.
+    fn expression(&mut self) -> Result<(Vec<(String, f64)>, f64), ImportError> {
+        let mut terms = Vec::new();
+        let mut constant = 0.0;
+        while let Some(line) = self.line() {
+            if matches!(self.peek(0), Some(Token::Op(_)))
+                || matches!(self.peek(1), Some(Token::Colon))
+            {
+                break;
+            }
+            let mut sign = 1.0;
+            while let Some(Token::Sign(s)) = self.peek(0) {
+                sign *= s;
+                self.position += 1;
+            }
+            let coefficient = match self.peek(0) {
+                Some(Token::Number(value)) => {
+                    self.position += 1;
+                    Some(*value)
+                }
+                _ => None,
+            };
+            match self.peek(0) {
+                Some(Token::Name(column)) => {
+                    self.position += 1;
+                    terms.push((column.clone(), sign * coefficient.unwrap_or(1.0)));
+                }
+                _ => match coefficient {
+                    Some(value) => constant += sign * value,
+                    None => return Err(ImportError::InvalidLine(line)),
+                },
+            }
+        }
+        Ok((terms, constant))
+    }
+}
+
+/// The sections of a program in the MPS format.
+#[derive(Clone, Copy, PartialEq)]
+enum MpsSection {
+    Name,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+}
+
+fn parse_mps(text: &str) -> Result<Parsed, ImportError> {
+    let mut parsed = Parsed::default();
+    let mut section = None;
+    let mut objective_row: Option<String> = None;
+    let mut maximize = false;
+    let mut free_rows: HashSet<String> = HashSet::new();
+    let mut rows: HashMap<String, usize> = HashMap::new();
+    let mut ranges: Vec<(usize, f64)> = Vec::new();
+    let mut integers = false;
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let invalid = ImportError::InvalidLine(number);
+        if !line.starts_with(char::is_whitespace) {
+            section = Some(match fields[0].to_ascii_uppercase().as_str() {
+                "NAME" => MpsSection::Name,
+                "OBJSENSE" => {
+                    maximize |= fields.get(1).is_some_and(|sense| sense.starts_with("MAX"));
+                    MpsSection::ObjSense
+                }
+                "ROWS" => MpsSection::Rows,
+                "COLUMNS" => MpsSection::Columns,
+                "RHS" => MpsSection::Rhs,
+                "RANGES" => MpsSection::Ranges,
+                "BOUNDS" => MpsSection::Bounds,
+                "ENDATA" => break,
+                _ => return Err(invalid),
+            });
+            continue;
+        }
+        let row = |name: &str| {
+            rows.get(name)
+                .copied()
+                .ok_or_else(|| ImportError::UnknownRow {
+                    line: number,
+                    row: name.into(),
+                })
+        };
+        // A coefficient for a row, where the objective and other free rows are not in `rows`.
+        let entry =
+            |name: &str, value: &str| -> Result<Option<(Option<usize>, f64)>, ImportError> {
+                let value: f64 = value
+                    .parse()
+                    .map_err(|_| ImportError::InvalidLine(number))?;
+                if objective_row.as_deref() == Some(name) {
+                    Ok(Some((None, value)))
+                } else if free_rows.contains(name) {
+                    Ok(None)
+                } else {
+                    Ok(Some((Some(row(name)?), value)))
+                }
+            };
+        match section {
+            Some(MpsSection::ObjSense) => maximize |= fields[0].starts_with("MAX"),
+            Some(MpsSection::Rows) => {
+                let [kind, name] = fields[..] else {
+                    return Err(invalid);
+                };
+                let op = match kind {
+                    "N" if objective_row.is_none() => {
+                        objective_row = Some(name.into());
+                        continue;
+                    }
+                    "N" => {
+                        free_rows.insert(name.into());
+                        continue;
+                    }
+                    "L" => RelationalOperator::LessOrEqual,
+                    "G" => RelationalOperator::GreaterOrEqual,
+                    "E" => RelationalOperator::Equal,
+                    _ => return Err(invalid),
+                };
+                rows.insert(name.into(), parsed.rows.len());
+                parsed.rows.push(Row {
+                    name: name.into(),
+                    terms: Vec::new(),
+                    op,
+                    rhs: 0.0,
+                });
+            }
+            Some(MpsSection::Columns) => {
+                if fields.get(1) == Some(&"'MARKER'") {
+                    match fields.get(2) {
+                        Some(&"'INTORG'") => integers = true,
+                        Some(&"'INTEND'") => integers = false,
+                        _ => return Err(invalid),
+                    }
+                    continue;
+                }
+                let (column, pairs) = match fields[..] {
+                    [column, _, _] | [column, _, _, _, _] => (column, &fields[1..]),
+                    _ => return Err(invalid),
+                };
+                if integers {
+                    return Err(ImportError::IntegerColumn(column.into()));
+                }
+                parsed.column(column);
+                for pair in pairs.chunks(2) {
+                    match entry(pair[0], pair[1])? {
+                        Some((Some(row), value)) => {
+                            parsed.rows[row].terms.push((column.into(), value));
+                        }
+                        Some((None, value)) => {
+                            let value = if maximize { -value } else { value };
+                            parsed.objective.push((column.into(), value));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Some(MpsSection::Rhs | MpsSection::Ranges) => {
+                // The name of the vector is optional in the free format.
+                let pairs = match fields.len() {
+                    2 | 4 => &fields[..],
+                    3 | 5 => &fields[1..],
+                    _ => return Err(invalid),
+                };
+                for pair in pairs.chunks(2) {
+                    let Some((Some(row), value)) = entry(pair[0], pair[1])? else {
+                        // The constant of the objective doesn't affect the solution.
+                        continue;
+                    };
+                    if section == Some(MpsSection::Rhs) {
+                        parsed.rows[row].rhs = value;
+                    } else {
+                        ranges.push((row, value));
+                    }
+                }
+            }
+            Some(MpsSection::Bounds) => {
+                let kind = fields[0].to_ascii_uppercase();
+                let takes_value = !matches!(kind.as_str(), "FR" | "MI" | "PL");
+                // The name of the vector is optional in the free format.
+                let rest = match (takes_value, fields.len()) {
+                    (true, 4) | (false, 3) => &fields[2..],
+                    (true, 3) | (false, 2) => &fields[1..],
+                    _ => return Err(invalid),
+                };
+                let column = rest[0];
+                let value = match rest.get(1) {
+                    Some(value) => value.parse().map_err(|_| invalid.clone())?,
+                    None => 0.0,
+                };
+                let bounds = parsed.bounds_mut(column);
+                match kind.as_str() {
+                    "UP" => {
+                        // A negative upper bound without a lower bound makes the lower bound
+                        // infinite, by convention.
+                        if value < 0.0 && bounds.0 == 0.0 {
+                            bounds.0 = f64::NEG_INFINITY;
+                        }
+                        bounds.1 = value;
+                    }
+                    "LO" => bounds.0 = value,
+                    "FX" => *bounds = (value, value),
+                    "FR" => *bounds = (f64::NEG_INFINITY, f64::INFINITY),
+                    "MI" => bounds.0 = f64::NEG_INFINITY,
+                    "PL" => bounds.1 = f64::INFINITY,
+                    "BV" | "LI" | "UI" | "SC" => {
+                        return Err(ImportError::IntegerColumn(column.into()));
+                    }
+                    _ => return Err(invalid),
+                }
+            }
+            Some(MpsSection::Name) | None => return Err(invalid),
+        }
+    }
+
+    // A range turns a row into a pair of inequalities.
+    for (row, range) in ranges {
+        let Row { op, rhs, .. } = parsed.rows[row];
+        let (lower, upper) = match op {
+            RelationalOperator::Equal if range < 0.0 => (rhs + range, rhs),
+            RelationalOperator::Equal => (rhs, rhs + range),
+            RelationalOperator::LessOrEqual => (rhs - range.abs(), rhs),
+            RelationalOperator::GreaterOrEqual => (rhs, rhs + range.abs()),
+        };
+        let upper_row = Row {
+            name: format!("{}.upper", parsed.rows[row].name),
+            terms: parsed.rows[row].terms.clone(),
+            op: RelationalOperator::LessOrEqual,
+            rhs: upper,
+        };
+        let lower_row = &mut parsed.rows[row];
+        lower_row.op = RelationalOperator::GreaterOrEqual;
+        lower_row.rhs = lower;
+        parsed.rows.push(upper_row);
+    }
+    Ok(parsed)
+}
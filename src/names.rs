@@ -0,0 +1,231 @@
+use alloc::string::String;
+use core::fmt;
+
+use hashbrown::HashMap;
+
+use crate::{Constraint, Expression, RelationalOperator, Term, Variable};
+
+/// Maps variables to human readable names for use in diagnostics.
+///
+/// Variables are otherwise only identified by an opaque number, which makes printed expressions
+/// and constraints hard to read. Formatting with a registry (e.g. with
+/// [`Constraint::display_with`]) prints `sidebar.width >= 200` instead of `v137 >= 200`. Variables
+/// without a name in the registry are printed with their [`Display`](fmt::Display)
+/// implementation, i.e. as `v` followed by their id unless they were given a name with the
+/// `variable-names` feature.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{Constraint, NameRegistry, Strength, Variable};
+///
+/// let width = Variable::new();
+/// let mut names = NameRegistry::new();
+/// names.insert(width, "sidebar.width");
+///
+/// let constraint: Constraint = width | GE(Strength::REQUIRED) | 200.0;
+/// assert_eq!(constraint.display_with(&names).to_string(), "sidebar.width >= 200");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct NameRegistry {
+    names: HashMap<Variable, String>,
+}
+
+impl NameRegistry {
+    /// Construct an empty registry.
+    pub fn new() -> NameRegistry {
+        NameRegistry::default()
+    }
+
+    /// Set the name of a variable, returning its previous name if it had one.
+    pub fn insert(&mut self, variable: Variable, name: impl Into<String>) -> Option<String> {
+        self.names.insert(variable, name.into())
+    }
+
+    /// Remove the name of a variable, returning it if it had one.
+    pub fn remove(&mut self, variable: Variable) -> Option<String> {
+        self.names.remove(&variable)
+    }
+
+    /// The name of a variable, if it has one.
+    pub fn name(&self, variable: Variable) -> Option<&str> {
+        self.names.get(&variable).map(String::as_str)
+    }
+
+    /// Write the name of a variable, falling back to its [`Display`](fmt::Display) output if it
+    /// has no name in the registry.
+    pub(crate) fn write_variable(&self, f: &mut fmt::Formatter, variable: Variable) -> fmt::Result {
+        match self.name(variable) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{variable}"),
+        }
+    }
+
+    /// Write a sum of terms, e.g. `2 * x + y - z`. Writes `0` if there are no terms.
+    pub(crate) fn write_terms(&self, f: &mut fmt::Formatter, terms: &[Term]) -> fmt::Result {
+        if terms.is_empty() {
+            return f.write_str("0");
+        }
+        for (i, term) in terms.iter().enumerate() {
+            let coefficient = match (i, term.coefficient < 0.0) {
+                (0, false) => term.coefficient,
+                (0, true) => {
+                    f.write_str("-")?;
+                    -term.coefficient
+                }
+                (_, false) => {
+                    f.write_str(" + ")?;
+                    term.coefficient
+                }
+                (_, true) => {
+                    f.write_str(" - ")?;
+                    -term.coefficient
+                }
+            };
+            if coefficient != 1.0 {
+                write!(f, "{coefficient} * ")?;
+            }
+            self.write_variable(f, term.variable)?;
+        }
+        Ok(())
+    }
+
+    /// Write an expression, e.g. `2 * x + y + 4`.
+    pub(crate) fn write_expression(
+        &self,
+        f: &mut fmt::Formatter,
+        expression: &Expression,
+    ) -> fmt::Result {
+        if expression.terms.is_empty() {
+            return write!(f, "{}", expression.constant);
+        }
+        self.write_terms(f, &expression.terms)?;
+        if expression.constant < 0.0 {
+            write!(f, " - {}", -expression.constant)
+        } else if expression.constant > 0.0 {
+            write!(f, " + {}", expression.constant)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write a constraint with its constant moved to the right hand side, e.g. `x + y >= 10`.
+    pub(crate) fn write_constraint(
+        &self,
+        f: &mut fmt::Formatter,
+        constraint: &Constraint,
+    ) -> fmt::Result {
+        self.write_terms(f, constraint.terms())?;
+        let rhs = if constraint.constant() == 0.0 {
+            0.0
+        } else {
+            -constraint.constant()
+        };
+        match (constraint.op(), constraint.is_strict()) {
+            (RelationalOperator::LessOrEqual, true) => write!(f, " < {rhs}"),
+            (RelationalOperator::GreaterOrEqual, true) => write!(f, " > {rhs}"),
+            (op, _) => write!(f, " {op} {rhs}"),
+        }
+    }
+}
+
+impl<S: Into<String>> FromIterator<(Variable, S)> for NameRegistry {
+    fn from_iter<I: IntoIterator<Item = (Variable, S)>>(iter: I) -> Self {
+        NameRegistry {
+            names: iter
+                .into_iter()
+                .map(|(variable, name)| (variable, name.into()))
+                .collect(),
+        }
+    }
+}
+
+impl<S: Into<String>> Extend<(Variable, S)> for NameRegistry {
+    fn extend<I: IntoIterator<Item = (Variable, S)>>(&mut self, iter: I) {
+        self.names.extend(
+            iter.into_iter()
+                .map(|(variable, name)| (variable, name.into())),
+        );
+    }
+}
+
+/// Formats a value using a [`NameRegistry`] to name its variables.
+pub(crate) struct WithNames<'a, T: ?Sized> {
+    pub(crate) value: &'a T,
+    pub(crate) names: &'a NameRegistry,
+}
+
+impl fmt::Display for WithNames<'_, Expression> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.names.write_expression(f, self.value)
+    }
+}
+
+impl fmt::Display for WithNames<'_, Constraint> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.names.write_constraint(f, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+    use crate::{RelationalOperator, Strength};
+
+    const X: Variable = Variable::from_id(0);
+    const Y: Variable = Variable::from_id(1);
+
+    fn names() -> NameRegistry {
+        [(X, "x")].into_iter().collect()
+    }
+
+    #[test]
+    fn name() {
+        let mut names = names();
+        assert_eq!(names.name(X), Some("x"));
+        assert_eq!(names.name(Y), None);
+        assert_eq!(names.insert(X, "left"), Some("x".into()));
+        assert_eq!(names.remove(X), Some("left".into()));
+        assert_eq!(names.name(X), None);
+    }
+
+    #[test]
+    fn display_expression() {
+        let names = names();
+        let display = |expression: Expression| expression.display_with(&names).to_string();
+        assert_eq!(display(Expression::from_constant(4.0)), "4");
+        assert_eq!(display(X + 0.0), "x");
+        assert_eq!(display(2.0 * X + Y + 4.0), "2 * x + v1 + 4");
+        assert_eq!(display(-X - 0.5 * Y - 4.0), "-x - 0.5 * v1 - 4");
+        let unnamed: Expression = 2.0 * X + Y + 4.0;
+        assert_eq!(unnamed.to_string(), "2 * v0 + v1 + 4");
+    }
+
+    #[test]
+    fn display_constraint() {
+        let names = names();
+        let display = |expression: Expression, op| {
+            Constraint::new(expression, op, Strength::REQUIRED)
+                .display_with(&names)
+                .to_string()
+        };
+        assert_eq!(
+            display(X - 200.0, RelationalOperator::GreaterOrEqual),
+            "x >= 200"
+        );
+        assert_eq!(
+            display(X - Y + 10.0, RelationalOperator::LessOrEqual),
+            "x - v1 <= -10"
+        );
+        assert_eq!(
+            display(Expression::new(vec![], 0.0), RelationalOperator::Equal),
+            "0 == 0"
+        );
+        let weak = Constraint::new(X + Y - 10.0, RelationalOperator::Equal, Strength::WEAK);
+        assert_eq!(weak.to_string(), "v0 + v1 == 10 [WEAK]");
+        let weaker = Constraint::new(X + 0.0, RelationalOperator::Equal, Strength::new(0.5));
+        assert_eq!(weaker.to_string(), "v0 == 0 [WEAK*0.5]");
+    }
+}
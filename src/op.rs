@@ -0,0 +1,66 @@
+use crate::{Constraint, Param, Solver, SolverOpError, Strength, Variable};
+
+/// An operation on a [`Solver`], as captured by [`Solver::start_recording`].
+///
+/// Only operations that took effect are recorded. Replaying the operations of a session with
+/// [`Solver::replay`] on a new solver reproduces the state the session left the solver in, which
+/// makes it possible to turn a failure observed in production into a test.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolverOp {
+    /// A call to [`Solver::add_constraint`].
+    AddConstraint(Constraint),
+    /// A call to [`Solver::remove_constraint`].
+    RemoveConstraint(Constraint),
+    /// A call to [`Solver::remove_below_strength`].
+    RemoveBelowStrength(Strength),
+    /// A call to [`Solver::set_enabled`].
+    SetEnabled(Constraint, bool),
+    /// A call to [`Solver::set_condition`].
+    SetCondition(u64, bool),
+    /// A call to [`Solver::add_edit_variable`].
+    AddEditVariable(Variable, Strength),
+    /// A call to [`Solver::remove_edit_variable`].
+    RemoveEditVariable(Variable),
+    /// A call to [`Solver::suggest_value`].
+    SuggestValue(Variable, f64),
+    /// A call to [`Solver::add_stay`].
+    AddStay(Variable, Strength),
+    /// A call to [`Solver::remove_stay`].
+    RemoveStay(Variable),
+    /// A call to [`Solver::set_param`].
+    SetParam(Param, f64),
+    /// A call to [`Solver::begin_edit`].
+    BeginEdit,
+    /// A call to [`Solver::end_edit`].
+    EndEdit,
+}
+
+impl SolverOp {
+    /// Perform the operation on a solver.
+    pub fn apply(&self, solver: &mut Solver) -> Result<(), SolverOpError> {
+        match self {
+            SolverOp::AddConstraint(constraint) => solver.add_constraint(constraint.clone())?,
+            SolverOp::RemoveConstraint(constraint) => solver.remove_constraint(constraint)?,
+            SolverOp::RemoveBelowStrength(threshold) => {
+                solver.remove_below_strength(*threshold)?;
+            }
+            SolverOp::SetEnabled(constraint, enabled) => {
+                solver.set_enabled(constraint, *enabled)?
+            }
+            SolverOp::SetCondition(condition, active) => {
+                solver.set_condition(*condition, *active)?
+            }
+            SolverOp::AddEditVariable(v, strength) => {
+                solver.add_edit_variable(*v, *strength)?;
+            }
+            SolverOp::RemoveEditVariable(v) => solver.remove_edit_variable(*v)?,
+            SolverOp::SuggestValue(v, value) => solver.suggest_value(*v, *value)?,
+            SolverOp::AddStay(v, strength) => solver.add_stay(*v, *strength)?,
+            SolverOp::RemoveStay(v) => solver.remove_stay(*v)?,
+            SolverOp::SetParam(param, value) => solver.set_param(*param, *value)?,
+            SolverOp::BeginEdit => solver.begin_edit(),
+            SolverOp::EndEdit => solver.end_edit()?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use core::ops;
+
+use crate::{Constraint, Expression, PartialConstraint, Term, Variable, WeightedRelation};
+
+/// A constant that appears in constraints, but whose value can be changed later with
+/// [`Solver::set_param`](crate::Solver::set_param).
+///
+/// Parameters are for values like spacing or font size, that appear in many constraints but rarely
+/// change. Changing the value of a parameter updates every constraint it appears in without
+/// removing and adding them again, and a parameter is never solved for. A parameter has the value
+/// zero until it is set.
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{Param, Solver, Strength, Variable};
+///
+/// let mut solver = Solver::new();
+/// let spacing = Param::new();
+/// let left = Variable::new();
+/// let right = Variable::new();
+/// solver.set_param(spacing, 4.0).unwrap();
+/// solver.add_constraint(left | EQ(Strength::REQUIRED) | 0.0).unwrap();
+/// solver
+///     .add_constraint(right | EQ(Strength::REQUIRED) | (left + spacing))
+///     .unwrap();
+/// assert_eq!(solver.get_value(right), 4.0);
+///
+/// solver.set_param(spacing, 8.0).unwrap();
+/// assert_eq!(solver.get_value(right), 8.0);
+/// ```
+#[derive(Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Param(Variable);
+
+impl Param {
+    /// Produces a new unique parameter.
+    #[inline]
+    pub fn new() -> Self {
+        Self(Variable::new_param())
+    }
+
+    /// The variable that stands for the parameter in the terms of expressions.
+    pub(crate) const fn variable(&self) -> Variable {
+        self.0
+    }
+}
+
+impl Default for Param {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Param> for Term {
+    #[inline]
+    fn from(param: Param) -> Term {
+        Term::from_variable(param.0)
+    }
+}
+
+impl From<Param> for Expression {
+    #[inline]
+    fn from(param: Param) -> Expression {
+        Expression::from_term(Term::from(param))
+    }
+}
+
+impl ops::Mul<f64> for Param {
+    type Output = Term;
+
+    #[inline]
+    fn mul(self, coefficient: f64) -> Term {
+        Term::new(self.0, coefficient)
+    }
+}
+
+impl ops::Mul<Param> for f64 {
+    type Output = Term;
+
+    #[inline]
+    fn mul(self, param: Param) -> Term {
+        Term::new(param.0, self)
+    }
+}
+
+impl ops::Neg for Param {
+    type Output = Term;
+
+    #[inline]
+    fn neg(self) -> Term {
+        -Term::from(self)
+    }
+}
+
+impl ops::Add<f64> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, constant: f64) -> Expression {
+        Term::from(self) + constant
+    }
+}
+
+impl ops::Add<Param> for f64 {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, param: Param) -> Expression {
+        Term::from(param) + self
+    }
+}
+
+impl ops::Add<Param> for Variable {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, param: Param) -> Expression {
+        self + Term::from(param)
+    }
+}
+
+impl ops::Add<Variable> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, variable: Variable) -> Expression {
+        Term::from(self) + variable
+    }
+}
+
+impl ops::Add<Param> for Term {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, param: Param) -> Expression {
+        self + Term::from(param)
+    }
+}
+
+impl ops::Add<Term> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, term: Term) -> Expression {
+        Term::from(self) + term
+    }
+}
+
+impl ops::Add<Param> for Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, param: Param) -> Expression {
+        self + Term::from(param)
+    }
+}
+
+impl ops::Add<Expression> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn add(self, expression: Expression) -> Expression {
+        Term::from(self) + expression
+    }
+}
+
+impl ops::Sub<f64> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, constant: f64) -> Expression {
+        Term::from(self) - constant
+    }
+}
+
+impl ops::Sub<Param> for f64 {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, param: Param) -> Expression {
+        self - Term::from(param)
+    }
+}
+
+impl ops::Sub<Param> for Variable {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, param: Param) -> Expression {
+        self - Term::from(param)
+    }
+}
+
+impl ops::Sub<Variable> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, variable: Variable) -> Expression {
+        Term::from(self) - variable
+    }
+}
+
+impl ops::Sub<Param> for Term {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, param: Param) -> Expression {
+        self - Term::from(param)
+    }
+}
+
+impl ops::Sub<Term> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, term: Term) -> Expression {
+        Term::from(self) - term
+    }
+}
+
+impl ops::Sub<Param> for Expression {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, param: Param) -> Expression {
+        self - Term::from(param)
+    }
+}
+
+impl ops::Sub<Expression> for Param {
+    type Output = Expression;
+
+    #[inline]
+    fn sub(self, expression: Expression) -> Expression {
+        Term::from(self) - expression
+    }
+}
+
+impl ops::BitOr<WeightedRelation> for Param {
+    type Output = PartialConstraint;
+
+    #[inline]
+    fn bitor(self, rhs: WeightedRelation) -> PartialConstraint {
+        PartialConstraint::new(Expression::from(self), rhs)
+    }
+}
+
+impl ops::BitOr<Param> for PartialConstraint {
+    type Output = Constraint;
+
+    #[inline]
+    fn bitor(self, rhs: Param) -> Constraint {
+        self | Term::from(rhs)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::Variable;
+
+/// Which loop of the simplex method performed a pivot.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PivotPhase {
+    /// The primal simplex, which minimizes the objective while keeping the tableau feasible. This
+    /// runs when constraints are added or removed, including the artificial objective used to
+    /// find an initial feasible solution.
+    Primal,
+    /// The dual simplex, which restores feasibility while keeping the objective optimal. This runs
+    /// when edit variables are suggested new values.
+    Dual,
+}
+
+/// A symbol in the solver's tableau, as seen by a pivot hook.
+///
+/// The ids of the internal symbols are only meaningful for distinguishing symbols within a single
+/// solver.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PivotSymbol {
+    /// A variable added by the user.
+    Variable(Variable),
+    /// A slack variable introduced for an inequality, or an artificial variable used to find an
+    /// initial feasible solution.
+    Slack(usize),
+    /// An error variable introduced for a non-required constraint.
+    Error(usize),
+    /// A dummy variable introduced as the marker of a required equality.
+    Dummy(usize),
+}
+
+/// A single pivot of the simplex method, as reported to the hook set with
+/// [`Solver::set_pivot_hook`](crate::Solver::set_pivot_hook).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PivotEvent {
+    /// Which loop of the simplex method performed the pivot.
+    pub phase: PivotPhase,
+    /// The symbol that entered the basis.
+    pub entering: PivotSymbol,
+    /// The symbol that left the basis, i.e. the subject of the row that was pivoted.
+    pub leaving: PivotSymbol,
+    /// The value of the objective function before the pivot.
+    pub objective_before: f64,
+    /// The value of the objective function after the pivot.
+    pub objective_after: f64,
+}
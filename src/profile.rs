@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use hashbrown::HashMap;
+
+use crate::row::{Symbol, SymbolKind};
+use crate::Constraint;
+
+/// How much solving work is attributable to a constraint, as reported by
+/// [`Solver::hot_constraints`](crate::Solver::hot_constraints).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintProfile {
+    /// The constraint.
+    pub constraint: Constraint,
+    /// The number of pivots in which one of the constraint's symbols entered or left the basis.
+    pub pivots: u64,
+    /// The total time spent in those pivots.
+    pub time: Duration,
+}
+
+/// Attributes pivots to the constraints that own the pivoted symbols.
+#[derive(Debug, Default)]
+pub(crate) struct Profiler {
+    owners: HashMap<Symbol, Constraint>,
+    profiles: HashMap<Constraint, (u64, Duration)>,
+}
+
+impl Profiler {
+    /// Record that the symbols belong to a constraint that was added to the tableau.
+    pub(crate) fn add(&mut self, constraint: &Constraint, symbols: [Symbol; 2]) {
+        for symbol in symbols {
+            if symbol.kind() != SymbolKind::Invalid {
+                self.owners.insert(symbol, constraint.clone());
+            }
+        }
+    }
+
+    /// Forget the symbols of a constraint that was removed from the tableau. Its profile is kept.
+    pub(crate) fn remove(&mut self, symbols: [Symbol; 2]) {
+        for symbol in symbols {
+            self.owners.remove(&symbol);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.owners.clear();
+        self.profiles.clear();
+    }
+
+    pub(crate) fn clear_profiles(&mut self) {
+        self.profiles.clear();
+    }
+
+    /// Attribute a pivot to the constraints owning the entering and leaving symbols.
+    pub(crate) fn pivot(&mut self, entering: Symbol, leaving: Symbol, time: Duration) {
+        let mut owners = [entering, leaving].map(|symbol| self.owners.get(&symbol));
+        if owners[0] == owners[1] {
+            owners[1] = None;
+        }
+        for constraint in owners.into_iter().flatten() {
+            let (pivots, total) = self.profiles.entry(constraint.clone()).or_default();
+            *pivots += 1;
+            *total += time;
+        }
+    }
+
+    /// The profiles of all constraints involved in pivots, most expensive first.
+    pub(crate) fn hot_constraints(&self) -> Vec<ConstraintProfile> {
+        let mut profiles: Vec<ConstraintProfile> = self
+            .profiles
+            .iter()
+            .map(|(constraint, &(pivots, time))| ConstraintProfile {
+                constraint: constraint.clone(),
+                pivots,
+                time,
+            })
+            .collect();
+        profiles.sort_by(|a, b| b.time.cmp(&a.time).then(b.pivots.cmp(&a.pivots)));
+        profiles
+    }
+}
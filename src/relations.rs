@@ -4,6 +4,7 @@ use crate::{Expression, PartialConstraint, Strength, Term, Variable};
 
 /// The possible relations that a constraint can specify.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RelationalOperator {
     /// `<=`
     LessOrEqual,
@@ -33,14 +34,33 @@ pub enum WeightedRelation {
     LE(Strength),
     /// `>=`
     GE(Strength),
+    /// `<`, kept a small epsilon away from equality, see [`Solver::set_strict_epsilon`].
+    ///
+    /// [`Solver::set_strict_epsilon`]: crate::Solver::set_strict_epsilon
+    LT(Strength),
+    /// `>`, kept a small epsilon away from equality, see [`Solver::set_strict_epsilon`].
+    ///
+    /// [`Solver::set_strict_epsilon`]: crate::Solver::set_strict_epsilon
+    GT(Strength),
 }
 
+impl WeightedRelation {
+    /// Whether the relation is a strict inequality.
+    pub fn is_strict(&self) -> bool {
+        matches!(self, WeightedRelation::LT(_) | WeightedRelation::GT(_))
+    }
+}
+
+/// Converts strict inequalities to their non-strict operators, see
+/// [`WeightedRelation::is_strict`].
 impl From<WeightedRelation> for (RelationalOperator, Strength) {
     fn from(relation: WeightedRelation) -> (RelationalOperator, Strength) {
         match relation {
             WeightedRelation::EQ(s) => (RelationalOperator::Equal, s),
             WeightedRelation::LE(s) => (RelationalOperator::LessOrEqual, s),
             WeightedRelation::GE(s) => (RelationalOperator::GreaterOrEqual, s),
+            WeightedRelation::LT(s) => (RelationalOperator::LessOrEqual, s),
+            WeightedRelation::GT(s) => (RelationalOperator::GreaterOrEqual, s),
         }
     }
 }
@@ -89,3 +109,12 @@ impl ops::BitOr<WeightedRelation> for Expression {
         PartialConstraint::new(self, rhs)
     }
 }
+
+impl ops::BitOr<WeightedRelation> for &Expression {
+    type Output = PartialConstraint;
+
+    #[inline]
+    fn bitor(self, rhs: WeightedRelation) -> PartialConstraint {
+        PartialConstraint::new(self.clone(), rhs)
+    }
+}
@@ -1,3 +1,6 @@
+use alloc::vec::Vec;
+use core::fmt;
+
 use hashbrown::hash_map::Entry;
 use hashbrown::HashMap;
 
@@ -8,9 +11,11 @@ pub struct Row {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Symbol(usize, SymbolKind);
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SymbolKind {
     Invalid,
     External,
@@ -30,6 +35,24 @@ impl Symbol {
     pub fn kind(&self) -> SymbolKind {
         self.1
     }
+    pub fn id(&self) -> usize {
+        self.0
+    }
+}
+
+/// Formats like kiwi, e.g. `s3` for the slack symbol with id 3, except that external symbols are
+/// prefixed with `x` to tell them apart from variables.
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = match self.1 {
+            SymbolKind::Invalid => 'i',
+            SymbolKind::External => 'x',
+            SymbolKind::Slack => 's',
+            SymbolKind::Error => 'e',
+            SymbolKind::Dummy => 'd',
+        };
+        write!(f, "{}{}", prefix, self.0)
+    }
 }
 
 pub fn near_zero(value: f64) -> bool {
@@ -119,3 +142,16 @@ impl Row {
         }
     }
 }
+
+/// Formats the constant followed by the cells ordered by symbol, e.g. `10 + -1 * s2 + 1 * d3`.
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.constant)?;
+        let mut cells: Vec<_> = self.cells.iter().collect();
+        cells.sort_by_key(|&(symbol, _)| symbol);
+        for (symbol, coefficient) in cells {
+            write!(f, " + {} * {}", coefficient, symbol)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use alloc::vec::Vec;
+
+use hashbrown::HashMap;
+
+use crate::{Constraint, Variable};
+
+/// An immutable snapshot of a solver's solution, created by [`Solver::freeze`].
+///
+/// A solution does not borrow from the solver that produced it, so it can be kept around as the
+/// result of a frame, compared with the solution of another frame, or sent to another thread.
+///
+/// Two solutions compare equal when they assign the same values to the same variables and report
+/// the same constraints as satisfied and violated. The generation is not compared.
+///
+/// [`Solver::freeze`]: crate::Solver::freeze
+#[derive(Debug, Clone)]
+pub struct Solution {
+    generation: u64,
+    values: HashMap<Variable, f64>,
+    satisfied: Vec<Constraint>,
+    violated: Vec<(Constraint, f64)>,
+}
+
+impl Solution {
+    pub(crate) fn new(
+        generation: u64,
+        values: HashMap<Variable, f64>,
+        satisfied: Vec<Constraint>,
+        violated: Vec<(Constraint, f64)>,
+    ) -> Solution {
+        Solution {
+            generation,
+            values,
+            satisfied,
+            violated,
+        }
+    }
+
+    /// The generation of the solver when the solution was taken.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The value of a variable in the solution.
+    ///
+    /// Variables that were not known to the solver have a value of zero.
+    pub fn value_of(&self, v: Variable) -> f64 {
+        self.values.get(&v).copied().unwrap_or(0.0)
+    }
+
+    /// The values of all the variables that were known to the solver, in no particular order.
+    pub fn values(&self) -> impl Iterator<Item = (Variable, f64)> + '_ {
+        self.values.iter().map(|(&v, &value)| (v, value))
+    }
+
+    /// The constraints that the solution satisfies, in no particular order.
+    pub fn satisfied(&self) -> impl Iterator<Item = &Constraint> {
+        self.satisfied.iter()
+    }
+
+    /// The constraints that the solution violates together with how far they are from being
+    /// satisfied, in no particular order.
+    pub fn violated(&self) -> impl Iterator<Item = (&Constraint, f64)> {
+        self.violated
+            .iter()
+            .map(|(constraint, violation)| (constraint, *violation))
+    }
+
+    /// Whether the solution satisfies every constraint.
+    pub fn is_satisfied(&self) -> bool {
+        self.violated.is_empty()
+    }
+}
+
+impl PartialEq for Solution {
+    fn eq(&self, other: &Solution) -> bool {
+        self.values == other.values
+            && self.satisfied.len() == other.satisfied.len()
+            && self.violated.len() == other.violated.len()
+            && self.satisfied.iter().all(|c| other.satisfied.contains(c))
+            && self
+                .violated
+                .iter()
+                .all(|violation| other.violated.contains(violation))
+    }
+}
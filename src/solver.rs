@@ -1,20 +1,95 @@
+use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec;
 use alloc::vec::Vec;
 use core::cell::RefCell;
-use core::f64;
+use core::fmt::{self, Write};
+use core::{f64, iter, mem, ops};
 
 use hashbrown::hash_map::Entry;
 use hashbrown::{HashMap, HashSet};
 
-use crate::constraint::Constraint;
+#[cfg(feature = "alloc-stats")]
+use crate::alloc_stats::AllocStats;
+#[cfg(feature = "animation")]
+use crate::animation::{Animation, Easing, Spring};
+use crate::component::{self, Component};
+use crate::constraint::{Constraint, ConstraintId, RangeConstraint};
+use crate::group::ConstraintGroup;
+use crate::guard::{ConstraintGuard, Scope};
+use crate::json;
+use crate::lp::LinearProgram;
+use crate::names::NameRegistry;
+use crate::op::SolverOp;
+use crate::pivot::{PivotEvent, PivotPhase, PivotSymbol};
+#[cfg(feature = "profiling")]
+use crate::profile::{ConstraintProfile, Profiler};
 use crate::row::{near_zero, Row, Symbol, SymbolKind};
+use crate::solution::Solution;
+use crate::state::{EditState, RowState, SolverState, TableauState};
 use crate::strength::Strength;
+use crate::var_map::VarMap;
 use crate::{
-    AddConstraintError, AddEditVariableError, Expression, RelationalOperator,
-    RemoveConstraintError, RemoveEditVariableError, SuggestValueError, Term, Variable,
+    AddConstraintError, AddEditVariableError, Expression, Param, RelationalOperator,
+    RemoveConstraintError, RemoveEditVariableError, RestoreStateError, SetEnabledError,
+    SolverOpError, SuggestOrAddError, SuggestValueError, Term, UpdateConstantError, Variable,
+    VariableCounter,
 };
 
+/// Evaluate `$operation` and record any growth in the capacity of `$map` in `$stats`.
+#[cfg(feature = "alloc-stats")]
+macro_rules! tracked {
+    ($stats:expr, $map:expr, $operation:expr) => {{
+        let before = $map.capacity();
+        let result = $operation;
+        $stats.record_growth(before, $map.capacity());
+        result
+    }};
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+macro_rules! tracked {
+    ($stats:expr, $map:expr, $operation:expr) => {
+        $operation
+    };
+}
+
+/// Update the allocation statistics. Expands to nothing without the `alloc-stats` feature.
+macro_rules! record {
+    ($($update:tt)*) => {
+        #[cfg(feature = "alloc-stats")]
+        {
+            $($update)*;
+        }
+    };
+}
+
+/// Whether pivot choices must be independent of hash map iteration order. See the `deterministic`
+/// feature.
+const DETERMINISTIC: bool = cfg!(feature = "deterministic");
+
+/// Pick the first of the candidate symbols.
+///
+/// With the `deterministic` feature, this is the smallest symbol rather than the first one in
+/// iteration order, since the iteration order of the maps differs between runs and platforms.
+fn first_symbol(mut candidates: impl Iterator<Item = Symbol>) -> Option<Symbol> {
+    if DETERMINISTIC {
+        candidates.min()
+    } else {
+        candidates.next()
+    }
+}
+
+/// Whether a candidate with ratio `r` should replace the current best candidate in a ratio test.
+///
+/// With the `deterministic` feature ties are broken by picking the smallest symbol.
+fn better_ratio(r: f64, symbol: Symbol, best: f64, best_symbol: Option<Symbol>) -> bool {
+    r < best || (DETERMINISTIC && r == best && best_symbol.is_some_and(|best| symbol < best))
+}
+
 #[derive(Debug, Copy, Clone, thiserror::Error)]
 #[error("The solver entered an invalid state. If this occurs please report the issue.")]
 pub enum InternalSolverError {
@@ -34,28 +109,266 @@ struct Tag {
     other: Symbol,
 }
 
+/// The outcome of [`Solver::solve_iteratively`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Convergence {
+    /// A pass left the values of all variables unchanged.
+    Converged {
+        /// The number of passes, including the final pass that changed nothing.
+        passes: usize,
+    },
+    /// The values alternate between two states, so further passes would not converge.
+    Oscillating {
+        /// The variables that changed in the last pass, ordered by variable.
+        changed: Vec<Variable>,
+    },
+    /// The values were still changing after the maximum number of passes.
+    Diverged {
+        /// The variables that changed in the last pass, ordered by variable.
+        changed: Vec<Variable>,
+    },
+}
+
+impl Convergence {
+    /// Whether the values converged.
+    pub fn is_converged(&self) -> bool {
+        matches!(self, Convergence::Converged { .. })
+    }
+}
+
+/// Constraints merged into a parallel constraint that is in the tableau.
+#[derive(Default, Clone)]
+struct ParallelGroup {
+    members: Vec<Constraint>,
+    /// The total weight the members add to the errors of the constraint they are merged into.
+    weight: f64,
+}
+
+/// Identifies constraints with the same normalized expression and operator, and with the same
+/// normalized constant unless `constant` is `None`.
+///
+/// The key without a constant is that of the tightest required inequality, which implies its
+/// parallel constraints with looser constants.
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct ParallelKey {
+    op: RelationalOperator,
+    constant: Option<u64>,
+    terms: Vec<(Variable, u64)>,
+}
+
+impl ParallelKey {
+    /// The key of an inequality without its constant.
+    fn any_constant(&self) -> Option<ParallelKey> {
+        if self.op == RelationalOperator::Equal {
+            return None;
+        }
+        Some(ParallelKey {
+            constant: None,
+            ..self.clone()
+        })
+    }
+
+    /// Whether a constraint with this key is satisfied whenever one with `other` is, where both
+    /// keys have the same expression and operator.
+    fn is_implied_by(&self, other: &ParallelKey) -> bool {
+        let (Some(constant), Some(other_constant)) = (self.constant, other.constant) else {
+            return false;
+        };
+        let (constant, other_constant) = (f64::from_bits(constant), f64::from_bits(other_constant));
+        // The normalized constraints are `e + constant <= 0` and `e + constant >= 0`.
+        match self.op {
+            RelationalOperator::LessOrEqual => constant <= other_constant,
+            RelationalOperator::GreaterOrEqual => constant >= other_constant,
+            RelationalOperator::Equal => constant == other_constant,
+        }
+    }
+}
+
+/// How far strict inequalities keep their expressions from equality by default.
+pub(crate) const DEFAULT_STRICT_EPSILON: f64 = 1e-6;
+
+/// The names, pinned variables, bound keys and stay variable of a constraint, the reverse of the
+/// `named`, `pins`, `bounds` and `stays` maps of the solver.
+#[derive(Default, Clone)]
+struct Handles {
+    names: Vec<String>,
+    pins: Vec<Variable>,
+    bounds: Vec<(Variable, RelationalOperator)>,
+    stay: Option<Variable>,
+}
+
+impl Handles {
+    fn is_empty(&self) -> bool {
+        self.names.is_empty()
+            && self.pins.is_empty()
+            && self.bounds.is_empty()
+            && self.stay.is_none()
+    }
+}
+
+/// The state of a solver at some point, created by [`Solver::snapshot`] and rolled back to with
+/// [`Solver::restore`].
+///
+/// A snapshot holds a copy of the tableau, so taking one costs about as much as cloning the
+/// solver. It can be restored any number of times.
+#[derive(Clone)]
+pub struct Snapshot {
+    constraints: HashMap<Constraint, Tag>,
+    named: HashMap<String, Constraint>,
+    pins: HashMap<Variable, Constraint>,
+    /// The bound constraints of variables, keyed by the variable and the operator of the bound.
+    bounds: HashMap<(Variable, RelationalOperator), Constraint>,
+    /// The stay constraint of each variable with a stay, and the value it holds the variable at.
+    stays: HashMap<Variable, (Constraint, f64)>,
+    /// The disabled constraints, with the weight taken out of the objective for those that are not
+    /// required. Disabled required constraints are taken out of the tableau.
+    disabled: HashMap<Constraint, Option<f64>>,
+    /// The active conditions.
+    conditions: HashSet<u64>,
+    params: HashMap<Variable, f64>,
+    parallel: HashMap<ParallelKey, (Constraint, f64)>,
+    groups: HashMap<Constraint, ParallelGroup>,
+    merged: HashMap<Constraint, (Constraint, f64)>,
+    var_data: VarMap<(f64, Symbol, usize)>,
+    var_for_symbol: HashMap<Symbol, Variable>,
+    rows: HashMap<Symbol, Box<Row>>,
+    edits: HashMap<Variable, usize>,
+    edit_infos: Vec<Option<EditInfo>>,
+    infeasible_rows: Vec<Symbol>,
+    objective: Row,
+    id_tick: usize,
+}
+
+/// A point to roll the solver back to, created by [`Solver::checkpoint`].
+///
+/// A checkpoint must be passed to either [`Solver::rollback_to`] or [`Solver::commit`]. Until then,
+/// the solver keeps a record of the constraints and edit variables added since.
+#[derive(Debug)]
+#[must_use]
+pub struct Checkpoint {
+    /// The length of the journal when the checkpoint was created.
+    position: usize,
+    /// The number of open checkpoints including this one.
+    depth: usize,
+}
+
+/// A constraint or edit variable added while a checkpoint is open.
+enum Added {
+    Constraint(Constraint),
+    EditVariable(Variable),
+    /// A named constraint removed by adding another under its name.
+    Replaced(String, Constraint),
+}
+
+/// Identifies an edit variable, returned by [`Solver::add_edit_variable`].
+///
+/// Suggesting a value through a handle with [`Solver::suggest`] skips looking up the edit variable,
+/// which adds up when many values are suggested every frame. A handle stays valid until its edit
+/// variable is removed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EditHandle {
+    slot: usize,
+    variable: Variable,
+}
+
+impl EditHandle {
+    /// The edit variable.
+    pub fn variable(&self) -> Variable {
+        self.variable
+    }
+}
+
+/// An edit variable whose suggested value is measured from the values of other variables.
+struct ContentSize {
+    output: Variable,
+    inputs: Vec<Variable>,
+    /// The input values at the last measurement.
+    measured: Vec<f64>,
+    measure: Measure,
+}
+
 #[derive(Clone)]
 struct EditInfo {
+    variable: Variable,
     tag: Tag,
     constraint: Constraint,
     constant: f64,
 }
 
+type PivotHook = Box<dyn FnMut(&PivotEvent)>;
+type Measure = Box<dyn FnMut(&[f64]) -> f64>;
+
 /// A constraint solver using the Cassowary algorithm. For proper usage please see the top level
 /// crate documentation.
+///
+/// By default, when several pivots are equally good the solver picks whichever it finds first,
+/// which depends on the iteration order of its internal hash maps. With the `deterministic`
+/// feature enabled, ties are always broken the same way and `fetch_changes` reports changes
+/// ordered by variable. The same sequence of operations (with variables created in the same
+/// order) then produces bit-identical results on every run and platform.
 pub struct Solver {
     constraints: HashMap<Constraint, Tag>,
-    var_data: HashMap<Variable, (f64, Symbol, usize)>,
+    named: HashMap<String, Constraint>,
+    pins: HashMap<Variable, Constraint>,
+    /// The bound constraints of variables, keyed by the variable and the operator of the bound.
+    bounds: HashMap<(Variable, RelationalOperator), Constraint>,
+    /// The stay constraint of each variable with a stay, and the value it holds the variable at.
+    stays: HashMap<Variable, (Constraint, f64)>,
+    /// The names, pins, bounds and stay of each constraint that has any, so that removing or
+    /// updating a constraint doesn't scan the maps above.
+    handles: HashMap<Constraint, Handles>,
+    /// The disabled constraints, with the weight taken out of the objective for those that are not
+    /// required. Disabled required constraints are taken out of the tableau.
+    disabled: HashMap<Constraint, Option<f64>>,
+    /// The active conditions.
+    conditions: HashSet<u64>,
+    params: HashMap<Variable, f64>,
+    /// The values reported for variables that no constraint refers to, see `set_default_value`.
+    defaults: HashMap<Variable, f64>,
+    /// The counter of `new_variable`.
+    variable_counter: VariableCounter,
+    merge_parallel: bool,
+    strict_epsilon: f64,
+    parallel: HashMap<ParallelKey, (Constraint, f64)>,
+    groups: HashMap<Constraint, ParallelGroup>,
+    merged: HashMap<Constraint, (Constraint, f64)>,
+    /// The value last reported by `fetch_changes`, the symbol and the number of constraint terms
+    /// of each variable, with a slot per variable of `variable_counter`.
+    var_data: VarMap<(f64, Symbol, usize)>,
     var_for_symbol: HashMap<Symbol, Variable>,
     public_changes: Vec<(Variable, f64)>,
     changed: HashSet<Variable>,
     should_clear_changes: bool,
     rows: HashMap<Symbol, Box<Row>>,
-    edits: HashMap<Variable, EditInfo>,
+    /// The slot in `edit_infos` of each edit variable.
+    edits: HashMap<Variable, usize>,
+    edit_infos: Vec<Option<EditInfo>>,
+    content_sizes: Vec<ContentSize>,
     infeasible_rows: Vec<Symbol>, // never contains external symbols
     objective: Rc<RefCell<Row>>,
     artificial: Option<Rc<RefCell<Row>>>,
     id_tick: usize,
+    generation: u64,
+    /// The variables whose values may have changed since the last call to `changes_since`, and
+    /// the last generation in which they did.
+    touched: HashMap<Variable, u64>,
+    /// The value of each variable as of the last call to `changes_since`, and the generation in
+    /// which it changed to that value.
+    stamped_values: HashMap<Variable, (f64, u64)>,
+    open_checkpoints: usize,
+    journal: Vec<Added>,
+    /// The positions in `journal` of each constraint in it.
+    journaled: HashMap<Constraint, Vec<usize>>,
+    /// Whether an edit session is open, see `begin_edit`.
+    editing: bool,
+    recording: Option<Vec<SolverOp>>,
+    pivot_hook: Option<PivotHook>,
+    #[cfg(feature = "alloc-stats")]
+    alloc_stats: AllocStats,
+    #[cfg(feature = "profiling")]
+    profiler: Profiler,
+    #[cfg(feature = "animation")]
+    animations: HashMap<Variable, Animation>,
 }
 
 impl Default for Solver {
@@ -64,22 +377,104 @@ impl Default for Solver {
     }
 }
 
+impl ops::Index<Variable> for Solver {
+    type Output = f64;
+
+    /// The current value of a variable, or its [default](Solver::set_default_value) if the solver
+    /// doesn't know the variable.
+    fn index(&self, v: Variable) -> &f64 {
+        match self.var_data.get(&v) {
+            Some(s) => self.rows.get(&s.1).map_or(&0.0, |row| &row.constant),
+            None => self.defaults.get(&v).unwrap_or(&0.0),
+        }
+    }
+}
+
 impl Solver {
     /// Construct a new solver.
     pub fn new() -> Solver {
         Solver {
             constraints: HashMap::new(),
-            var_data: HashMap::new(),
+            named: HashMap::new(),
+            pins: HashMap::new(),
+            bounds: HashMap::new(),
+            stays: HashMap::new(),
+            handles: HashMap::new(),
+            disabled: HashMap::new(),
+            conditions: HashSet::new(),
+            params: HashMap::new(),
+            defaults: HashMap::new(),
+            variable_counter: VariableCounter::new(),
+            merge_parallel: false,
+            strict_epsilon: DEFAULT_STRICT_EPSILON,
+            parallel: HashMap::new(),
+            groups: HashMap::new(),
+            merged: HashMap::new(),
+            var_data: VarMap::new(0),
             var_for_symbol: HashMap::new(),
             public_changes: Vec::new(),
             changed: HashSet::new(),
             should_clear_changes: false,
             rows: HashMap::new(),
             edits: HashMap::new(),
+            edit_infos: Vec::new(),
+            content_sizes: Vec::new(),
             infeasible_rows: Vec::new(),
             objective: Rc::new(RefCell::new(Row::new(0.0))),
             artificial: None,
             id_tick: 1,
+            generation: 0,
+            touched: HashMap::new(),
+            stamped_values: HashMap::new(),
+            open_checkpoints: 0,
+            journal: Vec::new(),
+            journaled: HashMap::new(),
+            editing: false,
+            recording: None,
+            pivot_hook: None,
+            #[cfg(feature = "alloc-stats")]
+            alloc_stats: AllocStats::default(),
+            #[cfg(feature = "profiling")]
+            profiler: Profiler::default(),
+            #[cfg(feature = "animation")]
+            animations: HashMap::new(),
+        }
+    }
+
+    /// Produces a new variable owned by this solver.
+    ///
+    /// Unlike [`Variable::new`], this doesn't go through the process-wide counter, so unrelated
+    /// solvers don't contend on it. The variables of each solver are counted separately by a
+    /// [`VariableCounter`], in a range of ids that [`Variable::new`] doesn't hand out, so they
+    /// never clash with other variables in this solver. Variables of different solvers can have
+    /// the same id though, so a variable must only be used with the solver that created it, its
+    /// [clones](Solver::clone_remapped) and [extracts](Solver::extract).
+    ///
+    /// The solver keeps the data of its own variables in slots indexed by their count, so looking
+    /// them up doesn't hash their ids the way it does for other variables.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength};
+    ///
+    /// let mut solver = Solver::new();
+    /// let (left, right) = (solver.new_variable(), solver.new_variable());
+    /// assert_ne!(left, right);
+    /// solver.add_constraint(left | EQ(Strength::REQUIRED) | 0.0).unwrap();
+    /// solver.add_constraint(right | EQ(Strength::REQUIRED) | (left + 10.0)).unwrap();
+    /// assert_eq!(solver.get_value(right), 10.0);
+    /// ```
+    pub fn new_variable(&mut self) -> Variable {
+        self.variable_counter.new_variable()
+    }
+
+    /// Construct a new solver whose [`Solver::new_variable`] continues a counter, so that the
+    /// variables created with the counter before the solver can be used in it.
+    pub fn with_variable_counter(counter: VariableCounter) -> Solver {
+        Solver {
+            var_data: VarMap::new(counter.namespace()),
+            variable_counter: counter,
+            ..Solver::new()
         }
     }
 
@@ -93,18 +488,170 @@ impl Solver {
         Ok(())
     }
 
+    /// Add a number of constraints to the solver, either all of them or none.
+    ///
+    /// Unlike [`Solver::add_constraints`], which leaves the constraints before a failing one in the
+    /// solver, this removes them again when a constraint can't be added. The error holds the
+    /// constraint that failed and the reason.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{AddConstraintError, Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let min: Constraint = width | GE(Strength::REQUIRED) | 100.0;
+    /// let max: Constraint = width | LE(Strength::REQUIRED) | 50.0;
+    /// let (failed, error) = solver
+    ///     .add_constraints_atomic([min.clone(), max.clone()])
+    ///     .unwrap_err();
+    /// assert_eq!(failed, max);
+    /// assert!(matches!(error, AddConstraintError::UnsatisfiableConstraint));
+    /// assert!(!solver.has_constraint(&min));
+    /// ```
+    pub fn add_constraints_atomic<I: IntoIterator<Item = Constraint>>(
+        &mut self,
+        constraints: I,
+    ) -> Result<(), (Constraint, AddConstraintError)> {
+        let editing = self.editing;
+        let checkpoint = self.checkpoint();
+        for constraint in constraints {
+            if let Err(error) = self.add_constraint(constraint.clone()) {
+                let rolled_back = self.rollback_to(checkpoint);
+                self.reopen_edit(editing);
+                return match rolled_back {
+                    Ok(()) => Err((constraint, error)),
+                    Err(internal) => Err((constraint, internal.into())),
+                };
+            }
+        }
+        self.commit(checkpoint);
+        Ok(())
+    }
+
     /// Add a constraint to the solver.
+    ///
+    /// If the constraint can't be added, the solver is left as it was before the call, apart from
+    /// its [generation](Solver::generation). An open [edit session](Solver::begin_edit) stays
+    /// open, although the values suggested in it may already be applied. The only exception is an
+    /// [`InternalSolverError`](AddConstraintError::InternalSolverError), after which the solver
+    /// can be recovered with [`Solver::rebuild`].
+    ///
+    /// A constraint created with a [name](Constraint::with_name) is added as with
+    /// [`Solver::add_named`].
     pub fn add_constraint(&mut self, constraint: Constraint) -> Result<(), AddConstraintError> {
-        if self.constraints.contains_key(&constraint) {
+        match constraint.name() {
+            Some(name) if !self.has_constraint(&constraint) => {
+                let name = String::from(name);
+                self.add_named(name, constraint).map(|_| ())
+            }
+            _ => {
+                let editing = self.editing;
+                let result = self.add_unnamed(constraint);
+                if result.is_err() {
+                    self.reopen_edit(editing);
+                }
+                result
+            }
+        }
+    }
+
+    /// Start the edit session again that a failed change ended, if one was open before it.
+    ///
+    /// Adding a constraint ends the session to apply its suggestions first. The session is started
+    /// again with [`Solver::begin_edit`], so that a recording ends and starts it as well.
+    fn reopen_edit(&mut self, editing: bool) {
+        if editing && !self.editing {
+            self.begin_edit();
+        }
+    }
+
+    /// Add a constraint to the solver that is removed again when the returned guard is dropped.
+    ///
+    /// See [`ConstraintGuard`] for an example.
+    pub fn add_constraint_scoped(
+        &mut self,
+        constraint: Constraint,
+    ) -> Result<ConstraintGuard<'_>, AddConstraintError> {
+        self.add_constraint(constraint.clone())?;
+        Ok(ConstraintGuard::new(self, constraint))
+    }
+
+    /// Start a [`Scope`]: constraints and edit variables added through it are removed from the
+    /// solver when it is closed or dropped.
+    pub fn scope(&mut self) -> Scope<'_> {
+        Scope::new(self)
+    }
+
+    fn add_unnamed(&mut self, constraint: Constraint) -> Result<(), AddConstraintError> {
+        if let Some(v) = constraint
+            .variables()
+            .find(|&v| self.variable_counter.collides(v))
+        {
+            return Err(AddConstraintError::VariableCollision(v));
+        }
+        let inactive = constraint
+            .condition()
+            .is_some_and(|condition| !self.condition(condition));
+        if inactive && constraint.strength() == Strength::REQUIRED {
+            // Kept out of the tableau until its condition is activated, so it can't conflict.
+            if self.has_constraint(&constraint) {
+                return Err(AddConstraintError::DuplicateConstraint);
+            }
+            self.end_edit()?;
+            self.disabled.insert(constraint.clone(), None);
+        } else {
+            self.add_constraint_with(constraint.clone(), self.merge_parallel)?;
+            if inactive {
+                self.disable(&constraint, true).map_err(|e| match e {
+                    SetEnabledError::InternalSolverError(e) => AddConstraintError::from(e),
+                    _ => unreachable!(),
+                })?;
+            }
+        }
+        self.record(|| SolverOp::AddConstraint(constraint.clone()));
+        self.journal_constraint(constraint);
+        Ok(())
+    }
+
+    fn add_constraint_with(
+        &mut self,
+        constraint: Constraint,
+        merge: bool,
+    ) -> Result<(), AddConstraintError> {
+        self.end_edit()?;
+        if self.constraints.contains_key(&constraint)
+            || self.merged.contains_key(&constraint)
+            || self.disabled.contains_key(&constraint)
+        {
             // TODO detrmine if we could just ignore duplicate constraints
             return Err(AddConstraintError::DuplicateConstraint);
         }
+        let key = if merge {
+            Solver::parallel_key(&constraint)
+        } else {
+            None
+        };
+        if let Some((key, scale)) = &key {
+            if let Some((primary, primary_scale)) = self.parallel.get(key).cloned() {
+                if self.merge_into(primary, primary_scale, constraint.clone(), *scale)? {
+                    return Ok(());
+                }
+            }
+            if let Some((primary, primary_scale)) = self.bounding(key) {
+                let (primary_key, _) = Solver::parallel_key(&primary).unwrap();
+                if key.is_implied_by(&primary_key) {
+                    // A required primary takes any constraint.
+                    self.merge_into(primary, primary_scale, constraint.clone(), *scale)?;
+                    return Ok(());
+                }
+            }
+        }
 
-        // Creating a row causes symbols to reserved for the variables in the constraint. If this
-        // method exits with an exception, then its possible those variables will linger in the var
-        // map. Since its likely that those variables will be used in other constraints and since
-        // exceptional conditions are uncommon, i'm not too worried about aggressive cleanup of the
-        // var map.
+        // Creating a row causes symbols to reserved for the variables in the constraint. If the
+        // constraint turns out to be unsatisfiable, they are released again so that the solver is
+        // left as it was.
+        self.generation += 1;
         let (mut row, tag) = self.create_row(&constraint);
         let mut subject = Solver::choose_subject(&row, &tag);
 
@@ -114,6 +661,8 @@ impl Solver {
         // constant is non-zero, then it represents an unsatisfiable constraint.
         if subject.kind() == SymbolKind::Invalid && Solver::all_dummies(&row) {
             if !near_zero(row.constant) {
+                record!(self.alloc_stats.rows_dropped += 1);
+                self.release_variables(&constraint);
                 return Err(AddConstraintError::UnsatisfiableConstraint);
             } else {
                 subject = tag.marker;
@@ -123,8 +672,21 @@ impl Solver {
         // If an entering symbol still isn't found, then the row must be added using an artificial
         // variable. If that fails, then the row represents an unsatisfiable constraint.
         if subject.kind() == SymbolKind::Invalid {
+            // The row is only used as a template for the artificial rows, and is dropped here.
+            record!(self.alloc_stats.rows_dropped += 1);
             let satisfiable = self.add_with_artificial_variable(&row)?;
             if !satisfiable {
+                // The symbols of the constraint are left in the tableau, and pin the variables to
+                // the values that came closest to satisfying it. Remove them like those of any
+                // other constraint.
+                self.constraints.insert(constraint.clone(), tag);
+                self.remove_constraint_unrecorded(&constraint)
+                    .map_err(|error| match error {
+                        RemoveConstraintError::InternalSolverError(s) => {
+                            AddConstraintError::InternalSolverError(s)
+                        }
+                        RemoveConstraintError::UnknownConstraint => unreachable!(),
+                    })?;
                 return Err(AddConstraintError::UnsatisfiableConstraint);
             }
         } else {
@@ -134,32 +696,133 @@ impl Solver {
                 let v = self.var_for_symbol[&subject];
                 self.var_changed(v);
             }
-            self.rows.insert(subject, row);
+            tracked!(self.alloc_stats, self.rows, self.rows.insert(subject, row));
         }
 
-        self.constraints.insert(constraint, tag);
+        let bounding = key.as_ref().and_then(|(key, scale)| {
+            self.parallel
+                .entry(key.clone())
+                .or_insert_with(|| (constraint.clone(), *scale));
+            let any_constant = key.any_constant()?;
+            (constraint.strength() == Strength::REQUIRED).then_some((any_constant, *scale))
+        });
+        #[cfg(feature = "profiling")]
+        self.profiler.add(&constraint, [tag.marker, tag.other]);
+        tracked!(
+            self.alloc_stats,
+            self.constraints,
+            self.constraints.insert(constraint.clone(), tag)
+        );
 
         // Optimizing after each constraint is added performs less aggregate work due to a smaller
         // average system size. It also ensures the solver remains in a consistent state.
         let objective = self.objective.clone();
         self.optimize(&objective)?;
+
+        // A required inequality that wasn't merged is tighter than the one bounding its parallel
+        // constraints, if any, which is merged into it in turn.
+        if let Some((any_constant, scale)) = bounding {
+            if let Some((previous, _)) = self.parallel.insert(any_constant, (constraint, scale)) {
+                self.merge_bounding(previous)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The required inequality that bounds the constraints parallel to a key, if any.
+    fn bounding(&self, key: &ParallelKey) -> Option<(Constraint, f64)> {
+        self.parallel.get(&key.any_constant()?).cloned()
+    }
+
+    /// Merge a required inequality that has a row of its own into the tighter one that now bounds
+    /// its parallel constraints, along with the constraints merged into it.
+    fn merge_bounding(&mut self, constraint: Constraint) -> Result<(), AddConstraintError> {
+        let names = self.names_of(&constraint);
+        self.remove_constraint_with(&constraint, true)
+            .map_err(|error| match error {
+                RemoveConstraintError::InternalSolverError(s) => {
+                    AddConstraintError::InternalSolverError(s)
+                }
+                RemoveConstraintError::UnknownConstraint => unreachable!(),
+            })?;
+        self.add_constraint_with(constraint.clone(), true)?;
+        self.restore_names(&constraint, names);
         Ok(())
     }
 
     /// Remove a constraint from the solver.
+    ///
+    /// If the constraint is not in the solver, the solver is left unchanged. After an
+    /// [`InternalSolverError`](RemoveConstraintError::InternalSolverError), the solver can be
+    /// recovered with [`Solver::rebuild`].
     pub fn remove_constraint(
         &mut self,
         constraint: &Constraint,
     ) -> Result<(), RemoveConstraintError> {
+        self.remove_constraint_unrecorded(constraint)?;
+        self.record(|| SolverOp::RemoveConstraint(constraint.clone()));
+        Ok(())
+    }
+
+    /// Remove a constraint without recording the operation, for operations that are recorded as a
+    /// whole.
+    fn remove_constraint_unrecorded(
+        &mut self,
+        constraint: &Constraint,
+    ) -> Result<(), RemoveConstraintError> {
+        self.remove_constraint_with(constraint, true)
+    }
+
+    /// Remove a constraint without recording the operation, optimizing afterwards only if
+    /// `optimize` is set. Without optimizing, the solution is feasible but not optimal.
+    fn remove_constraint_with(
+        &mut self,
+        constraint: &Constraint,
+        optimize: bool,
+    ) -> Result<(), RemoveConstraintError> {
+        self.end_edit()?;
+        match self.disabled.remove(constraint) {
+            // The constraint is no longer in the tableau.
+            Some(None) => {
+                self.generation += 1;
+                self.forget(constraint);
+                return Ok(());
+            }
+            // The weight of a merged constraint is taken out of its group, see `disable`.
+            Some(Some(weight)) if !self.merged.contains_key(constraint) => {
+                let tag = self.constraints[constraint];
+                self.add_error_weight(&tag, weight);
+            }
+            _ => {}
+        }
+        if let Some((primary, weight)) = self.merged.remove(constraint) {
+            return self.remove_merged(constraint, primary, weight, optimize);
+        }
         let tag = self
             .constraints
             .remove(constraint)
             .ok_or(RemoveConstraintError::UnknownConstraint)?;
+        self.generation += 1;
+        #[cfg(feature = "profiling")]
+        self.profiler.remove([tag.marker, tag.other]);
+        self.forget(constraint);
+        let group = self.groups.remove(constraint);
+        if let Some((key, _)) = Solver::parallel_key(constraint) {
+            let any_constant = key.any_constant();
+            for key in iter::once(key).chain(any_constant) {
+                if let Entry::Occupied(entry) = self.parallel.entry(key) {
+                    if entry.get().0 == *constraint {
+                        entry.remove();
+                    }
+                }
+            }
+        }
 
         // Remove the error effects from the objective function
         // *before* pivoting, or substitutions into the objective
         // will lead to incorrect solver results.
-        self.remove_constraint_effects(constraint, &tag);
+        let weight = group.as_ref().map_or(0.0, |group| group.weight);
+        self.remove_constraint_effects(constraint, &tag, weight);
 
         // If the marker is basic, simply drop the row. Otherwise,
         // pivot the marker into the basis and then drop the row.
@@ -172,117 +835,1924 @@ impl Solver {
             row.solve_for_symbols(leaving, tag.marker);
             self.substitute(tag.marker, &row);
         }
+        record!(self.alloc_stats.rows_dropped += 1);
 
         // Optimizing after each constraint is removed ensures that the
         // solver remains consistent. It makes the solver api easier to
         // use at a small tradeoff for speed.
-        let objective = self.objective.clone();
-        self.optimize(&objective)?;
+        if optimize {
+            let objective = self.objective.clone();
+            self.optimize(&objective)?;
+        }
 
-        // Check for and decrease the reference count for variables referenced by the constraint
-        // If the reference count is zero remove the variable from the variable map
-        for term in &constraint.expr().terms {
-            if !near_zero(term.coefficient) {
-                let mut should_remove = false;
-                if let Some(&mut (_, _, ref mut count)) = self.var_data.get_mut(&term.variable) {
-                    *count -= 1;
-                    should_remove = *count == 0;
-                }
-                if should_remove {
-                    self.var_for_symbol.remove(&self.var_data[&term.variable].1);
-                    self.var_data.remove(&term.variable);
-                }
+        self.release_variables(constraint);
+
+        // Constraints that were merged into the removed constraint are added back on their own,
+        // merging into the first of them where possible. Disabled ones are disabled again.
+        for member in group.into_iter().flat_map(|group| group.members) {
+            self.merged.remove(&member);
+            let disabled = self.disabled.remove(&member).is_some();
+            self.add_constraint_with(member.clone(), true)
+                .map_err(|error| match error {
+                    AddConstraintError::InternalSolverError(s) => {
+                        RemoveConstraintError::InternalSolverError(s)
+                    }
+                    AddConstraintError::DuplicateConstraint
+                    | AddConstraintError::UnsatisfiableConstraint
+                    | AddConstraintError::VariableCollision(_) => unreachable!(),
+                })?;
+            if disabled {
+                self.disable(&member, optimize)
+                    .map_err(|error| match error {
+                        SetEnabledError::InternalSolverError(s) => {
+                            RemoveConstraintError::InternalSolverError(s)
+                        }
+                        SetEnabledError::UnknownConstraint
+                        | SetEnabledError::UnsatisfiableConstraint => unreachable!(),
+                    })?;
             }
         }
         Ok(())
     }
 
-    /// Test whether a constraint has been added to the solver.
-    pub fn has_constraint(&self, constraint: &Constraint) -> bool {
-        self.constraints.contains_key(constraint)
+    /// Replace a constraint with one that only differs in the constant of its expression,
+    /// returning the new constraint.
+    ///
+    /// For a constraint that is not required, this adjusts the solution in place like
+    /// [`Solver::suggest_value`] does, which is much cheaper than removing the constraint and
+    /// adding the new one. Names and pins of the constraint carry over to the new one. Note that the
+    /// constant is on the same side as the variables, so `width == 240` has the constant `-240`.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let constraint: Constraint = width | EQ(Strength::STRONG) | 200.0;
+    /// solver.add_constraint(constraint.clone()).unwrap();
+    /// let constraint = solver.update_constant(&constraint, -240.0).unwrap();
+    /// assert_eq!(solver.get_value(width), 240.0);
+    /// assert!(solver.has_constraint(&constraint));
+    /// ```
+    ///
+    /// A required constraint is removed and the new one added. If the new one is unsatisfiable,
+    /// the old one is kept, and [`UpdateConstantError::UnsatisfiableConstraint`] is returned.
+    /// A [disabled](Solver::set_enabled) constraint is replaced by a disabled one.
+    pub fn update_constant(
+        &mut self,
+        constraint: &Constraint,
+        constant: f64,
+    ) -> Result<Constraint, UpdateConstantError> {
+        let updated = self.update_constant_unrecorded(constraint, constant)?;
+        // Recorded as a replacement, so that replaying it produces the same constraint.
+        self.record(|| SolverOp::RemoveConstraint(constraint.clone()));
+        self.record(|| SolverOp::AddConstraint(updated.clone()));
+        Ok(updated)
     }
 
-    /// Add an edit variable to the solver.
-    ///
-    /// This method should be called before the `suggest_value` method is
-    /// used to supply a suggested value for the given edit variable.
-    pub fn add_edit_variable(
+    fn update_constant_unrecorded(
         &mut self,
-        v: Variable,
-        strength: Strength,
-    ) -> Result<(), AddEditVariableError> {
-        if self.edits.contains_key(&v) {
-            return Err(AddEditVariableError::DuplicateEditVariable);
-        }
-        if strength == Strength::REQUIRED {
-            return Err(AddEditVariableError::BadRequiredStrength);
-        }
-        let cn = Constraint::new(
-            Expression::from_term(Term::new(v, 1.0)),
-            RelationalOperator::Equal,
-            strength,
-        );
-        self.add_constraint(cn.clone()).unwrap();
-        self.edits.insert(
-            v,
-            EditInfo {
-                tag: self.constraints[&cn],
-                constraint: cn,
-                constant: 0.0,
-            },
-        );
-        Ok(())
+        constraint: &Constraint,
+        constant: f64,
+    ) -> Result<Constraint, UpdateConstantError> {
+        let updated = constraint.with_constant(constant);
+        let is_primary = Solver::parallel_key(constraint).is_some_and(|(key, _)| {
+            self.parallel
+                .get(&key)
+                .is_some_and(|(primary, _)| primary == constraint)
+        });
+        let tag = match self.constraints.get(constraint) {
+            Some(&tag) if constraint.strength() < Strength::REQUIRED && !is_primary => tag,
+            Some(_) => return self.replace_constraint(constraint, updated),
+            None if self.merged.contains_key(constraint) => {
+                return self.replace_constraint(constraint, updated)
+            }
+            // A disabled required constraint is out of the tableau, so only its key changes.
+            None if self.disabled.get(constraint) == Some(&None) => {
+                self.generation += 1;
+                self.carry_over(constraint, &updated);
+                return Ok(updated);
+            }
+            None => return Err(UpdateConstantError::UnknownConstraint),
+        };
+        self.end_edit()?;
+        self.generation += 1;
+        self.constraints.remove(constraint);
+        self.constraints.insert(updated.clone(), tag);
+        self.carry_over(constraint, &updated);
+        #[cfg(feature = "profiling")]
+        self.profiler.add(&updated, [tag.marker, tag.other]);
+        let marker = Solver::marker_coefficient(constraint);
+        self.shift_constant(tag, (constant - constraint.constant()) / marker);
+        self.dual_optimize()?;
+        Ok(updated)
     }
 
-    /// Remove an edit variable from the solver.
-    pub fn remove_edit_variable(&mut self, v: Variable) -> Result<(), RemoveEditVariableError> {
-        if let Some(constraint) = self.edits.remove(&v).map(|e| e.constraint) {
-            self.remove_constraint(&constraint).map_err(|e| match e {
-                RemoveConstraintError::UnknownConstraint => {
-                    RemoveEditVariableError::InternalSolverError(
-                        InternalSolverError::EditConstraintNotInSystem,
-                    )
-                }
-                RemoveConstraintError::InternalSolverError(s) => {
-                    RemoveEditVariableError::InternalSolverError(s)
+    /// Replace a constraint by removing it and adding another, adding it back if the other can't
+    /// be added.
+    fn replace_constraint(
+        &mut self,
+        constraint: &Constraint,
+        updated: Constraint,
+    ) -> Result<Constraint, UpdateConstantError> {
+        let names = self.names_of(constraint);
+        let enabled = self.is_enabled(constraint);
+        self.remove_constraint_unrecorded(constraint)
+            .map_err(|e| match e {
+                RemoveConstraintError::UnknownConstraint => UpdateConstantError::UnknownConstraint,
+                RemoveConstraintError::InternalSolverError(e) => e.into(),
+            })?;
+        let merge = self.merge_parallel;
+        let (kept, result) = match self.add_constraint_with(updated.clone(), merge) {
+            Ok(()) => (updated, Ok(())),
+            Err(AddConstraintError::InternalSolverError(e)) => return Err(e.into()),
+            Err(_) => {
+                self.add_constraint_with(constraint.clone(), merge)
+                    .map_err(|e| match e {
+                        AddConstraintError::InternalSolverError(e) => UpdateConstantError::from(e),
+                        // The constraint was in the solver before.
+                        AddConstraintError::DuplicateConstraint
+                        | AddConstraintError::UnsatisfiableConstraint
+                        | AddConstraintError::VariableCollision(_) => unreachable!(),
+                    })?;
+                let unsatisfiable = Err(UpdateConstantError::UnsatisfiableConstraint);
+                (constraint.clone(), unsatisfiable)
+            }
+        };
+        self.restore_names(&kept, names);
+        if kept != *constraint {
+            self.carry_over(constraint, &kept);
+        }
+        if !enabled {
+            self.disable(&kept, true).map_err(|e| match e {
+                SetEnabledError::InternalSolverError(e) => UpdateConstantError::from(e),
+                SetEnabledError::UnknownConstraint | SetEnabledError::UnsatisfiableConstraint => {
+                    unreachable!()
                 }
             })?;
-            Ok(())
-        } else {
-            Err(RemoveEditVariableError::UnknownEditVariable)
         }
+        result.map(|()| kept)
     }
 
-    /// Test whether an edit variable has been added to the solver.
-    pub fn has_edit_variable(&self, v: &Variable) -> bool {
-        self.edits.contains_key(v)
+    /// Make the names, pins, bounds and checkpoint entries of a constraint refer to its
+    /// replacement.
+    fn carry_over(&mut self, constraint: &Constraint, updated: &Constraint) {
+        if let Some(mut handles) = self.handles.remove(constraint) {
+            // A stay holds its variable at the value it was added with, so it isn't carried over.
+            if let Some(v) = handles.stay.take() {
+                let stay = Handles {
+                    stay: Some(v),
+                    ..Handles::default()
+                };
+                self.handles.insert(constraint.clone(), stay);
+            }
+            self.restore_names(updated, handles);
+        }
+        if let Some(positions) = self.journaled.remove(constraint) {
+            for &position in &positions {
+                self.journal[position] = Added::Constraint(updated.clone());
+            }
+            let journaled = self.journaled.entry(updated.clone()).or_default();
+            journaled.extend(positions);
+            journaled.sort_unstable();
+        }
+        if let Some(weight) = self.disabled.remove(constraint) {
+            self.disabled.insert(updated.clone(), weight);
+        }
     }
 
-    /// Suggest a value for the given edit variable.
+    /// Enable or disable a constraint in the solver.
     ///
-    /// This method should be used after an edit variable has been added to
-    /// the solver in order to suggest the value for that variable.
-    pub fn suggest_value(
+    /// A disabled constraint has no effect on the solution, but stays in the solver, so that
+    /// enabling it again is cheap. This suits responsive layouts, which switch between sets of
+    /// constraints at breakpoints. A constraint that is not required keeps its place in the
+    /// tableau while disabled. A required constraint is taken out of the tableau, and enabling it
+    /// fails with [`SetEnabledError::UnsatisfiableConstraint`] if it conflicts with the enabled
+    /// constraints, in which case it stays disabled. Names and pins are kept either way, and
+    /// removing a disabled constraint works as usual.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let wide: Constraint = width | EQ(Strength::STRONG) | 120.0;
+    /// solver.add_constraint(wide.clone()).unwrap();
+    /// solver.add_constraint(width | EQ(Strength::WEAK) | 80.0).unwrap();
+    ///
+    /// solver.set_enabled(&wide, false).unwrap();
+    /// assert_eq!(solver.get_value(width), 80.0);
+    /// solver.set_enabled(&wide, true).unwrap();
+    /// assert_eq!(solver.get_value(width), 120.0);
+    /// ```
+    pub fn set_enabled(
         &mut self,
-        variable: Variable,
-        value: f64,
-    ) -> Result<(), SuggestValueError> {
-        let (info_tag_marker, info_tag_other, delta) = {
-            let info = self
-                .edits
-                .get_mut(&variable)
-                .ok_or(SuggestValueError::UnknownEditVariable)?;
-            let delta = value - info.constant;
-            info.constant = value;
-            (info.tag.marker, info.tag.other, delta)
-        };
-        // tag.marker and tag.other are never external symbols
+        constraint: &Constraint,
+        enabled: bool,
+    ) -> Result<(), SetEnabledError> {
+        if !self.has_constraint(constraint) {
+            return Err(SetEnabledError::UnknownConstraint);
+        }
+        if enabled != self.is_enabled(constraint) {
+            if enabled {
+                self.enable(constraint, true)?;
+            } else {
+                self.disable(constraint, true)?;
+            }
+        }
+        self.record(|| SolverOp::SetEnabled(constraint.clone(), enabled));
+        Ok(())
+    }
 
-        // The nice version of the following code runs into non-lexical borrow issues.
-        // Ideally the `if row...` code would be in the body of the if. Pretend that it is.
-        {
-            let infeasible_rows = &mut self.infeasible_rows;
-            if self
+    /// Whether a constraint is enabled, see [`Solver::set_enabled`]. Constraints that are not in
+    /// the solver are not disabled.
+    pub fn is_enabled(&self, constraint: &Constraint) -> bool {
+        !self.disabled.contains_key(constraint)
+    }
+
+    /// Activate or deactivate a condition, enabling or disabling every constraint in the solver
+    /// that was created [under it](Constraint::when).
+    ///
+    /// Conditions that were never activated are inactive. The solver is optimized once after all
+    /// of the constraints are switched. If a required constraint can't be enabled, the others are
+    /// still switched, it stays disabled, and [`SetEnabledError::UnsatisfiableConstraint`] is
+    /// returned.
+    pub fn set_condition(&mut self, condition: u64, active: bool) -> Result<(), SetEnabledError> {
+        if self.condition(condition) == active {
+            self.record(|| SolverOp::SetCondition(condition, active));
+            return Ok(());
+        }
+        if active {
+            self.conditions.insert(condition);
+        } else {
+            self.conditions.remove(&condition);
+        }
+        let mut guarded: Vec<Constraint> = self
+            .constraints
+            .keys()
+            .chain(self.merged.keys())
+            .chain(self.disabled.keys())
+            .filter(|constraint| {
+                constraint.condition() == Some(condition) && self.is_enabled(constraint) != active
+            })
+            .cloned()
+            .collect();
+        // Switch soft constraints first, so that adding a required one optimizes with their
+        // weights in place.
+        guarded.sort_by_key(|constraint| constraint.strength() == Strength::REQUIRED);
+        let mut result = Ok(());
+        for constraint in &guarded {
+            let switched = if active {
+                self.enable(constraint, false)
+            } else {
+                self.disable(constraint, false)
+            };
+            match switched {
+                Ok(()) => {}
+                Err(SetEnabledError::UnsatisfiableConstraint) => {
+                    result = Err(SetEnabledError::UnsatisfiableConstraint)
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        let objective = self.objective.clone();
+        self.optimize(&objective)?;
+        // The condition is switched even if a required constraint stays disabled.
+        self.record(|| SolverOp::SetCondition(condition, active));
+        result
+    }
+
+    /// Whether a condition is active, see [`Solver::set_condition`].
+    pub fn condition(&self, condition: u64) -> bool {
+        self.conditions.contains(&condition)
+    }
+
+    fn disable(&mut self, constraint: &Constraint, optimize: bool) -> Result<(), SetEnabledError> {
+        self.end_edit()?;
+        if constraint.strength() == Strength::REQUIRED {
+            let names = self.names_of(constraint);
+            self.remove_constraint_unrecorded(constraint)
+                .map_err(|e| match e {
+                    RemoveConstraintError::InternalSolverError(e) => SetEnabledError::from(e),
+                    RemoveConstraintError::UnknownConstraint => unreachable!(),
+                })?;
+            self.restore_names(constraint, names);
+            self.disabled.insert(constraint.clone(), None);
+            return Ok(());
+        }
+        let weight = match self.merged.get_mut(constraint) {
+            // The weight of a merged constraint is taken out of its group, so that the weight of
+            // the group stays what the primary constraint's error symbols have.
+            Some((primary, weight)) => {
+                let (primary, removed) = (primary.clone(), mem::replace(weight, 0.0));
+                if let Some(group) = self.groups.get_mut(&primary) {
+                    group.weight -= removed;
+                }
+                let tag = self.constraints[&primary];
+                self.add_error_weight(&tag, -removed);
+                removed
+            }
+            None => {
+                let weight = constraint.error_weight();
+                let tag = self.constraints[constraint];
+                self.add_error_weight(&tag, -weight);
+                weight
+            }
+        };
+        self.disabled.insert(constraint.clone(), Some(weight));
+        self.generation += 1;
+        if optimize {
+            let objective = self.objective.clone();
+            self.optimize(&objective)?;
+        }
+        Ok(())
+    }
+
+    fn enable(&mut self, constraint: &Constraint, optimize: bool) -> Result<(), SetEnabledError> {
+        self.end_edit()?;
+        let Some(weight) = self.disabled.remove(constraint) else {
+            return Ok(());
+        };
+        let Some(weight) = weight else {
+            let names = self.names_of(constraint);
+            return match self.add_constraint_with(constraint.clone(), self.merge_parallel) {
+                Ok(()) => {
+                    self.restore_names(constraint, names);
+                    Ok(())
+                }
+                Err(AddConstraintError::InternalSolverError(e)) => Err(e.into()),
+                Err(_) => {
+                    self.disabled.insert(constraint.clone(), None);
+                    self.restore_names(constraint, names);
+                    Err(SetEnabledError::UnsatisfiableConstraint)
+                }
+            };
+        };
+        let tag = match self.merged.get_mut(constraint) {
+            Some((primary, merged_weight)) => {
+                *merged_weight = weight;
+                let primary = primary.clone();
+                if let Some(group) = self.groups.get_mut(&primary) {
+                    group.weight += weight;
+                }
+                self.constraints[&primary]
+            }
+            None => self.constraints[constraint],
+        };
+        self.add_error_weight(&tag, weight);
+        self.generation += 1;
+        if optimize {
+            let objective = self.objective.clone();
+            self.optimize(&objective)?;
+        }
+        Ok(())
+    }
+
+    /// The names, pinned variables and bounds of a constraint, to keep while it is out of the
+    /// tableau.
+    fn names_of(&self, constraint: &Constraint) -> Handles {
+        self.handles
+            .get(constraint)
+            .map_or_else(Handles::default, |handles| Handles {
+                stay: None,
+                ..handles.clone()
+            })
+    }
+
+    /// Give a constraint the names, pins and bounds returned by `names_of`.
+    fn restore_names(&mut self, constraint: &Constraint, handles: Handles) {
+        for name in handles.names {
+            self.index_name(name, constraint.clone());
+        }
+        for v in handles.pins {
+            self.index_pin(v, constraint.clone());
+        }
+        for key in handles.bounds {
+            self.index_bound(key, constraint.clone());
+        }
+    }
+
+    /// Forget the names, pins, bounds and stays of a removed constraint.
+    fn forget(&mut self, constraint: &Constraint) {
+        let Some(handles) = self.handles.remove(constraint) else {
+            return;
+        };
+        for name in &handles.names {
+            self.named.remove(name);
+        }
+        for v in &handles.pins {
+            self.pins.remove(v);
+        }
+        for key in &handles.bounds {
+            self.bounds.remove(key);
+        }
+        if let Some(v) = &handles.stay {
+            self.stays.remove(v);
+        }
+    }
+
+    /// Name a constraint.
+    fn index_name(&mut self, name: String, constraint: Constraint) {
+        if let Some(previous) = self.named.insert(name.clone(), constraint.clone()) {
+            self.unindex(&previous, |handles| handles.names.retain(|n| *n != name));
+        }
+        self.handles.entry(constraint).or_default().names.push(name);
+    }
+
+    /// Make a constraint the pin of a variable.
+    fn index_pin(&mut self, v: Variable, constraint: Constraint) {
+        if let Some(previous) = self.pins.insert(v, constraint.clone()) {
+            self.unindex(&previous, |handles| handles.pins.retain(|&pin| pin != v));
+        }
+        self.handles.entry(constraint).or_default().pins.push(v);
+    }
+
+    /// Make a constraint the bound of a variable, keyed by the variable and operator.
+    fn index_bound(&mut self, key: (Variable, RelationalOperator), constraint: Constraint) {
+        if let Some(previous) = self.bounds.insert(key, constraint.clone()) {
+            self.unindex(&previous, |handles| handles.bounds.retain(|&k| k != key));
+        }
+        self.handles.entry(constraint).or_default().bounds.push(key);
+    }
+
+    /// Make a constraint the stay of a variable, holding it at `value`.
+    fn index_stay(&mut self, v: Variable, constraint: Constraint, value: f64) {
+        if let Some((previous, _)) = self.stays.insert(v, (constraint.clone(), value)) {
+            self.unindex(&previous, |handles| handles.stay = None);
+        }
+        self.handles.entry(constraint).or_default().stay = Some(v);
+    }
+
+    /// Remove a handle of a constraint from the index.
+    fn unindex(&mut self, constraint: &Constraint, remove: impl FnOnce(&mut Handles)) {
+        if let Some(handles) = self.handles.get_mut(constraint) {
+            remove(handles);
+            if handles.is_empty() {
+                self.handles.remove(constraint);
+            }
+        }
+    }
+
+    /// Rebuild the index of names, pins, bounds and stays after replacing those maps.
+    fn index_handles(&mut self) {
+        self.handles.clear();
+        for (name, constraint) in &self.named {
+            let handles = self.handles.entry(constraint.clone()).or_default();
+            handles.names.push(name.clone());
+        }
+        for (&v, constraint) in &self.pins {
+            self.handles
+                .entry(constraint.clone())
+                .or_default()
+                .pins
+                .push(v);
+        }
+        for (&key, constraint) in &self.bounds {
+            self.handles
+                .entry(constraint.clone())
+                .or_default()
+                .bounds
+                .push(key);
+        }
+        for (&v, (constraint, _)) in &self.stays {
+            self.handles.entry(constraint.clone()).or_default().stay = Some(v);
+        }
+    }
+
+    /// Add a constraint to the journal if a checkpoint is open.
+    fn journal_constraint(&mut self, constraint: Constraint) {
+        if self.open_checkpoints > 0 {
+            let position = self.journal.len();
+            self.journaled
+                .entry(constraint.clone())
+                .or_default()
+                .push(position);
+            self.journal.push(Added::Constraint(constraint));
+        }
+    }
+
+    /// Remove the entries of the journal from a position on, returning them.
+    fn truncate_journal(&mut self, position: usize) -> Vec<Added> {
+        let added = self.journal.split_off(position);
+        for added in &added {
+            if let Added::Constraint(constraint) = added {
+                if let Entry::Occupied(mut entry) = self.journaled.entry(constraint.clone()) {
+                    entry.get_mut().retain(|&p| p < position);
+                    if entry.get().is_empty() {
+                        entry.remove();
+                    }
+                }
+            }
+        }
+        added
+    }
+
+    /// Add both constraints of a range created with [`Constraint::range`].
+    ///
+    /// Either both constraints are added, or neither is.
+    pub fn add_range(&mut self, range: &RangeConstraint) -> Result<(), AddConstraintError> {
+        self.add_constraint(range.lower().clone())?;
+        if let Err(error) = self.add_constraint(range.upper().clone()) {
+            self.remove_constraint(range.lower()).map_err(|e| match e {
+                RemoveConstraintError::InternalSolverError(s) => {
+                    AddConstraintError::InternalSolverError(s)
+                }
+                RemoveConstraintError::UnknownConstraint => unreachable!(),
+            })?;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Remove both constraints of a range added with [`Solver::add_range`].
+    ///
+    /// Fails with [`RemoveConstraintError::UnknownConstraint`], leaving the solver unchanged, if
+    /// either of them is not in the solver.
+    pub fn remove_range(&mut self, range: &RangeConstraint) -> Result<(), RemoveConstraintError> {
+        if !self.has_constraint(range.lower()) || !self.has_constraint(range.upper()) {
+            return Err(RemoveConstraintError::UnknownConstraint);
+        }
+        self.remove_constraint(range.lower())?;
+        self.remove_constraint(range.upper())
+    }
+
+    /// Add all the constraints of a group.
+    ///
+    /// Either all of the constraints are added, or none of them is, see
+    /// [`Solver::add_constraints_atomic`].
+    pub fn add_group(
+        &mut self,
+        group: &ConstraintGroup,
+    ) -> Result<(), (Constraint, AddConstraintError)> {
+        self.add_constraints_atomic(group.constraints().iter().cloned())
+    }
+
+    /// Remove all the constraints of a group added with [`Solver::add_group`].
+    ///
+    /// The solver is optimized once after all of them are removed, which is cheaper than removing
+    /// them one by one. Fails with [`RemoveConstraintError::UnknownConstraint`], leaving the solver
+    /// unchanged, if any of them is not in the solver.
+    pub fn remove_group(&mut self, group: &ConstraintGroup) -> Result<(), RemoveConstraintError> {
+        if !group.constraints().iter().all(|c| self.has_constraint(c)) {
+            return Err(RemoveConstraintError::UnknownConstraint);
+        }
+        for constraint in group.constraints() {
+            self.remove_constraint_with(constraint, false)?;
+            self.record(|| SolverOp::RemoveConstraint(constraint.clone()));
+        }
+        let objective = self.objective.clone();
+        self.optimize(&objective)?;
+        Ok(())
+    }
+
+    /// The constraints in the solver created with a [tag](Constraint::with_tag), in no particular
+    /// order.
+    pub fn constraints_with_tag(&self, tag: u64) -> impl Iterator<Item = &Constraint> {
+        self.constraints
+            .keys()
+            .chain(self.merged.keys())
+            .chain(self.disabled_outside_tableau())
+            .filter(move |constraint| constraint.tag() == Some(tag))
+    }
+
+    /// The disabled constraints that are taken out of the tableau. Disabled soft constraints stay
+    /// in the tableau, and are already among the constraints.
+    fn disabled_outside_tableau(&self) -> impl Iterator<Item = &Constraint> {
+        self.disabled
+            .keys()
+            .filter(|constraint| !self.constraints.contains_key(*constraint))
+    }
+
+    /// Remove all constraints created with a [tag](Constraint::with_tag), returning them.
+    ///
+    /// The solver is optimized once after all of them are removed.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let (left, width) = (Variable::new(), Variable::new());
+    /// let sidebar = 7;
+    /// let min: Constraint = width | GE(Strength::REQUIRED) | 20.0;
+    /// let left: Constraint = left | EQ(Strength::REQUIRED) | 0.0;
+    /// solver.add_constraint(min.with_tag(sidebar)).unwrap();
+    /// solver.add_constraint(left.with_tag(sidebar)).unwrap();
+    /// assert_eq!(solver.remove_by_tag(sidebar).unwrap().len(), 2);
+    /// assert_eq!(solver.get_value(width), 0.0);
+    /// ```
+    pub fn remove_by_tag(&mut self, tag: u64) -> Result<Vec<Constraint>, RemoveConstraintError> {
+        // Remove merged constraints first, so that they aren't added back when the constraint they
+        // were merged into is removed.
+        let tagged: Vec<Constraint> = self
+            .merged
+            .keys()
+            .chain(self.constraints.keys())
+            .chain(self.disabled_outside_tableau())
+            .filter(|constraint| constraint.tag() == Some(tag))
+            .cloned()
+            .collect();
+        for constraint in &tagged {
+            self.remove_constraint_with(constraint, false)?;
+            self.record(|| SolverOp::RemoveConstraint(constraint.clone()));
+        }
+        let objective = self.objective.clone();
+        self.optimize(&objective)?;
+        Ok(tagged)
+    }
+
+    /// The constraint in the solver with an [id](Constraint::id), if any.
+    ///
+    /// If several constraints in the solver have the id, which happens when the same constraint is
+    /// created and added more than once, one of them is returned. The constraints of edit variables
+    /// are not included. The search takes time proportional to the number of constraints in the
+    /// solver.
+    pub fn constraint_by_id(&self, id: ConstraintId) -> Option<&Constraint> {
+        self.constraints
+            .keys()
+            .chain(self.merged.keys())
+            .chain(self.disabled_outside_tableau())
+            .filter(|constraint| constraint.id() == id)
+            .find(|constraint| {
+                !self
+                    .edit_infos()
+                    .any(|edit| edit.constraint == **constraint)
+            })
+    }
+
+    /// Remove the constraint with an [id](Constraint::id), returning it.
+    ///
+    /// This removes constraints that were restored from a serialized state or are described in a
+    /// log, where the original constraint is not at hand. See [`Solver::constraint_by_id`] for
+    /// which constraint is removed if several have the id.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let min: Constraint = width | GE(Strength::REQUIRED) | 20.0;
+    /// let id = min.id();
+    /// solver.add_constraint(min).unwrap();
+    /// assert_eq!(solver.get_value(width), 20.0);
+    ///
+    /// solver.remove_by_id(id).unwrap();
+    /// assert_eq!(solver.get_value(width), 0.0);
+    /// ```
+    pub fn remove_by_id(&mut self, id: ConstraintId) -> Result<Constraint, RemoveConstraintError> {
+        let constraint = self
+            .constraint_by_id(id)
+            .cloned()
+            .ok_or(RemoveConstraintError::UnknownConstraint)?;
+        self.remove_constraint(&constraint)?;
+        Ok(constraint)
+    }
+
+    /// Remove all constraints weaker than `threshold`, returning them.
+    ///
+    /// This is useful for switching from a best effort layout to a strict one, or for clearing all
+    /// hinting constraints, without keeping track of them separately. The constraints backing edit
+    /// variables are not removed, see [`Solver::remove_edit_variable`] for removing those. The
+    /// solver is optimized once after all of them are removed.
+    pub fn remove_below_strength(
+        &mut self,
+        threshold: Strength,
+    ) -> Result<Vec<Constraint>, RemoveConstraintError> {
+        // Remove merged constraints first, so that they aren't added back when the constraint they
+        // were merged into is removed.
+        let weaker: Vec<Constraint> = {
+            let edits: HashSet<&Constraint> = self.edit_infos().map(|e| &e.constraint).collect();
+            self.merged
+                .keys()
+                .chain(self.constraints.keys())
+                .filter(|constraint| {
+                    constraint.strength() < threshold && !edits.contains(constraint)
+                })
+                .cloned()
+                .collect()
+        };
+        for constraint in &weaker {
+            self.remove_constraint_with(constraint, false)?;
+        }
+        let objective = self.objective.clone();
+        self.optimize(&objective)?;
+        self.record(|| SolverOp::RemoveBelowStrength(threshold));
+        Ok(weaker)
+    }
+
+    /// Remove everything in the solver that mentions a variable, returning the removed
+    /// constraints.
+    ///
+    /// This removes the edit variable, content size, pin, bounds and stay of the variable, and
+    /// every other constraint whose expression mentions it, e.g. when the widget the variable
+    /// belongs to is destroyed. Content sizes that measure with the variable are removed too, and
+    /// their outputs stay edit variables at the last measured size. The solver forgets a variable
+    /// as soon as no constraint mentions it any more, so afterwards it holds no state for the
+    /// variable, and the variable reads as zero. The solver is optimized once after all of them
+    /// are removed.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let (left, width) = (Variable::new(), Variable::new());
+    /// solver.add_constraint(left | EQ(Strength::REQUIRED) | 10.0).unwrap();
+    /// solver.add_constraint(width | GE(Strength::REQUIRED) | left).unwrap();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// assert_eq!(solver.remove_variable(width).unwrap().len(), 1);
+    /// assert!(!solver.has_edit_variable(&width));
+    /// assert_eq!(solver.get_value(left), 10.0);
+    /// ```
+    pub fn remove_variable(
+        &mut self,
+        v: Variable,
+    ) -> Result<Vec<Constraint>, RemoveConstraintError> {
+        if self.edits.contains_key(&v) {
+            self.remove_edit_variable(v).map_err(|error| match error {
+                RemoveEditVariableError::InternalSolverError(s) => {
+                    RemoveConstraintError::InternalSolverError(s)
+                }
+                RemoveEditVariableError::UnknownEditVariable => unreachable!(),
+            })?;
+        }
+        self.content_sizes
+            .retain(|content| !content.inputs.contains(&v));
+        // Remove merged constraints first, so that they aren't added back when the constraint they
+        // were merged into is removed.
+        let mentioning: Vec<Constraint> = self
+            .merged
+            .keys()
+            .chain(self.constraints.keys())
+            .chain(self.disabled_outside_tableau())
+            .filter(|constraint| constraint.terms().iter().any(|term| term.variable == v))
+            .cloned()
+            .collect();
+        for constraint in &mentioning {
+            self.remove_constraint_with(constraint, false)?;
+            self.record(|| SolverOp::RemoveConstraint(constraint.clone()));
+        }
+        let objective = self.objective.clone();
+        self.optimize(&objective)?;
+        Ok(mentioning)
+    }
+
+    /// Remove a constraint that was merged into a parallel constraint.
+    fn remove_merged(
+        &mut self,
+        constraint: &Constraint,
+        primary: Constraint,
+        weight: f64,
+        optimize: bool,
+    ) -> Result<(), RemoveConstraintError> {
+        self.generation += 1;
+        self.forget(constraint);
+        if let Entry::Occupied(mut entry) = self.groups.entry(primary.clone()) {
+            let group = entry.get_mut();
+            group.members.retain(|member| member != constraint);
+            group.weight -= weight;
+            if group.members.is_empty() {
+                entry.remove();
+            }
+        }
+        if weight != 0.0 {
+            let tag = self.constraints[&primary];
+            self.add_error_weight(&tag, -weight);
+            if optimize {
+                let objective = self.objective.clone();
+                self.optimize(&objective)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Enable or disable merging of parallel constraints. Disabled by default.
+    ///
+    /// Constraints are parallel when their expressions are the same up to a positive factor and the
+    /// constant, and they have the same operator, e.g. `2 * width >= 200` and `width >= 120`.
+    /// Generated layouts often contain many of them, e.g. piles of minimum and maximum sizes. When
+    /// merging is enabled, a constraint is merged into a parallel constraint already in the solver
+    /// instead of adding another row to the tableau, where that leaves the solution the same:
+    ///
+    /// - A constraint is merged into a required inequality that implies it, i.e. one that is at
+    ///   least as tight, such as `width >= 120` for `width >= 100`. The tightest required
+    ///   inequality is the one with a row: adding a tighter one merges the previous one into it.
+    /// - A non-required constraint is merged into a non-required one with the same constant, and
+    ///   adds its strength to that constraint's.
+    ///
+    /// Other parallel constraints, e.g. non-required ones with different constants, keep rows of
+    /// their own, as their errors are measured from different values. A required constraint is
+    /// never merged into a non-required one.
+    ///
+    /// Merged constraints are added and removed like any other, and [`Solver::merged_into`] reports
+    /// which constraint a constraint was merged into. Only constraints added while merging is
+    /// enabled are merged.
+    pub fn set_merge_parallel(&mut self, merge: bool) {
+        self.merge_parallel = merge;
+    }
+
+    /// Set how far strict inequalities, created with
+    /// [`WeightedRelation::LT`](crate::WeightedRelation::LT) and
+    /// [`WeightedRelation::GT`](crate::WeightedRelation::GT), keep their expressions from
+    /// equality. The default is `1e-6`.
+    ///
+    /// A strict inequality `a < b` is solved as `a <= b - epsilon`. The epsilon should be at
+    /// least the resolution the solution is used at, e.g. 1 for a layout in terminal cells that
+    /// truncates positions to integers. It applies to strict inequalities added afterwards, and
+    /// to all of them when the solver is [rebuilt](Solver::rebuild).
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// solver.set_strict_epsilon(1.0);
+    /// let (left, right) = (Variable::new(), Variable::new());
+    /// solver.add_constraint(left | EQ(Strength::REQUIRED) | 10.0).unwrap();
+    /// solver.add_constraint(right | GT(Strength::REQUIRED) | left).unwrap();
+    /// solver.add_constraint(right | EQ(Strength::WEAK) | 0.0).unwrap();
+    /// assert_eq!(solver.get_value(right), 11.0);
+    /// ```
+    pub fn set_strict_epsilon(&mut self, epsilon: f64) {
+        self.strict_epsilon = epsilon;
+    }
+
+    /// How far strict inequalities keep their expressions from equality, see
+    /// [`Solver::set_strict_epsilon`].
+    pub fn strict_epsilon(&self) -> f64 {
+        self.strict_epsilon
+    }
+
+    /// The amount added to the expression of a constraint for solving, which moves strict
+    /// inequalities away from equality.
+    fn strict_offset(&self, constraint: &Constraint) -> f64 {
+        match constraint.op() {
+            _ if !constraint.is_strict() => 0.0,
+            RelationalOperator::LessOrEqual => self.strict_epsilon,
+            RelationalOperator::GreaterOrEqual => -self.strict_epsilon,
+            RelationalOperator::Equal => 0.0,
+        }
+    }
+
+    /// The constraint that a constraint was merged into, if it was merged.
+    ///
+    /// See [`Solver::set_merge_parallel`].
+    pub fn merged_into(&self, constraint: &Constraint) -> Option<&Constraint> {
+        self.merged.get(constraint).map(|(primary, _)| primary)
+    }
+
+    /// Merge a constraint into a parallel one in the tableau, if that is legal. The primary must
+    /// have the same constant, or be a required inequality that implies the constraint.
+    ///
+    /// The scales are the factors by which the constraints' normalized expressions were divided.
+    fn merge_into(
+        &mut self,
+        primary: Constraint,
+        primary_scale: f64,
+        constraint: Constraint,
+        scale: f64,
+    ) -> Result<bool, InternalSolverError> {
+        let weight = match (
+            constraint.strength() < Strength::REQUIRED,
+            primary.strength() < Strength::REQUIRED,
+        ) {
+            (false, true) => return Ok(false),
+            (true, true) => constraint.error_weight() * scale / primary_scale,
+            (_, false) => 0.0,
+        };
+        let group = self.groups.entry(primary.clone()).or_default();
+        group.members.push(constraint.clone());
+        group.weight += weight;
+        if weight != 0.0 {
+            let tag = self.constraints[&primary];
+            self.add_error_weight(&tag, weight);
+        }
+        self.merged.insert(constraint, (primary, weight));
+        self.generation += 1;
+        if weight != 0.0 {
+            let objective = self.objective.clone();
+            self.optimize(&objective)?;
+        }
+        Ok(true)
+    }
+
+    /// Add weight to the error symbols of a constraint in the objective function.
+    fn add_error_weight(&mut self, tag: &Tag, weight: f64) {
+        for symbol in [tag.marker, tag.other] {
+            if symbol.kind() == SymbolKind::Error {
+                self.remove_marker_effects(symbol, -weight);
+            }
+        }
+    }
+
+    /// The key of the normalized form of a constraint, including its constant, and the factor its
+    /// expression was divided by. Constraints without any variables have no normalized form.
+    fn parallel_key(constraint: &Constraint) -> Option<(ParallelKey, f64)> {
+        // The constants of constraints with parameters change independently of each other, and
+        // those of strict inequalities are offset when solving.
+        let terms = constraint.terms();
+        if constraint.is_strict() || terms.iter().any(|term| term.variable.is_param()) {
+            return None;
+        }
+        let mut terms: Vec<(Variable, f64)> = terms
+            .iter()
+            .map(|term| (term.variable, term.coefficient))
+            .collect();
+        terms.sort_by_key(|&(variable, _)| variable);
+        terms.dedup_by(|(variable, coefficient), (first, total)| {
+            let duplicate = variable == first;
+            if duplicate {
+                *total += *coefficient;
+            }
+            duplicate
+        });
+        terms.retain(|&(_, coefficient)| !near_zero(coefficient));
+        let &(_, leading) = terms.first()?;
+        let op = match constraint.op() {
+            RelationalOperator::LessOrEqual if leading < 0.0 => RelationalOperator::GreaterOrEqual,
+            RelationalOperator::GreaterOrEqual if leading < 0.0 => RelationalOperator::LessOrEqual,
+            op => op,
+        };
+        // adding zero turns negative zero into positive zero
+        let bits = |value: f64| (value / leading + 0.0).to_bits();
+        let key = ParallelKey {
+            op,
+            constant: Some(bits(constraint.constant())),
+            terms: terms
+                .iter()
+                .map(|&(variable, coefficient)| (variable, bits(coefficient)))
+                .collect(),
+        };
+        Some((key, leading.abs()))
+    }
+
+    /// Test whether a constraint has been added to the solver.
+    ///
+    /// This can be used to check whether a constraint is installed before removing or re-adding
+    /// it, rather than relying on [`RemoveConstraintError::UnknownConstraint`] or
+    /// [`AddConstraintError::DuplicateConstraint`].
+    pub fn has_constraint(&self, constraint: &Constraint) -> bool {
+        self.constraints.contains_key(constraint)
+            || self.merged.contains_key(constraint)
+            || self.disabled.contains_key(constraint)
+    }
+
+    /// Find a constraint in the solver that is structurally equal to the given constraint.
+    ///
+    /// Frameworks that rebuild their constraints every frame can use this to detect that they are
+    /// about to re-add a constraint that is already in the solver. See
+    /// [`Constraint::structurally_equals`] for what counts as equal. The search takes time
+    /// proportional to the number of constraints in the solver.
+    pub fn find_equivalent(&self, constraint: &Constraint) -> Option<&Constraint> {
+        self.constraints
+            .keys()
+            .chain(self.merged.keys())
+            .find(|candidate| candidate.structurally_equals(constraint))
+    }
+
+    /// Iterate over the constraints in the solver whose expressions mention a variable, in no
+    /// particular order.
+    ///
+    /// This is useful for finding out why a variable has the value it has, or for removing the
+    /// constraints of a widget that is destroyed. The constraint backing an edit variable is not
+    /// included, see [`Solver::has_edit_variable`].
+    pub fn constraints_for(&self, v: Variable) -> impl Iterator<Item = &Constraint> {
+        let edit = self.edit(v).map(|edit| &edit.constraint);
+        self.constraints
+            .keys()
+            .chain(self.merged.keys())
+            .filter(move |constraint| {
+                Some(*constraint) != edit
+                    && constraint.terms().iter().any(|term| term.variable == v)
+            })
+    }
+
+    /// The constraints that currently determine the value of a variable.
+    ///
+    /// These are the active constraints: the ones that hold with equality and whose slack or error
+    /// the value of the variable is expressed in. Changing any other constraint slightly would not
+    /// move the variable. The constraint backing an edit variable is included when the suggested
+    /// value is what pins the variable. Returns an empty list if the variable is unknown or is not
+    /// pinned by anything.
+    pub fn explain(&self, v: Variable) -> Vec<Constraint> {
+        let Some(row) = self
+            .var_data
+            .get(&v)
+            .and_then(|(_, symbol, _)| self.rows.get(symbol))
+        else {
+            return Vec::new();
+        };
+        let mut binding = Vec::new();
+        for (constraint, tag) in &self.constraints {
+            if row.cells.contains_key(&tag.marker) || row.cells.contains_key(&tag.other) {
+                binding.push(constraint.clone());
+            }
+        }
+        // Merged constraints share the row of the constraint they were merged into.
+        let merged = self
+            .merged
+            .iter()
+            .filter(|(_, (primary, _))| binding.contains(primary))
+            .map(|(member, _)| member.clone())
+            .collect::<Vec<_>>();
+        binding.extend(merged);
+        binding
+    }
+
+    /// Partition the constraints in the solver into independent components.
+    ///
+    /// Two constraints are in the same component when they mention a common variable, directly or
+    /// through other constraints. Large layouts such as dashboards typically consist of many
+    /// independent components, and this shows whether the solver sees them that way. The components
+    /// are ordered by their first variable and include the constraints of edit variables.
+    pub fn components(&self) -> Vec<Component> {
+        component::components(self.constraints.keys().chain(self.merged.keys()))
+    }
+
+    /// A new solver with the constraints that affect the given variables, directly or through
+    /// other constraints.
+    ///
+    /// This isolates e.g. the layout of a misbehaving panel from a large layout into a minimal
+    /// reproducer, see also [`Solver::components`]. The new solver shares the constraints with this
+    /// one, keeps their names and whether they are [enabled](Solver::set_enabled), has the same
+    /// active [conditions](Solver::set_condition), and has the same edit variables with the same
+    /// suggested values.
+    pub fn extract(&self, vars: &[Variable]) -> Solver {
+        let mut solver = Solver::with_variable_counter(self.variable_counter.clone());
+        solver.merge_parallel = self.merge_parallel;
+        solver.strict_epsilon = self.strict_epsilon;
+        solver.params = self.params.clone();
+        solver.defaults = self.defaults.clone();
+        solver.conditions = self.conditions.clone();
+        let edit_constraints: HashSet<&Constraint> = self
+            .edit_infos()
+            .map(|edit| &edit.constraint)
+            .chain(self.stays.values().map(|(stay, _)| stay))
+            .collect();
+        let names: HashMap<&Constraint, &String> = self
+            .named
+            .iter()
+            .map(|(name, constraint)| (constraint, name))
+            .collect();
+        // Disabled required constraints are not in the tableau, but belong to the components of
+        // their variables all the same.
+        let disabled_required = self
+            .disabled
+            .iter()
+            .filter(|(_, weight)| weight.is_none())
+            .map(|(constraint, _)| constraint);
+        let constraints = self.constraints.keys().chain(self.merged.keys());
+        for component in component::components(constraints.chain(disabled_required)) {
+            if !component.variables().iter().any(|v| vars.contains(v)) {
+                continue;
+            }
+            for constraint in component.constraints() {
+                if edit_constraints.contains(constraint) {
+                    continue;
+                }
+                if self.disabled.get(constraint) == Some(&None) {
+                    // Kept out of the tableau, as it may conflict with the other constraints.
+                    solver.disabled.insert(constraint.clone(), None);
+                    if let Some(&name) = names.get(constraint) {
+                        solver.index_name(name.clone(), constraint.clone());
+                    }
+                    continue;
+                }
+                // The constraints could all be added to this solver, so they can be added again.
+                let _ = match names.get(constraint) {
+                    Some(&name) => solver.add_named(name.clone(), constraint.clone()).map(drop),
+                    None => solver.add_constraint(constraint.clone()),
+                };
+                if !self.is_enabled(constraint) {
+                    let _ = solver.set_enabled(constraint, false);
+                }
+            }
+            for v in component.variables() {
+                if let Some((stay, value)) = self.stays.get(v) {
+                    let _ = solver.insert_stay(*v, stay.strength(), *value);
+                }
+                if let Some(edit) = self.edit(*v) {
+                    let _ = solver.add_edit_variable(*v, edit.constraint.strength());
+                    let _ = solver.suggest_value(*v, edit.constant);
+                }
+            }
+        }
+        solver
+    }
+
+    /// Add a constraint to the solver under a name, replacing the constraint previously added under
+    /// the same name, if any.
+    ///
+    /// Names give constraints a stable identity, e.g. for exposing overrides to end users. The
+    /// replaced constraint is removed from the solver and returned. If the new constraint cannot
+    /// be added, the replaced constraint is kept.
+    ///
+    /// A named constraint can be removed with either [`Solver::remove_named`] or
+    /// [`Solver::remove_constraint`], both of which also forget its name.
+    pub fn add_named(
+        &mut self,
+        name: impl Into<String>,
+        constraint: Constraint,
+    ) -> Result<Option<Constraint>, AddConstraintError> {
+        let name = name.into();
+        let editing = self.editing;
+        let previous = self.named.get(&name).cloned();
+        if let Some(previous) = &previous {
+            if *previous == constraint {
+                return Ok(None);
+            }
+            self.remove_constraint(previous)
+                .map_err(|error| match error {
+                    RemoveConstraintError::InternalSolverError(s) => {
+                        AddConstraintError::InternalSolverError(s)
+                    }
+                    RemoveConstraintError::UnknownConstraint => unreachable!(),
+                })?;
+        }
+        let position = self.journal.len();
+        if let Some(previous) = &previous {
+            if self.open_checkpoints > 0 {
+                self.journal
+                    .push(Added::Replaced(name.clone(), previous.clone()));
+            }
+        }
+        if let Err(error) = self.add_unnamed(constraint.clone()) {
+            self.journal.truncate(position);
+            if let Some(previous) = previous {
+                self.add_replaced(name, previous)?;
+            }
+            self.reopen_edit(editing);
+            return Err(error);
+        }
+        self.index_name(name, constraint);
+        Ok(previous)
+    }
+
+    /// Add back a named constraint that was replaced by [`Solver::add_named`].
+    fn add_replaced(
+        &mut self,
+        name: String,
+        previous: Constraint,
+    ) -> Result<(), AddConstraintError> {
+        // Not an addition that a checkpoint should roll back, but it must be replayed.
+        self.add_constraint_with(previous.clone(), self.merge_parallel)?;
+        self.record(|| SolverOp::AddConstraint(previous.clone()));
+        self.index_name(name, previous);
+        Ok(())
+    }
+
+    /// The constraint added under a name, if any.
+    #[doc(alias = "get_by_name")]
+    pub fn named(&self, name: &str) -> Option<&Constraint> {
+        self.named.get(name)
+    }
+
+    /// Remove the constraint added under a name, returning it.
+    #[doc(alias = "remove_by_name")]
+    pub fn remove_named(&mut self, name: &str) -> Result<Constraint, RemoveConstraintError> {
+        let constraint = self
+            .named
+            .get(name)
+            .cloned()
+            .ok_or(RemoveConstraintError::UnknownConstraint)?;
+        self.remove_constraint(&constraint)?;
+        Ok(constraint)
+    }
+
+    /// The names and constraints of all named constraints in the solver, in no particular order.
+    pub fn named_constraints(&self) -> impl Iterator<Item = (&str, &Constraint)> {
+        self.named
+            .iter()
+            .map(|(name, constraint)| (name.as_str(), constraint))
+    }
+
+    /// Add an edit variable to the solver.
+    ///
+    /// This method should be called before the `suggest_value` method is
+    /// used to supply a suggested value for the given edit variable. Like
+    /// [`Solver::add_constraint`], fails with [`AddEditVariableError::VariableCollision`] for a
+    /// variable of the solver's namespace that its counter hasn't created yet.
+    pub fn add_edit_variable(
+        &mut self,
+        v: Variable,
+        strength: Strength,
+    ) -> Result<EditHandle, AddEditVariableError> {
+        if self.edits.contains_key(&v) {
+            return Err(AddEditVariableError::DuplicateEditVariable);
+        }
+        if strength == Strength::REQUIRED {
+            return Err(AddEditVariableError::BadRequiredStrength);
+        }
+        if self.variable_counter.collides(v) {
+            return Err(AddEditVariableError::VariableCollision(v));
+        }
+        let cn = Constraint::new(
+            Expression::from_term(Term::new(v, 1.0)),
+            RelationalOperator::Equal,
+            strength,
+        );
+        self.add_constraint_with(cn.clone(), false)
+            .map_err(|e| match e {
+                AddConstraintError::InternalSolverError(e) => AddEditVariableError::from(e),
+                // The edit constraint is new and not required.
+                AddConstraintError::DuplicateConstraint
+                | AddConstraintError::UnsatisfiableConstraint
+                | AddConstraintError::VariableCollision(_) => unreachable!(),
+            })?;
+        let info = EditInfo {
+            variable: v,
+            tag: self.constraints[&cn],
+            constraint: cn,
+            constant: 0.0,
+        };
+        let slot = match self.edit_infos.iter().position(Option::is_none) {
+            Some(slot) => {
+                self.edit_infos[slot] = Some(info);
+                slot
+            }
+            None => {
+                self.edit_infos.push(Some(info));
+                self.edit_infos.len() - 1
+            }
+        };
+        tracked!(self.alloc_stats, self.edits, self.edits.insert(v, slot));
+        self.record(|| SolverOp::AddEditVariable(v, strength));
+        if self.open_checkpoints > 0 {
+            self.journal.push(Added::EditVariable(v));
+        }
+        Ok(EditHandle { slot, variable: v })
+    }
+
+    /// Remove an edit variable from the solver.
+    pub fn remove_edit_variable(&mut self, v: Variable) -> Result<(), RemoveEditVariableError> {
+        let info = self
+            .edits
+            .remove(&v)
+            .and_then(|slot| self.edit_infos[slot].take());
+        if let Some(constraint) = info.map(|e| e.constraint) {
+            self.content_sizes.retain(|content| content.output != v);
+            self.remove_constraint_unrecorded(&constraint)
+                .map_err(|e| match e {
+                    RemoveConstraintError::UnknownConstraint => {
+                        RemoveEditVariableError::InternalSolverError(
+                            InternalSolverError::EditConstraintNotInSystem,
+                        )
+                    }
+                    RemoveConstraintError::InternalSolverError(s) => {
+                        RemoveEditVariableError::InternalSolverError(s)
+                    }
+                })?;
+            self.record(|| SolverOp::RemoveEditVariable(v));
+            Ok(())
+        } else {
+            Err(RemoveEditVariableError::UnknownEditVariable)
+        }
+    }
+
+    /// Remove every edit variable from the solver.
+    ///
+    /// This is cheaper than calling [`Solver::remove_edit_variable`] for each of them, as the
+    /// solver is only optimized once all of the edit constraints have been removed. Handles to the
+    /// removed edit variables become stale.
+    pub fn remove_all_edit_variables(&mut self) -> Result<(), InternalSolverError> {
+        self.end_edit()?;
+        self.edits.clear();
+        self.content_sizes.clear();
+        let edits: Vec<EditInfo> = self.edit_infos.drain(..).flatten().collect();
+        if edits.is_empty() {
+            return Ok(());
+        }
+        self.generation += 1;
+
+        // Edit constraints are never merged with other constraints, so only their rows need to be
+        // dropped before optimizing.
+        for edit in &edits {
+            let tag = self
+                .constraints
+                .remove(&edit.constraint)
+                .ok_or(InternalSolverError::EditConstraintNotInSystem)?;
+            #[cfg(feature = "profiling")]
+            self.profiler.remove([tag.marker, tag.other]);
+            self.remove_constraint_effects(&edit.constraint, &tag, 0.0);
+            if self.rows.remove(&tag.marker).is_none() {
+                let (leaving, mut row) = self
+                    .get_marker_leaving_row(tag.marker)
+                    .ok_or(InternalSolverError::FailedToFindLeavingRow)?;
+                row.solve_for_symbols(leaving, tag.marker);
+                self.substitute(tag.marker, &row);
+            }
+            record!(self.alloc_stats.rows_dropped += 1);
+        }
+        let objective = self.objective.clone();
+        self.optimize(&objective)?;
+        for edit in &edits {
+            self.release_variables(&edit.constraint);
+        }
+        if self.recording.is_some() {
+            let mut variables: Vec<Variable> = edits.iter().map(|edit| edit.variable).collect();
+            variables.sort();
+            for v in variables {
+                self.record(|| SolverOp::RemoveEditVariable(v));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove an edit variable from the solver, keeping its last suggested value as a weak stay.
+    ///
+    /// Removing an edit variable normally lets the variable snap back to whatever the remaining
+    /// constraints dictate. This instead adds a `WEAK` constraint holding the variable at the last
+    /// value suggested for it, which is useful when ending a drag. The stay constraint is returned
+    /// so that it can be removed later to release the variable.
+    pub fn remove_edit_variable_with_stay(
+        &mut self,
+        v: Variable,
+    ) -> Result<Constraint, RemoveEditVariableError> {
+        let value = self
+            .edit(v)
+            .ok_or(RemoveEditVariableError::UnknownEditVariable)?
+            .constant;
+        let stay = Constraint::new(
+            Expression::new(vec![Term::new(v, 1.0)], -value),
+            RelationalOperator::Equal,
+            Strength::WEAK,
+        );
+        self.add_constraint(stay.clone()).map_err(|e| match e {
+            AddConstraintError::InternalSolverError(s) => {
+                RemoveEditVariableError::InternalSolverError(s)
+            }
+            // A new constraint that is not required can always be added.
+            AddConstraintError::DuplicateConstraint
+            | AddConstraintError::UnsatisfiableConstraint
+            | AddConstraintError::VariableCollision(_) => unreachable!(),
+        })?;
+        self.remove_edit_variable(v)?;
+        Ok(stay)
+    }
+
+    /// Change the value of a parameter in every constraint that it appears in.
+    ///
+    /// This is much cheaper than removing the constraints and adding them again. Like
+    /// [`Solver::suggest_value`], the solution is only updated when an edit session is not open
+    /// (see [`Solver::begin_edit`]).
+    pub fn set_param(&mut self, param: Param, value: f64) -> Result<(), InternalSolverError> {
+        let v = param.variable();
+        let previous = self.params.insert(v, value).unwrap_or(0.0);
+        let delta = value - previous;
+        if delta == 0.0 {
+            self.record(|| SolverOp::SetParam(param, value));
+            return Ok(());
+        }
+        let shifts: Vec<(Tag, f64)> = self
+            .constraints
+            .iter()
+            .filter_map(|(constraint, tag)| {
+                let terms = constraint.terms().iter();
+                let coefficient: f64 = terms
+                    .filter(|term| term.variable == v)
+                    .map(|term| term.coefficient)
+                    .sum();
+                if coefficient == 0.0 {
+                    return None;
+                }
+                let marker = Solver::marker_coefficient(constraint);
+                Some((*tag, coefficient * delta / marker))
+            })
+            .collect();
+        self.generation += 1;
+        for (tag, shift) in shifts {
+            self.shift_constant(tag, shift);
+        }
+        if !self.editing {
+            self.dual_optimize()?;
+        }
+        self.record(|| SolverOp::SetParam(param, value));
+        Ok(())
+    }
+
+    /// The value of a parameter, which is zero until it is set with [`Solver::set_param`].
+    pub fn param(&self, param: Param) -> f64 {
+        self.params.get(&param.variable()).copied().unwrap_or(0.0)
+    }
+
+    /// Hold a variable at its current value with the given strength.
+    ///
+    /// This is the usual way of keeping things the user didn't touch from moving when other
+    /// constraints change. Pinning a variable that is already pinned moves the pin to the current
+    /// value and strength. The pin is released with [`Solver::unpin`].
+    pub fn pin(&mut self, v: Variable, strength: Strength) -> Result<(), AddConstraintError> {
+        let value = self.get_value(v);
+        if let Some(previous) = self.pins.get(&v).cloned() {
+            self.remove_constraint(&previous).map_err(|e| match e {
+                RemoveConstraintError::InternalSolverError(s) => {
+                    AddConstraintError::InternalSolverError(s)
+                }
+                RemoveConstraintError::UnknownConstraint => unreachable!(),
+            })?;
+        }
+        let pin = Constraint::new(
+            Expression::new(vec![Term::new(v, 1.0)], -value),
+            RelationalOperator::Equal,
+            strength,
+        );
+        self.add_constraint(pin.clone())?;
+        self.index_pin(v, pin);
+        Ok(())
+    }
+
+    /// Release a variable pinned with [`Solver::pin`].
+    ///
+    /// Fails with [`RemoveConstraintError::UnknownConstraint`] if the variable is not pinned.
+    pub fn unpin(&mut self, v: Variable) -> Result<(), RemoveConstraintError> {
+        let pin = self
+            .pins
+            .get(&v)
+            .cloned()
+            .ok_or(RemoveConstraintError::UnknownConstraint)?;
+        self.remove_constraint(&pin)
+    }
+
+    /// Test whether a variable is pinned with [`Solver::pin`].
+    pub fn is_pinned(&self, v: Variable) -> bool {
+        self.pins.contains_key(&v)
+    }
+
+    /// Keep a variable at or above a value with the given strength.
+    ///
+    /// The solver manages the bound constraint, so there is no handle to keep: setting the bound
+    /// again moves it, which for a bound that is not required and keeps its strength is as cheap
+    /// as [`Solver::update_constant`]. If the moved bound can't be satisfied, the previous one is
+    /// kept. The bound is removed with [`Solver::clear_lower_bound`].
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_constraint(width | EQ(Strength::WEAK) | 0.0).unwrap();
+    /// solver.set_lower_bound(width, 10.0, Strength::REQUIRED).unwrap();
+    /// solver.set_upper_bound(width, 100.0, Strength::REQUIRED).unwrap();
+    /// assert_eq!(solver.get_value(width), 10.0);
+    /// solver.set_lower_bound(width, 20.0, Strength::REQUIRED).unwrap();
+    /// assert_eq!(solver.get_value(width), 20.0);
+    /// assert_eq!(solver.lower_bound(width), Some(20.0));
+    /// ```
+    pub fn set_lower_bound(
+        &mut self,
+        v: Variable,
+        value: f64,
+        strength: Strength,
+    ) -> Result<(), AddConstraintError> {
+        self.set_bound(v, RelationalOperator::GreaterOrEqual, value, strength)
+    }
+
+    /// Keep a variable at or below a value with the given strength, see
+    /// [`Solver::set_lower_bound`].
+    pub fn set_upper_bound(
+        &mut self,
+        v: Variable,
+        value: f64,
+        strength: Strength,
+    ) -> Result<(), AddConstraintError> {
+        self.set_bound(v, RelationalOperator::LessOrEqual, value, strength)
+    }
+
+    /// Remove the lower bound of a variable set with [`Solver::set_lower_bound`].
+    ///
+    /// Fails with [`RemoveConstraintError::UnknownConstraint`] if the variable has no lower bound.
+    pub fn clear_lower_bound(&mut self, v: Variable) -> Result<(), RemoveConstraintError> {
+        self.clear_bound(v, RelationalOperator::GreaterOrEqual)
+    }
+
+    /// Remove the upper bound of a variable set with [`Solver::set_upper_bound`].
+    ///
+    /// Fails with [`RemoveConstraintError::UnknownConstraint`] if the variable has no upper bound.
+    pub fn clear_upper_bound(&mut self, v: Variable) -> Result<(), RemoveConstraintError> {
+        self.clear_bound(v, RelationalOperator::LessOrEqual)
+    }
+
+    /// The lower bound of a variable set with [`Solver::set_lower_bound`], if any.
+    pub fn lower_bound(&self, v: Variable) -> Option<f64> {
+        self.bounds
+            .get(&(v, RelationalOperator::GreaterOrEqual))
+            .map(|bound| -bound.constant())
+    }
+
+    /// The upper bound of a variable set with [`Solver::set_upper_bound`], if any.
+    pub fn upper_bound(&self, v: Variable) -> Option<f64> {
+        self.bounds
+            .get(&(v, RelationalOperator::LessOrEqual))
+            .map(|bound| -bound.constant())
+    }
+
+    fn set_bound(
+        &mut self,
+        v: Variable,
+        op: RelationalOperator,
+        value: f64,
+        strength: Strength,
+    ) -> Result<(), AddConstraintError> {
+        let previous = self.bounds.get(&(v, op)).cloned();
+        if let Some(previous) = &previous {
+            if previous.strength() == strength {
+                // The bound is re-keyed to the updated constraint when it is carried over.
+                return match self.update_constant(previous, -value) {
+                    Ok(_) => Ok(()),
+                    Err(UpdateConstantError::UnsatisfiableConstraint) => {
+                        Err(AddConstraintError::UnsatisfiableConstraint)
+                    }
+                    Err(UpdateConstantError::InternalSolverError(e)) => Err(e.into()),
+                    Err(UpdateConstantError::UnknownConstraint) => unreachable!(),
+                };
+            }
+            self.remove_constraint(previous).map_err(|e| match e {
+                RemoveConstraintError::InternalSolverError(s) => {
+                    AddConstraintError::InternalSolverError(s)
+                }
+                RemoveConstraintError::UnknownConstraint => unreachable!(),
+            })?;
+        }
+        let bound = Constraint::new(
+            Expression::new(vec![Term::new(v, 1.0)], -value),
+            op,
+            strength,
+        );
+        if let Err(error) = self.add_constraint(bound.clone()) {
+            if let Some(previous) = previous {
+                self.add_constraint(previous.clone())?;
+                self.index_bound((v, op), previous);
+            }
+            return Err(error);
+        }
+        self.index_bound((v, op), bound);
+        Ok(())
+    }
+
+    fn clear_bound(
+        &mut self,
+        v: Variable,
+        op: RelationalOperator,
+    ) -> Result<(), RemoveConstraintError> {
+        let bound = self
+            .bounds
+            .get(&(v, op))
+            .cloned()
+            .ok_or(RemoveConstraintError::UnknownConstraint)?;
+        self.remove_constraint(&bound)
+    }
+
+    /// Add a stay to a variable, which holds it at its current value with the given strength.
+    ///
+    /// Unlike [`Solver::pin`], the stay follows the variable: whenever the solver is solved, the
+    /// stay moves to the new value of the variable, so that stronger constraints can move the
+    /// variable, and the stay then keeps it where it ended up. This is what keeps the parts of a
+    /// layout that nothing is pushing on from moving. Adding a stay to a variable that already
+    /// has one replaces it. The stay is removed with [`Solver::remove_stay`].
+    pub fn add_stay(&mut self, v: Variable, strength: Strength) -> Result<(), AddConstraintError> {
+        if let Some((previous, _)) = self.stays.get(&v).cloned() {
+            self.remove_constraint_unrecorded(&previous)
+                .map_err(|e| match e {
+                    RemoveConstraintError::InternalSolverError(s) => {
+                        AddConstraintError::InternalSolverError(s)
+                    }
+                    RemoveConstraintError::UnknownConstraint => unreachable!(),
+                })?;
+        }
+        let value = self.get_value(v);
+        self.insert_stay(v, strength, value)?;
+        self.record(|| SolverOp::AddStay(v, strength));
+        Ok(())
+    }
+
+    /// Add the constraint of a stay without recording it.
+    fn insert_stay(
+        &mut self,
+        v: Variable,
+        strength: Strength,
+        value: f64,
+    ) -> Result<(), AddConstraintError> {
+        let stay = Constraint::new(
+            Expression::new(vec![Term::new(v, 1.0)], -value),
+            RelationalOperator::Equal,
+            strength,
+        );
+        // The row of a stay is adjusted when it moves, so it must not be merged with others.
+        self.add_constraint_with(stay.clone(), false)?;
+        self.journal_constraint(stay.clone());
+        self.index_stay(v, stay, value);
+        Ok(())
+    }
+
+    /// Remove the stay added to a variable with [`Solver::add_stay`].
+    ///
+    /// Fails with [`RemoveConstraintError::UnknownConstraint`] if the variable has no stay.
+    pub fn remove_stay(&mut self, v: Variable) -> Result<(), RemoveConstraintError> {
+        let (stay, _) = self
+            .stays
+            .get(&v)
+            .cloned()
+            .ok_or(RemoveConstraintError::UnknownConstraint)?;
+        self.remove_constraint_unrecorded(&stay)?;
+        self.record(|| SolverOp::RemoveStay(v));
+        Ok(())
+    }
+
+    /// Test whether a variable has a stay added with [`Solver::add_stay`].
+    pub fn has_stay(&self, v: Variable) -> bool {
+        self.stays.contains_key(&v)
+    }
+
+    /// Test whether an edit variable has been added to the solver.
+    ///
+    /// This can be used to check whether a variable is already editable before calling
+    /// [`Solver::add_edit_variable`], rather than relying on
+    /// [`AddEditVariableError::DuplicateEditVariable`].
+    pub fn has_edit_variable(&self, v: &Variable) -> bool {
+        self.edits.contains_key(v)
+    }
+
+    fn edit(&self, v: Variable) -> Option<&EditInfo> {
+        self.edits
+            .get(&v)
+            .and_then(|&slot| self.edit_infos[slot].as_ref())
+    }
+
+    fn edit_infos(&self) -> impl Iterator<Item = &EditInfo> {
+        self.edit_infos.iter().flatten()
+    }
+
+    /// Add an edit variable whose value is measured from the values of other variables.
+    ///
+    /// This is intended for intrinsic sizes that depend on the layout, such as the height of
+    /// wrapped text given the width it is laid out in. The `measure` callback is passed the values
+    /// of `inputs` and returns the size, which is suggested as the value of `output`. It is called
+    /// once when the content size is added, and again by [`Solver::fetch_changes`] (or
+    /// [`Solver::measure_content_sizes`]) whenever the values of the inputs have changed since the
+    /// last measurement.
+    ///
+    /// `output` is an ordinary edit variable, so it can be removed with
+    /// [`Solver::remove_edit_variable`]. Each call to `fetch_changes` measures once, so if a
+    /// measurement changes the inputs of another content size, that content size is measured by
+    /// the next call.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let height = Variable::new();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    ///
+    /// // 1000 square pixels of text
+    /// solver
+    ///     .add_content_size(height, [width], Strength::STRONG, |inputs| {
+    ///         (1000.0 / inputs[0].max(1.0)).ceil()
+    ///     })
+    ///     .unwrap();
+    ///
+    /// solver.suggest_value(width, 100.0).unwrap();
+    /// solver.fetch_changes();
+    /// assert_eq!(solver.get_value(height), 10.0);
+    /// ```
+    pub fn add_content_size(
+        &mut self,
+        output: Variable,
+        inputs: impl IntoIterator<Item = Variable>,
+        strength: Strength,
+        measure: impl FnMut(&[f64]) -> f64 + 'static,
+    ) -> Result<(), AddEditVariableError> {
+        self.add_edit_variable(output, strength)?;
+        let inputs: Vec<Variable> = inputs.into_iter().collect();
+        let mut content = ContentSize {
+            measured: inputs.iter().map(|&v| self.get_value(v)).collect(),
+            output,
+            inputs,
+            measure: Box::new(measure),
+        };
+        let value = (content.measure)(&content.measured);
+        self.content_sizes.push(content);
+        self.suggest_value(output, value)
+            .map_err(|error| match error {
+                SuggestValueError::InternalSolverError(error) => AddEditVariableError::from(error),
+                SuggestValueError::UnknownEditVariable => {
+                    unreachable!("content sizes are edit variables")
+                }
+            })
+    }
+
+    /// Repeatedly measure content sizes and call `between_passes` until the values of the
+    /// variables stop changing, for at most `max_passes` passes.
+    ///
+    /// Layouts with circular measurement dependencies, such as a width that determines the height
+    /// of wrapped text which in turn determines the width of a scroll bar, need several rounds of
+    /// measuring and solving to settle. Each pass measures the content sizes whose inputs changed
+    /// (see [`Solver::add_content_size`]), then calls `between_passes` with the solver and the
+    /// number of the pass, which can suggest new values for edit variables. A pass that changes no
+    /// value by more than a small tolerance ends the loop.
+    ///
+    /// If the values return to where they were two passes earlier, the layout is oscillating and
+    /// [`Convergence::Oscillating`] is returned without using the remaining passes. Changes are
+    /// still reported by the next call to [`Solver::fetch_changes`] as usual, and errors while
+    /// measuring are ignored like there.
+    pub fn solve_iteratively(
+        &mut self,
+        max_passes: usize,
+        mut between_passes: impl FnMut(&mut Solver, usize),
+    ) -> Convergence {
+        let mut previous = self.values_snapshot();
+        let mut before_previous = None;
+        let mut changed = Vec::new();
+        for pass in 1..=max_passes {
+            let _ = self.measure_content_sizes();
+            between_passes(self, pass);
+            let current = self.values_snapshot();
+            changed = Solver::changed_values(&previous, &current);
+            if changed.is_empty() {
+                return Convergence::Converged { passes: pass };
+            }
+            if let Some(before_previous) = &before_previous {
+                if Solver::changed_values(before_previous, &current).is_empty() {
+                    return Convergence::Oscillating { changed };
+                }
+            }
+            before_previous = Some(core::mem::replace(&mut previous, current));
+        }
+        Convergence::Diverged { changed }
+    }
+
+    fn values_snapshot(&self) -> HashMap<Variable, f64> {
+        self.iter_values().collect()
+    }
+
+    /// The variables whose values differ between two snapshots, ordered by variable.
+    fn changed_values(
+        before: &HashMap<Variable, f64>,
+        after: &HashMap<Variable, f64>,
+    ) -> Vec<Variable> {
+        let value =
+            |values: &HashMap<Variable, f64>, v: &Variable| values.get(v).copied().unwrap_or(0.0);
+        let mut changed: Vec<Variable> = before
+            .keys()
+            .chain(after.keys().filter(|v| !before.contains_key(*v)))
+            .filter(|v| !near_zero(value(before, v) - value(after, v)))
+            .copied()
+            .collect();
+        changed.sort_unstable();
+        changed
+    }
+
+    /// Measure the content sizes whose inputs have changed since they were last measured, and
+    /// suggest the results. Returns whether any content size was measured.
+    ///
+    /// This is called by [`Solver::fetch_changes`], so it rarely needs to be called directly. If
+    /// suggesting a measured size fails, the content sizes that were not suggested yet are left to
+    /// be measured by the next call, and the error is returned.
+    pub fn measure_content_sizes(&mut self) -> Result<bool, InternalSolverError> {
+        let mut content_sizes = core::mem::take(&mut self.content_sizes);
+        let result = self.measure(&mut content_sizes);
+        self.content_sizes = content_sizes;
+        result
+    }
+
+    fn measure(&mut self, content_sizes: &mut [ContentSize]) -> Result<bool, InternalSolverError> {
+        let mut measured = false;
+        for content in content_sizes {
+            let changed = content
+                .inputs
+                .iter()
+                .zip(&content.measured)
+                .any(|(&v, &value)| self.get_value(v) != value);
+            if !changed {
+                continue;
+            }
+            let inputs: Vec<f64> = content.inputs.iter().map(|&v| self.get_value(v)).collect();
+            let value = (content.measure)(&inputs);
+            self.suggest_value(content.output, value)
+                .map_err(|error| match error {
+                    SuggestValueError::InternalSolverError(error) => error,
+                    SuggestValueError::UnknownEditVariable => {
+                        unreachable!("content sizes are edit variables")
+                    }
+                })?;
+            content.measured = inputs;
+            measured = true;
+        }
+        Ok(measured)
+    }
+
+    /// Suggest a value for the given edit variable.
+    ///
+    /// This method should be used after an edit variable has been added to
+    /// the solver in order to suggest the value for that variable.
+    pub fn suggest_value(
+        &mut self,
+        variable: Variable,
+        value: f64,
+    ) -> Result<(), SuggestValueError> {
+        let slot = *self
+            .edits
+            .get(&variable)
+            .ok_or(SuggestValueError::UnknownEditVariable)?;
+        self.suggest_slot(slot, value)?;
+        self.record(|| SolverOp::SuggestValue(variable, value));
+        Ok(())
+    }
+
+    /// Suggest a value for a variable, making it an edit variable with the given strength first if
+    /// it isn't one already.
+    ///
+    /// This saves special casing the first suggestion for a variable. The strength of an existing
+    /// edit variable is left as it is. The returned handle can be used with [`Solver::suggest`].
+    pub fn suggest_or_add(
+        &mut self,
+        variable: Variable,
+        strength: Strength,
+        value: f64,
+    ) -> Result<EditHandle, SuggestOrAddError> {
+        let handle = match self.edits.get(&variable) {
+            Some(&slot) => EditHandle { slot, variable },
+            None => self
+                .add_edit_variable(variable, strength)
+                .map_err(|e| match e {
+                    AddEditVariableError::BadRequiredStrength => {
+                        SuggestOrAddError::BadRequiredStrength
+                    }
+                    AddEditVariableError::VariableCollision(v) => {
+                        SuggestOrAddError::VariableCollision(v)
+                    }
+                    AddEditVariableError::InternalSolverError(e) => e.into(),
+                    AddEditVariableError::DuplicateEditVariable => unreachable!(),
+                })?,
+        };
+        self.suggest_value(variable, value).map_err(|e| match e {
+            SuggestValueError::InternalSolverError(e) => SuggestOrAddError::InternalSolverError(e),
+            SuggestValueError::UnknownEditVariable => unreachable!(),
+        })?;
+        Ok(handle)
+    }
+
+    /// Suggest a value for the edit variable of a handle returned by
+    /// [`Solver::add_edit_variable`].
+    ///
+    /// This is the same as [`Solver::suggest_value`], but skips looking up the edit variable.
+    /// Fails with [`SuggestValueError::UnknownEditVariable`] if the edit variable has been removed.
+    pub fn suggest(&mut self, handle: EditHandle, value: f64) -> Result<(), SuggestValueError> {
+        match self.edit_infos.get(handle.slot) {
+            Some(Some(info)) if info.variable == handle.variable => {
+                self.suggest_slot(handle.slot, value)?;
+                self.record(|| SolverOp::SuggestValue(handle.variable, value));
+                Ok(())
+            }
+            _ => Err(SuggestValueError::UnknownEditVariable),
+        }
+    }
+
+    fn suggest_slot(&mut self, slot: usize, value: f64) -> Result<(), SuggestValueError> {
+        let (tag, delta) = {
+            let info = self.edit_infos[slot]
+                .as_mut()
+                .ok_or(SuggestValueError::UnknownEditVariable)?;
+            let delta = value - info.constant;
+            info.constant = value;
+            (info.tag, delta)
+        };
+        self.generation += 1;
+        self.shift_constant(tag, delta);
+        if !self.editing {
+            self.dual_optimize()?;
+        }
+        Ok(())
+    }
+
+    /// The coefficient of the marker in the row created for a constraint.
+    fn marker_coefficient(constraint: &Constraint) -> f64 {
+        match constraint.op() {
+            RelationalOperator::LessOrEqual => 1.0,
+            RelationalOperator::GreaterOrEqual => -1.0,
+            RelationalOperator::Equal if constraint.strength() < Strength::REQUIRED => -1.0,
+            RelationalOperator::Equal => 1.0,
+        }
+    }
+
+    /// Move the solution to account for a change in the constant of a constraint, without
+    /// optimizing.
+    ///
+    /// `delta` is the change of the constant divided by the coefficient of the marker in the row of
+    /// the constraint, so it is the change of the suggested value for an edit constraint.
+    fn shift_constant(&mut self, tag: Tag, delta: f64) {
+        let (info_tag_marker, info_tag_other) = (tag.marker, tag.other);
+        // tag.marker and tag.other are never external symbols
+
+        // The nice version of the following code runs into non-lexical borrow issues.
+        // Ideally the `if row...` code would be in the body of the if. Pretend that it is.
+        {
+            let infeasible_rows = &mut self.infeasible_rows;
+            if self
                 .rows
                 .get_mut(&info_tag_marker)
                 .map(|row| {
@@ -290,76 +2760,1323 @@ impl Solver {
                         infeasible_rows.push(info_tag_marker);
                     }
                 })
-                .is_some()
-                || self
-                    .rows
-                    .get_mut(&info_tag_other)
-                    .map(|row| {
-                        if row.add(delta) < 0.0 {
-                            infeasible_rows.push(info_tag_other);
-                        }
-                    })
-                    .is_some()
-            {
-            } else {
-                for (symbol, row) in &mut self.rows {
-                    let coeff = row.coefficient_for(info_tag_marker);
-                    let diff = delta * coeff;
-                    if diff != 0.0 && symbol.kind() == SymbolKind::External {
-                        let v = self.var_for_symbol[symbol];
-                        // inline var_changed - borrow checker workaround
-                        if self.should_clear_changes {
-                            self.changed.clear();
-                            self.should_clear_changes = false;
-                        }
-                        self.changed.insert(v);
+                .is_some()
+                || self
+                    .rows
+                    .get_mut(&info_tag_other)
+                    .map(|row| {
+                        if row.add(delta) < 0.0 {
+                            infeasible_rows.push(info_tag_other);
+                        }
+                    })
+                    .is_some()
+            {
+            } else {
+                for (symbol, row) in &mut self.rows {
+                    let coeff = row.coefficient_for(info_tag_marker);
+                    let diff = delta * coeff;
+                    if diff != 0.0 && symbol.kind() == SymbolKind::External {
+                        let v = self.var_for_symbol[symbol];
+                        // inline var_changed - borrow checker workaround
+                        if self.should_clear_changes {
+                            self.changed.clear();
+                            self.should_clear_changes = false;
+                        }
+                        tracked!(self.alloc_stats, self.changed, self.changed.insert(v));
+                        self.touched.insert(v, self.generation);
+                    }
+                    if coeff != 0.0 && row.add(diff) < 0.0 && symbol.kind() != SymbolKind::External
+                    {
+                        infeasible_rows.push(*symbol);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Suggest values for several edit variables at once.
+    ///
+    /// All the edit constants are updated first, and the solution is then updated once, as in an
+    /// edit session (see [`Solver::begin_edit`]). This is cheaper than suggesting the values one
+    /// by one, and there are no intermediate layouts with only some of the values applied. If a
+    /// variable is not an edit variable, no value is suggested.
+    ///
+    /// ```
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let height = Variable::new();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// solver.add_edit_variable(height, Strength::STRONG).unwrap();
+    ///
+    /// solver.suggest_values(&[(width, 640.0), (height, 480.0)]).unwrap();
+    /// assert_eq!(solver.get_value(width), 640.0);
+    /// assert_eq!(solver.get_value(height), 480.0);
+    /// ```
+    pub fn suggest_values(&mut self, values: &[(Variable, f64)]) -> Result<(), SuggestValueError> {
+        if values.iter().any(|(v, _)| !self.edits.contains_key(v)) {
+            return Err(SuggestValueError::UnknownEditVariable);
+        }
+        let session = !self.editing;
+        if session {
+            self.begin_edit();
+        }
+        for &(v, value) in values {
+            self.suggest_value(v, value)?;
+        }
+        if session {
+            self.end_edit()?;
+        }
+        Ok(())
+    }
+
+    /// Animate an edit variable from its current value to `target` over `duration`.
+    ///
+    /// The animation is driven by [`Solver::tick`], which suggests the intermediate values. An
+    /// animation of a variable that is already being animated replaces it, starting from where
+    /// the variable is. The animation is dropped if the edit variable is removed.
+    ///
+    /// ```
+    /// use core::time::Duration;
+    ///
+    /// use kasuari::{Easing, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// solver
+    ///     .animate(width, 100.0, Duration::from_secs(1), Easing::Linear)
+    ///     .unwrap();
+    /// solver.tick(Duration::from_millis(500)).unwrap();
+    /// assert_eq!(solver.get_value(width), 50.0);
+    /// solver.tick(Duration::from_millis(500)).unwrap();
+    /// assert_eq!(solver.get_value(width), 100.0);
+    /// assert!(!solver.is_animating());
+    /// ```
+    #[cfg(feature = "animation")]
+    pub fn animate(
+        &mut self,
+        v: Variable,
+        target: f64,
+        duration: core::time::Duration,
+        easing: Easing,
+    ) -> Result<(), SuggestValueError> {
+        if !self.edits.contains_key(&v) {
+            return Err(SuggestValueError::UnknownEditVariable);
+        }
+        let animation = Animation::tween(self.get_value(v), target, duration, easing);
+        self.animations.insert(v, animation);
+        Ok(())
+    }
+
+    /// Pull an edit variable from its current value towards `target` with a spring.
+    ///
+    /// Like [`Solver::animate`], the spring is driven by [`Solver::tick`], and it finishes when the
+    /// variable comes to rest at the target. Changing the target of a variable that is already
+    /// pulled by a spring keeps its velocity, so that interrupted motion stays smooth.
+    ///
+    /// ```
+    /// use core::time::Duration;
+    ///
+    /// use kasuari::{Solver, Spring, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// solver.spring_to(width, 100.0, Spring::default()).unwrap();
+    /// while solver.is_animating() {
+    ///     solver.tick(Duration::from_millis(16)).unwrap();
+    /// }
+    /// assert_eq!(solver.get_value(width), 100.0);
+    /// ```
+    #[cfg(feature = "animation")]
+    pub fn spring_to(
+        &mut self,
+        v: Variable,
+        target: f64,
+        spring: Spring,
+    ) -> Result<(), SuggestValueError> {
+        if !self.edits.contains_key(&v) {
+            return Err(SuggestValueError::UnknownEditVariable);
+        }
+        let velocity = match self.animations.get(&v) {
+            Some(&Animation::Spring { velocity, .. }) => velocity,
+            _ => 0.0,
+        };
+        let animation = Animation::Spring {
+            value: self.get_value(v),
+            velocity,
+            target,
+            spring,
+        };
+        self.animations.insert(v, animation);
+        Ok(())
+    }
+
+    /// Advance all animations by `dt`, suggesting their new values together, and fetch the
+    /// resulting changes like [`Solver::fetch_changes`].
+    ///
+    /// Animations that reach their target are finished.
+    #[cfg(feature = "animation")]
+    pub fn tick(
+        &mut self,
+        dt: core::time::Duration,
+    ) -> Result<&[(Variable, f64)], InternalSolverError> {
+        let edits = &self.edits;
+        self.animations.retain(|v, _| edits.contains_key(v));
+        let mut values = Vec::with_capacity(self.animations.len());
+        self.animations.retain(|&v, animation| {
+            let (value, finished) = animation.advance(dt);
+            values.push((v, value));
+            !finished
+        });
+        values.sort_by_key(|&(v, _)| v);
+        self.suggest_values(&values).map_err(|e| match e {
+            SuggestValueError::InternalSolverError(e) => e,
+            SuggestValueError::UnknownEditVariable => unreachable!(),
+        })?;
+        Ok(self.fetch_changes())
+    }
+
+    /// Whether any animation started with [`Solver::animate`] or [`Solver::spring_to`] is still
+    /// running.
+    #[cfg(feature = "animation")]
+    pub fn is_animating(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    /// Stop animating an edit variable, leaving it at its current suggested value. Returns whether
+    /// the variable was being animated.
+    #[cfg(feature = "animation")]
+    pub fn stop_animation(&mut self, v: Variable) -> bool {
+        self.animations.remove(&v).is_some()
+    }
+
+    /// Start a session in which suggested values are applied together.
+    ///
+    /// Inside the session, [`Solver::suggest_value`] only records the suggestion, and the solution
+    /// is updated once for all of them by [`Solver::end_edit`]. When e.g. dragging the corner of a
+    /// window suggests a new width and height every frame, this optimizes once per frame instead
+    /// of twice. The values of variables are not meaningful until the session ends. Adding or
+    /// removing a constraint ends the session, unless adding it fails.
+    ///
+    /// ```
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// let height = Variable::new();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// solver.add_edit_variable(height, Strength::STRONG).unwrap();
+    ///
+    /// solver.begin_edit();
+    /// solver.suggest_value(width, 640.0).unwrap();
+    /// solver.suggest_value(height, 480.0).unwrap();
+    /// solver.end_edit().unwrap();
+    /// assert_eq!(solver.get_value(width), 640.0);
+    /// assert_eq!(solver.get_value(height), 480.0);
+    /// ```
+    pub fn begin_edit(&mut self) {
+        self.record(|| SolverOp::BeginEdit);
+        self.editing = true;
+    }
+
+    /// Whether an edit session started by [`Solver::begin_edit`] is open.
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// End the session started by [`Solver::begin_edit`] and update the solution for the
+    /// suggested values.
+    ///
+    /// Does nothing if no session is open.
+    pub fn end_edit(&mut self) -> Result<(), InternalSolverError> {
+        if !self.editing {
+            return Ok(());
+        }
+        self.editing = false;
+        self.dual_optimize()?;
+        self.record(|| SolverOp::EndEdit);
+        Ok(())
+    }
+
+    /// How fast a variable moves when the suggested value of an edit variable changes.
+    ///
+    /// This is the derivative of `output` with respect to the suggested value of `edit`, read off
+    /// the current tableau without solving. It predicts the effect of a suggestion exactly as long
+    /// as the set of active constraints doesn't change, i.e. for small enough changes. Returns
+    /// `None` if `edit` is not an edit variable.
+    pub fn sensitivity(&self, output: Variable, edit: Variable) -> Option<f64> {
+        let tag = &self.edit(edit)?.tag;
+        // If an error symbol of the edit constraint is basic, it absorbs the change of the
+        // suggested value, just like in `suggest_value`.
+        if self.rows.contains_key(&tag.marker) || self.rows.contains_key(&tag.other) {
+            return Some(0.0);
+        }
+        let sensitivity = self
+            .var_data
+            .get(&output)
+            .and_then(|(_, symbol, _)| self.rows.get(symbol))
+            .map_or(0.0, |row| row.coefficient_for(tag.marker));
+        Some(sensitivity)
+    }
+
+    fn var_changed(&mut self, v: Variable) {
+        if self.should_clear_changes {
+            self.changed.clear();
+            self.should_clear_changes = false;
+        }
+        tracked!(self.alloc_stats, self.changed, self.changed.insert(v));
+        self.touched.insert(v, self.generation);
+    }
+
+    /// Fetches all changes to the values of variables since the last call to this function.
+    ///
+    /// The list of changes returned is not in a specific order. Each change comprises the variable
+    /// changed and the new value of that variable.
+    ///
+    /// Content sizes are measured first. An [`InternalSolverError`] while suggesting a measured
+    /// size is not returned, and the content sizes that were not suggested are measured again by
+    /// the next call; [`Solver::measure_content_sizes`] returns the error.
+    pub fn fetch_changes(&mut self) -> &[(Variable, f64)] {
+        if !self.content_sizes.is_empty() {
+            let _ = self.measure_content_sizes();
+        }
+        if self.should_clear_changes {
+            self.changed.clear();
+            self.should_clear_changes = false;
+        } else {
+            self.should_clear_changes = true;
+        }
+        self.public_changes.clear();
+        for &v in &self.changed {
+            if let Some(var_data) = self.var_data.get_mut(&v) {
+                let new_value = self
+                    .rows
+                    .get(&var_data.1)
+                    .map(|r| r.constant)
+                    .unwrap_or(0.0);
+                let old_value = var_data.0;
+                if old_value != new_value {
+                    self.public_changes.push((v, new_value));
+                    var_data.0 = new_value;
+                }
+            } else if let Some(&value) = self.defaults.get(&v) {
+                self.public_changes.push((v, value));
+            }
+        }
+        if DETERMINISTIC {
+            self.public_changes.sort_unstable_by_key(|&(v, _)| v);
+        }
+        &self.public_changes
+    }
+
+    /// Whether the value of a variable changed since the last call to [`Solver::fetch_changes`].
+    ///
+    /// This is true exactly when the next call to `fetch_changes` would report the variable, except
+    /// that content sizes are only measured by `fetch_changes`. Checking a few variables this way
+    /// is cheaper than scanning the whole list of changes. Variables the solver doesn't know are
+    /// only changed when they report their [default](Solver::set_default_value).
+    pub fn has_changed(&self, v: Variable) -> bool {
+        match self.var_data.get(&v) {
+            Some((fetched, symbol, _)) => {
+                self.rows.get(symbol).map_or(0.0, |row| row.constant) != *fetched
+            }
+            None => {
+                self.defaults.contains_key(&v)
+                    && !self.should_clear_changes
+                    && self.changed.contains(&v)
+            }
+        }
+    }
+
+    /// The changes to the values of variables since a generation of the solver, ordered by
+    /// variable.
+    ///
+    /// Unlike [`Solver::fetch_changes`], this doesn't consume the changes, so any number of
+    /// independent consumers can each keep track of the [`Solver::generation`] they last looked at
+    /// and ask for what changed since then. Each change comprises the variable changed and its
+    /// current value. Pass zero to get the value of every variable that has been given a value.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// let seen = solver.generation();
+    /// solver.suggest_value(width, 100.0).unwrap();
+    /// assert_eq!(solver.changes_since(seen), [(width, 100.0)]);
+    /// assert_eq!(solver.changes_since(solver.generation()), []);
+    /// ```
+    pub fn changes_since(&mut self, generation: u64) -> Vec<(Variable, f64)> {
+        self.stamp_values();
+        let mut changes: Vec<(Variable, f64)> = self
+            .stamped_values
+            .iter()
+            .filter(|(_, &(_, stamp))| stamp > generation)
+            .map(|(&v, &(value, _))| (v, value))
+            .collect();
+        changes.sort_unstable_by_key(|&(v, _)| v);
+        changes
+    }
+
+    /// Record the generation in which each variable touched since the last call changed.
+    fn stamp_values(&mut self) {
+        for (v, generation) in self.touched.drain() {
+            let Some((_, symbol, _)) = self.var_data.get(&v) else {
+                continue;
+            };
+            let value = self.rows.get(symbol).map_or(0.0, |row| row.constant);
+            match self.stamped_values.entry(v) {
+                Entry::Occupied(mut entry) => {
+                    if entry.get().0 != value {
+                        entry.insert((value, generation));
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((value, generation));
+                }
+            }
+        }
+    }
+
+    /// Capture the current state of the solver: its constraints, edit variables and solution.
+    ///
+    /// Restoring the snapshot later with [`Solver::restore`] undoes everything that happened in
+    /// between, which makes speculative changes such as "what if these constraints were added"
+    /// cheap to try out.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_constraint(width | EQ(Strength::WEAK) | 100.0).unwrap();
+    /// let snapshot = solver.snapshot();
+    ///
+    /// solver.add_constraint(width | LE(Strength::REQUIRED) | 80.0).unwrap();
+    /// assert_eq!(solver.get_value(width), 80.0);
+    ///
+    /// solver.restore(snapshot);
+    /// assert_eq!(solver.get_value(width), 100.0);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            constraints: self.constraints.clone(),
+            named: self.named.clone(),
+            pins: self.pins.clone(),
+            bounds: self.bounds.clone(),
+            stays: self.stays.clone(),
+            disabled: self.disabled.clone(),
+            conditions: self.conditions.clone(),
+            params: self.params.clone(),
+            parallel: self.parallel.clone(),
+            groups: self.groups.clone(),
+            merged: self.merged.clone(),
+            var_data: self.var_data.clone(),
+            var_for_symbol: self.var_for_symbol.clone(),
+            rows: self.rows.clone(),
+            edits: self.edits.clone(),
+            edit_infos: self.edit_infos.clone(),
+            infeasible_rows: self.infeasible_rows.clone(),
+            objective: self.objective.borrow().clone(),
+            id_tick: self.id_tick,
+        }
+    }
+
+    /// A copy of the solver in which the variables are replaced by the variables they map to.
+    ///
+    /// The copy's constraints are new constraints that refer to the remapped variables, so they
+    /// can be removed from the copy without affecting this solver. Because the tableau is copied
+    /// rather than rebuilt, the copy starts out solved, which makes e.g. instantiating the nth
+    /// identical row of a list much cheaper than adding its constraints to a new solver.
+    ///
+    /// Variables that are not in the map are kept. The map must not send two variables to the same
+    /// variable, and must not send a variable to one that is kept. The copy reports the value of
+    /// every variable on its first call to [`Solver::fetch_changes`]. Content sizes, the pivot hook
+    /// and recordings are not copied. Use [`Solver::constraints_for`] or [`Solver::named`] on the
+    /// copy to find the remapped constraints.
+    ///
+    /// ```
+    /// use hashbrown::HashMap;
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut prototype = Solver::new();
+    /// let width = Variable::new();
+    /// prototype.add_constraint(width | EQ(Strength::REQUIRED) | 100.0).unwrap();
+    ///
+    /// let row_width = Variable::new();
+    /// let map = HashMap::from([(width, row_width)]);
+    /// let row = prototype.clone_remapped(&map);
+    /// assert_eq!(row.get_value(row_width), 100.0);
+    /// assert_eq!(row.try_value_of(width), None);
+    /// ```
+    pub fn clone_remapped(&self, map: &HashMap<Variable, Variable>) -> Solver {
+        let remap_variable = |v: &Variable| map.get(v).copied().unwrap_or(*v);
+        let remapped: HashMap<Constraint, Constraint> = self
+            .constraints
+            .keys()
+            .chain(self.merged.keys())
+            .chain(self.disabled.keys())
+            .map(|constraint| (constraint.clone(), constraint.remap(map)))
+            .collect();
+        let remap = |constraint: &Constraint| remapped[constraint].clone();
+
+        let mut solver = Solver::with_variable_counter(self.variable_counter.clone());
+        solver.merge_parallel = self.merge_parallel;
+        solver.strict_epsilon = self.strict_epsilon;
+        solver.constraints = self
+            .constraints
+            .iter()
+            .map(|(constraint, &tag)| (remap(constraint), tag))
+            .collect();
+        solver.named = self
+            .named
+            .iter()
+            .map(|(name, constraint)| (name.clone(), remap(constraint)))
+            .collect();
+        solver.params = self.params.clone();
+        solver.defaults = self
+            .defaults
+            .iter()
+            .map(|(v, &value)| (remap_variable(v), value))
+            .collect();
+        solver.conditions = self.conditions.clone();
+        solver.disabled = self
+            .disabled
+            .iter()
+            .map(|(constraint, &weight)| (remap(constraint), weight))
+            .collect();
+        solver.stays = self
+            .stays
+            .iter()
+            .map(|(v, (stay, value))| (remap_variable(v), (remap(stay), *value)))
+            .collect();
+        solver.pins = self
+            .pins
+            .iter()
+            .map(|(v, constraint)| (remap_variable(v), remap(constraint)))
+            .collect();
+        solver.bounds = self
+            .bounds
+            .iter()
+            .map(|(&(v, op), constraint)| ((remap_variable(&v), op), remap(constraint)))
+            .collect();
+        solver.index_handles();
+        solver.parallel = self
+            .parallel
+            .iter()
+            .filter_map(|(key, (primary, scale))| {
+                let primary = remap(primary);
+                let (remapped, _) = Solver::parallel_key(&primary)?;
+                let remapped = match key.constant {
+                    Some(_) => remapped,
+                    None => remapped.any_constant()?,
+                };
+                Some((remapped, (primary, *scale)))
+            })
+            .collect();
+        solver.groups = self
+            .groups
+            .iter()
+            .map(|(primary, group)| {
+                let group = ParallelGroup {
+                    members: group.members.iter().map(remap).collect(),
+                    weight: group.weight,
+                };
+                (remap(primary), group)
+            })
+            .collect();
+        solver.merged = self
+            .merged
+            .iter()
+            .map(|(member, (primary, weight))| (remap(member), (remap(primary), *weight)))
+            .collect();
+        for (v, &(_, symbol, count)) in self.var_data.iter() {
+            solver
+                .var_data
+                .insert(remap_variable(&v), (f64::NAN, symbol, count));
+        }
+        solver.var_for_symbol = self
+            .var_for_symbol
+            .iter()
+            .map(|(&symbol, v)| (symbol, remap_variable(v)))
+            .collect();
+        solver.rows = self.rows.clone();
+        solver.edits = self
+            .edits
+            .iter()
+            .map(|(v, &slot)| (remap_variable(v), slot))
+            .collect();
+        solver.edit_infos = self
+            .edit_infos
+            .iter()
+            .map(|edit| {
+                edit.as_ref().map(|edit| EditInfo {
+                    variable: remap_variable(&edit.variable),
+                    tag: edit.tag,
+                    constraint: remap(&edit.constraint),
+                    constant: edit.constant,
+                })
+            })
+            .collect();
+        solver.infeasible_rows = self.infeasible_rows.clone();
+        *solver.objective.borrow_mut() = self.objective.borrow().clone();
+        solver.id_tick = self.id_tick;
+        solver.editing = self.editing;
+        #[cfg(feature = "profiling")]
+        for (constraint, tag) in &solver.constraints {
+            solver.profiler.add(constraint, [tag.marker, tag.other]);
+        }
+        let variables: Vec<Variable> = solver.var_data.keys().collect();
+        for v in variables {
+            solver.var_changed(v);
+        }
+        solver
+    }
+
+    /// Roll the solver back to the state captured by [`Solver::snapshot`].
+    ///
+    /// Variables whose values differ from the ones last fetched are reported by the next call to
+    /// [`Solver::fetch_changes`], as if the changes had been made one by one. The generation still
+    /// increases, and the pivot hook and profiling data are kept. Content sizes whose output is not
+    /// an edit variable in the snapshot are dropped.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        #[cfg(feature = "profiling")]
+        {
+            for tag in self.constraints.values() {
+                self.profiler.remove([tag.marker, tag.other]);
+            }
+            for (constraint, tag) in &snapshot.constraints {
+                self.profiler.add(constraint, [tag.marker, tag.other]);
+            }
+        }
+        let mut var_data = snapshot.var_data;
+        for (v, (fetched, _, _)) in var_data.iter_mut() {
+            // Keep the values last fetched, so that fetch_changes reports the difference.
+            if let Some(data) = self.var_data.get(&v) {
+                *fetched = data.0;
+            }
+        }
+        self.constraints = snapshot.constraints;
+        self.named = snapshot.named;
+        self.pins = snapshot.pins;
+        self.bounds = snapshot.bounds;
+        self.stays = snapshot.stays;
+        self.index_handles();
+        self.disabled = snapshot.disabled;
+        self.conditions = snapshot.conditions;
+        self.params = snapshot.params;
+        self.parallel = snapshot.parallel;
+        self.groups = snapshot.groups;
+        self.merged = snapshot.merged;
+        self.var_data = var_data;
+        self.var_for_symbol = snapshot.var_for_symbol;
+        self.rows = snapshot.rows;
+        self.edits = snapshot.edits;
+        self.edit_infos = snapshot.edit_infos;
+        self.infeasible_rows = snapshot.infeasible_rows;
+        *self.objective.borrow_mut() = snapshot.objective;
+        self.artificial = None;
+        self.id_tick = snapshot.id_tick;
+        let edits = &self.edits;
+        self.content_sizes
+            .retain(|content| edits.contains_key(&content.output));
+        self.generation += 1;
+        let variables: Vec<Variable> = self.var_data.keys().collect();
+        for v in variables {
+            self.var_changed(v);
+        }
+    }
+
+    /// Capture the state of the solver as plain data, see [`SolverState`].
+    ///
+    /// With `tableau` set, the state includes the solved tableau, so that [`Solver::from_state`]
+    /// restores the solver without solving the constraints again. Content sizes, the pivot hook,
+    /// recordings, animations and open checkpoints are not captured.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_constraint(width | GE(Strength::REQUIRED) | 40.0).unwrap();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// solver.suggest_value(width, 60.0).unwrap();
+    ///
+    /// let mut restored = Solver::from_state(solver.state(true)).unwrap();
+    /// assert_eq!(restored.get_value(width), 60.0);
+    /// restored.suggest_value(width, 20.0).unwrap();
+    /// assert_eq!(restored.get_value(width), 40.0);
+    /// ```
+    pub fn state(&self, tableau: bool) -> SolverState {
+        let constraints = self.ordered_constraints();
+        let indices: HashMap<&Constraint, usize> = constraints
+            .iter()
+            .enumerate()
+            .map(|(i, &constraint)| (constraint, i))
+            .collect();
+        let index = |constraint: &Constraint| indices[constraint];
+        let sorted = |map: &HashMap<Variable, f64>| {
+            let mut values: Vec<(Variable, f64)> = map.iter().map(|(&v, &x)| (v, x)).collect();
+            values.sort_by_key(|&(v, _)| v);
+            values
+        };
+
+        let mut conditions: Vec<u64> = self.conditions.iter().copied().collect();
+        conditions.sort_unstable();
+        let mut named: Vec<_> = self
+            .named
+            .iter()
+            .map(|(name, constraint)| (name.clone(), index(constraint)))
+            .collect();
+        named.sort();
+        let mut pins: Vec<_> = self
+            .pins
+            .iter()
+            .map(|(&v, constraint)| (v, index(constraint)))
+            .collect();
+        pins.sort_unstable();
+        let mut bounds: Vec<_> = self
+            .bounds
+            .iter()
+            .map(|(&(v, op), constraint)| (v, op, index(constraint)))
+            .collect();
+        bounds.sort_unstable();
+        let mut stays: Vec<_> = self
+            .stays
+            .iter()
+            .map(|(&v, (stay, value))| (v, index(stay), *value))
+            .collect();
+        stays.sort_by_key(|&(v, _, _)| v);
+        let mut disabled: Vec<_> = self
+            .disabled
+            .iter()
+            .map(|(constraint, &weight)| (index(constraint), weight))
+            .collect();
+        disabled.sort_by_key(|&(i, _)| i);
+        let mut edits: Vec<EditState> = self
+            .edit_infos()
+            .map(|edit| EditState {
+                variable: edit.variable,
+                constraint: index(&edit.constraint),
+                constant: edit.constant,
+            })
+            .collect();
+        edits.sort_by_key(|edit| edit.constraint);
+
+        SolverState {
+            merge_parallel: self.merge_parallel,
+            strict_epsilon: self.strict_epsilon,
+            namespace: self.variable_counter.namespace(),
+            created_variables: self.variable_counter.created(),
+            conditions,
+            params: sorted(&self.params),
+            defaults: sorted(&self.defaults),
+            named,
+            pins,
+            bounds,
+            stays,
+            disabled,
+            edits,
+            tableau: tableau.then(|| self.tableau_state(&index)),
+            constraints: constraints.into_iter().cloned().collect(),
+        }
+    }
+
+    /// Every constraint in the solver, including the constraints of edit variables and stays, in
+    /// the order they were added. Merged constraints follow the constraints they are merged into, and
+    /// disabled required constraints, which are not in the tableau, come last.
+    fn ordered_constraints(&self) -> Vec<&Constraint> {
+        let mut added: Vec<_> = self.constraints.iter().collect();
+        added.sort_by_key(|&(_, tag)| tag.marker);
+        let outside = self
+            .disabled
+            .iter()
+            .filter(|&(constraint, weight)| {
+                weight.is_none() && !self.constraints.contains_key(constraint)
+            })
+            .map(|(constraint, _)| constraint);
+        added
+            .into_iter()
+            .flat_map(|(constraint, _)| {
+                let members = self.groups.get(constraint).map(|group| &group.members);
+                iter::once(constraint).chain(members.into_iter().flatten())
+            })
+            .chain(outside)
+            .collect()
+    }
+
+    fn tableau_state(&self, index: &dyn Fn(&Constraint) -> usize) -> TableauState {
+        let row_state = |row: &Row| {
+            let mut cells: Vec<(Symbol, f64)> = row.cells.iter().map(|(&s, &c)| (s, c)).collect();
+            cells.sort_by_key(|&(symbol, _)| symbol);
+            RowState {
+                constant: row.constant,
+                cells,
+            }
+        };
+        let mut tags: Vec<_> = self
+            .constraints
+            .iter()
+            .map(|(constraint, tag)| (index(constraint), tag.marker, tag.other))
+            .collect();
+        tags.sort_by_key(|&(i, _, _)| i);
+        let mut parallel: Vec<usize> = self
+            .parallel
+            .values()
+            .map(|(primary, _)| index(primary))
+            .collect();
+        parallel.sort_unstable();
+        parallel.dedup();
+        let mut groups: Vec<_> = self
+            .groups
+            .iter()
+            .map(|(primary, group)| {
+                let members = group.members.iter().map(index).collect();
+                (index(primary), members, group.weight)
+            })
+            .collect();
+        groups.sort_by_key(|&(i, _, _)| i);
+        let mut merged: Vec<_> = self
+            .merged
+            .iter()
+            .map(|(member, (primary, weight))| (index(member), index(primary), *weight))
+            .collect();
+        merged.sort_by_key(|&(i, _, _)| i);
+        let mut variables: Vec<_> = self
+            .var_data
+            .iter()
+            .map(|(v, &(_, symbol, count))| (v, symbol, count))
+            .collect();
+        variables.sort_by_key(|&(v, _, _)| v);
+        let mut rows: Vec<_> = self
+            .rows
+            .iter()
+            .map(|(&symbol, row)| (symbol, row_state(row)))
+            .collect();
+        rows.sort_by_key(|&(symbol, _)| symbol);
+        TableauState {
+            tags,
+            parallel,
+            groups,
+            merged,
+            variables,
+            rows,
+            objective: row_state(&self.objective.borrow()),
+            infeasible_rows: self.infeasible_rows.clone(),
+            id_tick: self.id_tick,
+        }
+    }
+
+    /// Construct a solver from a state captured by [`Solver::state`].
+    ///
+    /// A state with the tableau is restored as is. Otherwise the constraints are added again in
+    /// the order they were added to the original solver, followed by the edit variables and their
+    /// suggested values, which fails if the state's constraints conflict. Like
+    /// [`Solver::clone_remapped`], the new solver reports the value of every variable on its first
+    /// call to [`Solver::fetch_changes`].
+    pub fn from_state(state: SolverState) -> Result<Solver, RestoreStateError> {
+        let SolverState {
+            merge_parallel,
+            strict_epsilon,
+            namespace,
+            created_variables,
+            conditions,
+            params,
+            defaults,
+            constraints,
+            named,
+            pins,
+            bounds,
+            stays,
+            disabled,
+            edits,
+            tableau,
+        } = state;
+        let constraint = |i: usize| {
+            constraints
+                .get(i)
+                .cloned()
+                .ok_or(RestoreStateError::InvalidState)
+        };
+
+        let counter = VariableCounter::with_created(namespace, created_variables);
+        let variables = constraints
+            .iter()
+            .flat_map(Constraint::variables)
+            .chain(params.iter().chain(&defaults).map(|&(v, _)| v))
+            .chain(edits.iter().map(|edit| edit.variable))
+            .chain(
+                tableau
+                    .iter()
+                    .flat_map(|t| t.variables.iter().map(|&(v, ..)| v)),
+            );
+        for v in variables {
+            if counter.collides(v) {
+                return Err(RestoreStateError::VariableCollision(v));
+            }
+            v.reserve();
+        }
+
+        let mut solver = Solver::with_variable_counter(counter);
+        solver.merge_parallel = merge_parallel;
+        solver.strict_epsilon = strict_epsilon;
+        solver.conditions = conditions.into_iter().collect();
+        solver.params = params.into_iter().collect();
+        solver.defaults = defaults.into_iter().collect();
+        for (name, i) in named {
+            solver.named.insert(name, constraint(i)?);
+        }
+        for (v, i) in pins {
+            solver.pins.insert(v, constraint(i)?);
+        }
+        for (v, op, i) in bounds {
+            solver.bounds.insert((v, op), constraint(i)?);
+        }
+        solver.index_handles();
+
+        match tableau {
+            Some(tableau) => {
+                for (v, i, value) in stays {
+                    solver.index_stay(v, constraint(i)?, value);
+                }
+                for (i, weight) in disabled {
+                    solver.disabled.insert(constraint(i)?, weight);
+                }
+                solver.restore_tableau(tableau, &constraint, edits)?;
+            }
+            None => {
+                let skipped: HashSet<usize> = edits
+                    .iter()
+                    .map(|edit| edit.constraint)
+                    .chain(stays.iter().map(|&(_, i, _)| i))
+                    .collect();
+                let outside: HashSet<usize> = disabled
+                    .iter()
+                    .filter(|(_, weight)| weight.is_none())
+                    .map(|&(i, _)| i)
+                    .collect();
+                for (i, added) in constraints.iter().enumerate() {
+                    if outside.contains(&i) {
+                        solver.disabled.insert(added.clone(), None);
+                    } else if !skipped.contains(&i) {
+                        solver
+                            .add_constraint_with(added.clone(), merge_parallel)
+                            .map_err(SolverOpError::from)?;
                     }
-                    if coeff != 0.0 && row.add(diff) < 0.0 && symbol.kind() != SymbolKind::External
+                }
+                for (v, i, value) in stays {
+                    let stay = constraint(i)?;
+                    solver
+                        .add_constraint_with(stay.clone(), false)
+                        .map_err(SolverOpError::from)?;
+                    solver.index_stay(v, stay, value);
+                }
+                for (i, weight) in disabled {
+                    if weight.is_some() {
+                        solver
+                            .disable(&constraint(i)?, true)
+                            .map_err(SolverOpError::from)?;
+                    }
+                }
+                for edit in edits {
+                    let strength = constraint(edit.constraint)?.strength();
+                    solver
+                        .add_edit_variable(edit.variable, strength)
+                        .map_err(SolverOpError::from)?;
+                    solver
+                        .suggest_value(edit.variable, edit.constant)
+                        .map_err(SolverOpError::from)?;
+                }
+            }
+        }
+        let variables: Vec<Variable> = solver.var_data.keys().collect();
+        for v in variables {
+            solver.var_changed(v);
+        }
+        Ok(solver)
+    }
+
+    fn restore_tableau(
+        &mut self,
+        tableau: TableauState,
+        constraint: &dyn Fn(usize) -> Result<Constraint, RestoreStateError>,
+        edits: Vec<EditState>,
+    ) -> Result<(), RestoreStateError> {
+        let row = |state: RowState| {
+            let mut row = Row::new(state.constant);
+            row.cells = state.cells.into_iter().collect();
+            row
+        };
+        for (i, marker, other) in tableau.tags {
+            self.constraints
+                .insert(constraint(i)?, Tag { marker, other });
+        }
+        for i in tableau.parallel {
+            let primary = constraint(i)?;
+            if let Some((key, scale)) = Solver::parallel_key(&primary) {
+                // Required inequalities that are primaries bound their parallel constraints.
+                if let Some(any_constant) = key.any_constant() {
+                    if primary.strength() == Strength::REQUIRED {
+                        self.parallel.insert(any_constant, (primary.clone(), scale));
+                    }
+                }
+                self.parallel.insert(key, (primary, scale));
+            }
+        }
+        for (i, members, weight) in tableau.groups {
+            let members = members
+                .into_iter()
+                .map(constraint)
+                .collect::<Result<_, _>>()?;
+            self.groups
+                .insert(constraint(i)?, ParallelGroup { members, weight });
+        }
+        for (member, primary, weight) in tableau.merged {
+            self.merged
+                .insert(constraint(member)?, (constraint(primary)?, weight));
+        }
+        for (v, symbol, count) in tableau.variables {
+            self.var_data.insert(v, (f64::NAN, symbol, count));
+            self.var_for_symbol.insert(symbol, v);
+        }
+        self.rows = tableau
+            .rows
+            .into_iter()
+            .map(|(symbol, state)| (symbol, Box::new(row(state))))
+            .collect();
+        for edit in edits {
+            let constraint = constraint(edit.constraint)?;
+            let tag = *self
+                .constraints
+                .get(&constraint)
+                .ok_or(RestoreStateError::InvalidState)?;
+            self.edits.insert(edit.variable, self.edit_infos.len());
+            self.edit_infos.push(Some(EditInfo {
+                variable: edit.variable,
+                tag,
+                constraint,
+                constant: edit.constant,
+            }));
+        }
+        *self.objective.borrow_mut() = row(tableau.objective);
+        self.infeasible_rows = tableau.infeasible_rows;
+        self.id_tick = tableau.id_tick;
+        #[cfg(feature = "profiling")]
+        for (constraint, tag) in &self.constraints {
+            self.profiler.add(constraint, [tag.marker, tag.other]);
+        }
+        Ok(())
+    }
+
+    /// Start recording the operations performed on the solver, discarding any previous recording.
+    ///
+    /// Every successful call to [`Solver::add_constraint`], [`Solver::remove_constraint`],
+    /// [`Solver::add_edit_variable`], [`Solver::remove_edit_variable`], [`Solver::suggest_value`],
+    /// [`Solver::begin_edit`], [`Solver::end_edit`] and the other operations of [`SolverOp`] is
+    /// recorded once it has taken effect, and so are the calls that other methods make, e.g.
+    /// content sizes suggesting values. Calls that fail leave the solver unchanged and are not
+    /// recorded. The exception is [`Solver::set_condition`], which switches the condition even if
+    /// a required constraint can't be enabled, and is recorded either way. Restoring a
+    /// [`Snapshot`] and resetting the solver are not recorded, so a recording should be started
+    /// after them.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Stop recording and return the operations recorded since [`Solver::start_recording`].
+    ///
+    /// Returns an empty list if the solver wasn't recording.
+    pub fn take_recording(&mut self) -> Vec<SolverOp> {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// Perform recorded operations in order, stopping at the first one that fails.
+    ///
+    /// Replaying the operations of a session on a solver in the state the session started from
+    /// reproduces the state the session left the solver in, after which the call that failed in
+    /// the session can be repeated. A [`SolverOp::SetCondition`] that leaves a required constraint
+    /// disabled does so again without stopping the replay, as it did in the session. Any other
+    /// failure means that the solver wasn't in the same state, and the error holds the index of
+    /// the operation that failed.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.start_recording();
+    /// solver.add_constraint(width | GE(Strength::REQUIRED) | 100.0).unwrap();
+    /// solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    /// solver.suggest_value(width, 50.0).unwrap();
+    /// let ops = solver.take_recording();
+    ///
+    /// let mut replayed = Solver::new();
+    /// replayed.replay(&ops).unwrap();
+    /// assert_eq!(replayed.get_value(width), 100.0);
+    /// ```
+    pub fn replay(&mut self, ops: &[SolverOp]) -> Result<(), (usize, SolverOpError)> {
+        for (i, op) in ops.iter().enumerate() {
+            match (op, op.apply(self)) {
+                (_, Ok(()))
+                | (
+                    SolverOp::SetCondition(..),
+                    Err(SolverOpError::SetEnabled(SetEnabledError::UnsatisfiableConstraint)),
+                ) => {}
+                (_, Err(error)) => return Err((i, error)),
+            }
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, op: impl FnOnce() -> SolverOp) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(op());
+        }
+    }
+
+    /// Mark a point that the solver can be rolled back to with [`Solver::rollback_to`].
+    ///
+    /// Unlike a [`Snapshot`], a checkpoint doesn't copy the tableau. Instead, rolling back removes
+    /// every constraint and edit variable added since the checkpoint one by one, which is cheap
+    /// when only a few were added. Checkpoints can be nested, e.g. for nested speculative layout
+    /// passes, and must be committed or rolled back innermost first.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_constraint(width | EQ(Strength::WEAK) | 100.0).unwrap();
+    ///
+    /// let checkpoint = solver.checkpoint();
+    /// solver.add_constraint(width | LE(Strength::REQUIRED) | 80.0).unwrap();
+    /// assert_eq!(solver.get_value(width), 80.0);
+    ///
+    /// solver.rollback_to(checkpoint).unwrap();
+    /// assert_eq!(solver.get_value(width), 100.0);
+    /// ```
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.open_checkpoints += 1;
+        Checkpoint {
+            position: self.journal.len(),
+            depth: self.open_checkpoints,
+        }
+    }
+
+    /// Remove every constraint and edit variable added since a checkpoint.
+    ///
+    /// Constraints and edit variables that were removed since the checkpoint are not added back,
+    /// except for constraints replaced by adding another under their name with
+    /// [`Solver::add_named`], and suggested values are not reverted. Checkpoints created after
+    /// this one are rolled back as well. Rolling back to a checkpoint that an outer checkpoint was already committed or
+    /// rolled back past does nothing.
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) -> Result<(), InternalSolverError> {
+        if !self.close_checkpoint(&checkpoint) || checkpoint.position > self.journal.len() {
+            return Ok(());
+        }
+        let added = self.truncate_journal(checkpoint.position);
+        for added in added.into_iter().rev() {
+            match added {
+                Added::Constraint(constraint) => {
+                    if let Err(RemoveConstraintError::InternalSolverError(error)) =
+                        self.remove_constraint(&constraint)
+                    {
+                        return Err(error);
+                    }
+                }
+                Added::EditVariable(v) => {
+                    if let Err(RemoveEditVariableError::InternalSolverError(error)) =
+                        self.remove_edit_variable(v)
+                    {
+                        return Err(error);
+                    }
+                }
+                Added::Replaced(name, previous) => {
+                    if let Err(AddConstraintError::InternalSolverError(error)) =
+                        self.add_replaced(name, previous)
+                    {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Keep everything added since a checkpoint, and stop tracking it for that checkpoint.
+    ///
+    /// The additions can still be rolled back with an outer checkpoint.
+    pub fn commit(&mut self, checkpoint: Checkpoint) {
+        if self.close_checkpoint(&checkpoint) && self.open_checkpoints == 0 {
+            self.journal.clear();
+            self.journaled.clear();
+        }
+    }
+
+    /// Close a checkpoint and the checkpoints nested in it, returning whether it was still open.
+    fn close_checkpoint(&mut self, checkpoint: &Checkpoint) -> bool {
+        if checkpoint.depth > self.open_checkpoints {
+            return false;
+        }
+        self.open_checkpoints = checkpoint.depth - 1;
+        true
+    }
+
+    /// Rebuild the tableau from scratch from the constraints and edit variables in the solver.
+    ///
+    /// This is a way to recover after an [`InternalSolverError`], which can leave the tableau in an
+    /// inconsistent state. Names, suggested values, content sizes, open checkpoints and the pivot
+    /// hook are kept, and variables whose values end up different from the ones last fetched are
+    /// reported by the next call to [`Solver::fetch_changes`]. Rebuilding is not recorded. If a
+    /// constraint can't be added back, the others still are, and the first error is returned.
+    pub fn rebuild(&mut self) -> Result<(), AddConstraintError> {
+        let edits: Vec<(Variable, Strength, f64)> = self
+            .edit_infos()
+            .map(|edit| (edit.variable, edit.constraint.strength(), edit.constant))
+            .collect();
+        let stays: Vec<(Variable, Strength, f64)> = self
+            .stays
+            .iter()
+            .map(|(&v, (stay, value))| (v, stay.strength(), *value))
+            .collect();
+        let edit_constraints: HashSet<&Constraint> = self
+            .edit_infos()
+            .map(|edit| &edit.constraint)
+            .chain(self.stays.values().map(|(stay, _)| stay))
+            .collect();
+        // Constraints that were merged into others are added after them, so they are merged again.
+        let mut constraints: Vec<(&Constraint, Symbol)> = self
+            .constraints
+            .iter()
+            .filter(|(constraint, _)| !edit_constraints.contains(constraint))
+            .map(|(constraint, tag)| (constraint, tag.marker))
+            .collect();
+        let mut merged: Vec<(&Constraint, Symbol)> = self
+            .merged
+            .iter()
+            .map(|(member, (primary, _))| (member, self.constraints[primary].marker))
+            .collect();
+        if DETERMINISTIC {
+            constraints.sort_by_key(|&(_, marker)| marker);
+            merged.sort_by_key(|&(_, marker)| marker);
+        }
+        let constraints: Vec<Constraint> = constraints
+            .into_iter()
+            .chain(merged)
+            .map(|(constraint, _)| constraint.clone())
+            .collect();
+        drop(edit_constraints);
+        let fetched: HashMap<Variable, f64> =
+            self.var_data.iter().map(|(v, data)| (v, data.0)).collect();
+        let named = mem::take(&mut self.named);
+        let pins = mem::take(&mut self.pins);
+        let bounds = mem::take(&mut self.bounds);
+        let content_sizes = mem::take(&mut self.content_sizes);
+        let stamped_values = mem::take(&mut self.stamped_values);
+        let journal = mem::take(&mut self.journal);
+        let journaled = mem::take(&mut self.journaled);
+        let open_checkpoints = self.open_checkpoints;
+        let recording = self.recording.take();
+        let params = mem::take(&mut self.params);
+        let conditions = mem::take(&mut self.conditions);
+        let disabled = mem::take(&mut self.disabled);
+        #[cfg(feature = "animation")]
+        let animations = mem::take(&mut self.animations);
+
+        self.reset();
+        self.params = params;
+        self.conditions = conditions;
+        #[cfg(feature = "animation")]
+        {
+            self.animations = animations;
+        }
+        let mut result = Ok(());
+        // Stays are added first, so that they hold the variables where they were.
+        for (v, strength, value) in stays {
+            if let Err(error) = self.insert_stay(v, strength, value) {
+                result = result.and(Err(error));
+            }
+        }
+        for constraint in constraints {
+            if let Err(error) = self.add_constraint_with(constraint, self.merge_parallel) {
+                result = result.and(Err(error));
+            }
+        }
+        for (constraint, weight) in disabled {
+            match weight {
+                None => {
+                    self.disabled.insert(constraint, None);
+                }
+                Some(_) if self.has_constraint(&constraint) => {
+                    if let Err(SetEnabledError::InternalSolverError(error)) =
+                        self.disable(&constraint, true)
                     {
-                        infeasible_rows.push(*symbol);
+                        result = result.and(Err(error.into()));
                     }
                 }
+                Some(_) => {}
             }
         }
-        self.dual_optimize()?;
-        Ok(())
-    }
-
-    fn var_changed(&mut self, v: Variable) {
-        if self.should_clear_changes {
-            self.changed.clear();
-            self.should_clear_changes = false;
+        for (v, strength, value) in edits {
+            match self.add_edit_variable(v, strength) {
+                Ok(_) => {
+                    if let Err(SuggestValueError::InternalSolverError(error)) =
+                        self.suggest_value(v, value)
+                    {
+                        result = result.and(Err(error.into()));
+                    }
+                }
+                Err(AddEditVariableError::InternalSolverError(error)) => {
+                    result = result.and(Err(error.into()));
+                }
+                Err(_) => {}
+            }
         }
-        self.changed.insert(v);
-    }
 
-    /// Fetches all changes to the values of variables since the last call to this function.
-    ///
-    /// The list of changes returned is not in a specific order. Each change comprises the variable
-    /// changed and the new value of that variable.
-    pub fn fetch_changes(&mut self) -> &[(Variable, f64)] {
-        if self.should_clear_changes {
-            self.changed.clear();
-            self.should_clear_changes = false;
-        } else {
-            self.should_clear_changes = true;
-        }
-        self.public_changes.clear();
-        for &v in &self.changed {
-            if let Some(var_data) = self.var_data.get_mut(&v) {
-                let new_value = self
-                    .rows
-                    .get(&var_data.1)
-                    .map(|r| r.constant)
-                    .unwrap_or(0.0);
-                let old_value = var_data.0;
-                if old_value != new_value {
-                    self.public_changes.push((v, new_value));
-                    var_data.0 = new_value;
-                }
+        for (v, data) in self.var_data.iter_mut() {
+            if let Some(&value) = fetched.get(&v) {
+                data.0 = value;
             }
         }
-        &self.public_changes
+        let (constraints, merged, disabled) = (&self.constraints, &self.merged, &self.disabled);
+        let has_constraint = |constraint: &Constraint| {
+            constraints.contains_key(constraint)
+                || merged.contains_key(constraint)
+                || disabled.contains_key(constraint)
+        };
+        self.named = named
+            .into_iter()
+            .filter(|(_, constraint)| has_constraint(constraint))
+            .collect();
+        self.pins = pins
+            .into_iter()
+            .filter(|(_, constraint)| has_constraint(constraint))
+            .collect();
+        self.bounds = bounds
+            .into_iter()
+            .filter(|(_, constraint)| has_constraint(constraint))
+            .collect();
+        self.index_handles();
+        let edits = &self.edits;
+        self.content_sizes = content_sizes;
+        self.content_sizes
+            .retain(|content| edits.contains_key(&content.output));
+        self.stamped_values = stamped_values;
+        self.journal = journal;
+        self.journaled = journaled;
+        self.open_checkpoints = open_checkpoints;
+        self.recording = recording;
+        result
     }
 
     /// Reset the solver to the empty starting condition.
@@ -368,35 +4085,98 @@ impl Solver {
     /// condition, as if no constraints or edit variables have been added.
     /// This can be faster than deleting the solver and creating a new one
     /// when the entire system must change, since it can avoid unnecessary
-    /// heap (de)allocations.
+    /// heap (de)allocations: the internal maps keep their capacity. Pending changes are discarded
+    /// as well. The pivot hook, the `merge_parallel` setting and the default values of variables
+    /// are kept, and so is the count of variables created with [`Solver::new_variable`], so that
+    /// later ones don't reuse their ids.
     pub fn reset(&mut self) {
+        record!(self.alloc_stats.rows_dropped += self.rows.len());
         self.rows.clear();
         self.constraints.clear();
+        self.named.clear();
+        self.pins.clear();
+        self.bounds.clear();
+        self.stays.clear();
+        self.handles.clear();
+        self.disabled.clear();
+        self.conditions.clear();
+        self.params.clear();
+        self.parallel.clear();
+        self.groups.clear();
+        self.merged.clear();
         self.var_data.clear();
         self.var_for_symbol.clear();
         self.changed.clear();
         self.should_clear_changes = false;
+        self.public_changes.clear();
         self.edits.clear();
+        self.edit_infos.clear();
+        self.content_sizes.clear();
         self.infeasible_rows.clear();
         *self.objective.borrow_mut() = Row::new(0.0);
         self.artificial = None;
         self.id_tick = 1;
+        self.generation += 1;
+        self.touched.clear();
+        self.stamped_values.clear();
+        self.open_checkpoints = 0;
+        self.journal.clear();
+        self.journaled.clear();
+        self.editing = false;
+        #[cfg(feature = "profiling")]
+        self.profiler.clear();
+        #[cfg(feature = "animation")]
+        self.animations.clear();
+    }
+
+    /// Decrease the reference count of the variables referenced by a constraint, and forget the
+    /// variables whose count drops to zero.
+    fn release_variables(&mut self, constraint: &Constraint) {
+        for term in constraint.terms() {
+            if !near_zero(term.coefficient) {
+                let mut should_remove = false;
+                if let Some(&mut (_, _, ref mut count)) = self.var_data.get_mut(&term.variable) {
+                    *count -= 1;
+                    should_remove = *count == 0;
+                }
+                if should_remove {
+                    self.var_for_symbol.remove(&self.var_data[&term.variable].1);
+                    self.var_data.remove(&term.variable);
+                    self.touched.remove(&term.variable);
+                    self.stamped_values.remove(&term.variable);
+                    if self.defaults.contains_key(&term.variable) {
+                        // Report the variable going back to its default.
+                        self.var_changed(term.variable);
+                    }
+                }
+            }
+        }
     }
 
     /// Get the symbol for the given variable.
     ///
     /// If a symbol does not exist for the variable, one will be created.
     fn get_var_symbol(&mut self, v: Variable) -> Symbol {
+        if self.defaults.contains_key(&v) && !self.var_data.contains_key(&v) {
+            // Report the solved value even if it is zero, since the default was reported before.
+            self.var_changed(v);
+        }
         let id_tick = &mut self.id_tick;
         let var_for_symbol = &mut self.var_for_symbol;
-        let value = self.var_data.entry(v).or_insert_with(|| {
-            let s = Symbol::new(*id_tick, SymbolKind::External);
-            var_for_symbol.insert(s, v);
-            *id_tick += 1;
-            (f64::NAN, s, 0)
-        });
-        value.2 += 1;
-        value.1
+        tracked!(
+            self.alloc_stats,
+            var_for_symbol,
+            tracked!(self.alloc_stats, self.var_data, {
+                let value = self.var_data.get_or_insert_with(v, || {
+                    let s = Symbol::new(*id_tick, SymbolKind::External);
+                    var_for_symbol.insert(s, v);
+                    *id_tick += 1;
+                    (f64::NAN, s, 0)
+                });
+                value.2 += 1;
+                value.1
+            })
+        )
     }
 
     /// Create a new Row object for the given constraint.
@@ -412,12 +4192,16 @@ impl Solver {
     /// The tag will be updated with the marker and error symbols to use for tracking the movement
     /// of the constraint in the tableau.
     fn create_row(&mut self, constraint: &Constraint) -> (Box<Row>, Tag) {
-        let expr = constraint.expr();
-        let mut row = Row::new(expr.constant);
+        let mut row = Row::new(constraint.constant() + self.strict_offset(constraint));
+        record!(self.alloc_stats.rows_created += 1);
 
-        // Substitute the current basic variables into the row.
-        for term in &expr.terms {
-            if !near_zero(term.coefficient) {
+        // Substitute the current basic variables into the row, and the values of parameters into
+        // its constant.
+        for term in constraint.terms() {
+            if term.variable.is_param() {
+                let value = self.params.get(&term.variable).copied().unwrap_or(0.0);
+                row.add(term.coefficient * value);
+            } else if !near_zero(term.coefficient) {
                 let symbol = self.get_var_symbol(term.variable);
                 if let Some(other_row) = self.rows.get(&symbol) {
                     row.insert_row(other_row, term.coefficient);
@@ -428,6 +4212,8 @@ impl Solver {
         }
 
         let mut objective = self.objective.borrow_mut();
+        #[cfg(feature = "alloc-stats")]
+        let objective_capacity = objective.cells.capacity();
 
         // Add the necessary slack, error, and dummy variables.
         let tag = match constraint.op() {
@@ -444,7 +4230,7 @@ impl Solver {
                     let error = Symbol::new(self.id_tick, SymbolKind::Error);
                     self.id_tick += 1;
                     row.insert_symbol(error, -coeff);
-                    objective.insert_symbol(error, constraint.strength().value());
+                    objective.insert_symbol(error, constraint.error_weight());
                     Tag {
                         marker: slack,
                         other: error,
@@ -464,8 +4250,8 @@ impl Solver {
                     self.id_tick += 1;
                     row.insert_symbol(errplus, -1.0); // v = eplus - eminus
                     row.insert_symbol(errminus, 1.0); // v - eplus + eminus = 0
-                    objective.insert_symbol(errplus, constraint.strength().value());
-                    objective.insert_symbol(errminus, constraint.strength().value());
+                    objective.insert_symbol(errplus, constraint.error_weight());
+                    objective.insert_symbol(errminus, constraint.error_weight());
                     Tag {
                         marker: errplus,
                         other: errminus,
@@ -482,6 +4268,12 @@ impl Solver {
             }
         };
 
+        record!({
+            self.alloc_stats.record_growth(0, row.cells.capacity());
+            self.alloc_stats
+                .record_growth(objective_capacity, objective.cells.capacity());
+        });
+
         // Ensure the row has a positive constant.
         if row.constant < 0.0 {
             row.reverse_sign();
@@ -502,10 +4294,13 @@ impl Solver {
     ///
     /// If a subject cannot be found, an invalid symbol will be returned.
     fn choose_subject(row: &Row, tag: &Tag) -> Symbol {
-        for s in row.cells.keys() {
-            if s.kind() == SymbolKind::External {
-                return *s;
-            }
+        let external = row
+            .cells
+            .keys()
+            .copied()
+            .filter(|s| s.kind() == SymbolKind::External);
+        if let Some(s) = first_symbol(external) {
+            return s;
         }
         if (tag.marker.kind() == SymbolKind::Slack || tag.marker.kind() == SymbolKind::Error)
             && row.coefficient_for(tag.marker) < 0.0
@@ -527,7 +4322,12 @@ impl Solver {
         // Create and add the artificial variable to the tableau
         let art = Symbol::new(self.id_tick, SymbolKind::Slack);
         self.id_tick += 1;
-        self.rows.insert(art, Box::new(row.clone()));
+        record!(self.alloc_stats.rows_created += 2);
+        tracked!(
+            self.alloc_stats,
+            self.rows,
+            self.rows.insert(art, Box::new(row.clone()))
+        );
         self.artificial = Some(Rc::new(RefCell::new(row.clone())));
 
         // Optimize the artificial objective. This is successful
@@ -536,11 +4336,13 @@ impl Solver {
         self.optimize(&artificial)?;
         let success = near_zero(artificial.borrow().constant);
         self.artificial = None;
+        record!(self.alloc_stats.rows_dropped += 1);
 
         // If the artificial variable is basic, pivot the row so that
         // it becomes basic. If the row is constant, exit early.
         if let Some(mut row) = self.rows.remove(&art) {
             if row.cells.is_empty() {
+                record!(self.alloc_stats.rows_dropped += 1);
                 return Ok(success);
             }
             let entering = Solver::any_pivotable_symbol(&row); // never External
@@ -549,7 +4351,7 @@ impl Solver {
             }
             row.solve_for_symbols(art, entering);
             self.substitute(entering, &row);
-            self.rows.insert(entering, row);
+            tracked!(self.alloc_stats, self.rows, self.rows.insert(entering, row));
         }
 
         // Remove the artificial row from the tableau
@@ -566,7 +4368,11 @@ impl Solver {
     /// in the tableau and the objective function with the given row.
     fn substitute(&mut self, symbol: Symbol, row: &Row) {
         for (&other_symbol, other_row) in &mut self.rows {
-            let constant_changed = other_row.substitute(symbol, row);
+            let constant_changed = tracked!(
+                self.alloc_stats,
+                other_row.cells,
+                other_row.substitute(symbol, row)
+            );
             if other_symbol.kind() == SymbolKind::External && constant_changed {
                 let v = self.var_for_symbol[&other_symbol];
                 // inline var_changed
@@ -574,15 +4380,26 @@ impl Solver {
                     self.changed.clear();
                     self.should_clear_changes = false;
                 }
-                self.changed.insert(v);
+                tracked!(self.alloc_stats, self.changed, self.changed.insert(v));
+                self.touched.insert(v, self.generation);
             }
             if other_symbol.kind() != SymbolKind::External && other_row.constant < 0.0 {
                 self.infeasible_rows.push(other_symbol);
             }
         }
-        self.objective.borrow_mut().substitute(symbol, row);
+        let mut objective = self.objective.borrow_mut();
+        tracked!(
+            self.alloc_stats,
+            objective.cells,
+            objective.substitute(symbol, row)
+        );
         if let Some(artificial) = self.artificial.as_ref() {
-            artificial.borrow_mut().substitute(symbol, row);
+            let mut artificial = artificial.borrow_mut();
+            tracked!(
+                self.alloc_stats,
+                artificial.cells,
+                artificial.substitute(symbol, row)
+            );
         }
     }
 
@@ -594,11 +4411,17 @@ impl Solver {
         loop {
             let entering = Solver::get_entering_symbol(&objective.borrow());
             if entering.kind() == SymbolKind::Invalid {
+                if core::ptr::eq(objective, &*self.objective) {
+                    self.reset_stays();
+                }
                 return Ok(());
             }
             let (leaving, mut row) = self
                 .get_leaving_row(entering)
                 .ok_or(InternalSolverError::ObjectiveUnbounded)?;
+            let objective_before = objective.borrow().constant;
+            #[cfg(feature = "profiling")]
+            let started = std::time::Instant::now();
             // pivot the entering symbol into the basis
             row.solve_for_symbols(leaving, entering);
             self.substitute(entering, &row);
@@ -606,7 +4429,17 @@ impl Solver {
                 let v = self.var_for_symbol[&entering];
                 self.var_changed(v);
             }
-            self.rows.insert(entering, row);
+            tracked!(self.alloc_stats, self.rows, self.rows.insert(entering, row));
+            #[cfg(feature = "profiling")]
+            self.profiler.pivot(entering, leaving, started.elapsed());
+            let objective_after = objective.borrow().constant;
+            self.report_pivot(
+                PivotPhase::Primal,
+                entering,
+                leaving,
+                objective_before,
+                objective_after,
+            );
         }
     }
 
@@ -617,7 +4450,15 @@ impl Solver {
     /// an iteration of the dual simplex method to make the solution both
     /// optimal and feasible.
     fn dual_optimize(&mut self) -> Result<(), InternalSolverError> {
-        while let Some(leaving) = self.infeasible_rows.pop() {
+        loop {
+            if DETERMINISTIC {
+                // Process the infeasible rows in symbol order rather than discovery order.
+                self.infeasible_rows.sort_unstable_by(|a, b| b.cmp(a));
+                self.infeasible_rows.dedup();
+            }
+            let Some(leaving) = self.infeasible_rows.pop() else {
+                break;
+            };
             let row = if let Entry::Occupied(entry) = self.rows.entry(leaving) {
                 if entry.get().constant < 0.0 {
                     Some(entry.remove())
@@ -632,6 +4473,9 @@ impl Solver {
                 if entering.kind() == SymbolKind::Invalid {
                     return Err(InternalSolverError::DualOptimizeFailed);
                 }
+                let objective_before = self.objective.borrow().constant;
+                #[cfg(feature = "profiling")]
+                let started = std::time::Instant::now();
                 // pivot the entering symbol into the basis
                 row.solve_for_symbols(leaving, entering);
                 self.substitute(entering, &row);
@@ -639,12 +4483,80 @@ impl Solver {
                     let v = self.var_for_symbol[&entering];
                     self.var_changed(v);
                 }
-                self.rows.insert(entering, row);
+                tracked!(self.alloc_stats, self.rows, self.rows.insert(entering, row));
+                #[cfg(feature = "profiling")]
+                self.profiler.pivot(entering, leaving, started.elapsed());
+                let objective_after = self.objective.borrow().constant;
+                self.report_pivot(
+                    PivotPhase::Dual,
+                    entering,
+                    leaving,
+                    objective_before,
+                    objective_after,
+                );
             }
         }
+        self.reset_stays();
         Ok(())
     }
 
+    /// Move the stays to the current values of their variables, which doesn't change the solution.
+    fn reset_stays(&mut self) {
+        if self.stays.is_empty() {
+            return;
+        }
+        for (&v, (stay, value)) in &mut self.stays {
+            let Some(tag) = self.constraints.get(stay) else {
+                continue;
+            };
+            // The error of a violated stay is basic, and setting it to zero moves the stay to the
+            // value of the variable instead.
+            if let Some(row) = self.rows.get_mut(&tag.marker) {
+                row.constant = 0.0;
+            } else if let Some(row) = self.rows.get_mut(&tag.other) {
+                row.constant = 0.0;
+            }
+            let symbol = self.var_data.get(&v).map(|data| data.1);
+            *value = symbol
+                .and_then(|symbol| self.rows.get(&symbol))
+                .map_or(0.0, |row| row.constant);
+        }
+    }
+
+    /// Pass a pivot to the pivot hook, if one is set.
+    fn report_pivot(
+        &mut self,
+        phase: PivotPhase,
+        entering: Symbol,
+        leaving: Symbol,
+        objective_before: f64,
+        objective_after: f64,
+    ) {
+        if self.pivot_hook.is_none() {
+            return;
+        }
+        let event = PivotEvent {
+            phase,
+            entering: self.pivot_symbol(entering),
+            leaving: self.pivot_symbol(leaving),
+            objective_before,
+            objective_after,
+        };
+        if let Some(hook) = self.pivot_hook.as_mut() {
+            hook(&event);
+        }
+    }
+
+    fn pivot_symbol(&self, symbol: Symbol) -> PivotSymbol {
+        match symbol.kind() {
+            SymbolKind::External => PivotSymbol::Variable(self.var_for_symbol[&symbol]),
+            SymbolKind::Slack => PivotSymbol::Slack(symbol.id()),
+            SymbolKind::Error => PivotSymbol::Error(symbol.id()),
+            SymbolKind::Dummy => PivotSymbol::Dummy(symbol.id()),
+            SymbolKind::Invalid => unreachable!("invalid symbols are never pivoted"),
+        }
+    }
+
     /// Compute the entering variable for a pivot operation.
     ///
     /// This method will return first symbol in the objective function which
@@ -653,12 +4565,12 @@ impl Solver {
     /// invalid symbol is returned.
     /// Could return an External symbol
     fn get_entering_symbol(objective: &Row) -> Symbol {
-        for (symbol, value) in &objective.cells {
-            if symbol.kind() != SymbolKind::Dummy && *value < 0.0 {
-                return *symbol;
-            }
-        }
-        Symbol::invalid()
+        let candidates = objective
+            .cells
+            .iter()
+            .filter(|(symbol, value)| symbol.kind() != SymbolKind::Dummy && **value < 0.0)
+            .map(|(symbol, _)| *symbol);
+        first_symbol(candidates).unwrap_or_else(Symbol::invalid)
     }
 
     /// Compute the entering symbol for the dual optimize operation.
@@ -677,7 +4589,7 @@ impl Solver {
             if *value > 0.0 && symbol.kind() != SymbolKind::Dummy {
                 let coeff = objective.coefficient_for(*symbol);
                 let r = coeff / *value;
-                if r < ratio {
+                if better_ratio(r, *symbol, ratio, Some(entering)) {
                     ratio = r;
                     entering = *symbol;
                 }
@@ -691,12 +4603,12 @@ impl Solver {
     /// If no such symbol is present, and Invalid symbol will be returned.
     /// Never returns an External symbol
     fn any_pivotable_symbol(row: &Row) -> Symbol {
-        for symbol in row.cells.keys() {
-            if symbol.kind() == SymbolKind::Slack || symbol.kind() == SymbolKind::Error {
-                return *symbol;
-            }
-        }
-        Symbol::invalid()
+        let candidates = row
+            .cells
+            .keys()
+            .copied()
+            .filter(|symbol| matches!(symbol.kind(), SymbolKind::Slack | SymbolKind::Error));
+        first_symbol(candidates).unwrap_or_else(Symbol::invalid)
     }
 
     /// Compute the row which holds the exit symbol for a pivot.
@@ -714,7 +4626,7 @@ impl Solver {
                 let temp = row.coefficient_for(entering);
                 if temp < 0.0 {
                     let temp_ratio = -row.constant / temp;
-                    if temp_ratio < ratio {
+                    if better_ratio(temp_ratio, *symbol, ratio, found) {
                         ratio = temp_ratio;
                         found = Some(*symbol);
                     }
@@ -746,23 +4658,26 @@ impl Solver {
         let mut r2 = r1;
         let mut first = None;
         let mut second = None;
-        let mut third = None;
+        let mut third: Option<Symbol> = None;
         for (symbol, row) in &self.rows {
             let c = row.coefficient_for(marker);
             if c == 0.0 {
                 continue;
             }
             if symbol.kind() == SymbolKind::External {
-                third = Some(*symbol);
+                third = match third {
+                    Some(third) if DETERMINISTIC => Some(third.min(*symbol)),
+                    _ => Some(*symbol),
+                };
             } else if c < 0.0 {
                 let r = -row.constant / c;
-                if r < r1 {
+                if better_ratio(r, *symbol, r1, first) {
                     r1 = r;
                     first = Some(*symbol);
                 }
             } else {
                 let r = row.constant / c;
-                if r < r2 {
+                if better_ratio(r, *symbol, r2, second) {
                     r2 = r;
                     second = Some(*symbol);
                 }
@@ -778,11 +4693,19 @@ impl Solver {
     }
 
     /// Remove the effects of a constraint on the objective function.
-    fn remove_constraint_effects(&mut self, constraint: &Constraint, tag: &Tag) {
+    ///
+    /// The merged weight is the weight added by constraints merged into the constraint.
+    fn remove_constraint_effects(
+        &mut self,
+        constraint: &Constraint,
+        tag: &Tag,
+        merged_weight: f64,
+    ) {
+        let strength = constraint.error_weight() + merged_weight;
         if tag.marker.kind() == SymbolKind::Error {
-            self.remove_marker_effects(tag.marker, constraint.strength().value());
+            self.remove_marker_effects(tag.marker, strength);
         } else if tag.other.kind() == SymbolKind::Error {
-            self.remove_marker_effects(tag.other, constraint.strength().value());
+            self.remove_marker_effects(tag.other, strength);
         }
     }
 
@@ -805,14 +4728,692 @@ impl Solver {
         true
     }
 
+    /// The allocations performed by the solver since it was created or
+    /// [`reset_alloc_stats`](Self::reset_alloc_stats) was last called.
+    ///
+    /// Subtract the statistics taken before an operation from those taken after it to measure
+    /// the allocations performed by that operation.
+    #[cfg(feature = "alloc-stats")]
+    pub fn alloc_stats(&self) -> AllocStats {
+        self.alloc_stats
+    }
+
+    /// Reset the allocation statistics to zero.
+    #[cfg(feature = "alloc-stats")]
+    pub fn reset_alloc_stats(&mut self) {
+        self.alloc_stats = AllocStats::default();
+    }
+
+    /// The constraints involved in the most solving work, most expensive first.
+    ///
+    /// Every pivot of the simplex method is attributed to the constraints whose symbols enter or
+    /// leave the basis. A badly formulated constraint that slows down the layout shows up at the
+    /// top of this list with many pivots or much time. Constraints that were never involved in a
+    /// pivot are not listed, while constraints that have since been removed still are.
+    #[cfg(feature = "profiling")]
+    pub fn hot_constraints(&self) -> Vec<ConstraintProfile> {
+        self.profiler.hot_constraints()
+    }
+
+    /// Discard the profiles reported by [`Solver::hot_constraints`].
+    #[cfg(feature = "profiling")]
+    pub fn reset_profile(&mut self) {
+        self.profiler.clear_profiles();
+    }
+
+    /// The current generation of the solver.
+    ///
+    /// The generation starts at zero and increases every time the solver is modified, e.g. by
+    /// adding or removing a constraint or suggesting a value. It never decreases, not even when
+    /// the solver is reset.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Take an immutable snapshot of the current solution.
+    ///
+    /// The returned [`Solution`] holds the value of every variable known to the solver and reports
+    /// which constraints are currently satisfied. It is independent of the solver, so it can be
+    /// stored, compared with other solutions, or sent to another thread.
+    pub fn freeze(&self) -> Solution {
+        let values = self.iter_values().collect();
+        let mut satisfied = Vec::new();
+        let mut violated = Vec::new();
+        for constraint in self.constraints.keys().chain(self.merged.keys()) {
+            let violation = self.violation(constraint);
+            if violation == 0.0 {
+                satisfied.push(constraint.clone());
+            } else {
+                violated.push((constraint.clone(), violation));
+            }
+        }
+        Solution::new(self.generation, values, satisfied, violated)
+    }
+
+    /// How far the current solution is from satisfying a constraint, or zero if it is satisfied.
+    ///
+    /// This is the amount by which the constraint's expression would have to change to satisfy it,
+    /// e.g. 20 for `width == 100` when `width` is 80. Non-zero violations only occur for
+    /// non-required constraints, or for constraints that are not in the solver. This can be used to
+    /// detect when a preferred size had to be compromised, e.g. to show a scroll bar.
+    pub fn violation(&self, constraint: &Constraint) -> f64 {
+        let value = self.evaluate(constraint) + self.strict_offset(constraint);
+        let violation = match constraint.op() {
+            RelationalOperator::LessOrEqual => value.max(0.0),
+            RelationalOperator::Equal => value.abs(),
+            RelationalOperator::GreaterOrEqual => (-value).max(0.0),
+        };
+        if near_zero(violation) {
+            0.0
+        } else {
+            violation
+        }
+    }
+
+    /// How far an inequality constraint in the solver is from being tight, or `None` if the
+    /// constraint is an equality or is not in the solver.
+    ///
+    /// For example `width <= 100` has a slack of 20 when `width` is 80. A constraint with slack
+    /// does not currently affect the solution, so removing it would not change the layout (although
+    /// it may limit how far other constraints can move it later). A violated inequality has no
+    /// slack.
+    pub fn slack(&self, constraint: &Constraint) -> Option<f64> {
+        if !self.has_constraint(constraint) {
+            return None;
+        }
+        let value = self.evaluate(constraint) + self.strict_offset(constraint);
+        let slack = match constraint.op() {
+            RelationalOperator::LessOrEqual => (-value).max(0.0),
+            RelationalOperator::GreaterOrEqual => value.max(0.0),
+            RelationalOperator::Equal => return None,
+        };
+        Some(if near_zero(slack) { 0.0 } else { slack })
+    }
+
+    /// The current value of the objective function the solver minimizes, i.e. the sum of the
+    /// violations of the non-required constraints weighted by their strengths.
+    ///
+    /// This is zero when every constraint is satisfied. It is cheap to compute, so it can be logged
+    /// every frame to notice when a layout starts to degrade.
+    pub fn total_error(&self) -> f64 {
+        let error = self.objective.borrow().constant;
+        if near_zero(error) {
+            0.0
+        } else {
+            error
+        }
+    }
+
+    /// A human readable dump of the internal state of the solver, like kiwi's `dumps()`.
+    ///
+    /// Lists the objective, the rows of the tableau, the infeasible rows, which symbol each
+    /// variable is represented by, the edit variables and the constraints. Symbols are printed with
+    /// a prefix for their kind: `x` for variables, `s` for slacks, `e` for errors and `d` for
+    /// dummies. This is mostly useful for reporting bugs such as an
+    /// [`InternalSolverError`](crate::InternalSolverError).
+    pub fn dumps(&self) -> String {
+        let mut dump = String::new();
+        // Writing to a string can't fail.
+        let _ = self.write_dump(&mut dump);
+        dump
+    }
+
+    /// The constraint system of the solver in the CPLEX LP format, for inspecting a layout with
+    /// external LP solvers and viewers.
+    ///
+    /// The program is equivalent to the constraints in the solver: each variable becomes a free
+    /// column named `v` followed by its [id](Variable::id), and each constraint a row. A
+    /// constraint that is not required gets non-negative error columns, named after its row, which
+    /// the objective minimizes weighted by the strength of the constraint. Parameters are replaced
+    /// by their values, edit variables are constrained to their suggested values and disabled
+    /// constraints are left out. [`ImportedProgram`](crate::ImportedProgram) reads it back.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_constraint(width | GE(Strength::REQUIRED) | 10.0).unwrap();
+    /// solver.add_constraint(width | EQ(Strength::WEAK) | 0.0).unwrap();
+    /// let lp = solver.export_lp();
+    /// assert!(lp.contains(&format!(" c1: v{} >= 10\n", width.id())));
+    /// assert!(lp.contains(" obj: c2p + c2m\n"));
+    /// ```
+    pub fn export_lp(&self) -> String {
+        let mut lp = String::new();
+        // Writing to a string can't fail.
+        let _ = self.linear_program().write_lp(&mut lp);
+        lp
+    }
+
+    /// The constraint system of the solver in the MPS format, see [`Solver::export_lp`].
+    ///
+    /// The fields of each line are separated by spaces rather than at fixed positions, since the
+    /// names of columns can be longer than fixed positions allow.
+    pub fn export_mps(&self) -> String {
+        let mut mps = String::new();
+        // Writing to a string can't fail.
+        let _ = self.linear_program().write_mps(&mut mps);
+        mps
+    }
+
+    /// The constraint system and solution of the solver as a JSON document, for visualization tools
+    /// and web-based inspectors.
+    ///
+    /// The document is an object with three arrays:
+    ///
+    /// - `variables`: every variable of the solver with its `id`, its `name` or `null`, and its
+    ///   `value`.
+    /// - `constraints`: every constraint in the order it was added, leaving out the constraints of
+    ///   edit variables, with its [`id`](Constraint::id), `text`, `terms`, `constant`, `operator`,
+    ///   `strength` (formatted like [`Strength`]'s `Display`) and `strength_value`, `weight`,
+    ///   `name`, `tag`, `condition`, whether it is `enabled` and its `violation`.
+    /// - `edit_variables`: every edit variable with its `strength`, `strength_value` and
+    ///   `suggested` value.
+    ///
+    /// Variables are referred to by strings of `v` followed by their [id](Variable::id), since ids
+    /// may be too large for the numbers of JavaScript. The variables are named with the
+    /// `variable-names` feature, see [`Solver::to_debug_json_with`] for naming them with a
+    /// [`NameRegistry`]. Everything is listed in a fixed order, so that the documents of two states
+    /// can be compared line by line. Infinite values are written as `null`.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.add_constraint(width | GE(Strength::REQUIRED) | 10.0).unwrap();
+    /// let json = solver.to_debug_json();
+    /// assert!(json.contains(&format!(r#""id": "v{}", "name": null, "value": 10}}"#, width.id())));
+    /// ```
+    pub fn to_debug_json(&self) -> String {
+        self.to_debug_json_with(&NameRegistry::new())
+    }
+
+    /// [`Solver::to_debug_json`], with the variables named by a registry. Variables without a name
+    /// in the registry fall back to their names from the `variable-names` feature.
+    pub fn to_debug_json_with(&self, names: &NameRegistry) -> String {
+        let mut json = String::new();
+        // Writing to a string can't fail.
+        let _ = self.write_debug_json(&mut json, names);
+        json
+    }
+
+    fn write_debug_json(&self, out: &mut String, names: &NameRegistry) -> fmt::Result {
+        let edits: HashSet<&Constraint> = self.edit_infos().map(|edit| &edit.constraint).collect();
+        let constraints: Vec<&Constraint> = self
+            .ordered_constraints()
+            .into_iter()
+            .filter(|constraint| !edits.contains(constraint))
+            .collect();
+        let variables: BTreeSet<Variable> = self
+            .var_data
+            .keys()
+            .chain(constraints.iter().flat_map(|c| c.variables()))
+            .filter(|v| !v.is_param())
+            .collect();
+
+        out.push_str("{\n  \"variables\": [");
+        for (i, &v) in variables.iter().enumerate() {
+            out.push_str(if i == 0 { "\n    " } else { ",\n    " });
+            out.push_str("{\"id\": ");
+            json::write_variable(out, v)?;
+            out.push_str(", \"name\": ");
+            #[cfg(feature = "variable-names")]
+            let name = names
+                .name(v)
+                .map(Cow::Borrowed)
+                .or_else(|| v.name().map(Cow::Owned));
+            #[cfg(not(feature = "variable-names"))]
+            let name = names.name(v).map(Cow::Borrowed);
+            match name {
+                Some(name) => json::write_string(out, &name)?,
+                None => out.push_str("null"),
+            }
+            out.push_str(", \"value\": ");
+            json::write_number(out, self[v])?;
+            out.push('}');
+        }
+
+        out.push_str("\n  ],\n  \"constraints\": [");
+        for (i, constraint) in constraints.into_iter().enumerate() {
+            out.push_str(if i == 0 { "\n    " } else { ",\n    " });
+            write!(out, "{{\"id\": \"{}\", \"text\": ", constraint.id())?;
+            json::write_string(out, &constraint.display_with(names).to_string())?;
+            out.push_str(", \"terms\": [");
+            for (j, term) in constraint.terms().iter().enumerate() {
+                if j > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str("{\"variable\": ");
+                json::write_variable(out, term.variable)?;
+                out.push_str(", \"coefficient\": ");
+                json::write_number(out, term.coefficient)?;
+                out.push('}');
+            }
+            out.push_str("], \"constant\": ");
+            json::write_number(out, constraint.constant())?;
+            let operator = match (constraint.op(), constraint.is_strict()) {
+                (RelationalOperator::LessOrEqual, true) => "<",
+                (RelationalOperator::GreaterOrEqual, true) => ">",
+                (op, _) => match op {
+                    RelationalOperator::LessOrEqual => "<=",
+                    RelationalOperator::Equal => "==",
+                    RelationalOperator::GreaterOrEqual => ">=",
+                },
+            };
+            write!(out, ", \"operator\": \"{operator}\", \"strength\": ")?;
+            json::write_string(out, &constraint.strength().to_string())?;
+            out.push_str(", \"strength_value\": ");
+            json::write_number(out, constraint.strength().value())?;
+            out.push_str(", \"weight\": ");
+            json::write_number(out, constraint.weight())?;
+            out.push_str(", \"name\": ");
+            match constraint.name() {
+                Some(name) => json::write_string(out, name)?,
+                None => out.push_str("null"),
+            }
+            for (key, value) in [
+                ("tag", constraint.tag()),
+                ("condition", constraint.condition()),
+            ] {
+                match value {
+                    Some(value) => write!(out, ", \"{key}\": {value}")?,
+                    None => write!(out, ", \"{key}\": null")?,
+                }
+            }
+            write!(out, ", \"enabled\": {}", self.is_enabled(constraint))?;
+            out.push_str(", \"violation\": ");
+            json::write_number(out, self.violation(constraint))?;
+            out.push('}');
+        }
+
+        out.push_str("\n  ],\n  \"edit_variables\": [");
+        let mut edits: Vec<_> = self.edit_infos().collect();
+        edits.sort_by_key(|edit| edit.variable);
+        for (i, edit) in edits.into_iter().enumerate() {
+            out.push_str(if i == 0 { "\n    " } else { ",\n    " });
+            out.push_str("{\"variable\": ");
+            json::write_variable(out, edit.variable)?;
+            out.push_str(", \"strength\": ");
+            json::write_string(out, &edit.constraint.strength().to_string())?;
+            out.push_str(", \"strength_value\": ");
+            json::write_number(out, edit.constraint.strength().value())?;
+            out.push_str(", \"suggested\": ");
+            json::write_number(out, edit.constant)?;
+            out.push('}');
+        }
+        out.push_str("\n  ]\n}\n");
+        Ok(())
+    }
+
+    /// The linear program equivalent to the constraints in the solver, with the constraints in the
+    /// order they were added and merged constraints after the constraints they are merged into.
+    fn linear_program(&self) -> LinearProgram {
+        let edits: HashMap<&Constraint, f64> = self
+            .edit_infos()
+            .map(|edit| (&edit.constraint, edit.constant))
+            .collect();
+        let mut constraints: Vec<_> = self.constraints.iter().collect();
+        constraints.sort_by_key(|&(_, tag)| tag.marker);
+        let constraints = constraints.into_iter().flat_map(|(constraint, _)| {
+            let members = self.groups.get(constraint).map(|group| &group.members);
+            iter::once(constraint).chain(members.into_iter().flatten())
+        });
+        let mut program = LinearProgram::default();
+        for constraint in constraints {
+            if self.disabled.contains_key(constraint) {
+                continue;
+            }
+            let mut constant = constraint.constant() + self.strict_offset(constraint)
+                - edits.get(constraint).copied().unwrap_or(0.0);
+            let mut terms = Vec::new();
+            for term in constraint.terms() {
+                if term.variable.is_param() {
+                    constant += term.coefficient * self.params.get(&term.variable).unwrap_or(&0.0);
+                } else if !near_zero(term.coefficient) {
+                    terms.push((term.variable, term.coefficient));
+                }
+            }
+            let weight =
+                (constraint.strength() < Strength::REQUIRED).then(|| constraint.error_weight());
+            program.add_row(&terms, constraint.op(), -constant, weight);
+        }
+        program
+    }
+
+    fn write_dump(&self, out: &mut String) -> fmt::Result {
+        let names = NameRegistry::new();
+        writeln!(out, "Objective\n---------\n{}\n", self.objective.borrow())?;
+
+        writeln!(out, "Tableau\n-------")?;
+        let mut rows: Vec<_> = self.rows.iter().collect();
+        rows.sort_by_key(|&(symbol, _)| symbol);
+        for (symbol, row) in rows {
+            writeln!(out, "{} | {}", symbol, row)?;
+        }
+
+        writeln!(out, "\nInfeasible\n----------")?;
+        for symbol in &self.infeasible_rows {
+            writeln!(out, "{}", symbol)?;
+        }
+
+        writeln!(out, "\nVariables\n---------")?;
+        let mut variables: Vec<_> = self.var_data.iter().collect();
+        variables.sort_by_key(|&(v, _)| v);
+        for (v, (_, symbol, _)) in variables {
+            writeln!(out, "{} = {}", v, symbol)?;
+        }
+
+        writeln!(out, "\nEdit Variables\n--------------")?;
+        let mut edits: Vec<_> = self.edit_infos().collect();
+        edits.sort_by_key(|edit| edit.variable);
+        for edit in edits {
+            writeln!(out, "{} = {}", edit.variable, edit.constant)?;
+        }
+
+        writeln!(out, "\nConstraints\n-----------")?;
+        let mut constraints: Vec<_> = self.constraints.iter().collect();
+        constraints.sort_by_key(|&(_, tag)| tag.marker);
+        for (constraint, tag) in constraints {
+            writeln!(
+                out,
+                "{} | {} {} | {}",
+                constraint.display_with(&names),
+                tag.marker,
+                tag.other,
+                constraint.strength().value()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// An estimate of the heap memory held by the solver, in bytes.
+    ///
+    /// This counts the capacity of the tableau and of the internal maps, so it includes memory that
+    /// is allocated but currently unused. Maps don't shrink when constraints are removed, so an
+    /// application that adds and removes constraints constantly can compare this with the size a
+    /// freshly built solver would have and rebuild the solver when it has grown too much. The
+    /// constraints themselves are shared with the caller and are not counted.
+    pub fn heap_size(&self) -> usize {
+        let rows: usize = self
+            .rows
+            .values()
+            .map(|row| mem::size_of::<Row>() + map_heap_size(&row.cells))
+            .sum();
+        let objective = map_heap_size(&self.objective.borrow().cells);
+        let artificial = self.artificial.as_ref().map_or(0, |artificial| {
+            mem::size_of::<Row>() + map_heap_size(&artificial.borrow().cells)
+        });
+        let names: usize = self.named.keys().map(String::capacity).sum();
+        let handles: usize = self
+            .handles
+            .values()
+            .map(|handles| {
+                let names: usize = handles.names.iter().map(String::capacity).sum();
+                names
+                    + vec_heap_size(&handles.names)
+                    + vec_heap_size(&handles.pins)
+                    + vec_heap_size(&handles.bounds)
+            })
+            .sum();
+        let journaled: usize = self.journaled.values().map(vec_heap_size).sum();
+        let parallel: usize = self
+            .parallel
+            .keys()
+            .map(|key| vec_heap_size(&key.terms))
+            .sum();
+        let groups: usize = self
+            .groups
+            .values()
+            .map(|group| vec_heap_size(&group.members))
+            .sum();
+        let content_sizes: usize = self
+            .content_sizes
+            .iter()
+            .map(|content| vec_heap_size(&content.inputs) + vec_heap_size(&content.measured))
+            .sum();
+        rows + objective
+            + artificial
+            + names
+            + handles
+            + journaled
+            + parallel
+            + groups
+            + content_sizes
+            + map_heap_size(&self.constraints)
+            + map_heap_size(&self.named)
+            + map_heap_size(&self.pins)
+            + map_heap_size(&self.bounds)
+            + map_heap_size(&self.stays)
+            + map_heap_size(&self.handles)
+            + map_heap_size(&self.disabled)
+            + self.conditions.capacity() * (mem::size_of::<u64>() + 1)
+            + map_heap_size(&self.params)
+            + map_heap_size(&self.parallel)
+            + map_heap_size(&self.groups)
+            + map_heap_size(&self.merged)
+            + self.var_data.heap_size()
+            + map_heap_size(&self.var_for_symbol)
+            + map_heap_size(&self.rows)
+            + map_heap_size(&self.edits)
+            + vec_heap_size(&self.edit_infos)
+            + map_heap_size(&self.touched)
+            + map_heap_size(&self.stamped_values)
+            + self.changed.capacity() * (mem::size_of::<Variable>() + 1)
+            + vec_heap_size(&self.public_changes)
+            + vec_heap_size(&self.content_sizes)
+            + vec_heap_size(&self.infeasible_rows)
+            + vec_heap_size(&self.journal)
+            + map_heap_size(&self.journaled)
+    }
+
+    /// The interval of values a variable can take without violating any required constraint.
+    ///
+    /// The bounds are infinite in directions in which the variable is unconstrained. This is useful
+    /// for e.g. resize handles that need to know how far they can be dragged before the user
+    /// starts dragging them. Finding the bounds means solving a copy of the required constraints
+    /// twice, so it is much more expensive than querying a value.
+    pub fn bounds_of(&self, v: Variable) -> (f64, f64) {
+        match self.required_scratch(v) {
+            Some(mut scratch) => (
+                scratch.push_to_extreme(v, -1.0),
+                scratch.push_to_extreme(v, 1.0),
+            ),
+            None => (f64::NEG_INFINITY, f64::INFINITY),
+        }
+    }
+
+    /// The largest value a variable can take without violating any required constraint.
+    ///
+    /// Returns infinity if the variable is unbounded above. The solver itself is left untouched:
+    /// the variable is maximized in a copy of the required constraints, see [`Solver::bounds_of`].
+    pub fn maximize(&self, v: Variable) -> f64 {
+        self.required_scratch(v)
+            .map_or(f64::INFINITY, |mut scratch| scratch.push_to_extreme(v, 1.0))
+    }
+
+    /// The smallest value a variable can take without violating any required constraint.
+    ///
+    /// Returns negative infinity if the variable is unbounded below. The solver itself is left
+    /// untouched: the variable is minimized in a copy of the required constraints, see
+    /// [`Solver::bounds_of`].
+    pub fn minimize(&self, v: Variable) -> f64 {
+        self.required_scratch(v)
+            .map_or(f64::NEG_INFINITY, |mut scratch| {
+                scratch.push_to_extreme(v, -1.0)
+            })
+    }
+
+    /// A new solver with only the required constraints of this one and `v` as an edit variable.
+    ///
+    /// Returns `None` if `v` is not constrained at all.
+    fn required_scratch(&self, v: Variable) -> Option<Solver> {
+        if !self.var_data.contains_key(&v) {
+            return None;
+        }
+        let mut scratch = Solver::with_variable_counter(self.variable_counter.clone());
+        scratch.strict_epsilon = self.strict_epsilon;
+        scratch.params = self.params.clone();
+        let required = self
+            .constraints
+            .keys()
+            .filter(|constraint| constraint.strength() == Strength::REQUIRED)
+            .cloned();
+        scratch.add_constraints(required).ok()?;
+        scratch.add_edit_variable(v, Strength::STRONG).ok()?;
+        Some(scratch)
+    }
+
+    /// Push the edit variable `v` as far as possible in `direction` (1 or -1) and return where it
+    /// stops, or an infinity of that sign if it doesn't.
+    fn push_to_extreme(&mut self, v: Variable, direction: f64) -> f64 {
+        /// Far beyond any realistic layout value, but small enough to solve with some precision.
+        const FAR: f64 = 1e12;
+        let unbounded = direction * f64::INFINITY;
+        // Suggest a value far away, and if the variable stops short of it, suggest a value just
+        // past where it stopped to find the bound precisely.
+        if self.suggest_value(v, direction * FAR).is_err() {
+            return unbounded;
+        }
+        let value = self.get_value(v);
+        if direction * value >= FAR / 2.0 || self.suggest_value(v, value + direction).is_err() {
+            return unbounded;
+        }
+        self.get_value(v)
+    }
+
+    /// The value of a constraint's expression in the current solution, with the current values of
+    /// its parameters.
+    fn evaluate(&self, constraint: &Constraint) -> f64 {
+        constraint
+            .terms()
+            .iter()
+            .fold(constraint.constant(), |value, term| {
+                let value_of = if term.variable.is_param() {
+                    self.params.get(&term.variable).copied().unwrap_or(0.0)
+                } else {
+                    self.get_value(term.variable)
+                };
+                value + term.coefficient * value_of
+            })
+    }
+
+    /// Set a hook that is called after every pivot of the simplex method.
+    ///
+    /// This is intended for debuggers and educational tools that want to visualize how the solver
+    /// reaches a solution. Replaces any previously set hook.
+    ///
+    /// ```
+    /// use kasuari::{Solver, Variable, WeightedRelation::*, Strength};
+    ///
+    /// let mut solver = Solver::new();
+    /// let pivots = std::rc::Rc::new(std::cell::Cell::new(0));
+    /// let counter = pivots.clone();
+    /// solver.set_pivot_hook(move |_| counter.set(counter.get() + 1));
+    ///
+    /// let x = Variable::new();
+    /// solver.add_constraint(x | EQ(Strength::WEAK) | 100.0).unwrap();
+    /// solver.add_constraint(x | LE(Strength::REQUIRED) | 50.0).unwrap();
+    /// assert!(pivots.get() > 0);
+    /// ```
+    pub fn set_pivot_hook(&mut self, hook: impl FnMut(&PivotEvent) + 'static) {
+        self.pivot_hook = Some(Box::new(hook));
+    }
+
+    /// Remove the hook set with [`Solver::set_pivot_hook`], if any.
+    pub fn clear_pivot_hook(&mut self) {
+        self.pivot_hook = None;
+    }
+
     /// Get the stored value for a variable.
     ///
     /// Normally values should be retrieved and updated using `fetch_changes`, but this method can
     /// be used for debugging or testing.
     pub fn get_value(&self, v: Variable) -> f64 {
+        self.try_value_of(v)
+            .unwrap_or_else(|| self.default_value(v))
+    }
+
+    /// The current value of a variable, or its [default](Solver::set_default_value) if the solver
+    /// doesn't know the variable.
+    ///
+    /// Unlike tracking [`Solver::fetch_changes`], this always reflects the current solution, so no
+    /// shadow copy of the values needs to be maintained.
+    pub fn value_of(&self, v: Variable) -> f64 {
+        self.get_value(v)
+    }
+
+    /// The complete current solution, ordered by variable.
+    ///
+    /// Contains every variable that a constraint in the solver refers to. Unlike
+    /// [`Solver::fetch_changes`], which only reports what changed since it was last called, this is
+    /// suitable for a consumer that needs the whole layout, e.g. one that was attached after the
+    /// solver was set up.
+    pub fn values(&self) -> BTreeMap<Variable, f64> {
+        self.iter_values().collect()
+    }
+
+    /// Iterate over every variable that a constraint in the solver refers to, together with its
+    /// current value, in no particular order.
+    pub fn iter_values(&self) -> impl Iterator<Item = (Variable, f64)> + '_ {
+        self.var_data
+            .iter()
+            .map(|(v, s)| (v, self.rows.get(&s.1).map_or(0.0, |r| r.constant)))
+    }
+
+    /// The value reported for a variable while no constraint in the solver refers to it, zero
+    /// unless set with [`Solver::set_default_value`].
+    pub fn default_value(&self, v: Variable) -> f64 {
+        self.defaults.get(&v).copied().unwrap_or(0.0)
+    }
+
+    /// Set the value reported for a variable while no constraint in the solver refers to it.
+    ///
+    /// Variables are zero until they are constrained, which is not always a sensible size or
+    /// position. The default is returned by [`Solver::get_value`] instead, and
+    /// [`Solver::fetch_changes`] reports it when it is set and whenever the last constraint
+    /// referring to the variable is removed. Once a constraint refers to the variable, its solved
+    /// value is reported as usual.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Solver, Strength, Variable};
+    ///
+    /// let mut solver = Solver::new();
+    /// let width = Variable::new();
+    /// solver.set_default_value(width, 80.0);
+    /// assert_eq!(solver.get_value(width), 80.0);
+    /// assert_eq!(solver.fetch_changes(), [(width, 80.0)]);
+    ///
+    /// let constraint: Constraint = width | EQ(Strength::REQUIRED) | 100.0;
+    /// solver.add_constraint(constraint.clone()).unwrap();
+    /// assert_eq!(solver.fetch_changes(), [(width, 100.0)]);
+    /// solver.remove_constraint(&constraint).unwrap();
+    /// assert_eq!(solver.fetch_changes(), [(width, 80.0)]);
+    /// ```
+    pub fn set_default_value(&mut self, v: Variable, value: f64) {
+        self.defaults.insert(v, value);
+        if !self.var_data.contains_key(&v) {
+            self.var_changed(v);
+        }
+    }
+
+    /// The current value of a variable, or `None` if no constraint in the solver refers to it.
+    pub fn try_value_of(&self, v: Variable) -> Option<f64> {
         self.var_data
             .get(&v)
-            .and_then(|s| self.rows.get(&s.1).map(|r| r.constant))
-            .unwrap_or(0.0)
+            .map(|s| self.rows.get(&s.1).map_or(0.0, |r| r.constant))
     }
 }
+
+/// The heap memory held by a hash map, assuming one control byte per entry.
+fn map_heap_size<K, V>(map: &HashMap<K, V>) -> usize {
+    map.capacity() * (mem::size_of::<(K, V)>() + 1)
+}
+
+fn vec_heap_size<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * mem::size_of::<T>()
+}
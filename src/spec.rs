@@ -0,0 +1,287 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use hashbrown::HashMap;
+use serde::Deserialize;
+
+use crate::WeightedRelation::*;
+use crate::{Axis, Constraint, Element, LayoutSpecError, Strength};
+
+/// A declarative description of a layout, which can be deserialized with serde and compiled into
+/// elements and constraints for the solver.
+///
+/// This gives applications that describe their user interface in configuration files a path from
+/// data to constraints without writing their own translator. Elements are referred to by name.
+///
+/// ```
+/// use kasuari::{LayoutSpec, Solver};
+///
+/// let spec: LayoutSpec = serde_json::from_str(
+///     r#"{
+///         "elements": [
+///             { "name": "window", "width": { "min": 300, "max": 300 } },
+///             { "name": "sidebar", "width": { "preferred": 100 } },
+///             { "name": "content", "width": { "min": 50 } }
+///         ],
+///         "relations": [
+///             { "kind": "inside", "element": "sidebar", "container": "window" },
+///             { "kind": "inside", "element": "content", "container": "window" },
+///             { "kind": "after", "element": "content", "anchor": "sidebar", "gap": 10 }
+///         ]
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// let layout = spec.compile().unwrap();
+/// let mut solver = Solver::new();
+/// solver.add_constraints(layout.constraints().iter().cloned()).unwrap();
+///
+/// let sidebar = layout.element("sidebar").unwrap();
+/// assert_eq!(solver.get_value(sidebar.right) - solver.get_value(sidebar.left), 100.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct LayoutSpec {
+    /// The elements of the layout. Each must have a unique name.
+    pub elements: Vec<ElementSpec>,
+    /// The relations between elements.
+    #[serde(default)]
+    pub relations: Vec<RelationSpec>,
+}
+
+/// A named element of a [`LayoutSpec`] and the constraints on its size.
+///
+/// Every element is required to have a non-negative width and height.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct ElementSpec {
+    /// The name that relations use to refer to the element.
+    pub name: String,
+    /// The constraints on the width of the element.
+    #[serde(default)]
+    pub width: SizeSpec,
+    /// The constraints on the height of the element.
+    #[serde(default)]
+    pub height: SizeSpec,
+}
+
+/// The constraints on the size of an element along one axis.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SizeSpec {
+    /// The size the element must be at least.
+    pub min: Option<f64>,
+    /// The size the element must be at most.
+    pub max: Option<f64>,
+    /// The size the element should be if the other constraints allow.
+    pub preferred: Option<f64>,
+    /// The priority of the preferred size. Defaults to weak.
+    #[serde(default = "Priority::weak")]
+    pub priority: Priority,
+}
+
+/// A relation between elements of a [`LayoutSpec`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RelationSpec {
+    /// `element` starts at least `gap` after `anchor` ends along `axis`.
+    After {
+        element: String,
+        anchor: String,
+        #[serde(default)]
+        gap: f64,
+        #[serde(default)]
+        axis: Axis,
+        #[serde(default)]
+        priority: Priority,
+    },
+    /// `element` lies within `container`, at least `padding` from each of its edges.
+    Inside {
+        element: String,
+        container: String,
+        #[serde(default)]
+        padding: f64,
+        #[serde(default)]
+        priority: Priority,
+    },
+    /// All of `elements` have the same size along `axis`.
+    SameSize {
+        elements: Vec<String>,
+        #[serde(default)]
+        axis: Axis,
+        #[serde(default)]
+        priority: Priority,
+    },
+}
+
+/// The strength with which a [`LayoutSpec`] constraint is enforced.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    /// [`Strength::REQUIRED`]
+    #[default]
+    Required,
+    /// [`Strength::STRONG`]
+    Strong,
+    /// [`Strength::MEDIUM`]
+    Medium,
+    /// [`Strength::WEAK`]
+    Weak,
+}
+
+impl Priority {
+    /// The strength corresponding to the priority.
+    pub fn strength(self) -> Strength {
+        match self {
+            Priority::Required => Strength::REQUIRED,
+            Priority::Strong => Strength::STRONG,
+            Priority::Medium => Strength::MEDIUM,
+            Priority::Weak => Strength::WEAK,
+        }
+    }
+
+    fn weak() -> Priority {
+        Priority::Weak
+    }
+}
+
+/// The elements and constraints produced by [`LayoutSpec::compile`].
+#[derive(Debug, Clone)]
+pub struct CompiledLayout {
+    elements: Vec<(String, Element)>,
+    index: HashMap<String, usize>,
+    constraints: Vec<Constraint>,
+}
+
+impl CompiledLayout {
+    /// The element with the given name, if the spec defined one.
+    pub fn element(&self, name: &str) -> Option<Element> {
+        self.index.get(name).map(|&i| self.elements[i].1)
+    }
+
+    /// The elements and their names, in the order they were defined in the spec.
+    pub fn elements(&self) -> impl Iterator<Item = (&str, Element)> {
+        self.elements
+            .iter()
+            .map(|(name, element)| (name.as_str(), *element))
+    }
+
+    /// The constraints of the layout, ready to be added to a solver.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Consume the layout, returning its constraints.
+    pub fn into_constraints(self) -> Vec<Constraint> {
+        self.constraints
+    }
+
+    fn get(&self, name: &str) -> Result<Element, LayoutSpecError> {
+        self.element(name)
+            .ok_or_else(|| LayoutSpecError::UnknownElement(name.into()))
+    }
+}
+
+impl LayoutSpec {
+    /// Create a new element for each element of the spec and the constraints between them.
+    ///
+    /// Fails if two elements have the same name, or if a relation refers to an element that is not
+    /// defined.
+    pub fn compile(&self) -> Result<CompiledLayout, LayoutSpecError> {
+        let mut layout = CompiledLayout {
+            elements: Vec::with_capacity(self.elements.len()),
+            index: HashMap::with_capacity(self.elements.len()),
+            constraints: Vec::new(),
+        };
+        for spec in &self.elements {
+            if layout.index.contains_key(&spec.name) {
+                return Err(LayoutSpecError::DuplicateElement(spec.name.clone()));
+            }
+            let element = Element::new();
+            layout
+                .index
+                .insert(spec.name.clone(), layout.elements.len());
+            layout.elements.push((spec.name.clone(), element));
+            for (axis, size) in [
+                (Axis::Horizontal, &spec.width),
+                (Axis::Vertical, &spec.height),
+            ] {
+                size.compile(element, axis, &mut layout.constraints);
+            }
+        }
+        for relation in &self.relations {
+            match relation {
+                RelationSpec::After {
+                    element,
+                    anchor,
+                    gap,
+                    axis,
+                    priority,
+                } => {
+                    let element = layout.get(element)?;
+                    let anchor = layout.get(anchor)?;
+                    layout.constraints.extend(element.after_along(
+                        &anchor,
+                        *axis,
+                        *gap,
+                        priority.strength(),
+                    ));
+                }
+                RelationSpec::Inside {
+                    element,
+                    container,
+                    padding,
+                    priority,
+                } => {
+                    let element = layout.get(element)?;
+                    let container = layout.get(container)?;
+                    layout.constraints.extend(element.inside(
+                        &container,
+                        *padding,
+                        priority.strength(),
+                    ));
+                }
+                RelationSpec::SameSize {
+                    elements,
+                    axis,
+                    priority,
+                } => {
+                    let elements = elements
+                        .iter()
+                        .map(|name| layout.get(name))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    layout.constraints.extend(Element::same_size(
+                        &elements,
+                        *axis,
+                        priority.strength(),
+                    ));
+                }
+            }
+        }
+        Ok(layout)
+    }
+}
+
+impl Default for SizeSpec {
+    fn default() -> SizeSpec {
+        SizeSpec {
+            min: None,
+            max: None,
+            preferred: None,
+            priority: Priority::Weak,
+        }
+    }
+}
+
+impl SizeSpec {
+    fn compile(&self, element: Element, axis: Axis, constraints: &mut Vec<Constraint>) {
+        let size = element.size(axis);
+        constraints.push(size.clone() | GE(Strength::REQUIRED) | 0.0);
+        if let Some(min) = self.min {
+            constraints.push(size.clone() | GE(Strength::REQUIRED) | min);
+        }
+        if let Some(max) = self.max {
+            constraints.push(size.clone() | LE(Strength::REQUIRED) | max);
+        }
+        if let Some(preferred) = self.preferred {
+            constraints.push(size | EQ(self.priority.strength()) | preferred);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::row::Symbol;
+#[cfg(feature = "serde")]
+use crate::Solver;
+use crate::{Constraint, RelationalOperator, Variable};
+
+/// The state of a solver as plain data, created by [`Solver::state`] and turned back into a solver
+/// by [`Solver::from_state`].
+///
+/// A state holds the constraints of the solver, its names, pins, bounds, stays, conditions,
+/// parameters, defaults and edit variables with their suggested values, and optionally the solved
+/// tableau. It can be encoded with [`SolverState::to_bytes`], or serialized with the `serde`
+/// feature, so that an application can hibernate its layout engine and restore it later. A state
+/// without the tableau is smaller and doesn't depend on the solver's internals, but restoring it
+/// solves the constraints again.
+///
+/// Every constraint is stored once and referred to by its index, so the constraints of the
+/// restored solver are shared between its names, pins and bounds like in the original. A state
+/// holds the constraints of the solver it was captured from, but a deserialized state holds new
+/// constraints, distinct from the serialized ones: look them up with [`Solver::named`] or
+/// [`Solver::constraints_for`].
+///
+/// [`Solver::state`]: crate::Solver::state
+/// [`Solver::from_state`]: crate::Solver::from_state
+/// [`Solver::named`]: crate::Solver::named
+/// [`Solver::constraints_for`]: crate::Solver::constraints_for
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverState {
+    pub(crate) merge_parallel: bool,
+    pub(crate) strict_epsilon: f64,
+    /// The namespace of the solver's variable counter.
+    pub(crate) namespace: u16,
+    /// The number of variables created by the solver's variable counter.
+    pub(crate) created_variables: u64,
+    pub(crate) conditions: Vec<u64>,
+    pub(crate) params: Vec<(Variable, f64)>,
+    pub(crate) defaults: Vec<(Variable, f64)>,
+    /// Every constraint of the solver, in the order they were added.
+    pub(crate) constraints: Vec<Constraint>,
+    pub(crate) named: Vec<(String, usize)>,
+    pub(crate) pins: Vec<(Variable, usize)>,
+    pub(crate) bounds: Vec<(Variable, RelationalOperator, usize)>,
+    pub(crate) stays: Vec<(Variable, usize, f64)>,
+    /// The disabled constraints, with the weight taken out of the objective for those that are not
+    /// required.
+    pub(crate) disabled: Vec<(usize, Option<f64>)>,
+    pub(crate) edits: Vec<EditState>,
+    pub(crate) tableau: Option<TableauState>,
+}
+
+/// An edit variable, its constraint and its suggested value.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct EditState {
+    pub(crate) variable: Variable,
+    pub(crate) constraint: usize,
+    pub(crate) constant: f64,
+}
+
+/// The solved tableau, with constraints referred to by their index in the state.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct TableauState {
+    /// The marker and other symbol of each constraint in the tableau.
+    pub(crate) tags: Vec<(usize, Symbol, Symbol)>,
+    /// The constraints that parallel constraints are merged into.
+    pub(crate) parallel: Vec<usize>,
+    /// The constraints merged into each such constraint, and their total weight.
+    pub(crate) groups: Vec<(usize, Vec<usize>, f64)>,
+    /// The constraint each merged constraint is merged into, and the weight it adds.
+    pub(crate) merged: Vec<(usize, usize, f64)>,
+    /// The symbol of each variable and the number of constraints referring to it.
+    pub(crate) variables: Vec<(Variable, Symbol, usize)>,
+    pub(crate) rows: Vec<(Symbol, RowState)>,
+    pub(crate) objective: RowState,
+    pub(crate) infeasible_rows: Vec<Symbol>,
+    pub(crate) id_tick: usize,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct RowState {
+    pub(crate) constant: f64,
+    pub(crate) cells: Vec<(Symbol, f64)>,
+}
+
+impl SolverState {
+    /// Whether the state holds the solved tableau, so that restoring it doesn't solve the
+    /// constraints again.
+    pub fn has_tableau(&self) -> bool {
+        self.tableau.is_some()
+    }
+
+    /// The constraints of the state, in the order they were added to the solver.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+}
+
+/// Serializes the [state](Solver::state) of the solver, including the tableau.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Solver {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.state(true), serializer)
+    }
+}
+
+/// Deserializes a solver [from its state](Solver::from_state).
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Solver {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state: SolverState = serde::Deserialize::deserialize(deserializer)?;
+        Solver::from_state(state).map_err(serde::de::Error::custom)
+    }
+}
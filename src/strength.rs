@@ -20,9 +20,12 @@
 //! strongest first. This behaviour can be used (for example) to provide a "default" value for a
 //! variable should no other stronger constraints be put upon it.
 
-use core::ops;
+use alloc::vec::Vec;
+use core::{fmt, ops};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Strength(f64);
 
 impl Strength {
@@ -184,8 +187,170 @@ impl core::cmp::PartialOrd for Strength {
 
 impl core::cmp::Eq for Strength {}
 
+impl fmt::Display for Strength {
+    /// Formats the strength constants by name, e.g. `STRONG`, and other strengths as multiples of
+    /// the strong, medium and weak strengths they are made of, e.g. `STRONG*2 + WEAK*5`, like the
+    /// arguments of [`Strength::create`].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Strength::REQUIRED => return f.write_str("REQUIRED"),
+            Strength::ZERO => return f.write_str("ZERO"),
+            _ => {}
+        }
+        // Whole multiples of the stronger tiers, leaving the rest to the weak tier.
+        let strong = (self.0 / Strength::STRONG.0) as u64 as f64;
+        let rest = self.0 - strong * Strength::STRONG.0;
+        let medium = (rest / Strength::MEDIUM.0) as u64 as f64;
+        let weak = rest - medium * Strength::MEDIUM.0;
+        let tiers = [("STRONG", strong), ("MEDIUM", medium), ("WEAK", weak)];
+        let mut first = true;
+        for (name, multiple) in tiers.into_iter().filter(|&(_, multiple)| multiple != 0.0) {
+            if !first {
+                f.write_str(" + ")?;
+            }
+            first = false;
+            f.write_str(name)?;
+            if multiple != 1.0 {
+                write!(f, "*{multiple}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Strength({self})")
+    }
+}
+
+/// Deserializes from the value of the strength, clamped like [`Strength::new`].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Strength {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <f64 as serde::Deserialize>::deserialize(deserializer).map(Strength::new)
+    }
+}
+
+/// Strengths computed from an ordering of constraint groups, strongest group first.
+///
+/// Picking strengths by hand that interact correctly is error prone: a group of many weak
+/// constraints can outweigh a single stronger one if their strengths are too close together.
+/// `StrengthLevels` assigns each group a strength that outweighs the next weaker group combined by
+/// the same factor of 1000 used between [`Strength::WEAK`], [`Strength::MEDIUM`] and
+/// [`Strength::STRONG`], and so outweighs all of the weaker groups combined. If the groups do not
+/// fit below [`Strength::REQUIRED`] with that separation, the separation is reduced until they
+/// do, and [`StrengthLevels::fits`] reports whether the result is still safe.
+///
+/// ```
+/// use kasuari::StrengthLevels;
+///
+/// // one constraint that must beat three, which must beat ten
+/// let levels = StrengthLevels::new([1, 3, 10]);
+/// assert!(levels.fits());
+/// assert!(levels[0] > levels[1] * 3.0 && levels[1] > levels[2] * 10.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrengthLevels {
+    strengths: Vec<Strength>,
+    separation: f64,
+}
+
+impl StrengthLevels {
+    /// The factor by which each level should outweigh the combined constraints of the next weaker
+    /// level.
+    pub const SEPARATION: f64 = 1000.0;
+
+    /// The smallest separation for which [`StrengthLevels::fits`] is true. Below this, violations
+    /// of weaker constraints that are much larger than violations of stronger constraints may
+    /// win out over them.
+    pub const MIN_SEPARATION: f64 = 10.0;
+
+    /// The strongest strength that is assigned to a level, which is the strongest strength that
+    /// [`Strength::create`] can produce without reaching [`Strength::REQUIRED`].
+    const MAX: f64 = 1000.0 * Strength::STRONG.value();
+
+    /// Compute strengths for groups of constraints, given the number of constraints in each group
+    /// ordered from the strongest group to the weakest. Empty groups are treated as groups of one.
+    pub fn new<I: IntoIterator<Item = usize>>(group_sizes: I) -> StrengthLevels {
+        let sizes: Vec<f64> = group_sizes
+            .into_iter()
+            .map(|size| size.max(1) as f64)
+            .collect();
+        let mut separation = Self::SEPARATION;
+        if Self::strongest(&sizes, separation) > Self::MAX {
+            // the strongest level grows monotonically with the separation, so search for the
+            // largest separation that still fits
+            let mut low = 0.0;
+            let mut high = separation;
+            for _ in 0..64 {
+                let mid = (low + high) / 2.0;
+                if Self::strongest(&sizes, mid) > Self::MAX {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+            separation = low;
+        }
+        let mut strengths = Self::levels(&sizes, separation);
+        strengths.reverse();
+        StrengthLevels {
+            strengths,
+            separation,
+        }
+    }
+
+    /// The strengths of each level, in the order the groups were given.
+    pub fn strengths(&self) -> &[Strength] {
+        &self.strengths
+    }
+
+    /// The factor by which each level outweighs the combined constraints of the next weaker level.
+    pub fn separation(&self) -> f64 {
+        self.separation
+    }
+
+    /// Whether the levels are separated by at least [`StrengthLevels::MIN_SEPARATION`]. If not, the
+    /// requested ordering does not fit in the range of non-required strengths and the solver may
+    /// not respect it.
+    pub fn fits(&self) -> bool {
+        self.separation >= Self::MIN_SEPARATION
+    }
+
+    /// The strengths of each level from weakest to strongest. The weakest level is
+    /// [`Strength::WEAK`].
+    fn levels(sizes: &[f64], separation: f64) -> Vec<Strength> {
+        let mut strengths = Vec::with_capacity(sizes.len());
+        let mut weaker_total = Strength::WEAK.value() / separation;
+        for size in sizes.iter().rev() {
+            let strength = separation * weaker_total;
+            weaker_total = size * strength;
+            strengths.push(Strength(strength));
+        }
+        strengths
+    }
+
+    fn strongest(sizes: &[f64], separation: f64) -> f64 {
+        Self::levels(sizes, separation)
+            .last()
+            .map_or(0.0, |strength| strength.value())
+    }
+}
+
+impl ops::Index<usize> for StrengthLevels {
+    type Output = Strength;
+
+    fn index(&self, level: usize) -> &Strength {
+        &self.strengths[level]
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
     use rstest::rstest;
 
     use super::*;
@@ -319,4 +484,63 @@ mod tests {
         result *= rhs;
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case::empty(&[], &[], true)]
+    #[case::single(&[5], &[Strength::WEAK], true)]
+    #[case::standard(
+        &[1, 1, 1, 1],
+        &[1000.0 * Strength::STRONG, Strength::STRONG, Strength::MEDIUM, Strength::WEAK],
+        true
+    )]
+    #[case::group_sizes(&[1, 2, 3], &[Strength::new(6_000_000.0), Strength::new(3000.0), Strength::WEAK], true)]
+    #[case::empty_group(&[1, 0], &[Strength::MEDIUM, Strength::WEAK], true)]
+    fn strength_levels(#[case] sizes: &[usize], #[case] expected: &[Strength], #[case] fits: bool) {
+        let levels = StrengthLevels::new(sizes.iter().copied());
+        assert_eq!(levels.strengths(), expected);
+        assert_eq!(levels.fits(), fits);
+    }
+
+    #[rstest]
+    #[case::compressed(&[1; 6], true)]
+    #[case::too_many_levels(&[1; 12], false)]
+    #[case::too_many_constraints(&[1, 1_000_000, 1_000_000, 1_000_000], false)]
+    fn strength_levels_compressed(#[case] sizes: &[usize], #[case] fits: bool) {
+        let levels = StrengthLevels::new(sizes.iter().copied());
+        assert!(levels.separation() < StrengthLevels::SEPARATION);
+        assert_eq!(levels.fits(), fits);
+        assert!(levels[0] < Strength::REQUIRED);
+        if !fits {
+            return;
+        }
+        for (i, size) in sizes.iter().enumerate().skip(1) {
+            let weaker: f64 = levels.strengths()[i..]
+                .iter()
+                .zip(&sizes[i..])
+                .map(|(strength, size)| strength.value() * *size as f64)
+                .sum();
+            assert!(levels[i - 1].value() >= weaker, "level {i} of size {size}");
+        }
+    }
+
+    #[rstest]
+    #[case::required(Strength::REQUIRED, "REQUIRED")]
+    #[case::strong(Strength::STRONG, "STRONG")]
+    #[case::weak(Strength::WEAK, "WEAK")]
+    #[case::zero(Strength::ZERO, "ZERO")]
+    #[case::combined(Strength::create(2.0, 0.0, 5.0, 1.0), "STRONG*2 + WEAK*5")]
+    #[case::all_tiers(Strength::create(1.0, 1.0, 1.0, 1.0), "STRONG + MEDIUM + WEAK")]
+    #[case::fraction(Strength::new(0.5), "WEAK*0.5")]
+    #[case::carry(Strength::new(1_500_000.0), "STRONG + MEDIUM*500")]
+    fn display(#[case] strength: Strength, #[case] expected: &str) {
+        assert_eq!(strength.to_string(), expected);
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", Strength::MEDIUM * 3.0),
+            "Strength(MEDIUM*3)"
+        );
+    }
 }
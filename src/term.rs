@@ -1,7 +1,7 @@
 use alloc::vec;
 use core::ops;
 
-use crate::{Expression, Variable};
+use crate::{ConstraintBuilder, Expression, Variable};
 
 /// A variable and a coefficient to multiply that variable by.
 ///
@@ -11,6 +11,7 @@ use crate::{Expression, Variable};
 /// term = coefficient * variable
 /// ```
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Term {
     pub variable: Variable,
     pub coefficient: f64,
@@ -31,6 +32,23 @@ impl Term {
     pub const fn from_variable(variable: Variable) -> Term {
         Term::new(variable, 1.0)
     }
+
+    /// Start a constraint that this term equals an expression, see [`Expression::equals`].
+    pub fn equals(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        Expression::from(self).equals(rhs)
+    }
+
+    /// Start a constraint that this term is less than or equal to an expression, see
+    /// [`Expression::equals`].
+    pub fn leq(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        Expression::from(self).leq(rhs)
+    }
+
+    /// Start a constraint that this term is greater than or equal to an expression, see
+    /// [`Expression::equals`].
+    pub fn geq(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        Expression::from(self).geq(rhs)
+    }
 }
 
 impl From<Variable> for Term {
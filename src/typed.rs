@@ -0,0 +1,414 @@
+use core::marker::PhantomData;
+use core::{fmt, ops};
+
+use crate::{Constraint, Expression, PartialConstraint, Variable, WeightedRelation};
+
+/// A variable of a dimension, such as a horizontal position or a width, that can only be combined
+/// with variables and expressions of the same dimension.
+///
+/// Dimensions are marker types defined by the application, e.g. `enum Width {}`. Adding a
+/// vertical position to a width is then a compile time error rather than a layout bug. Typed
+/// variables become plain [`Variable`]s and [`Expression`]s at the solver boundary: a constraint
+/// between two expressions of the same dimension is an ordinary [`Constraint`], and values are
+/// read with the [variable](TypedVariable::variable).
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{Constraint, Solver, Strength, TypedVariable};
+///
+/// enum X {}
+/// enum Y {}
+///
+/// let (left, right) = (TypedVariable::<X>::new(), TypedVariable::<X>::new());
+/// let top = TypedVariable::<Y>::new();
+/// let mut solver = Solver::new();
+/// solver.add_constraint(left | EQ(Strength::REQUIRED) | 10.0).unwrap();
+/// solver.add_constraint(right | EQ(Strength::REQUIRED) | (left + 20.0)).unwrap();
+/// solver.add_constraint(top | EQ(Strength::REQUIRED) | 5.0).unwrap();
+/// assert_eq!(solver.get_value(right.variable()), 30.0);
+/// ```
+///
+/// Mixing dimensions does not compile:
+///
+/// ```compile_fail
+/// use kasuari::TypedVariable;
+///
+/// enum X {}
+/// enum Y {}
+///
+/// let mixed = TypedVariable::<X>::new() + TypedVariable::<Y>::new();
+/// ```
+pub struct TypedVariable<D> {
+    variable: Variable,
+    dimension: PhantomData<fn() -> D>,
+}
+
+/// An expression of a dimension, the result of arithmetic on [`TypedVariable`]s.
+pub struct TypedExpression<D> {
+    expression: Expression,
+    dimension: PhantomData<fn() -> D>,
+}
+
+/// This is an intermediate type used in the syntactic sugar for specifying constraints between
+/// [`TypedVariable`]s. You should not use it directly.
+pub struct TypedPartialConstraint<D> {
+    partial: PartialConstraint,
+    dimension: PhantomData<fn() -> D>,
+}
+
+impl<D> TypedVariable<D> {
+    /// Produces a new unique variable of the dimension.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_variable(Variable::new())
+    }
+
+    /// Treat a plain variable as a variable of the dimension.
+    #[inline]
+    pub const fn from_variable(variable: Variable) -> Self {
+        TypedVariable {
+            variable,
+            dimension: PhantomData,
+        }
+    }
+
+    /// The plain variable, e.g. to read its value from a solver.
+    #[inline]
+    pub const fn variable(&self) -> Variable {
+        self.variable
+    }
+}
+
+impl<D> TypedExpression<D> {
+    /// Treat a plain expression as an expression of the dimension.
+    #[inline]
+    pub const fn from_expression(expression: Expression) -> Self {
+        TypedExpression {
+            expression,
+            dimension: PhantomData,
+        }
+    }
+
+    /// The plain expression.
+    #[inline]
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// The plain expression, consuming this one.
+    #[inline]
+    pub fn into_expression(self) -> Expression {
+        self.expression
+    }
+}
+
+impl<D> Default for TypedVariable<D> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> Clone for TypedVariable<D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for TypedVariable<D> {}
+
+impl<D> PartialEq for TypedVariable<D> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.variable == other.variable
+    }
+}
+
+impl<D> Eq for TypedVariable<D> {}
+
+impl<D> core::hash::Hash for TypedVariable<D> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.variable.hash(state);
+    }
+}
+
+impl<D> fmt::Debug for TypedVariable<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedVariable")
+            .field(&self.variable)
+            .finish()
+    }
+}
+
+impl<D> Clone for TypedExpression<D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::from_expression(self.expression.clone())
+    }
+}
+
+impl<D> PartialEq for TypedExpression<D> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.expression == other.expression
+    }
+}
+
+impl<D> fmt::Debug for TypedExpression<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedExpression")
+            .field(&self.expression)
+            .finish()
+    }
+}
+
+impl<D> From<TypedVariable<D>> for Variable {
+    #[inline]
+    fn from(variable: TypedVariable<D>) -> Variable {
+        variable.variable
+    }
+}
+
+impl<D> From<TypedVariable<D>> for TypedExpression<D> {
+    #[inline]
+    fn from(variable: TypedVariable<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(Expression::from_variable(variable.variable))
+    }
+}
+
+impl<D> From<TypedExpression<D>> for Expression {
+    #[inline]
+    fn from(expression: TypedExpression<D>) -> Expression {
+        expression.expression
+    }
+}
+
+impl<D> ops::Add<TypedExpression<D>> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn add(self, rhs: TypedExpression<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression + rhs.expression)
+    }
+}
+
+impl<D> ops::Add<TypedVariable<D>> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn add(self, rhs: TypedVariable<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression + rhs.variable)
+    }
+}
+
+impl<D> ops::Add<TypedExpression<D>> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn add(self, rhs: TypedExpression<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.variable + rhs.expression)
+    }
+}
+
+impl<D> ops::Add<TypedVariable<D>> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn add(self, rhs: TypedVariable<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.variable + rhs.variable)
+    }
+}
+
+impl<D> ops::Add<f64> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn add(self, constant: f64) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression + constant)
+    }
+}
+
+impl<D> ops::Add<f64> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn add(self, constant: f64) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.variable + constant)
+    }
+}
+
+impl<D> ops::Sub<TypedExpression<D>> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn sub(self, rhs: TypedExpression<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression - rhs.expression)
+    }
+}
+
+impl<D> ops::Sub<TypedVariable<D>> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn sub(self, rhs: TypedVariable<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression - rhs.variable)
+    }
+}
+
+impl<D> ops::Sub<TypedExpression<D>> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn sub(self, rhs: TypedExpression<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.variable - rhs.expression)
+    }
+}
+
+impl<D> ops::Sub<TypedVariable<D>> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn sub(self, rhs: TypedVariable<D>) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.variable - rhs.variable)
+    }
+}
+
+impl<D> ops::Sub<f64> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn sub(self, constant: f64) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression - constant)
+    }
+}
+
+impl<D> ops::Sub<f64> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn sub(self, constant: f64) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.variable - constant)
+    }
+}
+
+impl<D> ops::Mul<f64> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn mul(self, coefficient: f64) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression * coefficient)
+    }
+}
+
+impl<D> ops::Mul<f64> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn mul(self, coefficient: f64) -> TypedExpression<D> {
+        TypedExpression::from(self) * coefficient
+    }
+}
+
+impl<D> ops::Mul<TypedExpression<D>> for f64 {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn mul(self, expression: TypedExpression<D>) -> TypedExpression<D> {
+        expression * self
+    }
+}
+
+impl<D> ops::Mul<TypedVariable<D>> for f64 {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn mul(self, variable: TypedVariable<D>) -> TypedExpression<D> {
+        variable * self
+    }
+}
+
+impl<D> ops::Div<f64> for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn div(self, divisor: f64) -> TypedExpression<D> {
+        TypedExpression::from_expression(self.expression / divisor)
+    }
+}
+
+impl<D> ops::Div<f64> for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn div(self, divisor: f64) -> TypedExpression<D> {
+        TypedExpression::from(self) / divisor
+    }
+}
+
+impl<D> ops::Neg for TypedExpression<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn neg(self) -> TypedExpression<D> {
+        TypedExpression::from_expression(-self.expression)
+    }
+}
+
+impl<D> ops::Neg for TypedVariable<D> {
+    type Output = TypedExpression<D>;
+
+    #[inline]
+    fn neg(self) -> TypedExpression<D> {
+        -TypedExpression::from(self)
+    }
+}
+
+impl<D> ops::BitOr<WeightedRelation> for TypedExpression<D> {
+    type Output = TypedPartialConstraint<D>;
+
+    #[inline]
+    fn bitor(self, relation: WeightedRelation) -> TypedPartialConstraint<D> {
+        TypedPartialConstraint {
+            partial: PartialConstraint::new(self.expression, relation),
+            dimension: PhantomData,
+        }
+    }
+}
+
+impl<D> ops::BitOr<WeightedRelation> for TypedVariable<D> {
+    type Output = TypedPartialConstraint<D>;
+
+    #[inline]
+    fn bitor(self, relation: WeightedRelation) -> TypedPartialConstraint<D> {
+        TypedExpression::from(self) | relation
+    }
+}
+
+impl<D> ops::BitOr<TypedExpression<D>> for TypedPartialConstraint<D> {
+    type Output = Constraint;
+
+    #[inline]
+    fn bitor(self, rhs: TypedExpression<D>) -> Constraint {
+        self.partial | rhs.expression
+    }
+}
+
+impl<D> ops::BitOr<TypedVariable<D>> for TypedPartialConstraint<D> {
+    type Output = Constraint;
+
+    #[inline]
+    fn bitor(self, rhs: TypedVariable<D>) -> Constraint {
+        self.partial | rhs.variable
+    }
+}
+
+impl<D> ops::BitOr<f64> for TypedPartialConstraint<D> {
+    type Output = Constraint;
+
+    #[inline]
+    fn bitor(self, rhs: f64) -> Constraint {
+        self.partial | rhs
+    }
+}
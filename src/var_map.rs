@@ -0,0 +1,164 @@
+use alloc::vec::Vec;
+use core::{mem, ops};
+
+use hashbrown::HashMap;
+
+use crate::Variable;
+
+/// How far past twice its slots a map still grows to give a variable a slot.
+///
+/// Variables created elsewhere, e.g. with [`Variable::from_id`], can have any index, so the slots
+/// only grow gradually, and variables far beyond them are kept in the hash map until they do.
+const MIN_GROWTH: usize = 64;
+
+/// A map from variables to values that keeps the variables of one
+/// [`VariableCounter`](crate::VariableCounter) namespace in slots indexed by their counter, and
+/// the others in a hash map.
+///
+/// The variables of a solver's own counter are created densely, see
+/// [`Solver::new_variable`](crate::Solver::new_variable), so looking them up is an index instead
+/// of a hash.
+#[derive(Debug, Clone)]
+pub(crate) struct VarMap<T> {
+    namespace: u16,
+    slots: Vec<Option<T>>,
+    others: HashMap<Variable, T>,
+}
+
+impl<T> VarMap<T> {
+    /// An empty map with slots for the variables of a namespace.
+    pub(crate) fn new(namespace: u16) -> VarMap<T> {
+        VarMap {
+            namespace,
+            slots: Vec::new(),
+            others: HashMap::new(),
+        }
+    }
+
+    /// The slot of a variable, if it has one.
+    fn slot(&self, v: Variable) -> Option<usize> {
+        v.index_in(self.namespace)
+            .filter(|&index| index < self.slots.len())
+    }
+
+    pub(crate) fn get(&self, v: &Variable) -> Option<&T> {
+        match self.slot(*v) {
+            Some(slot) => self.slots[slot].as_ref(),
+            None => self.others.get(v),
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, v: &Variable) -> Option<&mut T> {
+        match self.slot(*v) {
+            Some(slot) => self.slots[slot].as_mut(),
+            None => self.others.get_mut(v),
+        }
+    }
+
+    pub(crate) fn contains_key(&self, v: &Variable) -> bool {
+        self.get(v).is_some()
+    }
+
+    /// Insert a value for a variable, returning the previous one.
+    pub(crate) fn insert(&mut self, v: Variable, value: T) -> Option<T> {
+        self.grow_for(v);
+        match self.slot(v) {
+            Some(slot) => self.slots[slot].replace(value),
+            None => self.others.insert(v, value),
+        }
+    }
+
+    pub(crate) fn remove(&mut self, v: &Variable) -> Option<T> {
+        match self.slot(*v) {
+            Some(slot) => self.slots[slot].take(),
+            None => self.others.remove(v),
+        }
+    }
+
+    /// The value of a variable, inserting the result of `default` if there is none.
+    pub(crate) fn get_or_insert_with(
+        &mut self,
+        v: Variable,
+        default: impl FnOnce() -> T,
+    ) -> &mut T {
+        if !self.contains_key(&v) {
+            self.insert(v, default());
+        }
+        self.get_mut(&v).unwrap()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.slots.clear();
+        self.others.clear();
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Variable, &T)> + '_ {
+        let namespace = self.namespace;
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, value)| {
+                Some((Variable::from_index(namespace, index), value.as_ref()?))
+            })
+            .chain(self.others.iter().map(|(&v, value)| (v, value)))
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (Variable, &mut T)> + '_ {
+        let namespace = self.namespace;
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(move |(index, value)| {
+                Some((Variable::from_index(namespace, index), value.as_mut()?))
+            })
+            .chain(self.others.iter_mut().map(|(&v, value)| (v, value)))
+    }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = Variable> + '_ {
+        self.iter().map(|(v, _)| v)
+    }
+
+    /// The number of values the map can hold without allocating, for the allocation statistics.
+    #[cfg(feature = "alloc-stats")]
+    pub(crate) fn capacity(&self) -> usize {
+        self.slots.capacity() + self.others.capacity()
+    }
+
+    /// The bytes allocated by the map, estimated like the other maps of the solver.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.slots.capacity() * mem::size_of::<Option<T>>()
+            + self.others.capacity() * (mem::size_of::<(Variable, T)>() + 1)
+    }
+
+    /// Add slots up to a variable of the namespace, unless it's far beyond the slots there are.
+    ///
+    /// Values of variables that get a slot are moved over from the hash map.
+    fn grow_for(&mut self, v: Variable) {
+        let Some(index) = v.index_in(self.namespace) else {
+            return;
+        };
+        if index < self.slots.len() || index > self.slots.len() * 2 + MIN_GROWTH {
+            return;
+        }
+        let len = (index + 1).max(self.slots.len() * 2);
+        self.slots.resize_with(len, || None);
+        if !self.others.is_empty() {
+            for (v, value) in mem::take(&mut self.others) {
+                match self.slot(v) {
+                    Some(slot) => self.slots[slot] = Some(value),
+                    None => {
+                        self.others.insert(v, value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> ops::Index<&Variable> for VarMap<T> {
+    type Output = T;
+
+    fn index(&self, v: &Variable) -> &T {
+        self.get(v).expect("no value for the variable")
+    }
+}
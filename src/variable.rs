@@ -1,30 +1,363 @@
-use core::ops;
+#[cfg(all(not(feature = "portable-atomic"), target_has_atomic = "64"))]
+use core::sync::atomic::AtomicU64;
+#[cfg(all(not(feature = "portable-atomic"), not(target_has_atomic = "64")))]
+use core::sync::atomic::AtomicUsize;
 #[cfg(not(feature = "portable-atomic"))]
-use core::sync::atomic::{AtomicUsize, Ordering};
+use core::sync::atomic::Ordering;
+use core::{fmt, ops};
 
+#[cfg(feature = "variable-names")]
+use std::string::String;
+#[cfg(feature = "variable-names")]
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+#[cfg(feature = "variable-names")]
+use hashbrown::HashMap;
 #[cfg(feature = "portable-atomic")]
-use portable_atomic::{AtomicUsize, Ordering};
+use portable_atomic::{AtomicU64, Ordering};
 
-use crate::{Expression, Term};
+use crate::{ConstraintBuilder, Expression, Term};
 
 /// Identifies a variable for the constraint solver.
 /// Each new variable is unique in the view of the solver, but copying or cloning the variable
 /// produces a copy of the same variable.
+///
+/// Ids are 64 bits wide on all targets, so that creating variables never wraps around to ids that
+/// are still in use.
 #[derive(Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Debug)]
-pub struct Variable(usize);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Variable(u64);
+
+#[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+static VARIABLE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Take the next id from the process-wide counter.
+#[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+fn next_id() -> u64 {
+    VARIABLE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Advance the process-wide counter to at least `next`.
+#[cfg(any(feature = "portable-atomic", target_has_atomic = "64"))]
+fn reserve_id(next: u64) {
+    VARIABLE_ID.fetch_max(next, Ordering::Relaxed);
+}
+
+/// Targets without 64 bit atomics count in a `usize`, which is checked instead of wrapping around.
+#[cfg(not(any(feature = "portable-atomic", target_has_atomic = "64")))]
+static VARIABLE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Take the next id from the process-wide counter.
+#[cfg(not(any(feature = "portable-atomic", target_has_atomic = "64")))]
+fn next_id() -> u64 {
+    VARIABLE_ID
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| id.checked_add(1))
+        .expect("ran out of variable ids") as u64
+}
+
+/// Advance the process-wide counter to at least `next`.
+#[cfg(not(any(feature = "portable-atomic", target_has_atomic = "64")))]
+fn reserve_id(next: u64) {
+    let next = usize::try_from(next).unwrap_or(usize::MAX);
+    VARIABLE_ID.fetch_max(next, Ordering::Relaxed);
+}
+
+/// Set in the ids of the variables that stand for [`Param`](crate::Param)s.
+const PARAM_BIT: u64 = 1 << 63;
+
+/// Set in the ids of the variables created by a [`VariableCounter`].
+const SOLVER_BIT: u64 = 1 << 62;
+
+/// The position of the namespace of the counter in the ids of the variables created by a
+/// [`VariableCounter`]. The bits below count the variables of the counter.
+const NAMESPACE_SHIFT: u32 = 32;
+
+/// The bits of the ids of the variables created by a [`VariableCounter`] that count them.
+const INDEX_MASK: u64 = (1 << NAMESPACE_SHIFT) - 1;
+
+/// The names given to variables with [`Variable::named`] and [`Variable::set_name`].
+#[cfg(feature = "variable-names")]
+fn names() -> &'static RwLock<HashMap<Variable, String>> {
+    static NAMES: OnceLock<RwLock<HashMap<Variable, String>>> = OnceLock::new();
+    NAMES.get_or_init(RwLock::default)
+}
 
 impl Variable {
     /// Produces a new unique variable for use in constraint solving.
+    ///
+    /// Variables are counted by a process-wide atomic counter. Single-threaded programs can avoid
+    /// it with a [`VariableCounter`].
     #[inline]
     pub fn new() -> Self {
-        static VARIABLE_ID: AtomicUsize = AtomicUsize::new(0);
-        Self(VARIABLE_ID.fetch_add(1, Ordering::Relaxed))
+        Self(next_id())
     }
 
-    #[cfg(test)]
-    pub(crate) const fn from_id(id: usize) -> Self {
+    /// Produces a new unique variable that stands for a parameter.
+    pub(crate) fn new_param() -> Self {
+        Self(next_id() | PARAM_BIT)
+    }
+
+    /// Whether the variable stands for a parameter, and is not solved for.
+    #[inline]
+    pub(crate) const fn is_param(&self) -> bool {
+        self.0 & PARAM_BIT != 0
+    }
+
+    /// The variable with the given id, as returned by [`Variable::id`].
+    ///
+    /// This round-trips variables through systems that store plain numbers, e.g. a persisted
+    /// layout or an entity component system. It does not reserve the id, so an id that
+    /// [`Variable::new`] has not handed out yet will alias a variable it creates later.
+    ///
+    /// ```
+    /// use kasuari::Variable;
+    ///
+    /// let width = Variable::new();
+    /// assert_eq!(Variable::from_id(width.id()), width);
+    /// ```
+    #[inline]
+    pub const fn from_id(id: u64) -> Self {
         Self(id)
     }
+
+    /// The namespace of the [`VariableCounter`] that created the variable, or `None` if it was
+    /// created with [`Variable::new`] or stands for a [`Param`](crate::Param).
+    pub const fn namespace(&self) -> Option<u16> {
+        if self.0 & (PARAM_BIT | SOLVER_BIT) == SOLVER_BIT {
+            Some((self.0 >> NAMESPACE_SHIFT) as u16)
+        } else {
+            None
+        }
+    }
+
+    /// The index of the variable among the variables of the [`VariableCounter`] in a namespace,
+    /// or `None` if no such counter creates it.
+    #[inline]
+    pub(crate) const fn index_in(&self, namespace: u16) -> Option<usize> {
+        if self.0 & !INDEX_MASK == SOLVER_BIT | (namespace as u64) << NAMESPACE_SHIFT {
+            Some((self.0 & INDEX_MASK) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The variable at an index of the [`VariableCounter`] in a namespace.
+    #[inline]
+    pub(crate) const fn from_index(namespace: u16, index: usize) -> Variable {
+        Variable(SOLVER_BIT | (namespace as u64) << NAMESPACE_SHIFT | index as u64)
+    }
+
+    /// Make sure that [`Variable::new`] never hands out this variable, e.g. because it was read
+    /// from a serialized layout or created with [`Variable::from_id`].
+    ///
+    /// The process-wide counter is advanced past the id of the variable if it hasn't handed it out
+    /// yet. Variables created by a [`VariableCounter`] are left alone, since they never clash with
+    /// the variables of [`Variable::new`].
+    ///
+    /// ```
+    /// use kasuari::Variable;
+    ///
+    /// let loaded = Variable::from_id(Variable::new().id() + 100);
+    /// loaded.reserve();
+    /// assert!(Variable::new().id() > loaded.id());
+    /// ```
+    pub fn reserve(self) {
+        if self.0 & SOLVER_BIT == 0 {
+            reserve_id((self.0 & !PARAM_BIT).saturating_add(1));
+        }
+    }
+
+    /// The unique id of the variable.
+    ///
+    /// The ids of the variables that stand for [`Param`](crate::Param)s have the highest bit set.
+    #[inline]
+    pub const fn id(&self) -> u64 {
+        self.0
+    }
+
+    /// Start a constraint that this variable equals an expression, see [`Expression::equals`].
+    pub fn equals(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        Expression::from(self).equals(rhs)
+    }
+
+    /// Start a constraint that this variable is less than or equal to an expression, see
+    /// [`Expression::equals`].
+    pub fn leq(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        Expression::from(self).leq(rhs)
+    }
+
+    /// Start a constraint that this variable is greater than or equal to an expression, see
+    /// [`Expression::equals`].
+    pub fn geq(self, rhs: impl Into<Expression>) -> ConstraintBuilder {
+        Expression::from(self).geq(rhs)
+    }
+
+    /// Produces a new unique variable with a name.
+    ///
+    /// The name is used wherever the crate formats the variable, e.g. in the
+    /// [`Display`](fmt::Display) output of expressions and constraints and in
+    /// [`Solver::dumps`](crate::Solver::dumps), unless a [`NameRegistry`](crate::NameRegistry)
+    /// names it otherwise.
+    ///
+    /// ```
+    /// use kasuari::WeightedRelation::*;
+    /// use kasuari::{Constraint, Strength, Variable};
+    ///
+    /// let left = Variable::named("box1.left");
+    /// assert_eq!(left.name().as_deref(), Some("box1.left"));
+    /// let constraint: Constraint = left | GE(Strength::REQUIRED) | 10.0;
+    /// assert_eq!(constraint.to_string(), "box1.left >= 10 [REQUIRED]");
+    /// ```
+    #[cfg(feature = "variable-names")]
+    pub fn named(name: impl Into<String>) -> Self {
+        let variable = Self::new();
+        variable.set_name(name);
+        variable
+    }
+
+    /// The name of the variable, if it has one.
+    #[cfg(feature = "variable-names")]
+    pub fn name(&self) -> Option<String> {
+        let names = names().read().unwrap_or_else(PoisonError::into_inner);
+        names.get(self).cloned()
+    }
+
+    /// Give the variable a name, replacing its previous name.
+    #[cfg(feature = "variable-names")]
+    pub fn set_name(&self, name: impl Into<String>) {
+        let mut names = names().write().unwrap_or_else(PoisonError::into_inner);
+        names.insert(*self, name.into());
+    }
+
+    /// Remove the name of the variable.
+    ///
+    /// Names are kept until they are removed, so applications that keep creating named variables
+    /// should remove the names of the variables they no longer use.
+    #[cfg(feature = "variable-names")]
+    pub fn clear_name(&self) {
+        let mut names = names().write().unwrap_or_else(PoisonError::into_inner);
+        names.remove(self);
+    }
+}
+
+impl fmt::Display for Variable {
+    /// Formats the variable by its [name](Variable::named) if it has one, and otherwise as `v`
+    /// followed by its id.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(feature = "variable-names")]
+        if let Some(name) = names()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(self)
+        {
+            return f.write_str(name);
+        }
+        write!(f, "v{}", self.0)
+    }
+}
+
+/// Hands out variables without going through the process-wide atomic counter behind
+/// [`Variable::new`].
+///
+/// This suits single-threaded programs, e.g. on embedded targets where atomic operations are
+/// expensive or emulated. The variables of a counter are counted by the counter alone, in a range
+/// of ids that [`Variable::new`] doesn't hand out, so they never clash with variables created by
+/// it. Two counters hand out the same variables though, so the variables of different counters
+/// must not be used in the same solver. Each solver has a counter for
+/// [`Solver::new_variable`](crate::Solver::new_variable), and
+/// [`Solver::with_variable_counter`](crate::Solver::with_variable_counter) creates a solver that
+/// continues an existing one.
+///
+/// A solver rejects constraints with variables of its counter's namespace that the counter hasn't
+/// created yet, see [`AddConstraintError::VariableCollision`]. Layouts that are serialized and
+/// restored in other processes, or combined with the layouts of other solvers, should give each
+/// counter its own [namespace](VariableCounter::with_namespace), so that their variables never
+/// collide.
+///
+/// [`AddConstraintError::VariableCollision`]: crate::AddConstraintError::VariableCollision
+///
+/// ```
+/// use kasuari::WeightedRelation::*;
+/// use kasuari::{Solver, Strength, VariableCounter};
+///
+/// let mut counter = VariableCounter::new();
+/// let (left, right) = (counter.new_variable(), counter.new_variable());
+/// let mut solver = Solver::with_variable_counter(counter);
+/// let width = solver.new_variable();
+/// assert!(width != left && width != right);
+/// solver.add_constraint(right | EQ(Strength::REQUIRED) | (left + width)).unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct VariableCounter {
+    namespace: u16,
+    created: u64,
+}
+
+impl VariableCounter {
+    /// Construct a counter that has not created any variables yet, in namespace 0.
+    pub const fn new() -> VariableCounter {
+        VariableCounter::with_namespace(0)
+    }
+
+    /// Construct a counter that has not created any variables yet, in a namespace.
+    ///
+    /// Counters in different namespaces create different variables, so a process can create
+    /// variables that don't collide with the ones of the layouts it restores, as long as each
+    /// process or solver uses a namespace of its own.
+    ///
+    /// ```
+    /// use kasuari::VariableCounter;
+    ///
+    /// let mut local = VariableCounter::with_namespace(1);
+    /// let mut remote = VariableCounter::with_namespace(2);
+    /// let (a, b) = (local.new_variable(), remote.new_variable());
+    /// assert_ne!(a, b);
+    /// assert_eq!((a.namespace(), b.namespace()), (Some(1), Some(2)));
+    /// ```
+    pub const fn with_namespace(namespace: u16) -> VariableCounter {
+        VariableCounter {
+            namespace,
+            created: 0,
+        }
+    }
+
+    /// Produces a new variable, unique among the variables of this counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter has already created 2<sup>32</sup> variables.
+    pub fn new_variable(&mut self) -> Variable {
+        assert!(self.created <= INDEX_MASK, "ran out of variable ids");
+        let variable = Variable(SOLVER_BIT | self.namespace_bits() | self.created);
+        self.created += 1;
+        variable
+    }
+
+    /// A counter in a namespace that has created `created` variables.
+    pub(crate) const fn with_created(namespace: u16, created: u64) -> VariableCounter {
+        VariableCounter { namespace, created }
+    }
+
+    /// The namespace of the counter.
+    pub fn namespace(&self) -> u16 {
+        self.namespace
+    }
+
+    /// The number of variables the counter has created.
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /// Whether a variable is in the namespace of the counter, but was not created by it yet.
+    pub(crate) fn collides(&self, variable: Variable) -> bool {
+        variable.0 & !INDEX_MASK == SOLVER_BIT | self.namespace_bits()
+            && variable.0 & INDEX_MASK >= self.created
+    }
+
+    const fn namespace_bits(&self) -> u64 {
+        (self.namespace as u64) << NAMESPACE_SHIFT
+    }
 }
 
 impl Default for Variable {
@@ -289,6 +622,14 @@ mod tests {
         assert_ne!(LEFT, RIGHT);
     }
 
+    #[test]
+    fn variable_ids_beyond_32_bits() {
+        let variable = Variable::from_id(1 << 32);
+        assert_eq!(variable.id(), 1 << 32);
+        assert!(!variable.is_param());
+        assert_ne!(variable, Variable::from_id(0));
+    }
+
     #[test]
     fn variable_add_f64() {
         assert_eq!(LEFT + 5.0, Expression::new(vec![LEFT_TERM], 5.0),);
@@ -0,0 +1,32 @@
+#![cfg(feature = "alloc-stats")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{AllocStats, Constraint, Solver, Strength, Variable};
+
+#[test]
+fn alloc_stats_per_operation() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let right = Variable::new();
+
+    let before = solver.alloc_stats();
+    solver
+        .add_constraint(left | EQ(Strength::REQUIRED) | 0.0)
+        .unwrap();
+    let added = solver.alloc_stats() - before;
+    assert_eq!(added.rows_created, 1);
+    assert_eq!(added.rows_dropped, 0);
+    assert!(added.map_allocations > 0);
+
+    let constraint: Constraint = right | GE(Strength::REQUIRED) | (left + 10.0);
+    solver.add_constraint(constraint.clone()).unwrap();
+
+    let before = solver.alloc_stats();
+    solver.remove_constraint(&constraint).unwrap();
+    let removed = solver.alloc_stats() - before;
+    assert_eq!(removed.rows_created, 0);
+    assert_eq!(removed.rows_dropped, 1);
+
+    solver.reset_alloc_stats();
+    assert_eq!(solver.alloc_stats(), AllocStats::default());
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "animation")]
+
+use core::time::Duration;
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Easing, Solver, Spring, Strength, Variable};
+
+#[test]
+fn animate() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let width = Variable::new();
+    let right = Variable::new();
+    solver
+        .add_constraint(right | EQ(Strength::REQUIRED) | (left + width))
+        .unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::REQUIRED) | 10.0)
+        .unwrap();
+    solver.add_edit_variable(left, Strength::STRONG).unwrap();
+    solver.suggest_value(left, 20.0).unwrap();
+    solver.fetch_changes();
+
+    solver
+        .animate(left, 60.0, Duration::from_millis(400), Easing::Linear)
+        .unwrap();
+    assert!(solver.is_animating());
+    let mut changes = solver.tick(Duration::from_millis(100)).unwrap().to_vec();
+    changes.sort_by_key(|&(v, _)| v);
+    assert_eq!(changes, [(left, 30.0), (right, 40.0)]);
+
+    solver.tick(Duration::from_millis(200)).unwrap();
+    assert_eq!(solver.get_value(left), 50.0);
+    solver.tick(Duration::from_millis(200)).unwrap();
+    assert_eq!(solver.get_value(left), 60.0);
+    assert_eq!(solver.get_value(right), 70.0);
+    assert!(!solver.is_animating());
+    assert!(solver.tick(Duration::from_millis(100)).unwrap().is_empty());
+
+    // animations are dropped with their edit variable
+    solver
+        .animate(left, 0.0, Duration::from_secs(1), Easing::EaseOut)
+        .unwrap();
+    solver.remove_edit_variable(left).unwrap();
+    solver.tick(Duration::from_millis(100)).unwrap();
+    assert!(!solver.is_animating());
+    assert!(solver
+        .animate(left, 0.0, Duration::from_secs(1), Easing::Linear)
+        .is_err());
+}
+
+#[test]
+fn stop_animation() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    solver
+        .animate(x, 100.0, Duration::from_secs(1), Easing::EaseInOut)
+        .unwrap();
+    solver.tick(Duration::from_millis(500)).unwrap();
+    assert_eq!(solver.get_value(x), 50.0);
+    assert!(solver.stop_animation(x));
+    assert!(!solver.stop_animation(x));
+    solver.tick(Duration::from_millis(500)).unwrap();
+    assert_eq!(solver.get_value(x), 50.0);
+}
+
+#[test]
+fn spring_to() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    solver
+        .add_constraint(y | EQ(Strength::REQUIRED) | (x * 2.0))
+        .unwrap();
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    solver.spring_to(x, 50.0, Spring::default()).unwrap();
+    for _ in 0..10 {
+        solver.tick(Duration::from_millis(16)).unwrap();
+    }
+    let halfway = solver.get_value(x);
+    assert!(0.0 < halfway && halfway < 50.0);
+    assert_eq!(solver.get_value(y), 2.0 * halfway);
+
+    // retargeting keeps the velocity, so the variable keeps moving the same way at first
+    solver.spring_to(x, 0.0, Spring::default()).unwrap();
+    solver.tick(Duration::from_millis(16)).unwrap();
+    assert!(solver.get_value(x) > halfway);
+
+    let mut frames = 0;
+    while solver.is_animating() {
+        solver.tick(Duration::from_millis(16)).unwrap();
+        frames += 1;
+        assert!(frames < 200);
+    }
+    assert!(solver.get_value(x).abs() < 1e-9);
+    assert!(solver.get_value(y).abs() < 1e-9);
+}
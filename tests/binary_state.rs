@@ -0,0 +1,131 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{
+    Constraint, DecodeStateError, RestoreStateError, Solver, SolverState, Strength, Variable,
+};
+
+fn solver() -> (Solver, Variable, Variable) {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let (left, right) = (Variable::new(), Variable::new());
+    let gap: Constraint = right | GT(Strength::REQUIRED) | (left + 10.0);
+    let preferred: Constraint = left | EQ(Strength::MEDIUM) | 50.0;
+    let collapsed: Constraint = right | EQ(Strength::STRONG) | 0.0;
+    solver
+        .add_constraints([
+            gap.with_name("gap").with_tag(4),
+            left | GE(Strength::REQUIRED) | 0.0,
+            preferred.with_weight(3.0),
+            (2.0 * left) | EQ(Strength::MEDIUM) | 60.0,
+            collapsed.when(1),
+        ])
+        .unwrap();
+    solver.add_edit_variable(right, Strength::WEAK).unwrap();
+    solver.suggest_value(right, 200.0).unwrap();
+    (solver, left, right)
+}
+
+#[test]
+fn round_trip() {
+    let (solver, left, right) = solver();
+    for tableau in [true, false] {
+        let bytes = solver.state(tableau).to_bytes();
+        let state = SolverState::from_bytes(&bytes).unwrap();
+        assert_eq!(state.has_tableau(), tableau);
+        // the encoding is deterministic
+        assert_eq!(state.to_bytes(), bytes);
+
+        let gap = &state.constraints()[0];
+        assert_eq!(gap.name(), Some("gap"));
+        assert_eq!(gap.tag(), Some(4));
+        assert!(gap.is_strict());
+        assert!(state.constraints().iter().any(|c| c.weight() == 3.0));
+        assert!(state.constraints().iter().any(|c| c.condition() == Some(1)));
+
+        let mut restored = Solver::from_state(state).unwrap();
+        assert_eq!(solver.get_value(left), 50.0);
+        assert_eq!(restored.get_value(left), 50.0);
+        assert_eq!(restored.get_value(right), 200.0);
+        restored.set_condition(1, true).unwrap();
+        let gap = restored.get_value(right) - restored.get_value(left);
+        assert!((gap - 10.0 - 1e-6).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn invalid_bytes() {
+    let (solver, _, _) = solver();
+    let bytes = solver.state(true).to_bytes();
+
+    assert_eq!(
+        SolverState::from_bytes(b"").unwrap_err(),
+        DecodeStateError::Truncated
+    );
+    assert_eq!(
+        SolverState::from_bytes(b"{\"constraints\": []}").unwrap_err(),
+        DecodeStateError::NotAState
+    );
+    let mut newer = bytes.clone();
+    newer[4] = 3;
+    assert_eq!(
+        SolverState::from_bytes(&newer).unwrap_err(),
+        DecodeStateError::UnsupportedVersion(3)
+    );
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        SolverState::from_bytes(&trailing).unwrap_err(),
+        DecodeStateError::InvalidValue(bytes.len())
+    );
+    for len in 0..bytes.len() {
+        assert!(SolverState::from_bytes(&bytes[..len]).is_err());
+    }
+}
+
+#[test]
+fn compact() {
+    let mut solver = Solver::new();
+    let variables: Vec<Variable> = (0..1000).map(|_| solver.new_variable()).collect();
+    for pair in variables.windows(2) {
+        solver
+            .add_constraint(pair[1] | GE(Strength::REQUIRED) | (pair[0] + 1.0))
+            .unwrap();
+    }
+    let bytes = solver.state(false).to_bytes();
+    // two terms and a constant of 8 bytes each, and a few bytes of ids and flags
+    assert!(bytes.len() < 999 * 40, "{} bytes", bytes.len());
+}
+
+/// The offset of the namespace of the variable counter, after the magic, version, flags and the
+/// epsilon of strict constraints.
+const NAMESPACE: usize = 14;
+
+#[test]
+fn version_1() {
+    let (solver, _, right) = solver();
+    let mut bytes = solver.state(false).to_bytes();
+    assert_eq!(bytes[NAMESPACE], 0);
+    bytes.remove(NAMESPACE);
+    bytes[4] = 1;
+    let restored = Solver::from_state(SolverState::from_bytes(&bytes).unwrap()).unwrap();
+    assert_eq!(restored.get_value(right), 200.0);
+}
+
+#[test]
+fn variable_collision() {
+    let mut solver = Solver::new();
+    let (left, right) = (solver.new_variable(), solver.new_variable());
+    solver
+        .add_constraint(right | EQ(Strength::REQUIRED) | (left + 1.0))
+        .unwrap();
+    for tableau in [true, false] {
+        let mut bytes = solver.state(tableau).to_bytes();
+        assert_eq!(bytes[NAMESPACE + 1], 2);
+        // a counter that created only `left` would create `right` again
+        bytes[NAMESPACE + 1] = 1;
+        let state = SolverState::from_bytes(&bytes).unwrap();
+        assert!(matches!(
+            Solver::from_state(state),
+            Err(RestoreStateError::VariableCollision(v)) if v == right
+        ));
+    }
+}
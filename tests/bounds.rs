@@ -0,0 +1,126 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{AddConstraintError, RemoveConstraintError, Solver, Strength, Variable};
+
+#[test]
+fn bounds() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 50.0)
+        .unwrap();
+    assert_eq!(solver.lower_bound(width), None);
+
+    solver
+        .set_lower_bound(width, 0.0, Strength::REQUIRED)
+        .unwrap();
+    solver
+        .set_upper_bound(width, 40.0, Strength::REQUIRED)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 40.0);
+    assert_eq!(solver.upper_bound(width), Some(40.0));
+
+    // moving a bound keeps a single constraint
+    solver
+        .set_upper_bound(width, 45.0, Strength::REQUIRED)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 45.0);
+    assert_eq!(solver.constraints_for(width).count(), 3);
+
+    // an unsatisfiable bound keeps the previous one
+    assert!(matches!(
+        solver.set_lower_bound(width, 60.0, Strength::REQUIRED),
+        Err(AddConstraintError::UnsatisfiableConstraint)
+    ));
+    assert_eq!(solver.lower_bound(width), Some(0.0));
+
+    // changing the strength replaces the bound
+    solver
+        .set_lower_bound(width, 60.0, Strength::STRONG)
+        .unwrap();
+    assert_eq!(solver.lower_bound(width), Some(60.0));
+    assert_eq!(solver.get_value(width), 45.0);
+    solver
+        .set_upper_bound(width, 80.0, Strength::REQUIRED)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 60.0);
+    solver
+        .set_lower_bound(width, 70.0, Strength::STRONG)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 70.0);
+
+    solver.clear_lower_bound(width).unwrap();
+    assert_eq!(solver.get_value(width), 50.0);
+    assert!(matches!(
+        solver.clear_lower_bound(width),
+        Err(RemoveConstraintError::UnknownConstraint)
+    ));
+
+    // bounds survive rebuilding and are dropped on reset
+    solver.rebuild().unwrap();
+    assert_eq!(solver.upper_bound(width), Some(80.0));
+    solver.clear_upper_bound(width).unwrap();
+    assert_eq!(solver.constraints_for(width).count(), 1);
+    solver
+        .set_upper_bound(width, 10.0, Strength::REQUIRED)
+        .unwrap();
+    solver.reset();
+    assert_eq!(solver.upper_bound(width), None);
+}
+
+#[test]
+fn bounds_while_disabled() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 50.0)
+        .unwrap();
+    solver
+        .set_upper_bound(width, 40.0, Strength::REQUIRED)
+        .unwrap();
+    let bound = solver
+        .constraints_for(width)
+        .find(|c| c.strength() == Strength::REQUIRED)
+        .cloned()
+        .unwrap();
+    solver.set_enabled(&bound, false).unwrap();
+    assert_eq!(solver.upper_bound(width), Some(40.0));
+    assert_eq!(solver.get_value(width), 50.0);
+    solver.rebuild().unwrap();
+    solver.set_enabled(&bound, true).unwrap();
+    assert_eq!(solver.get_value(width), 40.0);
+}
+
+#[test]
+fn many_bounds() {
+    let mut solver = Solver::new();
+    let variables: Vec<Variable> = (0..200).map(|_| Variable::new()).collect();
+    for &v in &variables {
+        solver.set_lower_bound(v, 0.0, Strength::REQUIRED).unwrap();
+        solver.set_upper_bound(v, 100.0, Strength::STRONG).unwrap();
+        solver.pin(v, Strength::WEAK).unwrap();
+    }
+
+    // moving bounds inside a checkpoint rolls back the moved constraints
+    let checkpoint = solver.checkpoint();
+    for &v in &variables {
+        solver.set_upper_bound(v, 50.0, Strength::STRONG).unwrap();
+        solver.set_lower_bound(v, 10.0, Strength::REQUIRED).unwrap();
+    }
+    assert_eq!(solver.get_value(variables[0]), 10.0);
+    solver.rollback_to(checkpoint).unwrap();
+
+    for &v in &variables {
+        assert_eq!(solver.lower_bound(v), Some(10.0));
+        assert_eq!(solver.upper_bound(v), Some(50.0));
+        assert!(solver.is_pinned(v));
+        solver.clear_upper_bound(v).unwrap();
+        assert_eq!(solver.upper_bound(v), None);
+        assert_eq!(solver.lower_bound(v), Some(10.0));
+    }
+    for &v in &variables {
+        solver.clear_lower_bound(v).unwrap();
+        solver.unpin(v).unwrap();
+        assert!(solver.clear_lower_bound(v).is_err());
+    }
+    assert_eq!(solver.constraints_for(variables[0]).count(), 0);
+}
@@ -0,0 +1,58 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Term, Variable};
+
+#[test]
+fn builder_matches_pipe_syntax() {
+    let (x, y) = (Variable::new(), Variable::new());
+    let built = (x + y).leq(10.0).strong();
+    let piped: Constraint = (x + y) | LE(Strength::STRONG) | 10.0;
+    assert!(built.structurally_equals(&piped));
+
+    let built = Term::new(x, 2.0).equals(y).weak();
+    let piped: Constraint = (2.0 * x) | EQ(Strength::WEAK) | y;
+    assert!(built.structurally_equals(&piped));
+
+    let built: Constraint = x.geq(y + 1.0).into();
+    let piped: Constraint = x | GE(Strength::REQUIRED) | (y + 1.0);
+    assert!(built.structurally_equals(&piped));
+}
+
+#[test]
+fn builder_strengths() {
+    let x = Variable::new();
+    assert_eq!(x.equals(1.0).required().strength(), Strength::REQUIRED);
+    assert_eq!(x.equals(1.0).strong().strength(), Strength::STRONG);
+    assert_eq!(x.equals(1.0).medium().strength(), Strength::MEDIUM);
+    assert_eq!(x.equals(1.0).weak().strength(), Strength::WEAK);
+    assert_eq!(
+        x.equals(1.0).strength(Strength::new(5.0)).strength(),
+        Strength::new(5.0)
+    );
+}
+
+#[test]
+fn solve_with_builder() {
+    let mut solver = Solver::new();
+    let (left, width, right) = (Variable::new(), Variable::new(), Variable::new());
+    solver
+        .add_constraints([
+            left.geq(0.0).required(),
+            right.equals(left + width).required(),
+            right.leq(100.0).required(),
+            width.equals(80.0).medium(),
+            left.equals(30.0).weak(),
+        ])
+        .unwrap();
+    assert_eq!(solver.get_value(left), 20.0);
+    assert_eq!(solver.get_value(width), 80.0);
+    // comparing with `==` still compares the variables
+    assert!(left == left);
+}
+
+#[test]
+fn builder_does_not_shadow_partial_eq() {
+    let (x, y) = (Variable::new(), Variable::new());
+    let same: bool = x.eq(&x);
+    assert!(same && !x.eq(&y));
+    assert!(Term::new(x, 2.0).eq(&Term::new(x, 2.0)));
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "cassowary-compat")]
+
+use kasuari::compat::cassowary::strength::{self, MEDIUM, REQUIRED, STRONG, WEAK};
+use kasuari::compat::cassowary::WeightedRelation::*;
+use kasuari::compat::cassowary::{
+    AddConstraintError, AddEditVariableError, Constraint, Expression, RelationalOperator,
+    RemoveConstraintError, RemoveEditVariableError, Solver, SuggestValueError, Variable,
+};
+
+mod common;
+
+use common::new_values;
+
+#[test]
+fn example() {
+    let (value_of, update_values) = new_values();
+    let window_width = Variable::new();
+    let (left1, right1, left2, right2) = (
+        Variable::new(),
+        Variable::new(),
+        Variable::new(),
+        Variable::new(),
+    );
+
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(&[
+            window_width | GE(REQUIRED) | 0.0,
+            left1 | EQ(REQUIRED) | 0.0,
+            right2 | EQ(REQUIRED) | window_width,
+            left2 | GE(REQUIRED) | right1,
+            left1 | LE(REQUIRED) | right1,
+            left2 | LE(REQUIRED) | right2,
+            (right1 - left1) | EQ(WEAK) | 50.0,
+            (right2 - left2) | EQ(WEAK) | 100.0,
+        ])
+        .unwrap();
+    solver.add_edit_variable(window_width, STRONG).unwrap();
+    solver.suggest_value(window_width, 300.0).unwrap();
+    update_values(solver.fetch_changes());
+    assert_eq!(value_of(right1), 50.0);
+    assert_eq!(value_of(left2), 200.0);
+
+    solver.suggest_value(window_width, 75.0).unwrap();
+    solver
+        .add_constraint(((right1 - left1) / 50.0) | EQ(MEDIUM) | ((right2 - left2) / 100.0))
+        .unwrap();
+    update_values(solver.fetch_changes());
+    assert!((value_of(right1) - 25.0).abs() < 1e-9);
+    assert!((value_of(left2) - 25.0).abs() < 1e-9);
+}
+
+#[test]
+fn constraints() {
+    let x = Variable::new();
+    let constraint = Constraint::new(
+        Expression::from_variable(x) - 10.0,
+        RelationalOperator::GreaterOrEqual,
+        1e12,
+    );
+    assert_eq!(constraint.strength(), REQUIRED);
+    assert_eq!(constraint.op(), RelationalOperator::GreaterOrEqual);
+    assert_eq!(constraint.expr().constant, -10.0);
+    assert_eq!(constraint, constraint.clone());
+
+    let sugar: Constraint = (2.0 * x) | LE(strength::create(1.0, 0.0, 0.0, 2.0)) | 20.0f32;
+    assert_eq!(sugar.strength(), 2.0 * STRONG);
+    assert_eq!(sugar.expr().constant, -20.0);
+    assert_eq!(strength::clip(-1.0), 0.0);
+}
+
+#[test]
+fn errors() {
+    let x = Variable::new();
+    let mut solver = Solver::new();
+    let min: Constraint = x | GE(REQUIRED) | 10.0;
+    solver.add_constraint(min.clone()).unwrap();
+    assert!(solver.has_constraint(&min));
+    assert_eq!(
+        solver.add_constraint(min.clone()),
+        Err(AddConstraintError::DuplicateConstraint)
+    );
+    assert_eq!(
+        solver.add_constraint(x | LE(REQUIRED) | 0.0),
+        Err(AddConstraintError::UnsatisfiableConstraint)
+    );
+    solver.remove_constraint(&min).unwrap();
+    assert_eq!(
+        solver.remove_constraint(&min),
+        Err(RemoveConstraintError::UnknownConstraint)
+    );
+
+    assert_eq!(
+        solver.add_edit_variable(x, REQUIRED),
+        Err(AddEditVariableError::BadRequiredStrength)
+    );
+    assert_eq!(
+        solver.suggest_value(x, 1.0),
+        Err(SuggestValueError::UnknownEditVariable)
+    );
+    solver.add_edit_variable(x, WEAK).unwrap();
+    assert!(solver.has_edit_variable(&x));
+    assert_eq!(
+        solver.add_edit_variable(x, WEAK),
+        Err(AddEditVariableError::DuplicateEditVariable)
+    );
+    solver.remove_edit_variable(x).unwrap();
+    assert_eq!(
+        solver.remove_edit_variable(x),
+        Err(RemoveEditVariableError::UnknownEditVariable)
+    );
+
+    // the kasuari solver behind it keeps the constraints
+    solver.add_constraint(x | EQ(WEAK) | 5.0).unwrap();
+    let solver = solver.into_kasuari();
+    assert_eq!(solver.get_value(x), 5.0);
+}
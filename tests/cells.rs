@@ -0,0 +1,77 @@
+#![cfg(feature = "cells")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Axis, CellRect, Element, Solver, Strength};
+
+#[test]
+fn thirds_tile_the_area() {
+    let area = Element::new();
+    let columns = [Element::new(), Element::new(), Element::new()];
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(area.at_cells(CellRect::new(2, 1, 80, 10), Strength::REQUIRED))
+        .unwrap();
+    solver
+        .add_constraints(Element::no_overlap(&columns, Axis::Horizontal))
+        .unwrap();
+    solver
+        .add_constraints(Element::same_size(
+            &columns,
+            Axis::Horizontal,
+            Strength::REQUIRED,
+        ))
+        .unwrap();
+    for column in &columns {
+        solver
+            .add_constraints(column.inside(&area, 0.0, Strength::REQUIRED))
+            .unwrap();
+        solver
+            .add_constraint(column.height() | EQ(Strength::REQUIRED) | area.height())
+            .unwrap();
+    }
+    // no_overlap allows gaps, so make the columns abut
+    for pair in columns.windows(2) {
+        solver
+            .add_constraint(pair[1].left | EQ(Strength::REQUIRED) | pair[0].right)
+            .unwrap();
+    }
+    solver
+        .add_constraint(columns[0].left | EQ(Strength::REQUIRED) | area.left)
+        .unwrap();
+    solver
+        .add_constraint(columns[2].right | EQ(Strength::REQUIRED) | area.right)
+        .unwrap();
+
+    let solution = solver.freeze();
+    let rects = Element::cells_along(&columns, Axis::Horizontal, |v| solution.value_of(v));
+    assert_eq!(
+        rects,
+        [
+            CellRect::new(2, 1, 27, 10),
+            CellRect::new(29, 1, 26, 10),
+            CellRect::new(55, 1, 27, 10),
+        ]
+    );
+    assert_eq!(rects[0].right(), rects[1].x);
+    assert_eq!(rects[2].right(), 82);
+    assert_eq!(columns[1].cells(|v| solver.get_value(v)), rects[1]);
+}
+
+#[test]
+fn snapping() {
+    assert_eq!(CellRect::snap(f64::NAN), 0);
+    assert_eq!(CellRect::snap(f64::NEG_INFINITY), 0);
+    assert_eq!(CellRect::snap(f64::INFINITY), u16::MAX);
+    assert_eq!(CellRect::snap(-0.4), 0);
+
+    // an overlap of the solved segments doesn't overlap in cells
+    assert_eq!(
+        CellRect::snap_segments([(0.0, 5.6), (5.4, 9.0), (2.0, 3.0)]),
+        [(0, 6), (6, 3), (9, 0)]
+    );
+
+    // an element with a negative size covers no cells
+    let element = Element::new();
+    let rect = element.cells(|v| if v == element.left { 10.0 } else { 4.0 });
+    assert_eq!(rect, CellRect::new(10, 4, 0, 0));
+}
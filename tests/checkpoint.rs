@@ -0,0 +1,110 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn nested_checkpoints() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    let base: Constraint = width | EQ(Strength::WEAK) | 100.0;
+    solver.add_constraint(base.clone()).unwrap();
+
+    let outer = solver.checkpoint();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 80.0;
+    solver.add_constraint(max.clone()).unwrap();
+    solver.add_edit_variable(height, Strength::STRONG).unwrap();
+
+    let inner = solver.checkpoint();
+    let min: Constraint = width | GE(Strength::REQUIRED) | 90.0;
+    solver.remove_constraint(&max).unwrap();
+    solver.add_constraint(min.clone()).unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+
+    // constraints removed since the checkpoint are not added back
+    solver.rollback_to(inner).unwrap();
+    assert!(!solver.has_constraint(&min));
+    assert!(!solver.has_constraint(&max));
+    assert!(solver.has_edit_variable(&height));
+
+    solver.add_constraint(max.clone()).unwrap();
+    solver.rollback_to(outer).unwrap();
+    assert!(solver.has_constraint(&base));
+    assert!(!solver.has_constraint(&max));
+    assert!(!solver.has_edit_variable(&height));
+    assert_eq!(solver.get_value(width), 100.0);
+}
+
+#[test]
+fn commit() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let outer = solver.checkpoint();
+    let inner = solver.checkpoint();
+    let min: Constraint = width | GE(Strength::REQUIRED) | 90.0;
+    solver.add_constraint(min.clone()).unwrap();
+    solver.commit(inner);
+    assert!(solver.has_constraint(&min));
+
+    // committed additions are still rolled back by outer checkpoints
+    solver.rollback_to(outer).unwrap();
+    assert!(!solver.has_constraint(&min));
+
+    let checkpoint = solver.checkpoint();
+    solver.add_constraint(min.clone()).unwrap();
+    solver.commit(checkpoint);
+    let checkpoint = solver.checkpoint();
+    solver.rollback_to(checkpoint).unwrap();
+    assert!(solver.has_constraint(&min));
+}
+
+#[test]
+fn add_constraints_atomic() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let installed: Constraint = width | LE(Strength::REQUIRED) | 200.0;
+    solver.add_constraint(installed.clone()).unwrap();
+
+    let min: Constraint = width | GE(Strength::REQUIRED) | 100.0;
+    let preferred: Constraint = width | EQ(Strength::WEAK) | 150.0;
+    let conflict: Constraint = width | GE(Strength::REQUIRED) | 300.0;
+    let (failed, _) = solver
+        .add_constraints_atomic([min.clone(), preferred.clone(), conflict.clone()])
+        .unwrap_err();
+    assert_eq!(failed, conflict);
+    assert!(solver.has_constraint(&installed));
+    assert!(!solver.has_constraint(&min));
+    assert!(!solver.has_constraint(&preferred));
+
+    solver
+        .add_constraints_atomic([min.clone(), preferred.clone()])
+        .unwrap();
+    assert_eq!(solver.get_value(width), 150.0);
+
+    // an atomic add inside a checkpoint is rolled back with it
+    let checkpoint = solver.checkpoint();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 120.0;
+    solver.add_constraints_atomic([max.clone()]).unwrap();
+    assert_eq!(solver.get_value(width), 120.0);
+    solver.rollback_to(checkpoint).unwrap();
+    assert_eq!(solver.get_value(width), 150.0);
+}
+
+#[test]
+fn rollback_named_replacement() {
+    let mut solver = Solver::new();
+    let a = Variable::new();
+    let original: Constraint = a | EQ(Strength::REQUIRED) | 10.0;
+    let original = original.with_name("w");
+    solver.add_constraint(original.clone()).unwrap();
+
+    let replacement: Constraint = a | EQ(Strength::REQUIRED) | 20.0;
+    let replacement = replacement.with_name("w");
+    let conflict: Constraint = a | EQ(Strength::REQUIRED) | 30.0;
+    let (failed, _) = solver
+        .add_constraints_atomic([replacement.clone(), conflict.clone()])
+        .unwrap_err();
+    assert_eq!(failed, conflict);
+    assert!(!solver.has_constraint(&replacement));
+    assert_eq!(solver.named("w"), Some(&original));
+    assert_eq!(solver.get_value(a), 10.0);
+}
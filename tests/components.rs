@@ -0,0 +1,104 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn independent_layouts() {
+    let mut solver = Solver::new();
+    let [a, b, c, d, e] = [(); 5].map(|_| Variable::new());
+    let ab: Constraint = (b - a) | EQ(Strength::REQUIRED) | 10.0;
+    let bc: Constraint = c | GE(Strength::STRONG) | (b + 5.0);
+    let d_size: Constraint = d | EQ(Strength::WEAK) | 100.0;
+    let de: Constraint = e | LE(Strength::REQUIRED) | d;
+    solver
+        .add_constraints([ab.clone(), bc.clone(), d_size.clone(), de.clone()])
+        .unwrap();
+
+    let components = solver.components();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0].variables(), [a, b, c]);
+    assert_eq!(components[0].constraints().len(), 2);
+    assert!(components[0].constraints().contains(&ab));
+    assert!(components[0].constraints().contains(&bc));
+    assert_eq!(components[1].variables(), [d, e]);
+    assert_eq!(components[1].constraints().len(), 2);
+
+    // joining the two layouts merges their components
+    solver
+        .add_constraint(d | GE(Strength::REQUIRED) | c)
+        .unwrap();
+    let components = solver.components();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].variables(), [a, b, c, d, e]);
+    assert_eq!(components[0].constraints().len(), 5);
+}
+
+#[test]
+fn empty_solver() {
+    assert!(Solver::new().components().is_empty());
+}
+
+#[test]
+fn extract() {
+    let mut solver = Solver::new();
+    let [a, b, c, d] = [(); 4].map(|_| Variable::new());
+    let ab: Constraint = b | GE(Strength::REQUIRED) | (a + 10.0);
+    let bc: Constraint = c | EQ(Strength::STRONG) | (b * 2.0);
+    let d_size: Constraint = d | EQ(Strength::WEAK) | 100.0;
+    solver.add_constraint(ab.clone()).unwrap();
+    solver.add_named("double", bc.clone()).unwrap();
+    solver.add_constraint(d_size.clone()).unwrap();
+    solver.add_edit_variable(a, Strength::MEDIUM).unwrap();
+    solver.suggest_value(a, 5.0).unwrap();
+
+    let extracted = solver.extract(&[c]);
+    assert!(extracted.has_constraint(&ab));
+    assert_eq!(extracted.named("double"), Some(&bc));
+    assert!(!extracted.has_constraint(&d_size));
+    assert!(extracted.has_edit_variable(&a));
+    assert_eq!(extracted.get_value(c), solver.get_value(c));
+    assert_eq!(extracted.get_value(c), 30.0);
+    assert_eq!(extracted.components().len(), 1);
+
+    assert!(solver.extract(&[Variable::new()]).components().is_empty());
+}
+
+#[test]
+fn extract_disabled() {
+    let mut solver = Solver::new();
+    let a = Variable::new();
+    let min: Constraint = a | GE(Strength::REQUIRED) | 50.0;
+    let hint: Constraint = a | EQ(Strength::WEAK) | 99.0;
+    solver.add_constraint(min.clone()).unwrap();
+    solver.add_constraint(hint.clone()).unwrap();
+    solver
+        .add_constraint(a | EQ(Strength::WEAK * 0.5) | 10.0)
+        .unwrap();
+    solver.set_enabled(&min, false).unwrap();
+    solver.set_enabled(&hint, false).unwrap();
+    assert_eq!(solver.get_value(a), 10.0);
+
+    let mut extracted = solver.extract(&[a]);
+    assert!(extracted.has_constraint(&min));
+    assert!(!extracted.is_enabled(&min));
+    assert!(!extracted.is_enabled(&hint));
+    assert_eq!(extracted.get_value(a), 10.0);
+
+    extracted.set_enabled(&min, true).unwrap();
+    assert_eq!(extracted.get_value(a), 50.0);
+}
+
+#[test]
+fn extract_conditions() {
+    let mut solver = Solver::new();
+    let a = Variable::new();
+    let wide: Constraint = a | EQ(Strength::REQUIRED) | 10.0;
+    solver.add_constraint(wide.when(7)).unwrap();
+    solver.set_condition(7, true).unwrap();
+    assert_eq!(solver.get_value(a), 10.0);
+
+    let mut extracted = solver.extract(&[a]);
+    assert!(extracted.condition(7));
+    assert_eq!(extracted.get_value(a), 10.0);
+    extracted.set_condition(7, false).unwrap();
+    assert_eq!(extracted.get_value(a), 0.0);
+}
@@ -0,0 +1,102 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, SetEnabledError, Solver, Strength, Variable};
+
+const STACKED: u64 = 1;
+const SIDE_BY_SIDE: u64 = 2;
+
+#[test]
+fn set_condition() {
+    let mut solver = Solver::new();
+    let (left, top) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint(left | EQ(Strength::WEAK) | 5.0)
+        .unwrap();
+    solver
+        .add_constraint(top | EQ(Strength::WEAK) | 5.0)
+        .unwrap();
+
+    let stacked: Constraint = top | EQ(Strength::REQUIRED) | 20.0;
+    let stacked = stacked.when(STACKED);
+    let beside: Constraint = left | EQ(Strength::STRONG) | 40.0;
+    let beside = beside.when(SIDE_BY_SIDE);
+    assert_eq!(beside.condition(), Some(SIDE_BY_SIDE));
+    solver.add_constraint(stacked.clone()).unwrap();
+    solver.add_constraint(beside.clone()).unwrap();
+    assert!(!solver.condition(STACKED));
+    assert!(!solver.is_enabled(&stacked));
+    assert!(!solver.is_enabled(&beside));
+    assert_eq!((solver.get_value(left), solver.get_value(top)), (5.0, 5.0));
+
+    solver.set_condition(SIDE_BY_SIDE, true).unwrap();
+    assert!(solver.condition(SIDE_BY_SIDE));
+    assert_eq!((solver.get_value(left), solver.get_value(top)), (40.0, 5.0));
+
+    solver.set_condition(SIDE_BY_SIDE, false).unwrap();
+    solver.set_condition(STACKED, true).unwrap();
+    assert_eq!((solver.get_value(left), solver.get_value(top)), (5.0, 20.0));
+
+    // added under an active condition, so enabled right away
+    let wide: Constraint = left | GE(Strength::REQUIRED) | 10.0;
+    let wide = wide.when(STACKED);
+    solver.add_constraint(wide.clone()).unwrap();
+    assert!(solver.is_enabled(&wide));
+    assert_eq!(solver.get_value(left), 10.0);
+
+    solver.set_condition(STACKED, false).unwrap();
+    assert_eq!((solver.get_value(left), solver.get_value(top)), (5.0, 5.0));
+    solver.remove_constraint(&wide).unwrap();
+    solver.set_condition(STACKED, true).unwrap();
+    assert_eq!((solver.get_value(left), solver.get_value(top)), (5.0, 20.0));
+}
+
+#[test]
+fn conflicting_condition() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | GE(Strength::REQUIRED) | 100.0)
+        .unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 120.0)
+        .unwrap();
+
+    // an inactive required constraint can't conflict
+    let narrow: Constraint = width | LE(Strength::REQUIRED) | 50.0;
+    let narrow = narrow.when(STACKED);
+    let preferred: Constraint = width | EQ(Strength::STRONG) | 150.0;
+    let preferred = preferred.when(STACKED);
+    solver.add_constraint(narrow.clone()).unwrap();
+    solver.add_constraint(preferred.clone()).unwrap();
+    assert_eq!(solver.get_value(width), 120.0);
+
+    assert!(matches!(
+        solver.set_condition(STACKED, true),
+        Err(SetEnabledError::UnsatisfiableConstraint)
+    ));
+    assert!(solver.condition(STACKED));
+    assert!(!solver.is_enabled(&narrow));
+    assert!(solver.is_enabled(&preferred));
+    assert_eq!(solver.get_value(width), 150.0);
+}
+
+#[test]
+fn rebuild_keeps_conditions() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    solver
+        .add_constraint(left | EQ(Strength::WEAK) | 5.0)
+        .unwrap();
+    let beside: Constraint = left | EQ(Strength::STRONG) | 40.0;
+    let beside = beside.when(SIDE_BY_SIDE);
+    solver.add_constraint(beside.clone()).unwrap();
+    solver.set_condition(SIDE_BY_SIDE, true).unwrap();
+
+    solver.rebuild().unwrap();
+    assert!(solver.condition(SIDE_BY_SIDE));
+    assert!(solver.is_enabled(&beside));
+    assert_eq!(solver.get_value(left), 40.0);
+
+    solver.set_condition(SIDE_BY_SIDE, false).unwrap();
+    assert!(!solver.is_enabled(&beside));
+    assert_eq!(solver.get_value(left), 5.0);
+}
@@ -0,0 +1,71 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, ConstraintId, RemoveConstraintError, Solver, Strength, Variable};
+
+#[test]
+fn id_is_stable() {
+    let (x, y) = (Variable::from_id(1), Variable::from_id(2));
+    let constraint: Constraint = (x + 2.0 * y) | LE(Strength::STRONG) | 10.0;
+    // the hash doesn't depend on the target or the run
+    assert_eq!(
+        constraint.id(),
+        ConstraintId::from_value(0xe91e_e4f5_d063_a99d)
+    );
+    assert_eq!(
+        constraint.id().to_string(),
+        format!("c{:016x}", constraint.id().value())
+    );
+}
+
+#[test]
+fn id_follows_content() {
+    let (x, y) = (Variable::new(), Variable::new());
+    let base: Constraint = (x + y) | GE(Strength::STRONG) | 10.0;
+    let same: Constraint = (y + x + 0.0 * y) | GE(Strength::STRONG) | 10.0;
+    assert_eq!(base.id(), same.id());
+    assert_eq!(base.id(), base.with_constant(-10.0).id());
+
+    let different = [
+        base.with_constant(-20.0),
+        base.with_strength(Strength::WEAK),
+        base.with_weight(2.0),
+        base.with_name("a"),
+        base.with_tag(1),
+        base.when(1),
+        (x + y) | GT(Strength::STRONG) | 10.0,
+        (x + y) | LE(Strength::STRONG) | 10.0,
+        (x + 2.0 * y) | GE(Strength::STRONG) | 10.0,
+    ];
+    for (i, constraint) in different.iter().enumerate() {
+        assert_ne!(constraint.id(), base.id(), "{i}");
+    }
+    // a tag and a condition with the same value are told apart
+    assert_ne!(base.with_tag(1).id(), base.when(1).id());
+}
+
+#[test]
+fn remove_by_id() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let min: Constraint = width | GE(Strength::REQUIRED) | 20.0;
+    let hidden: Constraint = width | GE(Strength::REQUIRED) | 50.0;
+    solver.add_constraint(min.clone()).unwrap();
+    solver.add_constraint(hidden.clone()).unwrap();
+    solver.set_enabled(&hidden, false).unwrap();
+    solver.add_edit_variable(width, Strength::WEAK).unwrap();
+    assert_eq!(solver.get_value(width), 20.0);
+
+    assert_eq!(solver.constraint_by_id(min.id()), Some(&min));
+    assert_eq!(solver.constraint_by_id(hidden.id()), Some(&hidden));
+    assert_eq!(solver.remove_by_id(hidden.id()).unwrap(), hidden);
+    assert_eq!(solver.remove_by_id(min.id()).unwrap(), min);
+    assert_eq!(solver.get_value(width), 0.0);
+    assert!(matches!(
+        solver.remove_by_id(min.id()),
+        Err(RemoveConstraintError::UnknownConstraint)
+    ));
+
+    // the constraint of the edit variable can't be removed by id
+    let edit: Constraint = width | EQ(Strength::WEAK) | 0.0;
+    assert!(solver.constraint_by_id(edit.id()).is_none());
+    assert!(solver.has_edit_variable(&width));
+}
@@ -0,0 +1,64 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Solver, Strength, Variable};
+
+/// The height of a paragraph of text with the given area when wrapped to a width.
+fn wrapped_height(area: f64) -> impl FnMut(&[f64]) -> f64 {
+    move |inputs| (area / inputs[0].max(1.0)).ceil()
+}
+
+#[test]
+fn measured_when_inputs_change() {
+    let mut solver = Solver::new();
+    let window = Variable::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    solver.add_edit_variable(window, Strength::STRONG).unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::REQUIRED) | (window - 20.0))
+        .unwrap();
+    solver.suggest_value(window, 120.0).unwrap();
+
+    let measurements = Rc::new(Cell::new(0));
+    let counter = measurements.clone();
+    let mut measure = wrapped_height(1000.0);
+    solver
+        .add_content_size(height, [width], Strength::STRONG, move |inputs| {
+            counter.set(counter.get() + 1);
+            measure(inputs)
+        })
+        .unwrap();
+    assert_eq!(measurements.get(), 1);
+    assert_eq!(solver.get_value(height), 10.0);
+
+    // unrelated changes don't cause a measurement
+    solver.fetch_changes();
+    assert_eq!(measurements.get(), 1);
+
+    solver.suggest_value(window, 70.0).unwrap();
+    let changes = solver.fetch_changes().to_vec();
+    assert_eq!(measurements.get(), 2);
+    assert!(changes.contains(&(height, 20.0)));
+    assert_eq!(solver.get_value(height), 20.0);
+}
+
+#[test]
+fn remove_content_size() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    solver.suggest_value(width, 10.0).unwrap();
+    solver
+        .add_content_size(height, [width], Strength::STRONG, wrapped_height(100.0))
+        .unwrap();
+    assert_eq!(solver.get_value(height), 10.0);
+
+    solver.remove_edit_variable(height).unwrap();
+    solver.suggest_value(width, 50.0).unwrap();
+    assert!(!solver.measure_content_sizes().unwrap());
+    solver.fetch_changes();
+    assert_eq!(solver.get_value(height), 0.0);
+}
@@ -0,0 +1,100 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, NameRegistry, Solver, Strength, Variable};
+use serde_json::{json, Value};
+
+fn id(v: Variable) -> String {
+    format!("v{}", v.id())
+}
+
+#[test]
+fn debug_json() {
+    let mut solver = Solver::new();
+    let (left, width) = (Variable::new(), Variable::new());
+    let min: Constraint = width | GE(Strength::REQUIRED) | 10.0;
+    let min = min.with_name("min \"width\"");
+    solver.add_constraint(min.clone()).unwrap();
+    let preferred: Constraint = width | EQ(Strength::WEAK) | (left + 5.0);
+    solver
+        .add_constraint(preferred.with_tag(7).with_weight(2.0))
+        .unwrap();
+    let hidden: Constraint = width | LT(Strength::STRONG) | 0.0;
+    solver.add_constraint(hidden.clone()).unwrap();
+    solver.set_enabled(&hidden, false).unwrap();
+    solver.add_edit_variable(left, Strength::MEDIUM).unwrap();
+    solver.suggest_value(left, 20.0).unwrap();
+
+    let mut names = NameRegistry::new();
+    names.insert(width, "width");
+    let json: Value = serde_json::from_str(&solver.to_debug_json_with(&names)).unwrap();
+
+    // whole numbers are written without a fraction
+    assert_eq!(
+        json["variables"],
+        json!([
+            {"id": id(left), "name": null, "value": 20},
+            {"id": id(width), "name": "width", "value": 25},
+        ])
+    );
+
+    let constraints = json["constraints"].as_array().unwrap();
+    assert_eq!(constraints.len(), 3);
+    assert_eq!(
+        constraints[0],
+        json!({
+            "id": min.id().to_string(),
+            "text": "width >= 10",
+            "terms": [{"variable": id(width), "coefficient": 1}],
+            "constant": -10,
+            "operator": ">=",
+            "strength": "REQUIRED",
+            "strength_value": Strength::REQUIRED.value() as u64,
+            "weight": 1,
+            "name": "min \"width\"",
+            "tag": null,
+            "condition": null,
+            "enabled": true,
+            "violation": 0,
+        })
+    );
+    assert_eq!(constraints[1]["tag"], 7);
+    assert_eq!(constraints[1]["weight"], 2.0);
+    assert_eq!(constraints[1]["violation"], 0.0);
+    assert_eq!(constraints[2]["operator"], "<");
+    assert_eq!(constraints[2]["enabled"], false);
+    assert!(constraints[2]["violation"].as_f64().unwrap() > 25.0);
+
+    assert_eq!(
+        json["edit_variables"],
+        json!([{
+            "variable": id(left),
+            "strength": "MEDIUM",
+            "strength_value": Strength::MEDIUM.value() as u64,
+            "suggested": 20,
+        }])
+    );
+}
+
+#[test]
+fn debug_json_is_stable() {
+    let mut solver = Solver::new();
+    let variables: Vec<Variable> = (0..20).map(|_| Variable::new()).collect();
+    for pair in variables.windows(2) {
+        solver
+            .add_constraint(pair[1] | GE(Strength::REQUIRED) | (pair[0] + 1.0))
+            .unwrap();
+    }
+    let json = solver.to_debug_json();
+    assert_eq!(
+        solver.clone_remapped(&Default::default()).to_debug_json(),
+        json
+    );
+    let value: Value = serde_json::from_str(&json).unwrap();
+    let ids: Vec<&str> = value["variables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["id"].as_str().unwrap())
+        .collect();
+    let expected: Vec<String> = variables.iter().map(|&v| id(v)).collect();
+    assert_eq!(ids, expected);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "derive")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{LayoutVars, NameRegistry, Solver, Strength, Variable};
+
+#[derive(LayoutVars)]
+struct Panel {
+    left: Variable,
+    top: Variable,
+    right: Variable,
+    bottom: Variable,
+}
+
+#[derive(LayoutVars)]
+struct Span {
+    start: kasuari::Variable,
+    end: Variable,
+}
+
+#[test]
+fn new_creates_distinct_variables() {
+    let panel = Panel::new();
+    let [left, top, right, bottom] = panel.variables();
+    assert_eq!(
+        [left, top, right, bottom],
+        [panel.left, panel.top, panel.right, panel.bottom]
+    );
+    assert_ne!(left, top);
+    assert_ne!(right, bottom);
+    assert_ne!(Panel::new().left, panel.left);
+
+    let span = Span::new();
+    assert_eq!(span.variables(), [span.start, span.end]);
+}
+
+#[test]
+fn register_names() {
+    let panel = Panel::new();
+    let mut names = NameRegistry::new();
+    panel.register_names(&mut names, "sidebar");
+    assert_eq!(names.name(panel.left), Some("sidebar.left"));
+    assert_eq!(names.name(panel.bottom), Some("sidebar.bottom"));
+}
+
+#[test]
+fn width_and_height() {
+    let panel = Panel::new();
+    let mut solver = Solver::new();
+    solver
+        .add_constraints([
+            panel.left | EQ(Strength::REQUIRED) | 10.0,
+            panel.top | EQ(Strength::REQUIRED) | 5.0,
+            panel.width() | EQ(Strength::REQUIRED) | 100.0,
+            panel.height() | EQ(Strength::REQUIRED) | 20.0,
+        ])
+        .unwrap();
+    assert_eq!(solver.get_value(panel.right), 110.0);
+    assert_eq!(solver.get_value(panel.bottom), 25.0);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "deterministic")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Solver, Strength, Variable};
+
+/// Solve a layout where the two preferred widths cannot both be satisfied, so the solver has to
+/// choose between equally good solutions.
+fn solve(vars: &[Variable; 5]) -> Vec<(Variable, u64)> {
+    let [window_width, box1_left, box1_right, box2_left, box2_right] = *vars;
+    let mut solver = Solver::new();
+    solver
+        .add_constraints([
+            window_width | GE(Strength::REQUIRED) | 0.0,
+            box1_left | EQ(Strength::REQUIRED) | 0.0,
+            box2_right | EQ(Strength::REQUIRED) | window_width,
+            box2_left | GE(Strength::REQUIRED) | box1_right,
+            box1_left | LE(Strength::REQUIRED) | box1_right,
+            box2_left | LE(Strength::REQUIRED) | box2_right,
+            (box1_right - box1_left) | EQ(Strength::WEAK) | 50.0,
+            (box2_right - box2_left) | EQ(Strength::WEAK) | 100.0,
+        ])
+        .unwrap();
+    solver
+        .add_edit_variable(window_width, Strength::STRONG)
+        .unwrap();
+    solver.suggest_value(window_width, 300.0).unwrap();
+    solver.fetch_changes();
+    solver.suggest_value(window_width, 75.0).unwrap();
+    solver
+        .fetch_changes()
+        .iter()
+        .map(|&(variable, value)| (variable, value.to_bits()))
+        .collect()
+}
+
+#[test]
+fn identical_results() {
+    let vars = [(); 5].map(|_| Variable::new());
+    let expected = solve(&vars);
+    assert!(expected.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    for _ in 0..32 {
+        assert_eq!(solve(&vars), expected);
+    }
+}
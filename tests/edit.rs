@@ -0,0 +1,300 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, SuggestValueError, Variable};
+
+/// A solver where `width` prefers to be 100, but more weakly than a `WEAK` constraint.
+fn dragged_solver(width: Variable) -> Solver {
+    let mut solver = Solver::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK * 0.5) | 100.0)
+        .unwrap();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    solver.suggest_value(width, 250.0).unwrap();
+    assert_eq!(solver.get_value(width), 250.0);
+    solver
+}
+
+#[test]
+fn remove_edit_variable() {
+    let width = Variable::new();
+    let mut solver = dragged_solver(width);
+
+    solver.remove_edit_variable(width).unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+}
+
+#[test]
+fn remove_edit_variable_with_stay() {
+    let width = Variable::new();
+    let mut solver = dragged_solver(width);
+
+    let stay = solver.remove_edit_variable_with_stay(width).unwrap();
+    assert!(!solver.has_edit_variable(&width));
+    assert!(solver.has_constraint(&stay));
+    assert_eq!(solver.get_value(width), 250.0);
+
+    solver.remove_constraint(&stay).unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+}
+
+#[test]
+fn has_edit_variable() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    assert!(!solver.has_edit_variable(&width));
+
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    assert!(solver.has_edit_variable(&width));
+    assert!(!solver.has_edit_variable(&Variable::new()));
+
+    solver.remove_edit_variable(width).unwrap();
+    assert!(!solver.has_edit_variable(&width));
+}
+
+#[test]
+fn sensitivity() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let right = Variable::new();
+    let middle = Variable::new();
+    let unrelated = Variable::new();
+    solver
+        .add_constraints([
+            left | EQ(Strength::REQUIRED) | 0.0,
+            right | LE(Strength::REQUIRED) | 200.0,
+            middle | EQ(Strength::REQUIRED) | ((left + right) / 2.0),
+            unrelated | EQ(Strength::WEAK) | 5.0,
+        ])
+        .unwrap();
+    solver.add_edit_variable(right, Strength::STRONG).unwrap();
+    solver.suggest_value(right, 100.0).unwrap();
+
+    assert_eq!(solver.sensitivity(right, right), Some(1.0));
+    assert_eq!(solver.sensitivity(middle, right), Some(0.5));
+    assert_eq!(solver.sensitivity(left, right), Some(0.0));
+    assert_eq!(solver.sensitivity(unrelated, right), Some(0.0));
+    assert_eq!(solver.sensitivity(middle, left), None);
+
+    // once `right` hits its maximum, suggestions no longer move anything
+    solver.suggest_value(right, 300.0).unwrap();
+    assert_eq!(solver.sensitivity(middle, right), Some(0.0));
+}
+
+#[test]
+fn edit_session() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    let area = Variable::new();
+    solver
+        .add_constraints([
+            width | LE(Strength::REQUIRED) | 800.0,
+            area | EQ(Strength::REQUIRED) | (width + height),
+        ])
+        .unwrap();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    solver.add_edit_variable(height, Strength::STRONG).unwrap();
+
+    let pivots = std::rc::Rc::new(std::cell::Cell::new(0));
+    let counter = pivots.clone();
+    solver.set_pivot_hook(move |_| counter.set(counter.get() + 1));
+
+    solver.begin_edit();
+    solver.suggest_value(width, 1000.0).unwrap();
+    solver.suggest_value(height, 300.0).unwrap();
+    assert_eq!(pivots.get(), 0);
+    solver.end_edit().unwrap();
+    assert_eq!(solver.get_value(width), 800.0);
+    assert_eq!(solver.get_value(height), 300.0);
+    assert_eq!(solver.get_value(area), 1100.0);
+
+    // suggestions outside a session are applied immediately
+    solver.suggest_value(height, 200.0).unwrap();
+    assert_eq!(solver.get_value(area), 1000.0);
+    solver.end_edit().unwrap();
+
+    // adding a constraint ends the session
+    solver.begin_edit();
+    solver.suggest_value(width, 100.0).unwrap();
+    solver
+        .add_constraint(height | GE(Strength::REQUIRED) | 250.0)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+    assert_eq!(solver.get_value(height), 250.0);
+    solver.suggest_value(width, 200.0).unwrap();
+    assert_eq!(solver.get_value(width), 200.0);
+}
+
+#[test]
+fn suggest_values() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    solver
+        .add_constraint(height | LE(Strength::REQUIRED) | width)
+        .unwrap();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    solver.add_edit_variable(height, Strength::STRONG).unwrap();
+
+    solver
+        .suggest_values(&[(width, 100.0), (height, 80.0)])
+        .unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+    assert_eq!(solver.get_value(height), 80.0);
+
+    // nothing is suggested if one of the variables is unknown
+    assert!(solver
+        .suggest_values(&[(width, 50.0), (Variable::new(), 1.0)])
+        .is_err());
+    assert_eq!(solver.get_value(width), 100.0);
+
+    // inside an edit session, the values are only applied when it ends
+    solver.begin_edit();
+    solver
+        .suggest_values(&[(width, 40.0), (height, 30.0)])
+        .unwrap();
+    solver.suggest_value(width, 60.0).unwrap();
+    solver.end_edit().unwrap();
+    assert_eq!(solver.get_value(width), 60.0);
+    assert_eq!(solver.get_value(height), 30.0);
+}
+
+#[test]
+fn edit_handle() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    let handle = solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    assert_eq!(handle.variable(), width);
+
+    solver.suggest(handle, 100.0).unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+
+    // a handle is stale once its edit variable is removed, even if its slot is reused
+    solver.remove_edit_variable(width).unwrap();
+    let other = solver.add_edit_variable(height, Strength::STRONG).unwrap();
+    assert!(matches!(
+        solver.suggest(handle, 50.0),
+        Err(SuggestValueError::UnknownEditVariable)
+    ));
+    solver.suggest(other, 50.0).unwrap();
+    assert_eq!(solver.get_value(height), 50.0);
+}
+
+#[test]
+fn remove_all_edit_variables() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+    solver
+        .add_constraint(height | EQ(Strength::WEAK) | (width * 0.5))
+        .unwrap();
+    let handle = solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    solver.add_edit_variable(height, Strength::STRONG).unwrap();
+    solver
+        .suggest_values(&[(width, 300.0), (height, 20.0)])
+        .unwrap();
+    assert_eq!(solver.get_value(width), 300.0);
+    assert_eq!(solver.get_value(height), 20.0);
+
+    solver.remove_all_edit_variables().unwrap();
+    assert!(!solver.has_edit_variable(&width));
+    assert!(!solver.has_edit_variable(&height));
+    assert!(solver.suggest(handle, 10.0).is_err());
+    assert_eq!(solver.get_value(width), 100.0);
+    assert_eq!(solver.get_value(height), 50.0);
+
+    // edit variables can be added again afterwards
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    solver.suggest_value(width, 200.0).unwrap();
+    assert_eq!(solver.get_value(height), 100.0);
+}
+
+#[test]
+fn suggest_or_add() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+
+    let handle = solver
+        .suggest_or_add(width, Strength::STRONG, 250.0)
+        .unwrap();
+    assert!(solver.has_edit_variable(&width));
+    assert_eq!(solver.get_value(width), 250.0);
+
+    // the second call only suggests
+    assert_eq!(
+        solver
+            .suggest_or_add(width, Strength::STRONG, 150.0)
+            .unwrap(),
+        handle
+    );
+    assert_eq!(solver.get_value(width), 150.0);
+
+    assert!(solver
+        .suggest_or_add(Variable::new(), Strength::REQUIRED, 1.0)
+        .is_err());
+}
+
+#[test]
+fn pin() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let width = Variable::new();
+    let right = Variable::new();
+    solver
+        .add_constraint(right | EQ(Strength::REQUIRED) | (left + width))
+        .unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+    solver.add_edit_variable(right, Strength::STRONG).unwrap();
+    solver.suggest_value(right, 300.0).unwrap();
+    assert_eq!(solver.get_value(left), 200.0);
+
+    // with the left edge pinned, moving the right edge resizes instead of moving
+    solver.pin(left, Strength::MEDIUM).unwrap();
+    assert!(solver.is_pinned(left));
+    solver.suggest_value(right, 400.0).unwrap();
+    assert_eq!(solver.get_value(left), 200.0);
+    assert_eq!(solver.get_value(width), 200.0);
+
+    solver.unpin(left).unwrap();
+    assert!(!solver.is_pinned(left));
+    assert_eq!(solver.get_value(width), 100.0);
+    assert!(solver.unpin(left).is_err());
+}
+
+#[test]
+fn failed_add_keeps_edit_session() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | LE(Strength::REQUIRED) | 800.0)
+        .unwrap();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+
+    solver.begin_edit();
+    solver.suggest_value(width, 100.0).unwrap();
+    let conflict: Constraint = width | GE(Strength::REQUIRED) | 1000.0;
+    assert!(solver.add_constraint(conflict.clone()).is_err());
+    assert!(solver.add_named("min", conflict.clone()).is_err());
+    assert!(solver.add_constraints_atomic([conflict]).is_err());
+
+    assert!(solver.is_editing());
+    solver.suggest_value(width, 200.0).unwrap();
+    solver.end_edit().unwrap();
+    assert!(!solver.is_editing());
+    assert_eq!(solver.get_value(width), 200.0);
+
+    // a successful add ends it
+    solver.begin_edit();
+    solver
+        .add_constraint(width | GE(Strength::REQUIRED) | 0.0)
+        .unwrap();
+    assert!(!solver.is_editing());
+}
@@ -0,0 +1,142 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, SetEnabledError, Solver, Strength, Variable};
+
+#[test]
+fn set_enabled() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let wide: Constraint = width | EQ(Strength::STRONG) | 120.0;
+    let narrow: Constraint = width | LE(Strength::MEDIUM) | 60.0;
+    solver.add_constraint(wide.clone()).unwrap();
+    solver.add_named("narrow", narrow.clone()).unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 80.0)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 120.0);
+
+    solver.set_enabled(&wide, false).unwrap();
+    assert!(!solver.is_enabled(&wide));
+    assert!(solver.has_constraint(&wide));
+    assert_eq!(solver.get_value(width), 60.0);
+
+    solver.set_enabled(&narrow, false).unwrap();
+    assert_eq!(solver.get_value(width), 80.0);
+    assert_eq!(solver.named("narrow"), Some(&narrow));
+
+    // disabled constraints survive rebuilding
+    solver.rebuild().unwrap();
+    assert_eq!(solver.get_value(width), 80.0);
+
+    solver.set_enabled(&wide, true).unwrap();
+    assert!(solver.is_enabled(&wide));
+    assert_eq!(solver.get_value(width), 120.0);
+
+    // removing a disabled constraint works as usual
+    solver.remove_constraint(&wide).unwrap();
+    assert_eq!(solver.get_value(width), 80.0);
+    solver.remove_named("narrow").unwrap();
+    assert_eq!(solver.get_value(width), 80.0);
+    assert!(matches!(
+        solver.set_enabled(&wide, true),
+        Err(SetEnabledError::UnknownConstraint)
+    ));
+}
+
+#[test]
+fn set_required_enabled() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    solver.add_named("max", max.clone()).unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 150.0)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+
+    solver.set_enabled(&max, false).unwrap();
+    assert!(solver.has_constraint(&max));
+    assert_eq!(solver.named("max"), Some(&max));
+    assert_eq!(solver.get_value(width), 150.0);
+    assert!(solver.add_constraint(max.clone()).is_err());
+
+    // a conflicting constraint added meanwhile keeps it disabled
+    let min: Constraint = width | GE(Strength::REQUIRED) | 120.0;
+    solver.add_constraint(min.clone()).unwrap();
+    assert!(matches!(
+        solver.set_enabled(&max, true),
+        Err(SetEnabledError::UnsatisfiableConstraint)
+    ));
+    assert!(!solver.is_enabled(&max));
+
+    solver.remove_constraint(&min).unwrap();
+    solver.set_enabled(&max, true).unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+    assert_eq!(solver.named("max"), Some(&max));
+
+    solver.set_enabled(&max, false).unwrap();
+    solver.remove_constraint(&max).unwrap();
+    assert!(!solver.has_constraint(&max));
+    assert_eq!(solver.named("max"), None);
+}
+
+#[test]
+fn set_merged_enabled() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let width = Variable::new();
+    let first: Constraint = width | EQ(Strength::MEDIUM) | 100.0;
+    let second: Constraint = width | EQ(Strength::MEDIUM) | 100.0;
+    solver.add_constraint(first.clone()).unwrap();
+    solver.add_constraint(second.clone()).unwrap();
+    // together the two outweigh a stronger constraint
+    solver
+        .add_constraint(width | EQ(Strength::MEDIUM * 1.5) | 50.0)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+
+    solver.set_enabled(&second, false).unwrap();
+    assert_eq!(solver.get_value(width), 50.0);
+    solver.set_enabled(&second, true).unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+
+    solver.set_enabled(&first, false).unwrap();
+    assert_eq!(solver.get_value(width), 50.0);
+    solver.remove_constraint(&first).unwrap();
+    assert_eq!(solver.get_value(width), 50.0);
+    solver
+        .add_constraint(width | EQ(Strength::MEDIUM) | 100.0)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+}
+
+#[test]
+fn update_disabled_constant() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    solver.add_named("max", max.clone()).unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 150.0)
+        .unwrap();
+    solver.set_enabled(&max, false).unwrap();
+
+    let max = solver.update_constant(&max, -120.0).unwrap();
+    assert!(solver.has_constraint(&max));
+    assert!(!solver.is_enabled(&max));
+    assert_eq!(solver.named("max"), Some(&max));
+    assert_eq!(solver.get_value(width), 150.0);
+    solver.set_enabled(&max, true).unwrap();
+    assert_eq!(solver.get_value(width), 120.0);
+
+    // a soft constraint that is the primary of merged constraints stays disabled too
+    solver.set_merge_parallel(true);
+    let strong: Constraint = width | EQ(Strength::STRONG) | 50.0;
+    solver.add_constraint(strong.clone()).unwrap();
+    assert_eq!(solver.get_value(width), 50.0);
+    solver.set_enabled(&strong, false).unwrap();
+    let strong = solver.update_constant(&strong, -60.0).unwrap();
+    assert!(!solver.is_enabled(&strong));
+    assert_eq!(solver.get_value(width), 120.0);
+    solver.set_enabled(&strong, true).unwrap();
+    assert_eq!(solver.get_value(width), 60.0);
+}
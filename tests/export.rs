@@ -0,0 +1,90 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Expression, Param, Solver, Strength, Variable};
+
+fn layout() -> (Solver, Variable, Variable) {
+    let mut solver = Solver::new();
+    let (left, width) = (Variable::from_id(1), Variable::from_id(2));
+    solver
+        .add_constraints([
+            (left + width) | LE(Strength::REQUIRED) | 100.0,
+            width | EQ(Strength::WEAK) | 80.0,
+            (2.0 * left) | GE(Strength::MEDIUM) | 40.0,
+        ])
+        .unwrap();
+    (solver, left, width)
+}
+
+#[test]
+fn export_lp() {
+    let (solver, _, _) = layout();
+    assert_eq!(
+        solver.export_lp(),
+        "\\ kasuari solver
+Minimize
+ obj: c2p + c2m + 1000 c3e
+Subject To
+ c1: v1 + v2 <= 100
+ c2: v2 - c2p + c2m = 80
+ c3: 2 v1 + c3e >= 40
+Bounds
+ v1 free
+ v2 free
+End
+"
+    );
+}
+
+#[test]
+fn export_mps() {
+    let (solver, _, _) = layout();
+    assert_eq!(
+        solver.export_mps(),
+        "NAME          kasuari
+ROWS
+ N  obj
+ L  c1
+ E  c2
+ G  c3
+COLUMNS
+    v1         c1         1
+    v1         c3         2
+    v2         c1         1
+    v2         c2         1
+    c2p        obj        1
+    c2p        c2         -1
+    c2m        obj        1
+    c2m        c2         1
+    c3e        obj        1000
+    c3e        c3         1
+RHS
+    RHS        c1         100
+    RHS        c2         80
+    RHS        c3         40
+BOUNDS
+ FR BND        v1
+ FR BND        v2
+ENDATA
+"
+    );
+}
+
+#[test]
+fn export_edits_params_and_disabled() {
+    let (mut solver, left, width) = layout();
+    let gap = Param::new();
+    let disabled: Constraint = width | GE(Strength::REQUIRED) | 90.0;
+    solver.add_constraint(disabled.clone()).unwrap();
+    solver.set_enabled(&disabled, false).unwrap();
+    solver
+        .add_constraint(left | GE(Strength::REQUIRED) | (Expression::from(gap) + 10.0))
+        .unwrap();
+    solver.set_param(gap, 5.0).unwrap();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+    solver.suggest_value(width, 30.0).unwrap();
+
+    let lp = solver.export_lp();
+    assert!(!lp.contains(">= 90"), "{lp}");
+    assert!(lp.contains(" c4: v1 >= 15\n"), "{lp}");
+    assert!(lp.contains(" c5: v2 - c5p + c5m = 30\n"), "{lp}");
+    assert!(lp.contains(" + 1000000 c5p + 1000000 c5m\n"), "{lp}");
+}
@@ -0,0 +1,54 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn min_of() {
+    let mut solver = Solver::new();
+    let (available, preferred, width) = (Variable::new(), Variable::new(), Variable::new());
+    solver
+        .add_edit_variable(available, Strength::STRONG)
+        .unwrap();
+    solver
+        .add_edit_variable(preferred, Strength::STRONG)
+        .unwrap();
+    let group = Constraint::min_of(width, [available, preferred], Strength::MEDIUM);
+    assert_eq!(group.len(), 4);
+    solver.add_group(&group).unwrap();
+    // a weak push in the other direction doesn't move it off the minimum
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 0.0)
+        .unwrap();
+
+    solver.suggest_value(available, 80.0).unwrap();
+    solver.suggest_value(preferred, 120.0).unwrap();
+    assert_eq!(solver.get_value(width), 80.0);
+    solver.suggest_value(available, 200.0).unwrap();
+    assert_eq!(solver.get_value(width), 120.0);
+
+    solver.remove_group(&group).unwrap();
+    assert_eq!(solver.get_value(width), 0.0);
+}
+
+#[test]
+fn max_of() {
+    let mut solver = Solver::new();
+    let (left, right, end) = (Variable::new(), Variable::new(), Variable::new());
+    solver.add_edit_variable(left, Strength::STRONG).unwrap();
+    solver.add_edit_variable(right, Strength::STRONG).unwrap();
+    solver
+        .add_group(&Constraint::max_of(
+            end,
+            [left + 10.0, right + 20.0],
+            Strength::MEDIUM,
+        ))
+        .unwrap();
+    solver
+        .add_constraint(end | EQ(Strength::WEAK) | 1000.0)
+        .unwrap();
+
+    solver.suggest_value(left, 50.0).unwrap();
+    solver.suggest_value(right, 30.0).unwrap();
+    assert_eq!(solver.get_value(end), 60.0);
+    solver.suggest_value(right, 70.0).unwrap();
+    assert_eq!(solver.get_value(end), 90.0);
+}
@@ -0,0 +1,73 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+fn boxes(solver: &mut Solver, total: f64) -> (Variable, Variable) {
+    let (left, right) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint((left + right) | EQ(Strength::REQUIRED) | total)
+        .unwrap();
+    (left, right)
+}
+
+#[test]
+fn fair_equalities() {
+    let mut solver = Solver::new();
+    let (left, right) = boxes(&mut solver, 100.0);
+    let left_preferred: Constraint = left | EQ(Strength::WEAK) | 80.0;
+    let right_preferred: Constraint = right | EQ(Strength::WEAK) | 60.0;
+    let left_group = left_preferred.fair(5.0, 8);
+    solver.add_group(&left_group).unwrap();
+    solver.add_group(&right_preferred.fair(5.0, 8)).unwrap();
+    assert_eq!(solver.get_value(left), 60.0);
+    assert_eq!(solver.get_value(right), 40.0);
+
+    // the group can be removed like any other
+    solver.remove_group(&left_group).unwrap();
+    assert_eq!(solver.get_value(right), 60.0);
+
+    // a stronger constraint still wins outright
+    let (top, bottom) = boxes(&mut solver, 100.0);
+    let top_preferred: Constraint = top | EQ(Strength::MEDIUM) | 80.0;
+    let bottom_preferred: Constraint = bottom | EQ(Strength::WEAK) | 80.0;
+    solver.add_group(&top_preferred.fair(5.0, 8)).unwrap();
+    solver.add_group(&bottom_preferred.fair(5.0, 8)).unwrap();
+    assert_eq!(solver.get_value(top), 80.0);
+    assert_eq!(solver.get_value(bottom), 20.0);
+}
+
+#[test]
+fn fair_inequalities() {
+    let mut solver = Solver::new();
+    let (left, right) = boxes(&mut solver, 100.0);
+    let left_min: Constraint = left | GE(Strength::STRONG) | 70.0;
+    let right_min: Constraint = right | GE(Strength::STRONG) | 70.0;
+    solver.add_group(&left_min.fair(10.0, 4)).unwrap();
+    solver.add_group(&right_min.fair(10.0, 4)).unwrap();
+    assert_eq!(solver.get_value(left), 50.0);
+    assert_eq!(solver.get_value(right), 50.0);
+
+    // violations beyond the last step grow linearly, and are still split
+    let mut solver = Solver::new();
+    let (left, right) = boxes(&mut solver, 100.0);
+    let left_max: Constraint = left | LE(Strength::STRONG) | 10.0;
+    let right_max: Constraint = right | LE(Strength::STRONG) | 10.0;
+    solver.add_group(&left_max.fair(10.0, 4)).unwrap();
+    solver.add_group(&right_max.fair(10.0, 4)).unwrap();
+    assert!(solver.get_value(left) >= 40.0);
+    assert!(solver.get_value(right) >= 40.0);
+}
+
+#[test]
+fn fair_keeps_tag() {
+    let mut solver = Solver::new();
+    let (left, right) = boxes(&mut solver, 100.0);
+    let required: Constraint = left | GE(Strength::REQUIRED) | 10.0;
+    assert_eq!(required.fair(5.0, 8).constraints(), [required]);
+
+    let preferred: Constraint = right | EQ(Strength::WEAK) | 30.0;
+    let group = preferred.with_tag(3).fair(5.0, 2);
+    assert_eq!(group.len(), 11);
+    solver.add_group(&group).unwrap();
+    assert_eq!(solver.get_value(right), 30.0);
+    assert_eq!(solver.remove_by_tag(3).unwrap().len(), 11);
+}
@@ -0,0 +1,79 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, ConstraintGroup, Solver, Strength, Variable};
+
+/// A row of `n` boxes of preferred width 10 inside `container`, with 2 between them.
+fn row(container: Variable, n: usize) -> (Vec<Variable>, ConstraintGroup) {
+    let boxes: Vec<(Variable, Variable)> =
+        (0..n).map(|_| (Variable::new(), Variable::new())).collect();
+    let mut group = ConstraintGroup::new();
+    let mut previous_right = None;
+    for &(left, width) in &boxes {
+        group.push(width | EQ(Strength::MEDIUM) | 10.0);
+        group.push(width | GE(Strength::REQUIRED) | 0.0);
+        match previous_right {
+            None => group.push(left | EQ(Strength::REQUIRED) | 0.0),
+            Some(right) => group.push(left | EQ(Strength::REQUIRED) | (right + 2.0)),
+        }
+        previous_right = Some(left + width);
+    }
+    group.push(previous_right.unwrap() | LE(Strength::REQUIRED) | container);
+    (boxes.into_iter().map(|(_, width)| width).collect(), group)
+}
+
+#[test]
+fn add_and_remove_group() {
+    let mut solver = Solver::new();
+    let container = Variable::new();
+    solver
+        .add_constraint(container | EQ(Strength::STRONG) | 100.0)
+        .unwrap();
+    let (widths, group) = row(container, 5);
+    solver.add_group(&group).unwrap();
+    assert!(widths.iter().all(|&width| solver.get_value(width) == 10.0));
+
+    // the boxes of a second row that does not fit shrink
+    let (other_widths, other) = row(container, 20);
+    solver.add_group(&other).unwrap();
+    let total: f64 = other_widths
+        .iter()
+        .map(|&width| solver.get_value(width))
+        .sum();
+    assert!(total < 200.0);
+
+    solver.remove_group(&other).unwrap();
+    assert!(!other.constraints().iter().any(|c| solver.has_constraint(c)));
+    assert_eq!(solver.get_value(container), 100.0);
+    assert!(widths.iter().all(|&width| solver.get_value(width) == 10.0));
+
+    solver.remove_group(&group).unwrap();
+    assert!(solver.remove_group(&group).is_err());
+}
+
+#[test]
+fn add_group_is_atomic() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let conflicting: Constraint = x | GE(Strength::REQUIRED) | 20.0;
+    let group: ConstraintGroup = [
+        x | LE(Strength::REQUIRED) | 10.0,
+        x | EQ(Strength::WEAK) | 5.0,
+        conflicting.clone(),
+    ]
+    .into_iter()
+    .collect();
+    let (failed, _) = solver.add_group(&group).unwrap_err();
+    assert_eq!(failed, conflicting);
+    assert!(!group.constraints().iter().any(|c| solver.has_constraint(c)));
+}
+
+#[test]
+fn remove_group_with_unknown_constraint() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let added: Constraint = x | EQ(Strength::STRONG) | 10.0;
+    solver.add_constraint(added.clone()).unwrap();
+    let group = ConstraintGroup::from(vec![added.clone(), x | EQ(Strength::WEAK) | 0.0]);
+    assert!(solver.remove_group(&group).is_err());
+    assert!(solver.has_constraint(&added));
+    assert_eq!(solver.get_value(x), 10.0);
+}
@@ -0,0 +1,87 @@
+use core::ops::DerefMut;
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn constraint_guard() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+
+    let overlay = |solver: &mut Solver, early: bool| -> Option<f64> {
+        let guard = solver
+            .add_constraint_scoped(width | LE(Strength::REQUIRED) | 60.0)
+            .unwrap();
+        if early {
+            return None;
+        }
+        Some(guard.get_value(width))
+    };
+    assert_eq!(overlay(&mut solver, true), None);
+    assert_eq!(solver.get_value(width), 100.0);
+    assert_eq!(overlay(&mut solver, false), Some(60.0));
+    assert_eq!(solver.get_value(width), 100.0);
+
+    // a released constraint stays
+    let guard = solver
+        .add_constraint_scoped(width | EQ(Strength::STRONG) | 30.0)
+        .unwrap();
+    let kept = guard.release();
+    assert!(solver.has_constraint(&kept));
+    assert_eq!(solver.get_value(width), 30.0);
+
+    // removing the constraint through the guard is fine
+    let mut guard = solver
+        .add_constraint_scoped(width | EQ(Strength::REQUIRED) | 10.0)
+        .unwrap();
+    let constraint = guard.constraint().clone();
+    guard.remove_constraint(&constraint).unwrap();
+    drop(guard);
+    assert_eq!(solver.get_value(width), 30.0);
+}
+
+#[test]
+fn scope() {
+    let mut solver = Solver::new();
+    let (left, width) = (Variable::new(), Variable::new());
+    let outer: Constraint = width | EQ(Strength::WEAK) | 100.0;
+    solver.add_constraint(outer.clone()).unwrap();
+
+    let kept = {
+        let mut scope = solver.scope();
+        let min: Constraint = width | GE(Strength::REQUIRED) | 20.0;
+        scope.add_constraint(min.clone()).unwrap();
+        scope
+            .add_constraint(left | EQ(Strength::REQUIRED) | 10.0)
+            .unwrap();
+        scope.add_edit_variable(width, Strength::STRONG).unwrap();
+        scope.suggest_value(width, 5.0).unwrap();
+        assert_eq!(scope.get_value(width), 20.0);
+        assert_eq!(scope.get_value(left), 10.0);
+
+        // removed early through the scope
+        scope.remove_constraint(&min).unwrap();
+        assert_eq!(scope.get_value(width), 5.0);
+
+        // added to the solver directly, so it outlives the scope
+        let kept: Constraint = left | LE(Strength::REQUIRED) | 300.0;
+        scope.deref_mut().add_constraint(kept.clone()).unwrap();
+        assert_eq!(scope.constraints().len(), 2);
+        kept
+    };
+    assert!(!solver.has_edit_variable(&width));
+    assert!(solver.has_constraint(&outer));
+    assert!(solver.has_constraint(&kept));
+    assert_eq!(solver.get_value(width), 100.0);
+
+    let mut scope = solver.scope();
+    scope
+        .add_constraint(width | EQ(Strength::STRONG) | 40.0)
+        .unwrap();
+    assert_eq!(scope.get_value(width), 40.0);
+    scope.close().unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "heapless")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{FixedSolver, FixedSolverError, Strength, Variable};
+
+#[test]
+fn fixed_solver_matches_solver() {
+    let mut solver = FixedSolver::<4, 8>::new();
+
+    let window_width = Variable::new();
+    let left = Variable::new();
+    let right = Variable::new();
+
+    for constraint in [
+        window_width | GE(Strength::REQUIRED) | 0.0,
+        left | EQ(Strength::REQUIRED) | 0.0,
+        right | LE(Strength::REQUIRED) | window_width,
+        (right - left) | EQ(Strength::WEAK) | 50.0,
+    ] {
+        solver.add_constraint(&constraint).unwrap();
+    }
+    solver
+        .add_edit_variable(window_width, Strength::STRONG)
+        .unwrap();
+
+    solver.suggest_value(window_width, 300.0).unwrap();
+    assert_eq!(solver.get_value(window_width), 300.0);
+    assert_eq!(solver.get_value(right), 50.0);
+
+    solver.suggest_value(window_width, 30.0).unwrap();
+    assert_eq!(solver.get_value(window_width), 30.0);
+    assert_eq!(solver.get_value(right), 30.0);
+
+    let mut changes: Vec<_> = solver.fetch_changes().collect();
+    changes.sort_by_key(|&(variable, _)| variable);
+    assert_eq!(changes, [(window_width, 30.0), (right, 30.0)]);
+    assert_eq!(solver.fetch_changes().count(), 0);
+}
+
+#[test]
+fn remove_constraint() {
+    let mut solver = FixedSolver::<1, 2>::new();
+    let val = Variable::new();
+
+    let constraint = solver
+        .add_constraint(&(val | EQ(Strength::REQUIRED) | 100.0))
+        .unwrap();
+    assert_eq!(solver.get_value(val), 100.0);
+
+    solver.remove_constraint(constraint).unwrap();
+    assert!(!solver.has_constraint(constraint));
+    solver
+        .add_constraint(&(val | EQ(Strength::REQUIRED) | 10.0))
+        .unwrap();
+    assert_eq!(solver.get_value(val), 10.0);
+    assert!(matches!(
+        solver.remove_constraint(constraint),
+        Err(FixedSolverError::UnknownConstraint)
+    ));
+}
+
+#[test]
+fn capacity_errors() {
+    let mut solver = FixedSolver::<2, 2>::new();
+    let [a, b, c] = [Variable::new(), Variable::new(), Variable::new()];
+
+    solver
+        .add_constraint(&(a | GE(Strength::REQUIRED) | b))
+        .unwrap();
+    assert!(matches!(
+        solver.add_constraint(&(a | GE(Strength::REQUIRED) | c)),
+        Err(FixedSolverError::VariableCapacityExceeded)
+    ));
+    solver
+        .add_constraint(&(a | EQ(Strength::REQUIRED) | 5.0))
+        .unwrap();
+    assert!(matches!(
+        solver.add_constraint(&(b | EQ(Strength::WEAK) | 1.0)),
+        Err(FixedSolverError::ConstraintCapacityExceeded)
+    ));
+    assert_eq!(solver.get_value(a), 5.0);
+}
+
+#[test]
+fn unsatisfiable_constraint_is_not_kept() {
+    let mut solver = FixedSolver::<1, 2>::new();
+    let val = Variable::new();
+
+    solver
+        .add_constraint(&(val | GE(Strength::REQUIRED) | 10.0))
+        .unwrap();
+    assert!(matches!(
+        solver.add_constraint(&(val | LE(Strength::REQUIRED) | 5.0)),
+        Err(FixedSolverError::UnsatisfiableConstraint)
+    ));
+    solver
+        .add_constraint(&(val | LE(Strength::REQUIRED) | 20.0))
+        .unwrap();
+    assert!((10.0..=20.0).contains(&solver.get_value(val)));
+}
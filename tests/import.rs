@@ -0,0 +1,142 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, ImportError, ImportedProgram, Solver, Strength, Variable};
+
+fn layout() -> (Solver, Variable, Variable) {
+    let mut solver = Solver::new();
+    let (left, width) = (Variable::new(), Variable::new());
+    solver
+        .add_constraints([
+            (left + width) | LE(Strength::REQUIRED) | 100.0,
+            width | EQ(Strength::WEAK) | 120.0,
+            (2.0 * left) | GE(Strength::MEDIUM) | 40.0,
+        ])
+        .unwrap();
+    (solver, left, width)
+}
+
+fn round_trip(program: &ImportedProgram, solver: &Solver, variables: [Variable; 2]) {
+    let strengths: Vec<Strength> = program
+        .constraints()
+        .iter()
+        .map(Constraint::strength)
+        .collect();
+    assert_eq!(
+        strengths,
+        [Strength::REQUIRED, Strength::WEAK, Strength::MEDIUM]
+    );
+    assert_eq!(program.objective().terms, []);
+    let mut imported = Solver::new();
+    imported
+        .add_constraints(program.constraints().iter().cloned())
+        .unwrap();
+    for variable in variables {
+        let column = program.variable(&format!("v{}", variable.id())).unwrap();
+        assert_eq!(imported.get_value(column), solver.get_value(variable));
+    }
+}
+
+#[test]
+fn lp_round_trip() {
+    let (solver, left, width) = layout();
+    let program = ImportedProgram::from_lp(&solver.export_lp()).unwrap();
+    assert_eq!(program.columns().count(), 2);
+    assert_eq!(program.variable("c2p"), None);
+    round_trip(&program, &solver, [left, width]);
+}
+
+#[test]
+fn mps_round_trip() {
+    let (solver, left, width) = layout();
+    let program = ImportedProgram::from_mps(&solver.export_mps()).unwrap();
+    round_trip(&program, &solver, [left, width]);
+}
+
+#[test]
+fn lp_bounds() {
+    let program = ImportedProgram::from_lp(
+        "\\ bounds of all kinds
+        Minimize
+         cost: x + y - z
+        Subject To
+         total: x + y + z
+           + w >= 2
+        Bounds
+         -1 <= x <= 4
+         y >= 1.5
+         z <= 3
+         w free
+        End",
+    )
+    .unwrap();
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(program.constraints().iter().cloned())
+        .unwrap();
+    let cost = solver.new_variable();
+    solver
+        .add_constraint(cost | EQ(Strength::REQUIRED) | program.objective().clone())
+        .unwrap();
+    let (x, w) = (program.variable("x").unwrap(), program.variable("w").unwrap());
+    assert_eq!(solver.bounds_of(x), (-1.0, 4.0));
+    assert_eq!(solver.bounds_of(w), (f64::NEG_INFINITY, f64::INFINITY));
+    assert!((solver.minimize(cost) + 2.5).abs() < 1e-3);
+}
+
+#[test]
+fn mps_ranges_and_bounds() {
+    // maximize x + y with 1 <= x - y <= 3, x + 2 y <= 8 and y <= 2
+    let program = ImportedProgram::from_mps(
+        "NAME          TEST
+OBJSENSE
+    MAX
+ROWS
+ N  profit
+ L  diff
+ L  limit
+COLUMNS
+    x         profit    1          diff      1
+    x         limit     1
+    y         profit    1          diff      -1
+    y         limit     2
+RHS
+    RHS       diff      3          limit     8
+RANGES
+    RNG       diff      2
+BOUNDS
+ UP BND       y         2
+ENDATA
+",
+    )
+    .unwrap();
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(program.constraints().iter().cloned())
+        .unwrap();
+    let profit = solver.new_variable();
+    solver
+        .add_constraint(profit | EQ(Strength::REQUIRED) | program.objective().clone())
+        .unwrap();
+    // x = 14 / 3 and y = 5 / 3
+    assert!((solver.minimize(profit) + 19.0 / 3.0).abs() < 1e-3);
+    let y = program.variable("y").unwrap();
+    assert!(solver.bounds_of(y).0.abs() < 1e-9);
+}
+
+#[test]
+fn import_errors() {
+    assert_eq!(
+        ImportedProgram::from_lp("Minimize\n obj: x\nSubject To\n c1: x >=\nEnd").unwrap_err(),
+        ImportError::InvalidLine(4)
+    );
+    assert_eq!(
+        ImportedProgram::from_lp("Minimize\n obj: x\nGeneral\n x\nEnd").unwrap_err(),
+        ImportError::IntegerColumn("x".into())
+    );
+    assert_eq!(
+        ImportedProgram::from_mps("ROWS\n N  obj\nCOLUMNS\n    x  c1  1\nENDATA\n").unwrap_err(),
+        ImportError::UnknownRow {
+            line: 4,
+            row: "c1".into()
+        }
+    );
+}
@@ -0,0 +1,62 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Convergence, Solver, Strength, Variable};
+
+#[test]
+fn converges() {
+    let mut solver = Solver::new();
+    let window = Variable::new();
+    let text_width = Variable::new();
+    let text_height = Variable::new();
+    let scrollbar = Variable::new();
+    solver.add_edit_variable(window, Strength::STRONG).unwrap();
+    solver.suggest_value(window, 100.0).unwrap();
+    solver
+        .add_edit_variable(scrollbar, Strength::STRONG)
+        .unwrap();
+    solver
+        .add_constraint(text_width | EQ(Strength::REQUIRED) | (window - scrollbar))
+        .unwrap();
+    // wrapping 5500 square pixels of text into a 50 pixel high viewport needs a scroll bar
+    solver
+        .add_content_size(text_height, [text_width], Strength::STRONG, |inputs| {
+            (5500.0 / inputs[0]).ceil()
+        })
+        .unwrap();
+
+    let convergence = solver.solve_iteratively(10, |solver, _| {
+        let needs_scrollbar = solver.get_value(text_height) > 50.0;
+        solver
+            .suggest_value(scrollbar, if needs_scrollbar { 10.0 } else { 0.0 })
+            .unwrap();
+    });
+    assert!(convergence.is_converged());
+    assert_eq!(solver.get_value(scrollbar), 10.0);
+    assert_eq!(solver.get_value(text_width), 90.0);
+    assert_eq!(solver.get_value(text_height), 62.0);
+}
+
+#[test]
+fn oscillates() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    let convergence = solver.solve_iteratively(10, |solver, _| {
+        let flipped = 1.0 - solver.get_value(x);
+        solver.suggest_value(x, flipped).unwrap();
+    });
+    assert_eq!(convergence, Convergence::Oscillating { changed: vec![x] });
+}
+
+#[test]
+fn diverges() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    let mut passes = 0;
+    let convergence = solver.solve_iteratively(5, |solver, pass| {
+        passes = pass;
+        solver.suggest_value(x, pass as f64).unwrap();
+    });
+    assert_eq!(passes, 5);
+    assert_eq!(convergence, Convergence::Diverged { changed: vec![x] });
+}
@@ -0,0 +1,48 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{KeyedSolver, Strength, SuggestValueError};
+
+#[test]
+fn keyed_solver() {
+    let mut solver: KeyedSolver<u32> = KeyedSolver::new();
+    let (window, sidebar) = (solver.var(1), solver.var(2));
+    assert_eq!(solver.var(1), window);
+    assert_eq!(solver.variable(3), None);
+    assert_eq!(solver.key(sidebar), Some(2));
+    solver
+        .solver_mut()
+        .add_constraint(sidebar | EQ(Strength::REQUIRED) | (window / 4.0))
+        .unwrap();
+    solver.add_edit_key(1, Strength::STRONG).unwrap();
+    solver.suggest_key_value(1, 400.0).unwrap();
+    assert_eq!(solver.value(2), Some(100.0));
+    assert_eq!(solver.value(3), None);
+
+    let mut changes = solver.fetch_key_changes().to_vec();
+    changes.sort_by_key(|&(key, _)| key);
+    assert_eq!(changes, [(1, 400.0), (2, 100.0)]);
+    assert!(solver.fetch_key_changes().is_empty());
+    assert!(matches!(
+        solver.suggest_key_value(3, 1.0),
+        Err(SuggestValueError::UnknownEditVariable)
+    ));
+
+    assert_eq!(solver.forget(2), Some(sidebar));
+    assert_eq!(solver.key(sidebar), None);
+    assert_ne!(solver.var(2), sidebar);
+    solver.suggest_key_value(1, 800.0).unwrap();
+    assert_eq!(solver.fetch_key_changes(), [(1, 800.0)]);
+}
+
+#[test]
+fn keyed_value_uses_default() {
+    let mut solver: KeyedSolver<u32> = KeyedSolver::new();
+    let width = solver.var(1);
+    solver.solver_mut().set_default_value(width, 80.0);
+    assert_eq!(solver.value(1), Some(80.0));
+    assert_eq!(solver.fetch_key_changes(), [(1, 80.0)]);
+
+    // the keys survive a reset of the solver, as does the default
+    solver.solver_mut().reset();
+    assert_eq!(solver.variable(1), Some(width));
+    assert_eq!(solver.value(1), Some(80.0));
+}
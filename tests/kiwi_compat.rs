@@ -0,0 +1,47 @@
+#![cfg(feature = "kiwi-compat")]
+
+use kasuari::compat::kiwi::Kiwi;
+use kasuari::WeightedRelation::*;
+use kasuari::{
+    AddConstraintError, AddEditVariableError, Constraint, RemoveEditVariableError, Solver,
+    Strength, SuggestValueError, Variable,
+};
+
+#[test]
+fn aliases() {
+    let mut solver = Solver::new();
+    let (x, y) = (Variable::new(), Variable::new());
+    let sum: Constraint = (x + y) | EQ(Strength::REQUIRED) | 100.0;
+    solver.addConstraint(sum.clone()).unwrap();
+    assert!(solver.hasConstraint(&sum));
+    assert!(matches!(
+        solver.addConstraint(sum.clone()),
+        Err(AddConstraintError::DuplicateConstraint)
+    ));
+
+    assert!(matches!(
+        solver.suggestValue(x, 30.0),
+        Err(SuggestValueError::UnknownEditVariable)
+    ));
+    solver.addEditVariable(x, Strength::STRONG).unwrap();
+    assert!(solver.hasEditVariable(&x));
+    assert!(matches!(
+        solver.addEditVariable(x, Strength::STRONG),
+        Err(AddEditVariableError::DuplicateEditVariable)
+    ));
+    solver.suggestValue(x, 30.0).unwrap();
+    let mut changes = solver.updateVariables().to_vec();
+    changes.sort_by_key(|&(v, _)| v);
+    assert_eq!(changes, [(x, 30.0), (y, 70.0)]);
+    // only the changes since the last call are returned
+    assert!(solver.updateVariables().is_empty());
+
+    solver.removeEditVariable(x).unwrap();
+    assert!(matches!(
+        solver.removeEditVariable(x),
+        Err(RemoveEditVariableError::UnknownEditVariable)
+    ));
+    solver.removeConstraint(&sum).unwrap();
+    assert!(!solver.hasConstraint(&sum));
+    assert!(solver.dumps().contains("Constraints"));
+}
@@ -0,0 +1,109 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Axis, Constraint, Element, Solver, Strength};
+
+fn fixed_size(element: &Element, width: f64, height: f64) -> [Constraint; 2] {
+    [
+        element.width() | EQ(Strength::REQUIRED) | width,
+        element.height() | EQ(Strength::REQUIRED) | height,
+    ]
+}
+
+#[test]
+fn after_and_above() {
+    let a = Element::new();
+    let b = Element::new();
+    let c = Element::new();
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(
+            [
+                a.left | EQ(Strength::REQUIRED) | 0.0,
+                a.top | EQ(Strength::REQUIRED) | 0.0,
+                b.top | EQ(Strength::REQUIRED) | 0.0,
+                c.left | EQ(Strength::REQUIRED) | 0.0,
+                // pull everything towards the origin
+                b.left | EQ(Strength::WEAK) | 0.0,
+                c.top | EQ(Strength::WEAK) | 0.0,
+            ]
+            .into_iter()
+            .chain(fixed_size(&a, 10.0, 20.0))
+            .chain(fixed_size(&b, 10.0, 20.0))
+            .chain(fixed_size(&c, 10.0, 20.0))
+            .chain(b.after(&a, 5.0, Strength::REQUIRED))
+            .chain(a.above(&c, 2.0, Strength::REQUIRED)),
+        )
+        .unwrap();
+    assert_eq!(solver.get_value(b.left), 15.0);
+    assert_eq!(solver.get_value(c.top), 22.0);
+}
+
+#[test]
+fn inside() {
+    let outer = Element::new();
+    let inner = Element::new();
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(
+            [
+                outer.left | EQ(Strength::REQUIRED) | 0.0,
+                outer.top | EQ(Strength::REQUIRED) | 0.0,
+                inner.width() | EQ(Strength::WEAK) | 1000.0,
+                inner.height() | EQ(Strength::WEAK) | 1000.0,
+            ]
+            .into_iter()
+            .chain(fixed_size(&outer, 100.0, 50.0))
+            .chain(inner.inside(&outer, 10.0, Strength::REQUIRED)),
+        )
+        .unwrap();
+    assert_eq!(solver.get_value(inner.left), 10.0);
+    assert_eq!(solver.get_value(inner.right), 90.0);
+    assert_eq!(solver.get_value(inner.top), 10.0);
+    assert_eq!(solver.get_value(inner.bottom), 40.0);
+}
+
+#[test]
+fn no_overlap() {
+    let elements = [Element::new(), Element::new(), Element::new()];
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(Element::no_overlap(&elements, Axis::Vertical))
+        .unwrap();
+    solver
+        .add_constraint(elements[0].top | EQ(Strength::REQUIRED) | 0.0)
+        .unwrap();
+    for (element, height) in elements.iter().zip([10.0, 20.0, 30.0]) {
+        solver
+            .add_constraints([
+                element.height() | EQ(Strength::STRONG) | height,
+                element.top | EQ(Strength::WEAK) | 0.0,
+            ])
+            .unwrap();
+    }
+    let spans: Vec<_> = elements
+        .iter()
+        .map(|element| {
+            (
+                solver.get_value(element.top),
+                solver.get_value(element.bottom),
+            )
+        })
+        .collect();
+    assert_eq!(spans, [(0.0, 10.0), (10.0, 30.0), (30.0, 60.0)]);
+}
+
+#[test]
+fn same_size() {
+    let elements = [Element::new(), Element::new()];
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(
+            Element::same_size(&elements, Axis::Horizontal, Strength::REQUIRED)
+                .into_iter()
+                .chain([elements[0].width() | EQ(Strength::REQUIRED) | 42.0]),
+        )
+        .unwrap();
+    assert_eq!(
+        solver.get_value(elements[1].right) - solver.get_value(elements[1].left),
+        42.0
+    );
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "serde")]
+
+use kasuari::{LayoutSpec, LayoutSpecError, Priority, SizeSpec, Solver};
+
+fn solve(json: &str) -> (kasuari::CompiledLayout, Solver) {
+    let spec: LayoutSpec = serde_json::from_str(json).unwrap();
+    let layout = spec.compile().unwrap();
+    let mut solver = Solver::new();
+    solver
+        .add_constraints(layout.constraints().iter().cloned())
+        .unwrap();
+    (layout, solver)
+}
+
+#[test]
+fn row_of_elements() {
+    let (layout, solver) = solve(
+        r#"{
+            "elements": [
+                { "name": "window", "width": { "min": 300, "max": 300 } },
+                { "name": "left", "width": { "preferred": 100, "priority": "strong" } },
+                { "name": "right", "width": { "preferred": 1000 } }
+            ],
+            "relations": [
+                { "kind": "inside", "element": "left", "container": "window", "padding": 5 },
+                { "kind": "inside", "element": "right", "container": "window", "padding": 5 },
+                { "kind": "after", "element": "right", "anchor": "left", "gap": 10 }
+            ]
+        }"#,
+    );
+    let value = |name: &str| {
+        let element = layout.element(name).unwrap();
+        (
+            solver.get_value(element.left),
+            solver.get_value(element.right),
+        )
+    };
+    assert_eq!(value("window").1 - value("window").0, 300.0);
+    let (left_start, left_end) = value("left");
+    let (right_start, right_end) = value("right");
+    assert_eq!(left_start - value("window").0, 5.0);
+    assert_eq!(left_end - left_start, 100.0);
+    assert_eq!(right_start - left_end, 10.0);
+    assert_eq!(value("window").1 - right_end, 5.0);
+    assert_eq!(
+        layout.elements().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["window", "left", "right"]
+    );
+}
+
+#[test]
+fn same_size() {
+    let (layout, solver) = solve(
+        r#"{
+            "elements": [
+                { "name": "a", "height": { "min": 40 } },
+                { "name": "b", "height": { "preferred": 10 } }
+            ],
+            "relations": [
+                { "kind": "same_size", "elements": ["a", "b"], "axis": "vertical" }
+            ]
+        }"#,
+    );
+    let b = layout.element("b").unwrap();
+    assert_eq!(solver.get_value(b.bottom) - solver.get_value(b.top), 40.0);
+}
+
+#[test]
+fn compile_errors() {
+    let compile = |json: &str| {
+        serde_json::from_str::<LayoutSpec>(json)
+            .unwrap()
+            .compile()
+            .unwrap_err()
+    };
+    assert_eq!(
+        compile(r#"{ "elements": [{ "name": "a" }, { "name": "a" }] }"#),
+        LayoutSpecError::DuplicateElement("a".into())
+    );
+    assert_eq!(
+        compile(
+            r#"{
+                "elements": [{ "name": "a" }],
+                "relations": [{ "kind": "after", "element": "a", "anchor": "b" }]
+            }"#
+        ),
+        LayoutSpecError::UnknownElement("b".into())
+    );
+}
+
+#[test]
+fn default_size_priority() {
+    assert_eq!(SizeSpec::default().priority, Priority::Weak);
+    let spec: LayoutSpec =
+        serde_json::from_str(r#"{ "elements": [{ "name": "a", "width": { "preferred": 5 } }] }"#)
+            .unwrap();
+    assert_eq!(spec.elements[0].height, SizeSpec::default());
+    assert_eq!(spec.elements[0].width.priority, Priority::Weak);
+}
@@ -0,0 +1,165 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+/// A solver with a variable that is pulled towards 0 and 100 with the given strengths.
+fn tug_of_war(merge: bool, zero: Strength) -> (Solver, Variable) {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(merge);
+    let x = Variable::new();
+    solver
+        .add_constraints([
+            x | GE(Strength::REQUIRED) | 0.0,
+            x | LE(Strength::REQUIRED) | 100.0,
+            x | EQ(zero) | 0.0,
+            x | EQ(Strength::MEDIUM) | 100.0,
+        ])
+        .unwrap();
+    (solver, x)
+}
+
+#[test]
+fn merged_strengths_add_up() {
+    for merge in [false, true] {
+        let (mut solver, x) = tug_of_war(merge, Strength::MEDIUM * 0.75);
+        assert_eq!(solver.get_value(x), 100.0);
+
+        // a parallel constraint with a different scale, pulling towards zero
+        let parallel: Constraint = (2.0 * x) | EQ(Strength::MEDIUM * 0.25) | 0.0;
+        solver.add_constraint(parallel.clone()).unwrap();
+        assert_eq!(solver.merged_into(&parallel).is_some(), merge);
+        assert!(solver.has_constraint(&parallel));
+        assert_eq!(solver.get_value(x), 0.0, "merge: {merge}");
+
+        solver.remove_constraint(&parallel).unwrap();
+        assert!(!solver.has_constraint(&parallel));
+        assert_eq!(solver.get_value(x), 100.0, "merge: {merge}");
+    }
+}
+
+#[test]
+fn merge_into_required() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let x = Variable::new();
+    let required: Constraint = x | LE(Strength::REQUIRED) | 50.0;
+    let weak: Constraint = (-x) | GE(Strength::WEAK) | -50.0;
+    let duplicate: Constraint = x | LE(Strength::REQUIRED) | 50.0;
+    solver
+        .add_constraints([
+            required.clone(),
+            weak.clone(),
+            duplicate.clone(),
+            x | EQ(Strength::STRONG) | 80.0,
+        ])
+        .unwrap();
+    assert_eq!(solver.merged_into(&weak), Some(&required));
+    assert_eq!(solver.merged_into(&duplicate), Some(&required));
+    assert_eq!(solver.get_value(x), 50.0);
+
+    // the merged constraints take over when the constraint they were merged into is removed
+    solver.remove_constraint(&required).unwrap();
+    assert_eq!(solver.merged_into(&weak), None);
+    assert_eq!(solver.merged_into(&duplicate), None);
+    assert!(solver.has_constraint(&weak) && solver.has_constraint(&duplicate));
+    assert_eq!(solver.get_value(x), 50.0);
+
+    solver.remove_constraint(&duplicate).unwrap();
+    assert_eq!(solver.get_value(x), 80.0);
+}
+
+#[test]
+fn required_is_not_merged_into_weaker() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let x = Variable::new();
+    let weak: Constraint = x | LE(Strength::WEAK) | 10.0;
+    let required: Constraint = x | LE(Strength::REQUIRED) | 10.0;
+    let different_constant: Constraint = x | LE(Strength::WEAK) | 20.0;
+    solver
+        .add_constraints([
+            weak.clone(),
+            required.clone(),
+            different_constant.clone(),
+            x | EQ(Strength::MEDIUM) | 30.0,
+        ])
+        .unwrap();
+    assert_eq!(solver.merged_into(&required), None);
+    // the looser constraint is implied by the required one
+    assert_eq!(solver.merged_into(&different_constant), Some(&required));
+    assert_eq!(solver.get_value(x), 10.0);
+}
+
+#[test]
+fn merge_looser_bounds() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let x = Variable::new();
+    let min_10: Constraint = x | GE(Strength::REQUIRED) | 10.0;
+    let min_30: Constraint = (2.0 * x) | GE(Strength::REQUIRED) | 60.0;
+    let min_20: Constraint = x | GE(Strength::REQUIRED) | 20.0;
+    let preferred_min: Constraint = x | GE(Strength::STRONG) | 25.0;
+    solver
+        .add_constraints([
+            min_10.clone(),
+            min_30.clone(),
+            min_20.clone(),
+            preferred_min.clone(),
+            x | EQ(Strength::WEAK) | 0.0,
+        ])
+        .unwrap();
+    // the tightest bound has the row, and the looser ones are merged into it
+    assert_eq!(solver.merged_into(&min_30), None);
+    assert_eq!(solver.merged_into(&min_10), Some(&min_30));
+    assert_eq!(solver.merged_into(&min_20), Some(&min_30));
+    assert_eq!(solver.merged_into(&preferred_min), Some(&min_30));
+    assert_eq!(solver.get_value(x), 30.0);
+
+    // a non-required constraint that is tighter keeps its own row
+    let preferred_max: Constraint = x | GE(Strength::STRONG) | 40.0;
+    solver.add_constraint(preferred_max.clone()).unwrap();
+    assert_eq!(solver.merged_into(&preferred_max), None);
+    assert_eq!(solver.get_value(x), 40.0);
+    solver.remove_constraint(&preferred_max).unwrap();
+
+    // removing the tightest bound leaves the next one in charge
+    solver.remove_constraint(&min_30).unwrap();
+    assert_eq!(solver.get_value(x), 25.0);
+    solver.remove_constraint(&preferred_min).unwrap();
+    assert_eq!(solver.get_value(x), 20.0);
+    assert_eq!(solver.merged_into(&min_10), Some(&min_20));
+    assert_eq!(solver.merged_into(&min_20), None);
+    solver.remove_constraint(&min_20).unwrap();
+    assert_eq!(solver.get_value(x), 10.0);
+}
+
+#[test]
+fn merged_bounds_keep_their_state() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let x = Variable::new();
+    let max_50: Constraint = x | LE(Strength::REQUIRED) | 50.0;
+    let preferred_max: Constraint = x | LE(Strength::MEDIUM) | 60.0;
+    solver.add_named("max", max_50.clone()).unwrap();
+    solver.add_constraint(preferred_max.clone()).unwrap();
+    solver.set_enabled(&preferred_max, false).unwrap();
+    solver
+        .add_constraint(x | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+    assert_eq!(solver.get_value(x), 50.0);
+
+    // a tighter bound takes over, and the previous one keeps its name
+    let max_40: Constraint = x | LE(Strength::REQUIRED) | 40.0;
+    solver.add_constraint(max_40.clone()).unwrap();
+    assert_eq!(solver.merged_into(&max_50), Some(&max_40));
+    assert_eq!(solver.named("max"), Some(&max_50));
+    assert!(!solver.is_enabled(&preferred_max));
+    assert_eq!(solver.get_value(x), 40.0);
+
+    // the disabled member stays disabled when its bound is removed
+    solver.remove_constraint(&max_40).unwrap();
+    solver.remove_named("max").unwrap();
+    assert!(!solver.is_enabled(&preferred_max));
+    assert_eq!(solver.get_value(x), 100.0);
+    solver.set_enabled(&preferred_max, true).unwrap();
+    assert_eq!(solver.get_value(x), 60.0);
+}
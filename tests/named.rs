@@ -0,0 +1,105 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{AddConstraintError, Constraint, RemoveConstraintError, Solver, Strength, Variable};
+
+#[test]
+fn add_and_replace_named() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 500.0)
+        .unwrap();
+
+    let narrow: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    assert_eq!(
+        solver.add_named("sidebar.max", narrow.clone()).unwrap(),
+        None
+    );
+    assert_eq!(solver.named("sidebar.max"), Some(&narrow));
+    assert_eq!(solver.get_value(width), 100.0);
+
+    let wider: Constraint = width | LE(Strength::REQUIRED) | 200.0;
+    assert_eq!(
+        solver.add_named("sidebar.max", wider.clone()).unwrap(),
+        Some(narrow.clone())
+    );
+    assert!(!solver.has_constraint(&narrow));
+    assert_eq!(solver.named("sidebar.max"), Some(&wider));
+    assert_eq!(solver.get_value(width), 200.0);
+    assert_eq!(
+        solver.named_constraints().collect::<Vec<_>>(),
+        [("sidebar.max", &wider)]
+    );
+}
+
+#[test]
+fn failed_replacement_keeps_previous() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | GE(Strength::REQUIRED) | 50.0)
+        .unwrap();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    solver.add_named("max", max.clone()).unwrap();
+
+    let unsatisfiable: Constraint = width | LE(Strength::REQUIRED) | 10.0;
+    assert!(matches!(
+        solver.add_named("max", unsatisfiable),
+        Err(AddConstraintError::UnsatisfiableConstraint)
+    ));
+    assert_eq!(solver.named("max"), Some(&max));
+    assert!(solver.has_constraint(&max));
+}
+
+#[test]
+fn remove_named() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let min: Constraint = width | GE(Strength::REQUIRED) | 50.0;
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    solver.add_named("min", min.clone()).unwrap();
+    solver.add_named("max", max.clone()).unwrap();
+
+    assert_eq!(solver.remove_named("min").unwrap(), min);
+    assert!(!solver.has_constraint(&min));
+    assert_eq!(solver.named("min"), None);
+    assert!(matches!(
+        solver.remove_named("min"),
+        Err(RemoveConstraintError::UnknownConstraint)
+    ));
+
+    // removing the constraint directly also forgets its name
+    solver.remove_constraint(&max).unwrap();
+    assert_eq!(solver.named("max"), None);
+}
+
+#[test]
+fn constraint_with_name() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 500.0)
+        .unwrap();
+
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    let narrow = max.with_name("sidebar.max");
+    assert_eq!(max.name(), None);
+    assert_eq!(narrow.name(), Some("sidebar.max"));
+    assert!(narrow != max);
+    solver.add_constraint(narrow.clone()).unwrap();
+    assert_eq!(solver.named("sidebar.max"), Some(&narrow));
+    assert_eq!(solver.get_value(width), 100.0);
+    assert!(matches!(
+        solver.add_constraint(narrow.clone()),
+        Err(AddConstraintError::DuplicateConstraint)
+    ));
+
+    // a constraint with the same name replaces it
+    let wider: Constraint = width | LE(Strength::REQUIRED) | 200.0;
+    let wider = wider.with_name("sidebar.max");
+    solver.add_constraint(wider.clone()).unwrap();
+    assert!(!solver.has_constraint(&narrow));
+    assert_eq!(solver.get_value(width), 200.0);
+
+    assert_eq!(solver.remove_named("sidebar.max").unwrap(), wider);
+    assert_eq!(solver.get_value(width), 500.0);
+}
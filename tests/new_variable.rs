@@ -0,0 +1,154 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{
+    AddConstraintError, AddEditVariableError, Constraint, Solver, Strength, Variable,
+    VariableCounter,
+};
+
+#[test]
+fn solver_variables() {
+    let mut solver = Solver::new();
+    let global = Variable::new();
+    let (left, right) = (solver.new_variable(), solver.new_variable());
+    assert_ne!(left, right);
+    assert_ne!(left, global);
+    solver
+        .add_constraint(right | EQ(Strength::REQUIRED) | (left + global))
+        .unwrap();
+    solver
+        .add_constraint(left | EQ(Strength::REQUIRED) | 10.0)
+        .unwrap();
+    solver
+        .add_constraint(global | EQ(Strength::REQUIRED) | 5.0)
+        .unwrap();
+    assert_eq!(solver.get_value(right), 15.0);
+
+    // Other solvers count their variables separately.
+    let mut other = Solver::new();
+    assert_eq!(other.new_variable(), left);
+
+    // Clones keep counting where the original left off, and so does a reset solver.
+    let mut clone = solver.clone_remapped(&Default::default());
+    let next = clone.new_variable();
+    assert!(next != left && next != right);
+    solver.reset();
+    assert_eq!(solver.new_variable(), next);
+}
+
+#[test]
+fn variable_counter() {
+    let mut counter = VariableCounter::new();
+    let first = counter.new_variable();
+    assert_ne!(counter.new_variable(), first);
+    assert_eq!(counter.created(), 2);
+    assert_eq!(VariableCounter::new().new_variable(), first);
+
+    let mut solver = Solver::with_variable_counter(counter.clone());
+    let third = solver.new_variable();
+    assert_eq!(third, counter.new_variable());
+    solver
+        .add_constraint(third | EQ(Strength::REQUIRED) | (first + 1.0))
+        .unwrap();
+    solver
+        .add_constraint(first | EQ(Strength::REQUIRED) | 0.0)
+        .unwrap();
+    assert_eq!(solver.get_value(third), 1.0);
+}
+
+#[test]
+fn namespaces() {
+    let mut local = VariableCounter::with_namespace(1);
+    let mut remote = VariableCounter::with_namespace(2);
+    let (a, b) = (local.new_variable(), remote.new_variable());
+    assert_ne!(a, b);
+    assert_eq!(a.namespace(), Some(1));
+    assert_eq!(VariableCounter::new().new_variable().namespace(), Some(0));
+    assert_eq!(Variable::new().namespace(), None);
+
+    // Variables of other namespaces can be used together.
+    let mut solver = Solver::with_variable_counter(local);
+    let c = solver.new_variable();
+    solver
+        .add_constraint(c | EQ(Strength::REQUIRED) | (a + b))
+        .unwrap();
+}
+
+#[test]
+fn collision() {
+    let mut remote = VariableCounter::new();
+    remote.new_variable();
+    let foreign = remote.new_variable();
+    let mut solver = Solver::new();
+    let local = solver.new_variable();
+    let constraint: Constraint = foreign | EQ(Strength::REQUIRED) | local;
+    let error = solver.add_constraint(constraint.clone()).unwrap_err();
+    assert!(matches!(error, AddConstraintError::VariableCollision(v) if v == foreign));
+    assert!(!solver.has_constraint(&constraint));
+    let error = solver
+        .add_edit_variable(foreign, Strength::STRONG)
+        .unwrap_err();
+    assert!(matches!(error, AddEditVariableError::VariableCollision(v) if v == foreign));
+    assert!(!solver.has_edit_variable(&foreign));
+
+    // Once the solver created the variable, it is its own.
+    assert_eq!(solver.new_variable(), foreign);
+    solver.add_constraint(constraint).unwrap();
+    solver.add_edit_variable(foreign, Strength::STRONG).unwrap();
+}
+
+#[test]
+fn restore_state() {
+    let mut solver = Solver::with_variable_counter(VariableCounter::with_namespace(3));
+    let (width, global) = (solver.new_variable(), Variable::new());
+    let loaded = Variable::from_id(global.id() + 1000);
+    solver
+        .add_constraint(width | EQ(Strength::REQUIRED) | (global + loaded))
+        .unwrap();
+    let state = solver.state(false);
+
+    // Restoring reserves the ids of the process-wide counter.
+    let mut restored = Solver::from_state(state.clone()).unwrap();
+    assert!(Variable::new().id() > loaded.id());
+    // and continues the counter in its namespace.
+    let next = restored.new_variable();
+    assert_eq!(next.namespace(), Some(3));
+    assert_ne!(next, width);
+}
+
+#[test]
+fn many_solver_variables() {
+    let mut solver = Solver::new();
+    let variables: Vec<Variable> = (0..200).map(|_| solver.new_variable()).collect();
+    let global = Variable::new();
+    solver
+        .add_constraint(global | EQ(Strength::REQUIRED) | 1.0)
+        .unwrap();
+    // Constrain the last variables first, so the early ones are added after them.
+    let constraints: Vec<Constraint> = variables
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, &v)| v | EQ(Strength::REQUIRED) | (global * i as f64))
+        .collect();
+    for constraint in &constraints {
+        solver.add_constraint(constraint.clone()).unwrap();
+    }
+    for (i, &v) in variables.iter().enumerate() {
+        assert_eq!(solver.get_value(v), i as f64);
+    }
+    assert_eq!(solver.iter_values().count(), 201);
+
+    for constraint in &constraints[..100] {
+        solver.remove_constraint(constraint).unwrap();
+    }
+    assert_eq!(solver.try_value_of(variables[150]), None);
+    assert_eq!(solver.try_value_of(variables[50]), Some(50.0));
+    assert_eq!(solver.iter_values().count(), 101);
+
+    for constraint in &constraints[..100] {
+        solver.add_constraint(constraint.clone()).unwrap();
+    }
+    for (i, &v) in variables.iter().enumerate() {
+        assert_eq!(solver.get_value(v), i as f64);
+    }
+    assert_eq!(solver.iter_values().count(), 201);
+}
@@ -0,0 +1,46 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn or() {
+    let mut solver = Solver::new();
+    let (x, width) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint(width | EQ(Strength::REQUIRED) | 20.0)
+        .unwrap();
+    let left: Constraint = x | EQ(Strength::MEDIUM) | 0.0;
+    let right: Constraint = (x + width) | EQ(Strength::WEAK) | 100.0;
+    let snap = left.or(&right);
+    assert_eq!(snap.len(), 2);
+    assert_eq!(snap.constraints()[1], right);
+    solver.add_group(&snap).unwrap();
+    assert_eq!(solver.get_value(x), 0.0);
+
+    // a stronger constraint moves it only as far as needed, not over to the fallback
+    let limit = x | GE(Strength::STRONG) | 30.0;
+    solver.add_constraint(limit).unwrap();
+    assert_eq!(solver.get_value(x), 30.0);
+
+    solver.remove_group(&snap).unwrap();
+    assert_eq!(solver.get_value(x), 30.0);
+}
+
+#[test]
+fn or_keeps_fallback_strength() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let preferred: Constraint = x | EQ(Strength::WEAK) | 0.0;
+    let fallback: Constraint = x | EQ(Strength::STRONG) | 100.0;
+    let fallback = fallback.with_weight(3.0);
+    let group = preferred.or(&fallback);
+    let [first, second] = group.constraints() else {
+        panic!("expected two constraints");
+    };
+    assert_eq!(first.strength(), Strength::WEAK);
+    assert_eq!(second.strength(), Strength::STRONG);
+    assert_eq!(second.weight(), 3.0);
+
+    // the stronger fallback wins, as both pull at once
+    solver.add_group(&group).unwrap();
+    assert_eq!(solver.get_value(x), 100.0);
+}
@@ -0,0 +1,92 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Param, Solver, Strength, Variable};
+
+#[test]
+fn set_param() {
+    let mut solver = Solver::new();
+    let spacing = Param::new();
+    let left = Variable::new();
+    let right = Variable::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(left | EQ(Strength::REQUIRED) | spacing)
+        .unwrap();
+    solver
+        .add_constraint(right | EQ(Strength::REQUIRED) | (left + width + spacing))
+        .unwrap();
+    solver
+        .add_constraint(width | GE(Strength::REQUIRED) | (spacing * 10.0))
+        .unwrap();
+    solver
+        .add_constraint(width | LE(Strength::STRONG) | (100.0 - spacing * 2.0))
+        .unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 1000.0)
+        .unwrap();
+    assert_eq!(solver.param(spacing), 0.0);
+    assert_eq!(solver.get_value(width), 100.0);
+
+    for (value, expected_width) in [(2.0, 96.0), (5.0, 90.0), (20.0, 200.0), (1.0, 98.0)] {
+        solver.set_param(spacing, value).unwrap();
+        assert_eq!(solver.param(spacing), value);
+        assert_eq!(solver.get_value(left), value);
+        assert_eq!(solver.get_value(width), expected_width);
+        assert_eq!(solver.get_value(right), 2.0 * value + expected_width);
+    }
+}
+
+#[test]
+fn set_param_before_adding() {
+    let mut solver = Solver::new();
+    let spacing = Param::new();
+    let x = Variable::new();
+    solver.set_param(spacing, 3.0).unwrap();
+    solver
+        .add_constraint(x | EQ(Strength::MEDIUM) | (spacing * 2.0))
+        .unwrap();
+    assert_eq!(solver.get_value(x), 6.0);
+
+    // parameters survive rebuilding the solver
+    solver.rebuild().unwrap();
+    assert_eq!(solver.get_value(x), 6.0);
+    solver.set_param(spacing, 4.0).unwrap();
+    assert_eq!(solver.get_value(x), 8.0);
+}
+
+#[test]
+fn set_param_in_edit_session() {
+    let mut solver = Solver::new();
+    let spacing = Param::new();
+    let x = Variable::new();
+    solver
+        .add_constraint(x | EQ(Strength::REQUIRED) | (spacing + 1.0))
+        .unwrap();
+    solver.begin_edit();
+    solver.set_param(spacing, 9.0).unwrap();
+    solver.end_edit().unwrap();
+    assert_eq!(solver.get_value(x), 10.0);
+}
+
+#[test]
+fn queries_use_params() {
+    let mut solver = Solver::new();
+    let limit = Param::new();
+    let a = Variable::new();
+    let max: Constraint = a | LE(Strength::REQUIRED) | limit;
+    let preferred: Constraint = a | EQ(Strength::WEAK) | 100.0;
+    solver.add_constraint(max.clone()).unwrap();
+    solver
+        .add_constraint(a | GE(Strength::REQUIRED) | 0.0)
+        .unwrap();
+    solver.add_constraint(preferred.clone()).unwrap();
+    solver.set_param(limit, 50.0).unwrap();
+    assert_eq!(solver.get_value(a), 50.0);
+
+    assert_eq!(solver.violation(&max), 0.0);
+    assert_eq!(solver.slack(&max), Some(0.0));
+    assert_eq!(solver.violation(&preferred), 50.0);
+    let solution = solver.freeze();
+    assert!(solution.satisfied().any(|c| c == &max));
+    assert_eq!(solver.maximize(a), 50.0);
+    assert_eq!(solver.bounds_of(a), (0.0, 50.0));
+}
@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use kasuari::WeightedRelation::*;
+use kasuari::{PivotEvent, PivotPhase, Solver, Strength, Variable};
+
+fn recording_solver() -> (Solver, Rc<RefCell<Vec<PivotEvent>>>) {
+    let mut solver = Solver::new();
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let recorded = events.clone();
+    solver.set_pivot_hook(move |event| recorded.borrow_mut().push(*event));
+    (solver, events)
+}
+
+#[test]
+fn primal_pivots() {
+    let (mut solver, events) = recording_solver();
+    let x = Variable::new();
+    solver
+        .add_constraint(x | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+    events.borrow_mut().clear();
+
+    solver
+        .add_constraint(x | LE(Strength::STRONG) | 50.0)
+        .unwrap();
+    let events = events.borrow();
+    assert!(!events.is_empty());
+    for event in events.iter() {
+        assert_eq!(event.phase, PivotPhase::Primal);
+        assert_ne!(event.entering, event.leaving);
+        // every primal pivot moves the objective towards its minimum
+        assert!(event.objective_after <= event.objective_before);
+    }
+    assert_eq!(solver.get_value(x), 50.0);
+}
+
+#[test]
+fn dual_pivots() {
+    let (mut solver, events) = recording_solver();
+    let x = Variable::new();
+    let y = Variable::new();
+    solver
+        .add_constraints([
+            (x + y) | LE(Strength::REQUIRED) | 100.0,
+            y | GE(Strength::REQUIRED) | 0.0,
+        ])
+        .unwrap();
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    solver.suggest_value(x, 20.0).unwrap();
+    events.borrow_mut().clear();
+
+    solver.suggest_value(x, 200.0).unwrap();
+    let events = events.borrow();
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| event.phase == PivotPhase::Dual));
+    assert_eq!(solver.get_value(x), 100.0);
+}
+
+#[test]
+fn clear_pivot_hook() {
+    let (mut solver, events) = recording_solver();
+    solver.clear_pivot_hook();
+    let x = Variable::new();
+    solver
+        .add_constraints([
+            x | EQ(Strength::WEAK) | 100.0,
+            x | LE(Strength::REQUIRED) | 50.0,
+        ])
+        .unwrap();
+    assert!(events.borrow().is_empty());
+}
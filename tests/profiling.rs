@@ -0,0 +1,28 @@
+#![cfg(feature = "profiling")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn hot_constraints() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    let preferred: Constraint = x | EQ(Strength::WEAK) | 100.0;
+    let max: Constraint = x | LE(Strength::REQUIRED) | 50.0;
+    solver.add_constraint(preferred.clone()).unwrap();
+    solver.add_constraint(max.clone()).unwrap();
+    solver
+        .add_constraint(y | EQ(Strength::REQUIRED) | 10.0)
+        .unwrap();
+
+    let hot = solver.hot_constraints();
+    assert!(!hot.is_empty());
+    assert!(hot.windows(2).all(|pair| pair[0].time >= pair[1].time));
+    let involved: Vec<&Constraint> = hot.iter().map(|profile| &profile.constraint).collect();
+    assert!(involved.contains(&&preferred) || involved.contains(&&max));
+    assert!(hot.iter().all(|profile| profile.pivots > 0));
+
+    solver.reset_profile();
+    assert!(solver.hot_constraints().is_empty());
+}
@@ -0,0 +1,227 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn constraints_for() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let right = Variable::new();
+    let other = Variable::new();
+    let position: Constraint = left | EQ(Strength::REQUIRED) | 0.0;
+    let width: Constraint = (right - left) | EQ(Strength::STRONG) | 100.0;
+    let unrelated: Constraint = other | EQ(Strength::WEAK) | 5.0;
+    solver
+        .add_constraints([position.clone(), width.clone(), unrelated.clone()])
+        .unwrap();
+    solver.add_edit_variable(right, Strength::MEDIUM).unwrap();
+
+    let mut for_left: Vec<&Constraint> = solver.constraints_for(left).collect();
+    for_left.sort_by_key(|constraint| constraint.strength());
+    assert_eq!(for_left, [&width, &position]);
+    assert_eq!(solver.constraints_for(right).collect::<Vec<_>>(), [&width]);
+    assert_eq!(
+        solver.constraints_for(other).collect::<Vec<_>>(),
+        [&unrelated]
+    );
+    assert_eq!(solver.constraints_for(Variable::new()).count(), 0);
+}
+
+#[test]
+fn violation() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 80.0;
+    let preferred: Constraint = width | EQ(Strength::WEAK) | 100.0;
+    let min: Constraint = width | GE(Strength::MEDIUM) | 90.0;
+    solver
+        .add_constraints([max.clone(), preferred.clone(), min.clone()])
+        .unwrap();
+
+    assert_eq!(solver.get_value(width), 80.0);
+    assert_eq!(solver.violation(&max), 0.0);
+    assert_eq!(solver.violation(&preferred), 20.0);
+    assert_eq!(solver.violation(&min), 10.0);
+}
+
+#[test]
+fn slack() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    let min: Constraint = width | GE(Strength::REQUIRED) | 80.0;
+    let unsatisfied: Constraint = width | GE(Strength::WEAK) | 120.0;
+    let preferred: Constraint = width | EQ(Strength::MEDIUM) | 80.0;
+    solver
+        .add_constraints([
+            max.clone(),
+            min.clone(),
+            unsatisfied.clone(),
+            preferred.clone(),
+        ])
+        .unwrap();
+
+    assert_eq!(solver.get_value(width), 80.0);
+    assert_eq!(solver.slack(&max), Some(20.0));
+    assert_eq!(solver.slack(&min), Some(0.0));
+    assert_eq!(solver.slack(&unsatisfied), Some(0.0));
+    assert_eq!(solver.slack(&preferred), None);
+
+    solver.remove_constraint(&max).unwrap();
+    assert_eq!(solver.slack(&max), None);
+}
+
+#[test]
+fn total_error() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+    assert_eq!(solver.total_error(), 0.0);
+
+    let max: Constraint = width | LE(Strength::REQUIRED) | 80.0;
+    solver.add_constraint(max.clone()).unwrap();
+    assert_eq!(solver.total_error(), 20.0 * Strength::WEAK.value());
+
+    solver
+        .add_constraint((2.0 * width) | GE(Strength::MEDIUM) | 170.0)
+        .unwrap();
+    assert_eq!(
+        solver.total_error(),
+        20.0 * Strength::WEAK.value() + 10.0 * Strength::MEDIUM.value()
+    );
+
+    solver.remove_constraint(&max).unwrap();
+    assert_eq!(solver.total_error(), 0.0);
+}
+
+#[test]
+fn bounds_of() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let right = Variable::new();
+    let free = Variable::new();
+    solver
+        .add_constraints([
+            left | GE(Strength::REQUIRED) | 0.0,
+            right | LE(Strength::REQUIRED) | 300.0,
+            (right - left) | GE(Strength::REQUIRED) | 50.0,
+            // non-required constraints don't limit the bounds
+            left | EQ(Strength::STRONG) | 100.0,
+            free | EQ(Strength::WEAK) | 10.0,
+        ])
+        .unwrap();
+
+    assert_eq!(solver.bounds_of(left), (0.0, 250.0));
+    assert_eq!(solver.bounds_of(right), (50.0, 300.0));
+    assert_eq!(solver.bounds_of(free), (f64::NEG_INFINITY, f64::INFINITY));
+    assert_eq!(
+        solver.bounds_of(Variable::new()),
+        (f64::NEG_INFINITY, f64::INFINITY)
+    );
+    // the solver itself is unaffected
+    assert_eq!(solver.get_value(left), 100.0);
+}
+
+#[test]
+fn maximize_minimize() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    solver
+        .add_constraints([
+            width | LE(Strength::REQUIRED) | 640.0,
+            width | GE(Strength::REQUIRED) | 10.0,
+            height | GE(Strength::REQUIRED) | (width * 0.5),
+            width | EQ(Strength::MEDIUM) | 200.0,
+        ])
+        .unwrap();
+
+    assert_eq!(solver.maximize(width), 640.0);
+    assert_eq!(solver.minimize(width), 10.0);
+    assert_eq!(solver.minimize(height), 5.0);
+    assert_eq!(solver.maximize(height), f64::INFINITY);
+    assert_eq!(solver.maximize(Variable::new()), f64::INFINITY);
+    assert_eq!(solver.minimize(Variable::new()), f64::NEG_INFINITY);
+    // the current solution is unaffected
+    assert_eq!(solver.get_value(width), 200.0);
+    assert_eq!(solver.get_value(height), 100.0);
+}
+
+#[test]
+fn explain() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let right = Variable::new();
+    let position: Constraint = left | EQ(Strength::REQUIRED) | 0.0;
+    let min_width: Constraint = (right - left) | GE(Strength::REQUIRED) | 100.0;
+    let preferred: Constraint = right | EQ(Strength::WEAK) | 50.0;
+    solver
+        .add_constraints([position.clone(), min_width.clone(), preferred.clone()])
+        .unwrap();
+    assert_eq!(solver.get_value(right), 100.0);
+
+    let explanation = solver.explain(right);
+    assert_eq!(explanation.len(), 2);
+    assert!(explanation.contains(&position));
+    assert!(explanation.contains(&min_width));
+    assert!(solver.explain(Variable::new()).is_empty());
+}
+
+#[test]
+fn heap_size() {
+    let mut solver = Solver::new();
+    let empty = solver.heap_size();
+
+    let variables: Vec<Variable> = (0..50).map(|_| Variable::new()).collect();
+    let constraints: Vec<Constraint> = variables
+        .windows(2)
+        .map(|pair| pair[1] | GE(Strength::REQUIRED) | (pair[0] + 10.0))
+        .collect();
+    solver.add_constraints(constraints.iter().cloned()).unwrap();
+    let full = solver.heap_size();
+    assert!(full > empty);
+
+    // removing constraints keeps the capacity of the maps
+    for constraint in &constraints {
+        solver.remove_constraint(constraint).unwrap();
+    }
+    assert!(solver.heap_size() > empty);
+    assert!(Solver::new().heap_size() < solver.heap_size());
+}
+
+#[test]
+fn dumps() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraints([
+            width | LE(Strength::REQUIRED) | 80.0,
+            width | EQ(Strength::WEAK) | 100.0,
+        ])
+        .unwrap();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+
+    let dump = solver.dumps();
+    let sections: Vec<&str> = dump
+        .lines()
+        .filter(|line| line.starts_with("---"))
+        .collect();
+    assert_eq!(sections.len(), 6);
+    assert!(dump.contains(" <= 80 | s"));
+    assert!(dump.contains(" == 100 | e"));
+}
+
+#[test]
+fn find_equivalent() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let installed: Constraint = width | GE(Strength::REQUIRED) | 100.0;
+    solver.add_constraint(installed.clone()).unwrap();
+
+    let rebuilt: Constraint = width | GE(Strength::REQUIRED) | 100.0;
+    assert!(!solver.has_constraint(&rebuilt));
+    assert_eq!(solver.find_equivalent(&rebuilt), Some(&installed));
+    let weaker: Constraint = width | GE(Strength::STRONG) | 100.0;
+    assert_eq!(solver.find_equivalent(&weaker), None);
+}
@@ -0,0 +1,72 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{AddConstraintError, Constraint, Solver, Strength, Variable};
+
+#[test]
+fn unsatisfiable_constraint_leaves_solver_unchanged() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let other = Variable::new();
+    solver
+        .add_constraints([
+            width | LE(Strength::REQUIRED) | 200.0,
+            width | GE(Strength::REQUIRED) | 100.0,
+            width | EQ(Strength::WEAK) | 150.0,
+        ])
+        .unwrap();
+    let before = solver.values();
+
+    // needs an artificial variable to find out that it's unsatisfiable
+    let conflict: Constraint = width | GE(Strength::REQUIRED) | 300.0;
+    assert!(matches!(
+        solver.add_constraint(conflict.clone()),
+        Err(AddConstraintError::UnsatisfiableConstraint)
+    ));
+    assert!(!solver.has_constraint(&conflict));
+    assert_eq!(solver.values(), before);
+
+    // only dummy variables
+    let required: Constraint = other | EQ(Strength::REQUIRED) | 10.0;
+    solver.add_constraint(required).unwrap();
+    let before = solver.values();
+    assert!(solver
+        .add_constraint(other | EQ(Strength::REQUIRED) | 20.0)
+        .is_err());
+    assert_eq!(solver.values(), before);
+
+    // the solver keeps working
+    solver
+        .add_constraint(width | LE(Strength::REQUIRED) | 120.0)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 120.0);
+}
+
+#[test]
+fn rebuild() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let width = Variable::new();
+    let right = Variable::new();
+    let min: Constraint = width | GE(Strength::REQUIRED) | 50.0;
+    let sum: Constraint = right | EQ(Strength::REQUIRED) | (left + width);
+    solver.add_constraint(sum.clone()).unwrap();
+    solver.add_named("min", min.clone()).unwrap();
+    solver.add_edit_variable(left, Strength::STRONG).unwrap();
+    solver.add_edit_variable(width, Strength::MEDIUM).unwrap();
+    solver.suggest_value(left, 10.0).unwrap();
+    solver.suggest_value(width, 20.0).unwrap();
+    solver.fetch_changes();
+    let before = solver.values();
+
+    solver.rebuild().unwrap();
+    assert_eq!(solver.values(), before);
+    assert_eq!(solver.fetch_changes(), []);
+    assert_eq!(solver.named("min"), Some(&min));
+    assert!(solver.has_constraint(&sum));
+    assert!(solver.has_edit_variable(&left));
+
+    solver.suggest_value(width, 100.0).unwrap();
+    assert_eq!(solver.get_value(right), 110.0);
+    solver.remove_named("min").unwrap();
+    solver.suggest_value(width, 20.0).unwrap();
+    assert_eq!(solver.get_value(right), 30.0);
+}
@@ -0,0 +1,44 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Expression, Solver, Strength, Term, Variable};
+
+#[test]
+fn reference_operators() {
+    let columns: Vec<Variable> = (0..4).map(|_| Variable::new()).collect();
+    let total: Expression = columns.iter().copied().sum();
+    let (x, y) = (Variable::new(), Variable::new());
+
+    assert_eq!(&total + &total, total.clone() + total.clone());
+    assert_eq!(&total - &total, total.clone() - total.clone());
+    assert_eq!(-&total, -total.clone());
+    assert_eq!(&total * 2.0, total.clone() * 2.0);
+    assert_eq!(&total + 1.0, total.clone() + 1.0);
+    assert_eq!(&total - 1.0, total.clone() - 1.0);
+    assert_eq!(&total + x, total.clone() + x);
+    assert_eq!(&total - x, total.clone() - x);
+    assert_eq!(
+        &total + Term::new(y, 3.0),
+        total.clone() + Term::new(y, 3.0)
+    );
+    assert_eq!(
+        &total - Term::new(y, 3.0),
+        total.clone() - Term::new(y, 3.0)
+    );
+    let mut sum = Expression::from(x);
+    sum += &total;
+    assert_eq!(sum, x + total.clone());
+    sum -= &total;
+    assert_eq!(sum, x + total.clone() - total.clone());
+
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let fits: Constraint = &total | LE(Strength::REQUIRED) | width;
+    let fills: Constraint = width | EQ(Strength::REQUIRED) | &total;
+    solver.add_constraint(fits).unwrap();
+    solver.add_constraint(fills).unwrap();
+    for &column in &columns {
+        solver
+            .add_constraint(column | EQ(Strength::REQUIRED) | 10.0)
+            .unwrap();
+    }
+    assert_eq!(solver.get_value(width), 40.0);
+}
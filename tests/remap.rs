@@ -0,0 +1,108 @@
+use hashbrown::HashMap;
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+/// A list row whose label takes the width left over by a fixed size icon.
+struct Row {
+    left: Variable,
+    icon: Variable,
+    label: Variable,
+}
+
+impl Row {
+    fn new() -> Row {
+        Row {
+            left: Variable::new(),
+            icon: Variable::new(),
+            label: Variable::new(),
+        }
+    }
+
+    fn map_to(&self, other: &Row) -> HashMap<Variable, Variable> {
+        HashMap::from([
+            (self.left, other.left),
+            (self.icon, other.icon),
+            (self.label, other.label),
+        ])
+    }
+}
+
+#[test]
+fn clone_remapped() {
+    let container = Variable::new();
+    let prototype = Row::new();
+    let mut solver = Solver::new();
+    let shared: Constraint = container | EQ(Strength::REQUIRED) | 300.0;
+    solver
+        .add_constraints([
+            shared.clone(),
+            prototype.left | EQ(Strength::REQUIRED) | 0.0,
+            (prototype.left + prototype.icon + prototype.label)
+                | EQ(Strength::REQUIRED)
+                | container,
+        ])
+        .unwrap();
+    solver
+        .add_named("icon", prototype.icon | GE(Strength::REQUIRED) | 16.0)
+        .unwrap();
+    solver
+        .add_edit_variable(prototype.icon, Strength::STRONG)
+        .unwrap();
+    solver.suggest_value(prototype.icon, 20.0).unwrap();
+    solver.fetch_changes();
+
+    let instance = Row::new();
+    let mut copy = solver.clone_remapped(&prototype.map_to(&instance));
+    assert_eq!(copy.get_value(instance.icon), 20.0);
+    assert_eq!(copy.get_value(instance.label), 280.0);
+    assert_eq!(copy.try_value_of(prototype.label), None);
+    // variables that are not mapped are shared, and the constraints are new
+    assert_eq!(copy.get_value(container), 300.0);
+    assert!(copy.constraints_for(container).all(|c| c != &shared));
+    assert_eq!(copy.fetch_changes().len(), 4);
+
+    // the copy is independent and fully functional
+    assert!(copy.has_edit_variable(&instance.icon));
+    copy.suggest_value(instance.icon, 50.0).unwrap();
+    assert_eq!(copy.get_value(instance.label), 250.0);
+    assert_eq!(solver.get_value(prototype.label), 280.0);
+    let icon = copy.named("icon").unwrap().clone();
+    assert_eq!(icon.expr().terms[0].variable, instance.icon);
+    copy.remove_named("icon").unwrap();
+    copy.suggest_value(instance.icon, 0.0).unwrap();
+    assert_eq!(copy.get_value(instance.label), 300.0);
+    assert!(solver.named("icon").is_some());
+}
+
+#[test]
+fn clone_remapped_disabled() {
+    let (a, b) = (Variable::new(), Variable::new());
+    let (copy_a, copy_b) = (Variable::new(), Variable::new());
+    let mut solver = Solver::new();
+    let fixed: Constraint = a | EQ(Strength::REQUIRED) | 10.0;
+    let guarded: Constraint = b | EQ(Strength::REQUIRED) | 20.0;
+    solver.add_named("fixed", fixed.clone()).unwrap();
+    solver.add_named("guarded", guarded.when(1)).unwrap();
+    solver.add_constraint(a | EQ(Strength::WEAK) | 5.0).unwrap();
+    solver.add_constraint(b | EQ(Strength::WEAK) | 5.0).unwrap();
+    solver.set_enabled(&fixed, false).unwrap();
+    assert_eq!(solver.get_value(a), 5.0);
+    assert_eq!(solver.get_value(b), 5.0);
+
+    let map = HashMap::from([(a, copy_a), (b, copy_b)]);
+    let mut copy = solver.clone_remapped(&map);
+    assert_eq!(copy.get_value(copy_a), 5.0);
+    assert_eq!(copy.get_value(copy_b), 5.0);
+    let fixed = copy.named("fixed").unwrap().clone();
+    assert!(copy.has_constraint(&fixed));
+    assert!(!copy.is_enabled(&fixed));
+    assert!(!copy.is_enabled(copy.named("guarded").unwrap()));
+
+    copy.set_enabled(&fixed, true).unwrap();
+    assert_eq!(copy.get_value(copy_a), 10.0);
+    assert_eq!(solver.get_value(a), 5.0);
+    // the copy of the guarded constraint is still under its condition
+    copy.set_condition(1, true).unwrap();
+    assert_eq!(copy.get_value(copy_b), 20.0);
+    assert_eq!(solver.get_value(b), 5.0);
+}
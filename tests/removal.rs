@@ -1,5 +1,8 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
 use kasuari::WeightedRelation::*;
-use kasuari::{Constraint, Solver, Strength, Variable};
+use kasuari::{Constraint, Solver, SolverOp, Strength, Variable};
 
 mod common;
 
@@ -27,3 +30,158 @@ fn remove_constraint() {
 
     assert_eq!(value_of(val), 0.0);
 }
+
+#[test]
+fn remove_below_strength() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+
+    let required: Constraint = x | GE(Strength::REQUIRED) | 0.0;
+    let strong: Constraint = x | EQ(Strength::STRONG) | 10.0;
+    let medium: Constraint = x | EQ(Strength::MEDIUM) | 20.0;
+    let weak: Constraint = y | EQ(Strength::WEAK) | 30.0;
+    solver
+        .add_constraints([
+            required.clone(),
+            strong.clone(),
+            medium.clone(),
+            weak.clone(),
+        ])
+        .unwrap();
+    solver.add_edit_variable(y, Strength::WEAK * 0.5).unwrap();
+    solver.suggest_value(y, 40.0).unwrap();
+
+    solver.start_recording();
+    let mut removed = solver.remove_below_strength(Strength::STRONG).unwrap();
+    assert_eq!(
+        solver.take_recording(),
+        [SolverOp::RemoveBelowStrength(Strength::STRONG)]
+    );
+    removed.sort_by_key(|constraint| constraint.strength());
+    assert_eq!(removed, [weak, medium]);
+    assert!(solver.has_constraint(&required) && solver.has_constraint(&strong));
+    assert!(solver.has_edit_variable(&y));
+    assert_eq!(solver.get_value(x), 10.0);
+    assert_eq!(solver.get_value(y), 40.0);
+}
+
+#[test]
+fn has_constraint() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let constraint: Constraint = x | EQ(Strength::REQUIRED) | 10.0;
+    let equivalent: Constraint = x | EQ(Strength::REQUIRED) | 10.0;
+    assert!(!solver.has_constraint(&constraint));
+
+    solver.add_constraint(constraint.clone()).unwrap();
+    assert!(solver.has_constraint(&constraint));
+    // constraints are compared by identity, not by their contents
+    assert!(!solver.has_constraint(&equivalent));
+
+    solver.remove_constraint(&constraint).unwrap();
+    assert!(!solver.has_constraint(&constraint));
+}
+
+#[test]
+fn reset() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let constraint: Constraint = width | EQ(Strength::REQUIRED) | 100.0;
+    solver.add_constraint(constraint.clone()).unwrap();
+    solver.add_edit_variable(width, Strength::STRONG).unwrap();
+
+    solver.reset();
+    assert!(!solver.has_constraint(&constraint));
+    assert!(!solver.has_edit_variable(&width));
+    assert_eq!(solver.fetch_changes(), []);
+    assert_eq!(solver.get_value(width), 0.0);
+    // the capacity of the maps is kept
+    assert!(solver.heap_size() > Solver::new().heap_size());
+
+    // the same constraint can be added again
+    solver.add_constraint(constraint).unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+}
+
+#[test]
+fn range() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 500.0)
+        .unwrap();
+    let range = Constraint::range(width, 100.0, 200.0, Strength::REQUIRED);
+    solver.add_range(&range).unwrap();
+    assert!(solver.has_constraint(range.lower()));
+    assert!(solver.has_constraint(range.upper()));
+    assert_eq!(solver.get_value(width), 200.0);
+
+    // a range that conflicts with the other one is not added at all
+    let conflicting = Constraint::range(width * 2.0, 500.0, 600.0, Strength::REQUIRED);
+    assert!(solver.add_range(&conflicting).is_err());
+    assert!(!solver.has_constraint(conflicting.lower()));
+    assert!(!solver.has_constraint(conflicting.upper()));
+
+    solver.remove_range(&range).unwrap();
+    assert!(!solver.has_constraint(range.lower()));
+    assert_eq!(solver.get_value(width), 500.0);
+    assert!(solver.remove_range(&range).is_err());
+}
+
+#[test]
+fn remove_variable() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let (left, width, other) = (Variable::new(), Variable::new(), Variable::new());
+    solver
+        .add_constraint(left | EQ(Strength::REQUIRED) | 10.0)
+        .unwrap();
+    let wide: Constraint = width | GE(Strength::WEAK) | 50.0;
+    let wider: Constraint = width | GE(Strength::WEAK) | 50.0;
+    solver.add_constraint(wide).unwrap();
+    solver.add_constraint(wider).unwrap();
+    let spaced: Constraint = (left + width) | LE(Strength::STRONG) | other;
+    solver.add_constraint(spaced.clone()).unwrap();
+    solver.set_enabled(&spaced, false).unwrap();
+    solver
+        .set_upper_bound(width, 80.0, Strength::REQUIRED)
+        .unwrap();
+    solver.pin(width, Strength::STRONG).unwrap();
+    solver.add_edit_variable(width, Strength::MEDIUM).unwrap();
+    solver.suggest_value(width, 70.0).unwrap();
+
+    let removed = solver.remove_variable(width).unwrap();
+    assert_eq!(removed.len(), 5);
+    assert_eq!(solver.try_value_of(width), None);
+    assert!(!solver.has_edit_variable(&width));
+    assert!(!solver.is_pinned(width));
+    assert_eq!(solver.upper_bound(width), None);
+    assert_eq!(solver.constraints_for(width).count(), 0);
+    assert_eq!(solver.get_value(left), 10.0);
+    assert!(solver.remove_variable(width).unwrap().is_empty());
+}
+
+#[test]
+fn remove_measured_variable() {
+    let mut solver = Solver::new();
+    let (width, height) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint(width | EQ(Strength::REQUIRED) | 100.0)
+        .unwrap();
+    let measurements = Rc::new(Cell::new(0));
+    let counter = measurements.clone();
+    solver
+        .add_content_size(height, [width], Strength::STRONG, move |inputs| {
+            counter.set(counter.get() + 1);
+            1000.0 / inputs[0]
+        })
+        .unwrap();
+    assert_eq!(solver.get_value(height), 10.0);
+
+    solver.remove_variable(width).unwrap();
+    assert!(!solver.measure_content_sizes().unwrap());
+    assert_eq!(measurements.get(), 1);
+    assert!(solver.has_edit_variable(&height));
+    assert_eq!(solver.get_value(height), 10.0);
+}
@@ -0,0 +1,83 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{AddConstraintError, Constraint, Solver, SolverOp, Strength, Variable};
+
+#[test]
+fn replay_reproduces_session() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    let min: Constraint = width | GE(Strength::REQUIRED) | 100.0;
+    let ratio: Constraint = height | EQ(Strength::STRONG) | (width * 0.5);
+    solver.add_constraint(min.clone()).unwrap();
+
+    solver.start_recording();
+    solver.add_constraint(ratio.clone()).unwrap();
+    solver.add_edit_variable(width, Strength::MEDIUM).unwrap();
+    solver.begin_edit();
+    solver.suggest_value(width, 300.0).unwrap();
+    solver.end_edit().unwrap();
+    solver.remove_edit_variable(width).unwrap();
+    let conflict: Constraint = width | LE(Strength::REQUIRED) | 50.0;
+    assert!(solver.add_constraint(conflict.clone()).is_err());
+    assert!(solver.suggest_value(height, 10.0).is_err());
+    solver.add_stay(height, Strength::WEAK).unwrap();
+    let ops = solver.take_recording();
+    assert!(solver.take_recording().is_empty());
+
+    assert_eq!(
+        ops,
+        [
+            SolverOp::AddConstraint(ratio.clone()),
+            SolverOp::AddEditVariable(width, Strength::MEDIUM),
+            SolverOp::BeginEdit,
+            SolverOp::SuggestValue(width, 300.0),
+            SolverOp::EndEdit,
+            SolverOp::RemoveEditVariable(width),
+            SolverOp::AddStay(height, Strength::WEAK),
+        ]
+    );
+
+    let mut replayed = Solver::new();
+    replayed.add_constraint(min).unwrap();
+    replayed.replay(&ops).unwrap();
+    assert!(matches!(
+        replayed.add_constraint(conflict),
+        Err(AddConstraintError::UnsatisfiableConstraint)
+    ));
+    assert!(replayed.has_constraint(&ratio));
+    assert!(replayed.has_stay(height));
+    assert!(!replayed.has_edit_variable(&width));
+    assert_eq!(replayed.get_value(height), solver.get_value(height));
+}
+
+#[test]
+fn replay_skips_failed_operations() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let min: Constraint = width | GE(Strength::REQUIRED) | 100.0;
+    let wide: Constraint = width | GE(Strength::REQUIRED) | 200.0;
+    let wide = wide.when(1);
+    let unknown: Constraint = width | EQ(Strength::WEAK) | 0.0;
+    let narrow: Constraint = width | LE(Strength::REQUIRED) | 150.0;
+    solver.start_recording();
+    solver.add_constraint(min.clone()).unwrap();
+    solver.add_constraint(wide.clone()).unwrap();
+    solver.add_constraint(narrow.clone()).unwrap();
+    // the old constraint is kept, and nothing is recorded
+    assert!(solver.update_constant(&min, -300.0).is_err());
+    assert!(solver.remove_constraint(&unknown).is_err());
+    // the condition is switched, but its constraint stays disabled
+    assert!(solver.set_condition(1, true).is_err());
+    solver.add_edit_variable(width, Strength::WEAK).unwrap();
+    solver.suggest_value(width, 120.0).unwrap();
+    let ops = solver.take_recording();
+    assert_eq!(ops.len(), 6);
+
+    let mut replayed = Solver::new();
+    replayed.replay(&ops).unwrap();
+    assert!(replayed.has_constraint(&min));
+    assert!(replayed.condition(1));
+    assert!(!replayed.is_enabled(&wide));
+    assert_eq!(replayed.get_value(width), 120.0);
+    assert_eq!(replayed.get_value(width), solver.get_value(width));
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "serde")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, SolverState, Strength, Variable};
+
+#[test]
+fn constraint_round_trip() {
+    let (x, y) = (Variable::new(), Variable::new());
+    let constraint: Constraint = (x + 2.0 * y) | GT(Strength::STRONG) | 10.0;
+    let constraint = constraint
+        .with_name("gap")
+        .with_tag(3)
+        .when(4)
+        .with_weight(2.0);
+    let json = serde_json::to_string(&constraint).unwrap();
+    let copy: Constraint = serde_json::from_str(&json).unwrap();
+
+    assert_ne!(copy, constraint);
+    assert_eq!(copy.terms(), constraint.terms());
+    assert_eq!(copy.constant(), constraint.constant());
+    assert_eq!(copy.op(), constraint.op());
+    assert_eq!(copy.strength(), constraint.strength());
+    assert!(copy.is_strict());
+    assert_eq!(copy.name(), Some("gap"));
+    assert_eq!(copy.tag(), Some(3));
+    assert_eq!(copy.condition(), Some(4));
+    assert_eq!(copy.weight(), 2.0);
+}
+
+#[test]
+fn strength_is_clamped() {
+    let strength: Strength = serde_json::from_str("1e300").unwrap();
+    assert_eq!(strength, Strength::REQUIRED);
+}
+
+#[test]
+fn solver_round_trip() {
+    let mut solver = Solver::new();
+    let (left, right) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint(right | GE(Strength::REQUIRED) | (left + 20.0))
+        .unwrap();
+    solver
+        .add_named("left", left | EQ(Strength::WEAK) | 5.0)
+        .unwrap();
+    solver.add_edit_variable(right, Strength::STRONG).unwrap();
+    solver.suggest_value(right, 40.0).unwrap();
+
+    let json = serde_json::to_string(&solver).unwrap();
+    let mut restored: Solver = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.get_value(left), 5.0);
+    assert_eq!(restored.get_value(right), 40.0);
+    restored.suggest_value(right, 10.0).unwrap();
+    assert_eq!(restored.get_value(left), -10.0);
+
+    // without the tableau, the constraints are solved again
+    let json = serde_json::to_string(&solver.state(false)).unwrap();
+    let state: SolverState = serde_json::from_str(&json).unwrap();
+    assert!(!state.has_tableau());
+    let restored = Solver::from_state(state).unwrap();
+    assert_eq!(restored.get_value(right), 40.0);
+    assert!(restored.named("left").is_some());
+}
+
+#[test]
+fn invalid_state() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    solver
+        .add_named("x", x | EQ(Strength::REQUIRED) | 1.0)
+        .unwrap();
+    let json = serde_json::to_string(&solver.state(false)).unwrap();
+    let json = json.replace(r#"["x",0]"#, r#"["x",5]"#);
+    assert!(serde_json::from_str::<Solver>(&json).is_err());
+}
@@ -0,0 +1,36 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn constraints_share_terms() {
+    let mut solver = Solver::new();
+    let (top, height) = (Variable::new(), Variable::new());
+    let template: Constraint = (top + height) | LE(Strength::REQUIRED) | 0.0;
+    let limits: Vec<Constraint> = (1..=100)
+        .map(|row| template.with_constant(-(row as f64)))
+        .collect();
+    assert!(limits
+        .iter()
+        .all(|limit| limit.shares_terms_with(&template)));
+    assert_eq!(limits[41].terms(), template.terms());
+    assert_eq!(limits[41].constant(), -42.0);
+    assert_ne!(limits[41], template);
+    for limit in &limits {
+        solver.add_constraint(limit.clone()).unwrap();
+    }
+    solver
+        .add_constraint(top | EQ(Strength::REQUIRED) | 0.0)
+        .unwrap();
+    solver
+        .add_constraint(height | EQ(Strength::WEAK) | 50.0)
+        .unwrap();
+    assert_eq!(solver.get_value(height), 1.0);
+
+    let preferred = template
+        .with_strength(Strength::STRONG)
+        .with_constant(-10.0);
+    assert!(preferred.shares_terms_with(&template));
+    assert_eq!(preferred.strength(), Strength::STRONG);
+    let other: Constraint = (top + height) | LE(Strength::REQUIRED) | 0.0;
+    assert!(!other.shares_terms_with(&template));
+}
@@ -0,0 +1,44 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn restore_undoes_changes() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let width = Variable::new();
+    let base: Constraint = width | EQ(Strength::WEAK) | 100.0;
+    solver.add_constraint(base.clone()).unwrap();
+    solver.add_edit_variable(left, Strength::STRONG).unwrap();
+    solver.suggest_value(left, 10.0).unwrap();
+    solver.fetch_changes();
+    let snapshot = solver.snapshot();
+
+    let max: Constraint = width | LE(Strength::REQUIRED) | 80.0;
+    solver.add_constraint(max.clone()).unwrap();
+    solver.remove_constraint(&base).unwrap();
+    solver.remove_edit_variable(left).unwrap();
+    let generation = solver.generation();
+    assert_eq!(solver.get_value(width), 80.0);
+
+    solver.restore(snapshot.clone());
+    assert!(solver.generation() > generation);
+    assert!(solver.has_constraint(&base));
+    assert!(!solver.has_constraint(&max));
+    assert!(solver.has_edit_variable(&left));
+    assert_eq!(solver.get_value(width), 100.0);
+    assert_eq!(solver.get_value(left), 10.0);
+    // the values are back to what was last fetched
+    assert_eq!(solver.fetch_changes(), []);
+
+    // the restored solver keeps working, and the snapshot can be restored again
+    solver.suggest_value(left, 20.0).unwrap();
+    assert_eq!(solver.fetch_changes(), [(left, 20.0)]);
+    solver.add_constraint(max.clone()).unwrap();
+    assert_eq!(solver.fetch_changes(), [(width, 80.0)]);
+    solver.restore(snapshot);
+    assert_eq!(solver.get_value(width), 100.0);
+    assert_eq!(solver.get_value(left), 10.0);
+    let mut changes = solver.fetch_changes().to_vec();
+    changes.sort_by_key(|&(v, _)| v);
+    assert_eq!(changes, [(left, 10.0), (width, 100.0)]);
+}
@@ -0,0 +1,39 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solution, Solver, Strength, Variable};
+
+#[test]
+fn freeze() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    let preferred: Constraint = width | EQ(Strength::WEAK) | 150.0;
+    solver
+        .add_constraints([max.clone(), preferred.clone()])
+        .unwrap();
+
+    let solution = solver.freeze();
+    assert_eq!(solution.generation(), solver.generation());
+    assert_eq!(solution.value_of(width), 100.0);
+    assert_eq!(solution.value_of(Variable::new()), 0.0);
+    assert_eq!(solution.values().collect::<Vec<_>>(), [(width, 100.0)]);
+    assert_eq!(solution.satisfied().collect::<Vec<_>>(), [&max]);
+    assert_eq!(
+        solution.violated().collect::<Vec<_>>(),
+        [(&preferred, 50.0)]
+    );
+    assert!(!solution.is_satisfied());
+
+    // the solution is unaffected by later changes to the solver
+    solver.remove_constraint(&max).unwrap();
+    assert!(solver.generation() > solution.generation());
+    assert_eq!(solution.value_of(width), 100.0);
+    assert!(solver.freeze().is_satisfied());
+    assert_ne!(solver.freeze(), solution);
+}
+
+#[test]
+fn solution_is_send() {
+    fn assert_send<T: Send + Sync>() {}
+    assert_send::<Solution>();
+}
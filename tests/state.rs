@@ -0,0 +1,153 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+struct Layout {
+    solver: Solver,
+    left: Variable,
+    width: Variable,
+    right: Variable,
+    hidden: Constraint,
+}
+
+fn layout() -> Layout {
+    let mut solver = Solver::new();
+    let (left, width, right) = (Variable::new(), Variable::new(), Variable::new());
+    solver
+        .add_constraints([
+            right | EQ(Strength::REQUIRED) | (left + width),
+            width | GE(Strength::REQUIRED) | 10.0,
+            // gives `left` a single value to be pinned at
+            left | EQ(Strength::WEAK) | 0.0,
+        ])
+        .unwrap();
+    solver
+        .add_named("width.preferred", width | EQ(Strength::WEAK) | 80.0)
+        .unwrap();
+    solver.pin(left, Strength::MEDIUM).unwrap();
+    solver
+        .set_lower_bound(right, 0.0, Strength::REQUIRED)
+        .unwrap();
+    let hidden: Constraint = width | EQ(Strength::STRONG) | 0.0;
+    solver.add_constraint(hidden.clone()).unwrap();
+    solver.set_enabled(&hidden, false).unwrap();
+    solver.add_edit_variable(right, Strength::STRONG).unwrap();
+    solver.suggest_value(right, 50.0).unwrap();
+    Layout {
+        solver,
+        left,
+        width,
+        right,
+        hidden,
+    }
+}
+
+#[test]
+fn restore_state() {
+    for tableau in [true, false] {
+        let Layout {
+            solver,
+            left,
+            width,
+            right,
+            hidden,
+        } = layout();
+        let state = solver.state(tableau);
+        assert_eq!(state.has_tableau(), tableau);
+        let mut restored = Solver::from_state(state).unwrap();
+
+        assert_eq!(restored.get_value(left), 0.0);
+        assert_eq!(restored.get_value(width), 50.0);
+        assert_eq!(restored.get_value(right), 50.0);
+        assert!(restored.is_pinned(left));
+        assert_eq!(restored.lower_bound(right), Some(0.0));
+        assert!(restored.has_edit_variable(&right));
+        assert!(restored.has_constraint(&hidden));
+        assert!(!restored.is_enabled(&hidden));
+        let named = restored.named("width.preferred").unwrap().clone();
+
+        // the restored solver carries on like the original
+        restored.suggest_value(right, 200.0).unwrap();
+        assert_eq!(restored.get_value(width), 200.0);
+        restored.set_enabled(&hidden, true).unwrap();
+        assert_eq!(restored.get_value(width), 10.0);
+        restored.remove_constraint(&named).unwrap();
+        assert!(restored.named("width.preferred").is_none());
+    }
+}
+
+#[test]
+fn restored_solver_reports_every_variable() {
+    let Layout {
+        solver,
+        left,
+        width,
+        right,
+        ..
+    } = layout();
+    let mut restored = Solver::from_state(solver.state(true)).unwrap();
+    let mut changes = restored.fetch_changes().to_vec();
+    changes.sort_by_key(|&(v, _)| v);
+    let mut expected = vec![(left, 0.0), (width, 50.0), (right, 50.0)];
+    expected.sort_by_key(|&(v, _)| v);
+    assert_eq!(changes, expected);
+    assert!(restored.fetch_changes().is_empty());
+}
+
+#[test]
+fn restore_state_under_conditions() {
+    const WIDE: u64 = 7;
+    for tableau in [true, false] {
+        let mut solver = Solver::new();
+        let width = Variable::new();
+        let wide: Constraint = width | EQ(Strength::REQUIRED) | 120.0;
+        solver.add_constraint(wide.when(WIDE)).unwrap();
+        solver
+            .add_constraint(width | EQ(Strength::WEAK) | 80.0)
+            .unwrap();
+        assert_eq!(solver.get_value(width), 80.0);
+
+        let mut restored = Solver::from_state(solver.state(tableau)).unwrap();
+        assert_eq!(restored.get_value(width), 80.0);
+        restored.set_condition(WIDE, true).unwrap();
+        assert_eq!(restored.get_value(width), 120.0);
+    }
+}
+
+#[test]
+fn restore_merged_constraints() {
+    for tableau in [true, false] {
+        let mut solver = Solver::new();
+        solver.set_merge_parallel(true);
+        let x = Variable::new();
+        solver
+            .add_constraint(x | EQ(Strength::WEAK) | 10.0)
+            .unwrap();
+        solver
+            .add_constraint(x | EQ(Strength::MEDIUM) | 30.0)
+            .unwrap();
+        let merged: Constraint = (2.0 * x) | EQ(Strength::MEDIUM) | 60.0;
+        solver.add_constraint(merged.clone()).unwrap();
+        assert!(solver.merged_into(&merged).is_some());
+
+        let state = solver.state(tableau);
+        let restored_merged = state.constraints()[2].clone();
+        let mut restored = Solver::from_state(state).unwrap();
+        assert_eq!(restored.get_value(x), 30.0);
+        assert!(restored.merged_into(&restored_merged).is_some());
+        restored.remove_constraint(&restored_merged).unwrap();
+        assert_eq!(restored.get_value(x), 30.0);
+    }
+}
+
+#[test]
+fn restore_keeps_variable_counter() {
+    let mut solver = Solver::new();
+    let first = solver.new_variable();
+    solver
+        .add_constraint(first | EQ(Strength::REQUIRED) | 1.0)
+        .unwrap();
+    let mut restored = Solver::from_state(solver.state(false)).unwrap();
+    let second = restored.new_variable();
+    assert_ne!(first, second);
+    assert_eq!(restored.get_value(first), 1.0);
+}
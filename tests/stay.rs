@@ -0,0 +1,91 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn stay_follows_the_variable() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let hint: Constraint = x | EQ(Strength::WEAK * 0.5) | 10.0;
+    solver.add_constraint(hint.clone()).unwrap();
+    assert_eq!(solver.get_value(x), 10.0);
+    solver.add_stay(x, Strength::WEAK).unwrap();
+    assert!(solver.has_stay(x));
+
+    // a stronger constraint moves the variable, and the stay keeps it there afterwards
+    let push: Constraint = x | EQ(Strength::MEDIUM) | 50.0;
+    solver.add_constraint(push.clone()).unwrap();
+    assert_eq!(solver.get_value(x), 50.0);
+    solver.remove_constraint(&push).unwrap();
+    assert_eq!(solver.get_value(x), 50.0);
+
+    // the same happens with edit variables
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    solver.suggest_value(x, 70.0).unwrap();
+    solver.remove_edit_variable(x).unwrap();
+    assert_eq!(solver.get_value(x), 70.0);
+
+    // without the stay, the weaker hint takes over again
+    solver.remove_stay(x).unwrap();
+    assert!(!solver.has_stay(x));
+    assert_eq!(solver.get_value(x), 10.0);
+    assert!(solver.remove_stay(x).is_err());
+}
+
+#[test]
+fn stays_hold_untouched_variables() {
+    let mut solver = Solver::new();
+    let left = Variable::new();
+    let width = Variable::new();
+    let right = Variable::new();
+    solver
+        .add_constraint(right | EQ(Strength::REQUIRED) | (left + width))
+        .unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+    // gives the left edge a single value for its stay to hold
+    solver
+        .add_constraint(left | EQ(Strength::WEAK * 0.5) | 0.0)
+        .unwrap();
+    solver.add_stay(left, Strength::MEDIUM).unwrap();
+    solver.add_stay(width, Strength::WEAK * 2.0).unwrap();
+
+    // moving the right edge resizes, as the left edge has the stronger stay
+    solver.add_edit_variable(right, Strength::STRONG).unwrap();
+    solver.suggest_value(right, 150.0).unwrap();
+    assert_eq!(solver.get_value(left), 0.0);
+    assert_eq!(solver.get_value(width), 150.0);
+    solver.remove_edit_variable(right).unwrap();
+    assert_eq!(solver.get_value(width), 150.0);
+
+    // moving the left edge keeps the new width
+    solver.add_edit_variable(left, Strength::STRONG).unwrap();
+    solver.suggest_value(left, 30.0).unwrap();
+    assert_eq!(solver.get_value(width), 150.0);
+    assert_eq!(solver.get_value(right), 180.0);
+
+    // stays survive rebuilding at the values they hold
+    solver.remove_edit_variable(left).unwrap();
+    solver.rebuild().unwrap();
+    assert_eq!(solver.get_value(left), 30.0);
+    assert_eq!(solver.get_value(width), 150.0);
+}
+
+#[test]
+fn replay_stays() {
+    let mut solver = Solver::new();
+    solver.start_recording();
+    let x = Variable::new();
+    solver
+        .add_constraint(x | EQ(Strength::WEAK * 0.5) | 10.0)
+        .unwrap();
+    solver.add_stay(x, Strength::WEAK).unwrap();
+    let push: Constraint = x | EQ(Strength::MEDIUM) | 50.0;
+    solver.add_constraint(push.clone()).unwrap();
+    solver.remove_constraint(&push).unwrap();
+    let ops = solver.take_recording();
+
+    let mut replayed = Solver::new();
+    replayed.replay(&ops).unwrap();
+    assert_eq!(replayed.get_value(x), 50.0);
+}
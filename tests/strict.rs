@@ -0,0 +1,56 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, NameRegistry, Solver, Strength, Variable};
+
+#[test]
+fn strict_inequalities() {
+    let mut solver = Solver::new();
+    assert_eq!(solver.strict_epsilon(), 1e-6);
+    solver.set_strict_epsilon(1.0);
+    let (left, right) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint(left | EQ(Strength::REQUIRED) | 10.0)
+        .unwrap();
+    let after: Constraint = right | GT(Strength::REQUIRED) | left;
+    let before: Constraint = right | LT(Strength::REQUIRED) | 20.0;
+    assert!(after.is_strict());
+    solver.add_constraint(after.clone()).unwrap();
+    solver.add_constraint(before.clone()).unwrap();
+
+    solver
+        .add_constraint(right | EQ(Strength::WEAK) | 0.0)
+        .unwrap();
+    assert_eq!(solver.get_value(right), 11.0);
+    assert_eq!(solver.violation(&after), 0.0);
+    assert_eq!(solver.slack(&after), Some(0.0));
+    assert_eq!(solver.slack(&before), Some(8.0));
+
+    solver
+        .add_constraint(right | EQ(Strength::STRONG) | 30.0)
+        .unwrap();
+    assert_eq!(solver.get_value(right), 19.0);
+
+    // a strict and a non-strict inequality are not merged
+    solver.set_merge_parallel(true);
+    let at_most: Constraint = right | LE(Strength::REQUIRED) | 20.0;
+    solver.add_constraint(at_most.clone()).unwrap();
+    assert_eq!(solver.merged_into(&at_most), None);
+    solver.remove_constraint(&before).unwrap();
+    assert_eq!(solver.get_value(right), 20.0);
+
+    let mut names = NameRegistry::new();
+    names.insert(left, "left");
+    names.insert(right, "right");
+    assert_eq!(after.display_with(&names).to_string(), "right - left > 0");
+}
+
+#[test]
+fn strict_soft_inequality() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let narrow: Constraint = width | LT(Strength::STRONG) | 10.0;
+    solver.add_constraint(narrow.clone()).unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::REQUIRED) | 10.0)
+        .unwrap();
+    assert!((solver.violation(&narrow) - 1e-6).abs() < 1e-9);
+}
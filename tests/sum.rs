@@ -0,0 +1,53 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Expression, Solver, Strength, Term, Variable};
+
+#[test]
+fn sum() {
+    let children = [Variable::new(), Variable::new(), Variable::new()];
+    let parent = Variable::new();
+    let mut solver = Solver::new();
+    let total: Expression = children.iter().copied().sum();
+    solver
+        .add_constraint(parent | EQ(Strength::REQUIRED) | total)
+        .unwrap();
+    for (i, &child) in children.iter().enumerate() {
+        solver
+            .add_constraint(child | EQ(Strength::REQUIRED) | (10.0 * (i + 1) as f64))
+            .unwrap();
+    }
+    assert_eq!(solver.get_value(parent), 60.0);
+
+    let terms: Expression = children.iter().map(|&v| Term::new(v, 2.0)).sum();
+    assert_eq!(terms.terms.len(), 3);
+    let expressions: Expression = children.iter().map(|&v| v + 1.0).sum();
+    assert_eq!(expressions.constant, 3.0);
+    assert_eq!(
+        Expression::sum([children[0] + 1.0, children[1] + 2.0]).constant,
+        3.0
+    );
+    assert_eq!(
+        Expression::sum(Vec::<Variable>::new()),
+        Expression::from_constant(0.0)
+    );
+}
+
+#[test]
+fn from_pairs() {
+    let (x, y) = (Variable::new(), Variable::new());
+    let pairs = [(x, 1.0), (y, 3.0)];
+    let mut solver = Solver::new();
+    solver
+        .add_constraint(Expression::from_pairs(&pairs, -12.0) | EQ(Strength::REQUIRED) | 0.0)
+        .unwrap();
+    solver
+        .add_constraint(x | EQ(Strength::REQUIRED) | 3.0)
+        .unwrap();
+    assert_eq!(solver.get_value(y), 3.0);
+
+    let collected: Expression = pairs.iter().copied().collect();
+    assert_eq!(collected, Expression::from_pairs(&pairs, 0.0));
+    assert_eq!(
+        Expression::from_pairs(&[], 4.0),
+        Expression::from_constant(4.0)
+    );
+}
@@ -0,0 +1,60 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn remove_by_tag() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let (left, width) = (Variable::new(), Variable::new());
+    let (sidebar, content) = (1, 2);
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 100.0)
+        .unwrap();
+
+    let max: Constraint = width | LE(Strength::REQUIRED) | 80.0;
+    let max = max.with_name("sidebar.max").with_tag(sidebar);
+    assert_eq!(max.name(), Some("sidebar.max"));
+    assert_eq!(max.tag(), Some(sidebar));
+    let preferred: Constraint = width | EQ(Strength::MEDIUM) | 60.0;
+    let merged: Constraint = width | EQ(Strength::MEDIUM) | 60.0;
+    let disabled: Constraint = width | GE(Strength::REQUIRED) | 70.0;
+    let disabled = disabled.with_tag(sidebar);
+    let other: Constraint = left | EQ(Strength::REQUIRED) | 10.0;
+    let other = other.with_tag(content);
+    for constraint in [
+        max.clone(),
+        preferred.with_tag(sidebar),
+        merged.with_tag(sidebar),
+        disabled.clone(),
+        other.clone(),
+    ] {
+        solver.add_constraint(constraint).unwrap();
+    }
+    solver.set_enabled(&disabled, false).unwrap();
+    assert_eq!(solver.get_value(width), 60.0);
+    assert_eq!(solver.constraints_with_tag(sidebar).count(), 4);
+
+    let removed = solver.remove_by_tag(sidebar).unwrap();
+    assert_eq!(removed.len(), 4);
+    assert!(removed.contains(&max));
+    assert_eq!(solver.constraints_with_tag(sidebar).count(), 0);
+    assert_eq!(solver.named("sidebar.max"), None);
+    assert_eq!(solver.get_value(width), 100.0);
+    assert!(solver.has_constraint(&other));
+    assert_eq!(solver.get_value(left), 10.0);
+
+    assert!(solver.remove_by_tag(sidebar).unwrap().is_empty());
+}
+
+#[test]
+fn remove_disabled_soft_constraint_by_tag() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let preferred: Constraint = width | EQ(Strength::STRONG) | 60.0;
+    let preferred = preferred.with_tag(1);
+    solver.add_constraint(preferred.clone()).unwrap();
+    solver.set_enabled(&preferred, false).unwrap();
+    assert_eq!(solver.constraints_with_tag(1).count(), 1);
+    assert_eq!(solver.remove_by_tag(1).unwrap(), [preferred]);
+    assert_eq!(solver.constraints_with_tag(1).count(), 0);
+}
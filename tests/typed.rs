@@ -0,0 +1,30 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Expression, Solver, Strength, TypedExpression, TypedVariable, Variable};
+
+enum X {}
+enum Width {}
+
+#[test]
+fn typed_variables() {
+    let (left, right) = (TypedVariable::<X>::new(), TypedVariable::<X>::new());
+    let (sidebar, content) = (TypedVariable::<Width>::new(), TypedVariable::<Width>::new());
+
+    let span: TypedExpression<X> = right - left;
+    assert_eq!(span.expression(), &(right.variable() - left.variable()));
+    let halves: TypedExpression<Width> = 0.5 * content + sidebar / 2.0 - 1.0;
+    let plain: Expression = halves.into_expression();
+    assert_eq!(plain.constant, -1.0);
+
+    let mut solver = Solver::new();
+    let constraints: [Constraint; 4] = [
+        left | EQ(Strength::REQUIRED) | 0.0,
+        right | EQ(Strength::REQUIRED) | (left + 120.0),
+        (sidebar + content) | EQ(Strength::REQUIRED) | 120.0,
+        sidebar | EQ(Strength::REQUIRED) | (content * 0.5),
+    ];
+    solver.add_constraints(constraints).unwrap();
+    assert_eq!(solver.get_value(right.variable()), 120.0);
+    assert_eq!(solver.get_value(sidebar.variable()), 40.0);
+    assert_eq!(Variable::from(content), content.variable());
+    assert_eq!(TypedVariable::<X>::from_variable(left.variable()), left);
+}
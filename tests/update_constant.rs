@@ -0,0 +1,63 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, UpdateConstantError, Variable};
+
+#[test]
+fn update_constant() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    solver
+        .add_constraint(height | EQ(Strength::REQUIRED) | (width * 0.5))
+        .unwrap();
+    let max: Constraint = width | LE(Strength::STRONG) | 300.0;
+    let min: Constraint = width | GE(Strength::STRONG) | 100.0;
+    let preferred: Constraint = width | EQ(Strength::MEDIUM) | 200.0;
+    solver.add_constraint(max.clone()).unwrap();
+    solver.add_constraint(min.clone()).unwrap();
+    solver.add_named("preferred", preferred.clone()).unwrap();
+    assert_eq!(solver.get_value(width), 200.0);
+
+    let preferred = solver.update_constant(&preferred, -250.0).unwrap();
+    assert_eq!(solver.get_value(width), 250.0);
+    assert_eq!(solver.get_value(height), 125.0);
+    assert_eq!(solver.named("preferred"), Some(&preferred));
+
+    let max = solver.update_constant(&max, -220.0).unwrap();
+    assert_eq!(solver.get_value(width), 220.0);
+    // the strong constraints now conflict, and the preferred width breaks the tie
+    let min = solver.update_constant(&min, -240.0).unwrap();
+    assert_eq!(solver.get_value(width), 240.0);
+
+    // the updated constraints behave like ones added from scratch
+    solver.remove_constraint(&max).unwrap();
+    assert_eq!(solver.get_value(width), 250.0);
+    solver.remove_constraint(&min).unwrap();
+    solver.remove_named("preferred").unwrap();
+    assert!(solver.update_constant(&min, 0.0).is_err());
+}
+
+#[test]
+fn update_required_constant() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let max: Constraint = width | LE(Strength::REQUIRED) | 100.0;
+    solver.add_constraint(max.clone()).unwrap();
+    solver
+        .add_constraint(width | GE(Strength::REQUIRED) | 50.0)
+        .unwrap();
+    solver
+        .add_constraint(width | EQ(Strength::WEAK) | 1000.0)
+        .unwrap();
+    assert_eq!(solver.get_value(width), 100.0);
+
+    let max = solver.update_constant(&max, -80.0).unwrap();
+    assert_eq!(solver.get_value(width), 80.0);
+
+    // an unsatisfiable update keeps the old constraint
+    assert!(matches!(
+        solver.update_constant(&max, -20.0),
+        Err(UpdateConstantError::UnsatisfiableConstraint)
+    ));
+    assert!(solver.has_constraint(&max));
+    assert_eq!(solver.get_value(width), 80.0);
+}
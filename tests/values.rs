@@ -0,0 +1,173 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Solver, Strength, Variable};
+
+#[test]
+fn value_of() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    let unknown = Variable::new();
+    solver
+        .add_constraints([
+            (x + y) | EQ(Strength::REQUIRED) | 10.0,
+            x | EQ(Strength::STRONG) | 10.0,
+        ])
+        .unwrap();
+
+    assert_eq!(solver.value_of(x), 10.0);
+    assert_eq!(solver.value_of(y), 0.0);
+    assert_eq!(solver.value_of(unknown), 0.0);
+    assert_eq!(solver.try_value_of(x), Some(10.0));
+    assert_eq!(solver.try_value_of(y), Some(0.0));
+    assert_eq!(solver.try_value_of(unknown), None);
+}
+
+#[test]
+fn values() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    solver
+        .add_constraints([
+            x | EQ(Strength::REQUIRED) | 10.0,
+            y | EQ(Strength::REQUIRED) | (x + 5.0),
+        ])
+        .unwrap();
+    // the full solution is available even after the changes were fetched
+    solver.fetch_changes();
+
+    let values = solver.values();
+    assert_eq!(
+        values.into_iter().collect::<Vec<_>>(),
+        [(x, 10.0), (y, 15.0)]
+    );
+}
+
+#[test]
+fn index() {
+    let mut solver = Solver::new();
+    let width = Variable::new();
+    let height = Variable::new();
+    solver
+        .add_constraints([
+            width | EQ(Strength::REQUIRED) | 100.0,
+            height | GE(Strength::REQUIRED) | 0.0,
+        ])
+        .unwrap();
+
+    assert_eq!(solver[width], 100.0);
+    assert_eq!(solver[height], 0.0);
+    assert_eq!(solver[Variable::new()], 0.0);
+}
+
+#[test]
+fn iter_values() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    assert_eq!(solver.iter_values().count(), 0);
+
+    solver
+        .add_constraints([
+            x | EQ(Strength::REQUIRED) | 1.0,
+            y | EQ(Strength::REQUIRED) | 2.0,
+        ])
+        .unwrap();
+    let mut values: Vec<_> = solver.iter_values().collect();
+    values.sort_by_key(|&(v, _)| v);
+    assert_eq!(values, [(x, 1.0), (y, 2.0)]);
+}
+
+#[test]
+fn has_changed() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    solver
+        .add_constraints([
+            x | EQ(Strength::WEAK) | 10.0,
+            y | EQ(Strength::REQUIRED) | 20.0,
+        ])
+        .unwrap();
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    solver.add_edit_variable(y, Strength::STRONG).unwrap();
+    assert!(solver.has_changed(x));
+    assert!(solver.has_changed(y));
+    assert!(!solver.has_changed(Variable::new()));
+
+    solver.fetch_changes();
+    assert!(!solver.has_changed(x));
+    assert!(!solver.has_changed(y));
+
+    solver.suggest_value(x, 15.0).unwrap();
+    assert!(solver.has_changed(x));
+    // the required constraint wins, so nothing changes
+    solver.suggest_value(y, 15.0).unwrap();
+    assert!(!solver.has_changed(y));
+}
+
+#[test]
+fn changes_since() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    solver.add_edit_variable(x, Strength::STRONG).unwrap();
+    solver
+        .add_constraint(y | EQ(Strength::REQUIRED) | (x * 2.0))
+        .unwrap();
+    solver.suggest_value(x, 10.0).unwrap();
+
+    assert_eq!(solver.changes_since(0), [(x, 10.0), (y, 20.0)]);
+    let renderer = solver.generation();
+    solver.suggest_value(x, 20.0).unwrap();
+    let accessibility = solver.generation();
+    solver.suggest_value(x, 20.0).unwrap();
+
+    // consumers don't steal changes from each other
+    assert_eq!(solver.changes_since(renderer), [(x, 20.0), (y, 40.0)]);
+    assert_eq!(solver.changes_since(accessibility), []);
+    assert_eq!(solver.changes_since(renderer), [(x, 20.0), (y, 40.0)]);
+    assert_eq!(solver.changes_since(solver.generation()), []);
+    // fetching changes doesn't affect them either
+    solver.fetch_changes();
+    assert_eq!(solver.changes_since(renderer), [(x, 20.0), (y, 40.0)]);
+
+    let z = Variable::new();
+    let before = solver.generation();
+    solver
+        .add_constraint(z | EQ(Strength::REQUIRED) | 5.0)
+        .unwrap();
+    assert_eq!(solver.changes_since(before), [(z, 5.0)]);
+}
+
+#[test]
+fn default_value() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    let y = Variable::new();
+    solver.set_default_value(x, 50.0);
+    assert_eq!(solver.default_value(x), 50.0);
+    assert_eq!(solver.default_value(y), 0.0);
+    assert_eq!(solver.value_of(x), 50.0);
+    assert_eq!(solver[x], 50.0);
+    assert_eq!(solver.try_value_of(x), None);
+    assert!(solver.has_changed(x));
+    assert_eq!(solver.fetch_changes(), [(x, 50.0)]);
+    assert!(!solver.has_changed(x));
+    assert_eq!(solver.fetch_changes(), []);
+
+    // the solved value of x is reported even though it is zero, unlike that of y
+    solver
+        .add_constraints([
+            (x + y) | EQ(Strength::REQUIRED) | 0.0,
+            x | EQ(Strength::STRONG) | 0.0,
+        ])
+        .unwrap();
+    assert_eq!(solver.value_of(x), 0.0);
+    assert_eq!(solver.fetch_changes(), [(x, 0.0)]);
+
+    // removing the last constraint on x reports its default again, but not y's
+    assert_eq!(solver.remove_variable(x).unwrap().len(), 2);
+    assert_eq!(solver.value_of(x), 50.0);
+    assert_eq!(solver.fetch_changes(), [(x, 50.0)]);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "variable-names")]
+
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, NameRegistry, Solver, Strength, Variable};
+
+#[test]
+fn named_variables() {
+    let left = Variable::named("box.left");
+    let right = Variable::new();
+    assert_eq!(left.name().as_deref(), Some("box.left"));
+    assert_eq!(right.name(), None);
+    assert_eq!(left.to_string(), "box.left");
+    assert_eq!(right.to_string(), format!("v{}", right.id()));
+
+    right.set_name("box.right");
+    let constraint: Constraint = right | GE(Strength::REQUIRED) | (left + 10.0);
+    assert_eq!(
+        constraint.to_string(),
+        "box.right - box.left >= 10 [REQUIRED]"
+    );
+    let names: NameRegistry = [(left, "l")].into_iter().collect();
+    assert_eq!(
+        constraint.display_with(&names).to_string(),
+        "box.right - l >= 10"
+    );
+
+    let mut solver = Solver::new();
+    solver.add_constraint(constraint).unwrap();
+    assert!(solver.dumps().contains("box.right = "));
+
+    right.clear_name();
+    assert_eq!(right.name(), None);
+}
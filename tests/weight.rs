@@ -0,0 +1,83 @@
+use kasuari::WeightedRelation::*;
+use kasuari::{Constraint, Solver, Strength, Variable};
+
+#[test]
+fn weights_bias_equal_strengths() {
+    let mut solver = Solver::new();
+    let (left, right) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint((left + right) | EQ(Strength::REQUIRED) | 100.0)
+        .unwrap();
+    let left_preferred: Constraint = left | EQ(Strength::WEAK) | 80.0;
+    let right_preferred: Constraint = right | EQ(Strength::WEAK) | 80.0;
+    let right_preferred = right_preferred.with_weight(3.0);
+    assert_eq!(right_preferred.weight(), 3.0);
+    assert_eq!(right_preferred.strength(), Strength::WEAK);
+    solver.add_constraint(left_preferred.clone()).unwrap();
+    solver.add_constraint(right_preferred.clone()).unwrap();
+    assert_eq!(solver.get_value(left), 20.0);
+    assert_eq!(solver.get_value(right), 80.0);
+
+    // a weight doesn't lift a constraint above a stronger one
+    solver
+        .add_constraint(right | EQ(Strength::MEDIUM) | 30.0)
+        .unwrap();
+    assert_eq!(solver.get_value(right), 30.0);
+
+    // nor does it move it to another strength
+    let removed = solver.remove_below_strength(Strength::MEDIUM).unwrap();
+    assert_eq!(removed.len(), 2);
+    assert!(removed.contains(&right_preferred));
+    assert!(!left_preferred.structurally_equals(&left_preferred.with_weight(2.0)));
+}
+
+#[test]
+fn weights_of_merged_and_disabled_constraints() {
+    let mut solver = Solver::new();
+    solver.set_merge_parallel(true);
+    let (left, right) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint((left + right) | EQ(Strength::REQUIRED) | 100.0)
+        .unwrap();
+    let heavy: Constraint = left | EQ(Strength::WEAK) | 80.0;
+    let heavy = heavy.with_weight(2.0);
+    let light: Constraint = left | EQ(Strength::WEAK) | 80.0;
+    solver.add_constraint(heavy.clone()).unwrap();
+    solver.add_constraint(light.clone()).unwrap();
+    let right_preferred: Constraint = right | EQ(Strength::WEAK) | 80.0;
+    solver
+        .add_constraint(right_preferred.with_weight(2.5))
+        .unwrap();
+    assert_eq!(solver.get_value(left), 80.0);
+
+    solver.set_enabled(&light, false).unwrap();
+    assert_eq!(solver.get_value(left), 20.0);
+    solver.set_enabled(&light, true).unwrap();
+    assert_eq!(solver.get_value(left), 80.0);
+    solver.remove_constraint(&light).unwrap();
+    assert_eq!(solver.get_value(left), 20.0);
+}
+
+#[test]
+fn update_constant_keeps_weight() {
+    let mut solver = Solver::new();
+    let (left, right) = (Variable::new(), Variable::new());
+    solver
+        .add_constraint((left + right) | EQ(Strength::REQUIRED) | 100.0)
+        .unwrap();
+    let left_preferred: Constraint = left | EQ(Strength::WEAK) | 80.0;
+    let left_preferred = left_preferred.with_weight(2.0).with_tag(1);
+    let right_preferred: Constraint = right | EQ(Strength::WEAK) | 80.0;
+    solver.add_constraint(left_preferred.clone()).unwrap();
+    solver.add_constraint(right_preferred).unwrap();
+    assert_eq!(solver.get_value(left), 80.0);
+
+    let updated = solver.update_constant(&left_preferred, -70.0).unwrap();
+    assert_eq!(updated.weight(), 2.0);
+    assert_eq!(updated.tag(), Some(1));
+    assert_eq!(solver.get_value(left), 70.0);
+    solver.set_enabled(&updated, false).unwrap();
+    assert_eq!(solver.get_value(right), 80.0);
+    solver.remove_constraint(&updated).unwrap();
+    assert_eq!(solver.get_value(right), 80.0);
+}